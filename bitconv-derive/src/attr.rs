@@ -0,0 +1,106 @@
+use syn::{Attribute, Expr, LitInt, LitStr, Result, Type};
+
+/// Options given to a field through `#[bitconv(...)]` attributes.
+#[derive(Default)]
+pub struct FieldAttrs {
+    /// First format version in which the field is present.
+    pub since: Option<u32>,
+    /// Whether the field is a presence bitmap for the optional fields that
+    /// follow it.
+    pub mask: bool,
+    /// Bit of the preceding presence bitmap that says whether the field is
+    /// present.
+    pub bit: Option<u32>,
+    /// Expression over earlier fields giving the number of elements in a
+    /// `Vec` field.
+    pub count: Option<Expr>,
+    /// Largest number of elements allowed in a `count` field.
+    pub max: Option<Expr>,
+}
+
+impl FieldAttrs {
+    pub fn parse(attrs: &[Attribute]) -> Result<Self> {
+        let mut parsed = FieldAttrs::default();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("bitconv")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("since") {
+                    parsed.since = Some(meta.value()?.parse::<LitInt>()?.base10_parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("mask") {
+                    parsed.mask = true;
+                    Ok(())
+                } else if meta.path.is_ident("bit") {
+                    parsed.bit = Some(meta.value()?.parse::<LitInt>()?.base10_parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("count") {
+                    parsed.count = Some(meta.value()?.parse::<LitStr>()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("max") {
+                    parsed.max = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else {
+                    Err(meta.error("unsupported bitconv field attribute"))
+                }
+            })?;
+        }
+        Ok(parsed)
+    }
+}
+
+/// Options given to a struct or enum through `#[bitconv(...)]` attributes.
+#[derive(Default)]
+pub struct ContainerAttrs {
+    /// Type of the discriminant that precedes the fields of an enum.
+    pub tag: Option<Type>,
+    /// Expected total size of a fixed-size struct.
+    pub size: Option<Expr>,
+    /// Whether to also implement `FromBytesLenient`.
+    pub lenient: bool,
+}
+
+impl ContainerAttrs {
+    pub fn parse(attrs: &[Attribute]) -> Result<Self> {
+        let mut parsed = ContainerAttrs::default();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("bitconv")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("tag") {
+                    parsed.tag = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("size") {
+                    parsed.size = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else if meta.path.is_ident("lenient") {
+                    parsed.lenient = true;
+                    Ok(())
+                } else {
+                    Err(meta.error("unsupported bitconv container attribute"))
+                }
+            })?;
+        }
+        Ok(parsed)
+    }
+}
+
+/// Options given to an enum variant through `#[bitconv(...)]` attributes.
+#[derive(Default)]
+pub struct VariantAttrs {
+    /// Discriminant value selecting the variant.
+    pub tag: Option<Expr>,
+}
+
+impl VariantAttrs {
+    pub fn parse(attrs: &[Attribute]) -> Result<Self> {
+        let mut parsed = VariantAttrs::default();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("bitconv")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("tag") {
+                    parsed.tag = Some(meta.value()?.parse()?);
+                    Ok(())
+                } else {
+                    Err(meta.error("unsupported bitconv variant attribute"))
+                }
+            })?;
+        }
+        Ok(parsed)
+    }
+}
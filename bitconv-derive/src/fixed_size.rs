@@ -0,0 +1,80 @@
+use crate::attr::{ContainerAttrs, FieldAttrs};
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{parse_quote, Data, DeriveInput, Error, Result};
+
+pub fn expand(input: &DeriveInput) -> Result<TokenStream> {
+    let attrs = ContainerAttrs::parse(&input.attrs)?;
+    let fields = match &input.data {
+        Data::Struct(data) => &data.fields,
+        _ => return Err(Error::new_spanned(input, "FixedSize can only be derived for structs")),
+    };
+
+    let mut sizes = Vec::new();
+    let mut offsets = Vec::new();
+    for field in fields {
+        let field_attrs = FieldAttrs::parse(&field.attrs)?;
+        if field_attrs.since.is_some() || field_attrs.bit.is_some() || field_attrs.count.is_some() {
+            return Err(Error::new_spanned(
+                field,
+                "fields with `since`, `bit` or `count` attributes do not have a fixed size",
+            ));
+        }
+        let ty = &field.ty;
+        let offset = quote! { 0 #(+ #sizes)* };
+        if let Some(ident) = &field.ident {
+            let vis = &field.vis;
+            let name = format_ident!("{}_OFFSET", ident.to_string().to_uppercase());
+            offsets.push(quote! { #vis const #name: usize = #offset; });
+        }
+        sizes.push(quote! { <#ty as ::bitconv::FixedSize>::SIZE });
+    }
+    let all_offsets = (0..sizes.len()).map(|index| {
+        let before = &sizes[..index];
+        quote! { 0 #(+ #before)* }
+    });
+    let count = sizes.len();
+
+    let mut generics = input.generics.clone();
+    for param in generics.type_params_mut() {
+        param.bounds.push(parse_quote!(::bitconv::FixedSize));
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let name = &input.ident;
+
+    let assertion = match &attrs.size {
+        Some(size) if !input.generics.params.is_empty() => {
+            return Err(Error::new_spanned(size, "`size` is not supported on generic structs"))
+        }
+        Some(size) => quote! {
+            const _: () = assert!(
+                <#name as ::bitconv::FixedSize>::SIZE == #size,
+                concat!(
+                    "the fields of `",
+                    stringify!(#name),
+                    "` do not add up to #[bitconv(size = ",
+                    stringify!(#size),
+                    ")]"
+                )
+            );
+        },
+        None => TokenStream::new(),
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::bitconv::FixedSize for #name #ty_generics #where_clause {
+            const SIZE: usize = 0 #(+ #sizes)*;
+        }
+
+        #[allow(dead_code)]
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Byte offset of each field from the start of the struct, in
+            /// declaration order.
+            pub const OFFSETS: [usize; #count] = [#(#all_offsets),*];
+
+            #(#offsets)*
+        }
+
+        #assertion
+    })
+}
@@ -0,0 +1,301 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{
+    parse::{Parse, ParseStream}, punctuated::Punctuated, Error, Expr, LitStr, Result, Token
+};
+
+/// Kind of a single item of a Python `struct` format string.
+#[derive(Clone, Copy)]
+enum Kind {
+    /// `x`, a padding byte.
+    Pad,
+    /// An integer of the given Rust type and size.
+    Int(&'static str, usize),
+    /// `?`, a byte that is `true` unless 0.
+    Bool,
+    /// `f` and `d`, floating-point numbers of the given Rust type and size.
+    Float(&'static str, usize),
+    /// `Ns`, a run of N raw bytes.
+    Bytes(usize),
+}
+
+impl Kind {
+    fn size(self) -> usize {
+        match self {
+            Kind::Pad | Kind::Bool => 1,
+            Kind::Int(_, size) | Kind::Float(_, size) => size,
+            Kind::Bytes(len) => len,
+        }
+    }
+
+    fn has_value(self) -> bool { !matches!(self, Kind::Pad) }
+}
+
+/// A parsed format string: its endianness, as the name of a `bitconv` marker,
+/// and its items with the padding native alignment puts before each.
+pub struct Format {
+    endian: &'static str,
+    items: Vec<(usize, Kind)>,
+}
+
+impl Format {
+    pub fn parse(format: &LitStr) -> Result<Self> {
+        let value = format.value();
+        let error = |message: String| Error::new(format.span(), message);
+        let mut chars = value.chars().peekable();
+        let (endian, aligned) = match chars.peek() {
+            Some('<') => ("Little", false),
+            Some('>') | Some('!') => ("Big", false),
+            Some('=') => ("Native", false),
+            Some('@') => ("Native", true),
+            _ => ("Native", true),
+        };
+        if matches!(chars.peek(), Some('<' | '>' | '!' | '=' | '@')) {
+            chars.next();
+        }
+
+        let mut items = Vec::new();
+        let mut offset = 0;
+        while let Some(c) = chars.next() {
+            if c.is_whitespace() {
+                continue;
+            }
+            let mut count = None;
+            let mut c = c;
+            while let Some(digit) = c.to_digit(10) {
+                count = Some(count.unwrap_or(0) * 10 + digit as usize);
+                c = match chars.next() {
+                    Some(next) => next,
+                    None => {
+                        return Err(error("repeat count given without format specifier".into()))
+                    }
+                };
+            }
+            let kind = match c {
+                'x' => Kind::Pad,
+                'c' | 'B' => Kind::Int("u8", 1),
+                'b' => Kind::Int("i8", 1),
+                '?' => Kind::Bool,
+                'h' => Kind::Int("i16", 2),
+                'H' => Kind::Int("u16", 2),
+                'i' => Kind::Int("i32", 4),
+                'I' => Kind::Int("u32", 4),
+                'l' | 'L' if aligned => {
+                    return Err(error(format!(
+                        "the native size of `{}` depends on the platform; start the format \
+                         with `=`, `<`, `>` or `!` for its standard size of 4 bytes",
+                        c
+                    )))
+                }
+                'l' => Kind::Int("i32", 4),
+                'L' => Kind::Int("u32", 4),
+                'q' => Kind::Int("i64", 8),
+                'Q' => Kind::Int("u64", 8),
+                'f' => Kind::Float("f32", 4),
+                'd' => Kind::Float("f64", 8),
+                's' => Kind::Bytes(count.take().unwrap_or(1)),
+                'e' | 'n' | 'N' | 'p' | 'P' => {
+                    return Err(error(format!("format specifier `{}` is not supported", c)))
+                }
+                _ => return Err(error(format!("bad format specifier `{}`", c))),
+            };
+            for _ in 0..count.unwrap_or(1) {
+                let padding = match (aligned, kind) {
+                    (true, Kind::Int(..) | Kind::Float(..)) => {
+                        (kind.size() - offset % kind.size()) % kind.size()
+                    }
+                    _ => 0,
+                };
+                items.push((padding, kind));
+                offset += padding + kind.size();
+            }
+        }
+        Ok(Format { endian, items })
+    }
+
+    fn size(&self) -> usize { self.items.iter().map(|(padding, kind)| padding + kind.size()).sum() }
+
+    fn value_types(&self) -> Vec<TokenStream> {
+        self.items
+            .iter()
+            .filter(|(_, kind)| kind.has_value())
+            .map(|(_, kind)| match *kind {
+                Kind::Pad => unreachable!(),
+                Kind::Int(ty, _) | Kind::Float(ty, _) => {
+                    let ty = format_ident!("{}", ty);
+                    quote!(#ty)
+                }
+                Kind::Bool => quote!(bool),
+                Kind::Bytes(len) => quote!([u8; #len]),
+            })
+            .collect()
+    }
+
+    fn endian(&self) -> TokenStream {
+        let endian = format_ident!("{}", self.endian);
+        quote!(::bitconv::endian::#endian)
+    }
+}
+
+/// Input of `unpack!` and `unpack_from!`: a format string, the bytes and, for
+/// the latter, the offset to start at.
+pub struct UnpackInput {
+    format: LitStr,
+    data: Expr,
+    offset: Option<Expr>,
+}
+
+impl Parse for UnpackInput {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let format = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let data = input.parse()?;
+        let offset = match input.parse::<Option<Token![,]>>()? {
+            Some(_) if !input.is_empty() => Some(input.parse()?),
+            _ => None,
+        };
+        input.parse::<Option<Token![,]>>()?;
+        Ok(UnpackInput { format, data, offset })
+    }
+}
+
+pub fn expand_unpack(input: UnpackInput, from: bool) -> Result<TokenStream> {
+    let format = Format::parse(&input.format)?;
+    let endian = format.endian();
+    let types = format.value_types();
+    let data = &input.data;
+    let (offset, end) = match (from, &input.offset) {
+        (true, Some(offset)) => (quote!(#offset), TokenStream::new()),
+        (true, None) => return Err(Error::new(input.format.span(), "expected an offset")),
+        (false, Some(offset)) => return Err(Error::new_spanned(offset, "unexpected offset")),
+        (false, None) => (quote!(0), quote! { __reader.assert_exhausted()?; }),
+    };
+
+    let mut reads = Vec::new();
+    let mut values = Vec::new();
+    for (padding, kind) in &format.items {
+        if *padding > 0 {
+            reads.push(quote! { __reader.read_bytes(#padding)?; });
+        }
+        let index = values.len();
+        let value = format_ident!("__v{}", index);
+        let read = match *kind {
+            Kind::Pad => {
+                reads.push(quote! { __reader.read_bytes(1)?; });
+                continue;
+            }
+            Kind::Int(ty, _) => {
+                let ty = format_ident!("{}", ty);
+                quote! { __reader.read::<#ty>() }
+            }
+            Kind::Bool => quote! { __reader.read::<u8>().map(|byte| byte != 0) },
+            Kind::Float(ty, size) => {
+                let ty = format_ident!("{}", ty);
+                let bits = format_ident!("u{}", size * 8);
+                quote! { __reader.read::<#bits>().map(#ty::from_bits) }
+            }
+            Kind::Bytes(len) => quote! { __reader.read::<[u8; #len]>() },
+        };
+        reads.push(quote! {
+            let #value = #read.map_err(|error| error.with_index(#index))?;
+        });
+        values.push(value);
+    }
+
+    Ok(quote! {
+        {
+            fn __unpack(__data: &[u8], __offset: usize) -> ::bitconv::Result<(#(#types,)*)> {
+                let mut __reader = ::bitconv::Reader::<#endian>::new(__data);
+                __reader.set_position(__offset);
+                #(#reads)*
+                #end
+                ::core::result::Result::Ok((#(#values,)*))
+            }
+            __unpack(::core::convert::AsRef::<[u8]>::as_ref(&#data), #offset)
+        }
+    })
+}
+
+/// Input of `pack!`: a format string followed by a value for each of its
+/// items but padding.
+pub struct PackInput {
+    format: LitStr,
+    values: Punctuated<Expr, Token![,]>,
+}
+
+impl Parse for PackInput {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let format = input.parse()?;
+        let values = match input.parse::<Option<Token![,]>>()? {
+            Some(_) => Punctuated::parse_terminated(input)?,
+            None => Punctuated::new(),
+        };
+        Ok(PackInput { format, values })
+    }
+}
+
+pub fn expand_pack(input: PackInput) -> Result<TokenStream> {
+    let format = Format::parse(&input.format)?;
+    let endian = format.endian();
+    let size = format.size();
+    let expected = format.items.iter().filter(|(_, kind)| kind.has_value()).count();
+    if input.values.len() != expected {
+        return Err(Error::new(
+            input.format.span(),
+            format!("format requires {} values, but {} were given", expected, input.values.len()),
+        ));
+    }
+
+    let mut values = input.values.iter();
+    let mut writes = Vec::new();
+    for (padding, kind) in &format.items {
+        if *padding > 0 {
+            writes.push(quote! { __writer.write_bytes(&[0; #padding]); });
+        }
+        let write = match *kind {
+            Kind::Pad => {
+                writes.push(quote! { __writer.write_bytes(&[0]); });
+                continue;
+            }
+            Kind::Int(ty, _) | Kind::Float(ty, _) => {
+                let ty = format_ident!("{}", ty);
+                let value = values.next();
+                quote! {
+                    let __value: #ty = #value;
+                    __writer.write(&__value);
+                }
+            }
+            Kind::Bool => {
+                let value = values.next();
+                quote! {
+                    let __value: bool = #value;
+                    __writer.write(&__value);
+                }
+            }
+            Kind::Bytes(len) => {
+                let value = values.next();
+                quote! {
+                    let __value: &[u8] = ::core::convert::AsRef::<[u8]>::as_ref(&#value);
+                    let __len = __value.len().min(#len);
+                    __writer.write_bytes(&__value[..__len]);
+                    __writer.write_bytes(&[0; #len][__len..]);
+                }
+            }
+        };
+        writes.push(quote! { { #write } });
+    }
+
+    Ok(quote! {
+        {
+            #[allow(unused_mut)]
+            let mut __writer = ::bitconv::Writer::<#endian>::with_capacity(#size);
+            #(#writes)*
+            __writer.into_inner()
+        }
+    })
+}
+
+pub fn expand_calcsize(format: LitStr) -> Result<TokenStream> {
+    let size = Format::parse(&format)?.size();
+    Ok(quote!(#size))
+}
@@ -0,0 +1,312 @@
+use crate::attr::{ContainerAttrs, FieldAttrs, VariantAttrs};
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{parse_quote, Data, DataEnum, DeriveInput, Error, Expr, Fields};
+use syn::{GenericArgument, PathArguments, Result, Type};
+
+pub fn expand(input: &DeriveInput) -> Result<TokenStream> {
+    let attrs = ContainerAttrs::parse(&input.attrs)?;
+    let body = match &input.data {
+        Data::Struct(data) => {
+            let (reads, construct) = read_fields(&data.fields, quote!(Self), Mode::Strict)?;
+            quote! {
+                #reads
+                ::core::result::Result::Ok((#construct, __offset - __start_index))
+            }
+        }
+        Data::Enum(data) => {
+            let tag = attrs.tag.as_ref().ok_or_else(|| {
+                Error::new_spanned(input, "enums need a `#[bitconv(tag = ...)]` discriminant type")
+            })?;
+            read_variants(data, tag)?
+        }
+        Data::Union(_) => {
+            return Err(Error::new_spanned(input, "FromBytes cannot be derived for unions"))
+        }
+    };
+
+    let mut generics = input.generics.clone();
+    for param in generics.type_params_mut() {
+        param.bounds.push(parse_quote!(::bitconv::FromBytes));
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let name = &input.ident;
+
+    let lenient = match (&input.data, attrs.lenient) {
+        (Data::Struct(data), true) => {
+            let mut generics = generics.clone();
+            for param in generics.type_params_mut() {
+                param.bounds.push(parse_quote!(::core::default::Default));
+            }
+            let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+            let (reads, construct) = read_fields(&data.fields, quote!(Self), Mode::Lenient)?;
+            quote! {
+                impl #impl_generics ::bitconv::FromBytesLenient for #name #ty_generics #where_clause {
+                    fn from_bytes_lenient<__T: ::bitconv::BitConvEndian>(
+                        __data: &[u8],
+                        __start_index: usize,
+                    ) -> (Self, usize, ::bitconv::__private::Vec<::bitconv::Error>) {
+                        let __version = u32::MAX;
+                        let mut __errors = ::bitconv::__private::Vec::new();
+                        #[allow(unused_mut)]
+                        let mut __offset = __start_index;
+                        #reads
+                        (#construct, __offset - __start_index, __errors)
+                    }
+                }
+            }
+        }
+        (_, true) => {
+            return Err(Error::new_spanned(input, "`lenient` is only supported on structs"))
+        }
+        (_, false) => TokenStream::new(),
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::bitconv::FromBytes for #name #ty_generics #where_clause {
+            #[inline]
+            fn from_bytes<__T: ::bitconv::BitConvEndian>(
+                __data: &[u8],
+                __start_index: usize,
+            ) -> ::bitconv::Result<(Self, usize)> {
+                <Self as ::bitconv::FromBytes>::from_bytes_versioned::<__T>(
+                    __data,
+                    __start_index,
+                    u32::MAX,
+                )
+            }
+
+            fn from_bytes_versioned<__T: ::bitconv::BitConvEndian>(
+                __data: &[u8],
+                __start_index: usize,
+                __version: u32,
+            ) -> ::bitconv::Result<(Self, usize)> {
+                #[allow(unused_mut)]
+                let mut __offset = __start_index;
+                #body
+            }
+        }
+
+        #lenient
+    })
+}
+
+/// How the generated code reacts to a field that fails to convert.
+#[derive(Clone, Copy)]
+enum Mode {
+    /// Return the error from the conversion.
+    Strict,
+    /// Push the error to `__errors`, set the field to its default value and
+    /// carry on with the next field.
+    Lenient,
+}
+
+impl Mode {
+    /// Generates the statement leaving the current field with `error`.
+    fn fail(self, error: TokenStream) -> TokenStream {
+        match self {
+            Mode::Strict => quote! { return ::core::result::Result::Err(#error) },
+            Mode::Lenient => quote! { break '__field ::core::result::Result::Err(#error) },
+        }
+    }
+}
+
+/// Generates the statements reading `fields` in declaration order, along with
+/// the expression constructing `path` from them.
+fn read_fields(
+    fields: &Fields,
+    path: TokenStream,
+    mode: Mode,
+) -> Result<(TokenStream, TokenStream)> {
+    let mut reads = Vec::new();
+    let mut bindings = Vec::new();
+    let mut mask = None;
+    for (index, field) in fields.iter().enumerate() {
+        let attrs = FieldAttrs::parse(&field.attrs)?;
+        let binding = match &field.ident {
+            Some(ident) => ident.clone(),
+            None => format_ident!("__field{}", index),
+        };
+        let ty = &field.ty;
+        let name = match &field.ident {
+            Some(ident) => ident.to_string(),
+            None => index.to_string(),
+        };
+
+        let value_ty = if attrs.bit.is_some() { generic_inner(ty, "Option")? } else { ty };
+        let read = match (&attrs.count, &attrs.max) {
+            (Some(count), max) => {
+                read_vec(generic_inner(value_ty, "Vec")?, count, max.as_ref(), &name, mode)
+            }
+            (None, Some(_)) => {
+                return Err(Error::new_spanned(field, "`max` requires a `count` attribute"))
+            }
+            (None, None) => read_value(value_ty, quote! { __error.with_field(#name) }, mode),
+        };
+        let read = match attrs.bit {
+            Some(bit) => {
+                let mask = mask.as_ref().ok_or_else(|| {
+                    Error::new_spanned(field, "`bit` requires a preceding `mask` field")
+                })?;
+                quote! {
+                    if #mask & (1 << #bit) != 0 {
+                        ::core::option::Option::Some(#read)
+                    } else {
+                        ::core::option::Option::None
+                    }
+                }
+            }
+            None => read,
+        };
+        let read = match attrs.since {
+            Some(since) => quote! {
+                if __version >= #since { #read } else { ::core::default::Default::default() }
+            },
+            None => read,
+        };
+        let read = match mode {
+            Mode::Strict => read,
+            Mode::Lenient => quote! {{
+                let __field_start = __offset;
+                let __result: ::bitconv::Result<#ty> =
+                    '__field: { ::core::result::Result::Ok(#read) };
+                match __result {
+                    ::core::result::Result::Ok(__value) => __value,
+                    ::core::result::Result::Err(__error) => {
+                        __offset = __field_start;
+                        __errors.push(__error);
+                        ::core::default::Default::default()
+                    }
+                }
+            }},
+        };
+
+        reads.push(quote! {
+            ::bitconv::__trace_field!(::core::any::type_name::<Self>(), #name, __offset);
+            let #binding: #ty = #read;
+        });
+        if attrs.mask {
+            mask = Some(binding.clone());
+        }
+        bindings.push(binding);
+    }
+
+    let construct = match fields {
+        Fields::Named(_) => quote! { #path { #(#bindings),* } },
+        Fields::Unnamed(_) => quote! { #path(#(#bindings),*) },
+        Fields::Unit => quote! { #path },
+    };
+    Ok((quote! { #(#reads)* }, construct))
+}
+
+/// Generates the statements reading a discriminant of type `tag`, followed by
+/// the fields of the variant it selects.
+fn read_variants(data: &DataEnum, tag: &Type) -> Result<TokenStream> {
+    let mut arms = Vec::new();
+    for variant in &data.variants {
+        let attrs = VariantAttrs::parse(&variant.attrs)?;
+        let value = match (&attrs.tag, &variant.discriminant) {
+            (Some(value), _) | (None, Some((_, value))) => value,
+            (None, None) => {
+                return Err(Error::new_spanned(
+                    variant,
+                    "variants need a `#[bitconv(tag = ...)]` value or an explicit discriminant",
+                ))
+            }
+        };
+        let ident = &variant.ident;
+        let (reads, construct) = read_fields(&variant.fields, quote!(Self::#ident), Mode::Strict)?;
+        arms.push(quote! {
+            if __tag == #value {
+                #reads
+                ::core::result::Result::Ok((#construct, __offset - __start_index))
+            }
+        });
+    }
+
+    let read = read_value(tag, quote! { __error }, Mode::Strict);
+    Ok(quote! {
+        let __tag: #tag = #read;
+        #(#arms else)* {
+            ::core::result::Result::Err(::bitconv::Error::new(::bitconv::ErrorKind::InvalidTag {
+                start_index: __start_index,
+            }))
+        }
+    })
+}
+
+/// Generates the block reading a single value of type `ty` at `__offset`,
+/// adding context to any error through the `context` expression over
+/// `__error`.
+fn read_value(ty: &Type, context: TokenStream, mode: Mode) -> TokenStream {
+    let fail = mode.fail(context);
+    quote! {{
+        match <#ty as ::bitconv::FromBytes>::from_bytes_versioned::<__T>(
+            __data,
+            __offset,
+            __version,
+        ) {
+            ::core::result::Result::Ok((__value, __read)) => {
+                __offset += __read;
+                __value
+            }
+            ::core::result::Result::Err(__error) => #fail,
+        }
+    }}
+}
+
+/// Generates the block reading `count` consecutive values of type `ty` at
+/// `__offset` into a `Vec` for the field `name`.
+fn read_vec(ty: &Type, count: &Expr, max: Option<&Expr>, name: &str, mode: Mode) -> TokenStream {
+    let read = read_value(ty, quote! { __error.with_index(__index).with_field(#name) }, mode);
+    let fail = mode.fail(quote! {
+        ::bitconv::Error::new(::bitconv::ErrorKind::InvalidCount { start_index: __offset })
+            .with_field(#name)
+    });
+    let limit = max.map(|max| {
+        let fail = mode.fail(quote! {
+            ::bitconv::Error::new(::bitconv::ErrorKind::LimitExceeded {
+                start_index: __offset,
+                len: __count,
+                max: __max,
+            })
+            .with_field(#name)
+        });
+        quote! {
+            let __max: usize = #max;
+            if __count > __max {
+                #fail;
+            }
+        }
+    });
+    quote! {{
+        let __count: usize = match ::core::convert::TryFrom::try_from(#count) {
+            ::core::result::Result::Ok(__count) => __count,
+            ::core::result::Result::Err(_) => #fail,
+        };
+        #limit
+        let mut __values = ::bitconv::__private::Vec::with_capacity(::core::cmp::min(
+            __count,
+            __data.len().saturating_sub(__offset),
+        ));
+        for __index in 0..__count {
+            __values.push(#read);
+        }
+        __values
+    }}
+}
+
+/// Returns `T` for a field of type `wrapper<T>`.
+fn generic_inner<'a>(ty: &'a Type, wrapper: &str) -> Result<&'a Type> {
+    if let Type::Path(path) = ty {
+        let segment = path.path.segments.last().unwrap();
+        if let (true, PathArguments::AngleBracketed(args)) =
+            (segment.ident == wrapper, &segment.arguments)
+        {
+            if let Some(GenericArgument::Type(inner)) = args.args.first() {
+                return Ok(inner);
+            }
+        }
+    }
+    Err(Error::new_spanned(ty, format!("expected a field of type `{}<T>`", wrapper)))
+}
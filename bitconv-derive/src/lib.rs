@@ -0,0 +1,345 @@
+//! Derive macros for the [`bitconv`](https://docs.rs/bitconv) crate.
+//!
+//! These are re-exported by `bitconv` when its `derive` feature is enabled
+//! and should be used through that crate rather than depended on directly.
+extern crate proc_macro;
+
+mod attr;
+mod fixed_size;
+mod format;
+mod from_bytes;
+mod to_bytes;
+
+use proc_macro::TokenStream;
+use syn::{parse_macro_input, DeriveInput, LitStr};
+
+/// Derives `bitconv::FromBytes` for a struct by converting each of its fields
+/// in declaration order.
+///
+/// Enums can be derived too when given a discriminant type with
+/// `#[bitconv(tag = u8)]`. The discriminant is read first and selects the
+/// variant whose `#[bitconv(tag = N)]` value (or explicit discriminant, for
+/// enums without fields) matches it, whose fields are then read like those of
+/// a struct. Unknown discriminants fail with `ErrorKind::InvalidTag`.
+///
+/// Errors from fields are given the name of the field (or its index, for tuple
+/// structs) and the index of the element within `Vec` fields as context, so a
+/// failure deep inside nested types reports a path such as
+/// `header.entries[3].length`.
+///
+/// Every field type must itself implement `FromBytes`. Fields can be tuned
+/// with `#[bitconv(...)]` attributes:
+///
+/// * `since = N` marks a field that only exists from format version `N`
+///   onwards. When reading an older version through `from_bytes_versioned` the
+///   field consumes no bytes and is set to its `Default` value instead.
+///   `from_bytes` always reads the latest version.
+/// * `mask` marks an integer field as a presence bitmap for the optional fields
+///   that follow it.
+/// * `bit = N` marks an `Option<T>` field that is only present when bit `N`
+///   (counting from the least significant bit) of the preceding `mask` field is
+///   set. Absent fields consume no bytes and are set to `None`.
+/// * `count = "expr"` marks a `Vec<T>` field holding as many elements as the
+///   given expression over earlier fields evaluates to. A negative count, or
+///   one that does not fit in a `usize`, fails with `ErrorKind::InvalidCount`.
+///   Space is never reserved for more elements than there are bytes left, so a
+///   bogus count cannot force a large allocation.
+/// * `max = N` caps the element count of a `count` field, failing with
+///   `ErrorKind::LimitExceeded` before anything is read when the count is
+///   larger. This also bounds the work done for elements that take up no bytes,
+///   which the remaining length does not limit.
+///
+/// Adding `#[bitconv(lenient)]` to a struct also derives
+/// `bitconv::FromBytesLenient`, which sets fields that fail to convert to
+/// their `Default` value and returns their errors together instead of stopping
+/// at the first one. It requires the `alloc` feature of `bitconv`, and every
+/// field type must implement `Default`.
+/// # Example
+///
+/// ```
+/// use bitconv::{endian::Little, ErrorKind, FromBytes, FromBytesLenient};
+///
+/// #[derive(Debug, PartialEq, FromBytes)]
+/// struct Save {
+///     level: u8,
+///     score: u32,
+///     #[bitconv(since = 3)]
+///     coins: u16,
+/// }
+///
+/// let buffer = [7, 232, 3, 0, 0, 5, 0];
+/// assert_eq!(
+///     Ok((Save { level: 7, score: 1000, coins: 0 }, 5)),
+///     Save::from_bytes_versioned::<Little>(&buffer, 0, 2)
+/// );
+/// assert_eq!(
+///     Ok((Save { level: 7, score: 1000, coins: 5 }, 7)),
+///     Save::from_bytes::<Little>(&buffer, 0)
+/// );
+///
+/// #[derive(Debug, PartialEq, FromBytes)]
+/// struct Telemetry {
+///     #[bitconv(mask)]
+///     present: u8,
+///     #[bitconv(bit = 0)]
+///     temperature: Option<i16>,
+///     #[bitconv(bit = 1)]
+///     pressure: Option<u32>,
+/// }
+///
+/// let buffer = [2, 160, 134, 1, 0];
+/// assert_eq!(
+///     Ok((Telemetry { present: 2, temperature: None, pressure: Some(100000) }, 5)),
+///     Telemetry::from_bytes::<Little>(&buffer, 0)
+/// );
+///
+/// #[derive(Debug, PartialEq, FromBytes)]
+/// #[bitconv(tag = u8)]
+/// enum Message {
+///     #[bitconv(tag = 1)]
+///     Ping { sequence: u16 },
+///     #[bitconv(tag = 2)]
+///     Move(i16, i16),
+///     #[bitconv(tag = 3)]
+///     Quit,
+/// }
+///
+/// let buffer = [2, 10, 0, 246, 255];
+/// assert_eq!(Ok((Message::Move(10, -10), 5)), Message::from_bytes::<Little>(&buffer, 0));
+/// assert_eq!(
+///     ErrorKind::InvalidTag { start_index: 0 },
+///     Message::from_bytes::<Little>(&[4], 0).unwrap_err().kind()
+/// );
+///
+/// #[derive(Debug, PartialEq, FromBytes)]
+/// struct Table {
+///     num_entries: u8,
+///     #[bitconv(count = "num_entries", max = 16)]
+///     entries: Vec<u16>,
+/// }
+///
+/// let buffer = [2, 10, 0, 20, 0];
+/// assert_eq!(
+///     Ok((Table { num_entries: 2, entries: vec![10, 20] }, 5)),
+///     Table::from_bytes::<Little>(&buffer, 0)
+/// );
+/// assert_eq!(
+///     ErrorKind::LimitExceeded { start_index: 1, len: 17, max: 16 },
+///     Table::from_bytes::<Little>(&[17], 0).unwrap_err().kind()
+/// );
+///
+/// #[derive(Debug, PartialEq, FromBytes)]
+/// #[bitconv(lenient)]
+/// struct Reading {
+///     id: u8,
+///     value: u32,
+///     flags: u16,
+/// }
+///
+/// let (reading, read, errors) = Reading::from_bytes_lenient::<Little>(&[9, 1, 0], 0);
+/// assert_eq!(Reading { id: 9, value: 0, flags: 1 }, reading);
+/// assert_eq!(3, read);
+/// assert_eq!(1, errors.len());
+/// ```
+#[proc_macro_derive(FromBytes, attributes(bitconv))]
+pub fn derive_from_bytes(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    from_bytes::expand(&input).unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
+/// Derives `bitconv::FixedSize` for a struct whose fields all implement
+/// `FixedSize`, with `SIZE` being the sum of their sizes.
+///
+/// The byte offset of every field is exposed as well, through an associated
+/// `OFFSETS` array holding them in declaration order and, for named fields, a
+/// constant named after the field in upper case followed by `_OFFSET` with
+/// the same visibility as the field.
+///
+/// Adding `#[bitconv(size = N)]` to the struct fails the build if the fields
+/// do not add up to exactly `N` bytes, catching layout drift at compile time.
+/// Fields using the `since`, `bit` or `count` attributes of the `FromBytes`
+/// derive vary in size and are rejected.
+/// # Example
+///
+/// ```
+/// use bitconv::FixedSize;
+///
+/// #[derive(FixedSize)]
+/// #[bitconv(size = 8)]
+/// struct Record {
+///     id: u32,
+///     kind: u16,
+///     flags: (u8, u8),
+/// }
+///
+/// assert_eq!(8, Record::SIZE);
+/// assert_eq!([0, 4, 6], Record::OFFSETS);
+/// assert_eq!(4, Record::KIND_OFFSET);
+/// ```
+///
+/// ```compile_fail
+/// use bitconv::FixedSize;
+///
+/// #[derive(FixedSize)]
+/// #[bitconv(size = 8)]
+/// struct Record {
+///     id: u32,
+///     kind: u16,
+/// }
+/// ```
+#[proc_macro_derive(FixedSize, attributes(bitconv))]
+pub fn derive_fixed_size(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    fixed_size::expand(&input).unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
+/// Derives `bitconv::ToBytes` for a struct by writing each of its fields in
+/// declaration order, mirroring the `FromBytes` derive so a value written
+/// with the one reads back with the other.
+///
+/// Enums given a discriminant type with `#[bitconv(tag = u8)]` write the
+/// `#[bitconv(tag = N)]` value (or explicit discriminant) of their variant
+/// before its fields. Field attributes of the `FromBytes` derive need no
+/// special handling: `since` fields are always written, as for the latest
+/// version, `Option` fields write nothing when they are `None`, and `Vec`
+/// fields write their elements without a count. Keeping a `mask` or count
+/// field in step with the fields it describes is left to the caller.
+///
+/// Errors from fields are given the same context as when reading. Every field
+/// type must itself implement `ToBytes`, and through it `bitconv::ToWriter`,
+/// which the derived type implements as well.
+/// # Example
+///
+/// ```
+/// use bitconv::{endian::Little, FromBytes, ToBytes, ToWriter, Writer};
+///
+/// #[derive(Debug, PartialEq, FromBytes, ToBytes)]
+/// struct Point(i16, i16);
+///
+/// #[derive(Debug, PartialEq, FromBytes, ToBytes)]
+/// #[bitconv(tag = u8)]
+/// enum Shape {
+///     #[bitconv(tag = 1)]
+///     Dot(Point),
+///     #[bitconv(tag = 2)]
+///     Line { from: Point, to: Point },
+/// }
+///
+/// let shape = Shape::Line { from: Point(0, 1), to: Point(10, -10) };
+/// let mut writer = Writer::<Little>::new();
+/// shape.write_to(&mut writer).unwrap();
+/// assert_eq!([2, 0, 0, 1, 0, 10, 0, 246, 255], writer.as_slice());
+/// assert_eq!(Ok((shape, 9)), Shape::from_bytes::<Little>(writer.as_slice(), 0));
+/// ```
+#[proc_macro_derive(ToBytes, attributes(bitconv))]
+pub fn derive_to_bytes(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    to_bytes::expand(&input).unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
+/// Converts bytes laid out as described by a format string of Python's
+/// `struct` module, such as `"<IHH8s"`, to a tuple, as `struct.unpack` does.
+///
+/// The format is parsed at compile time, so a malformed one fails the build
+/// and the tuple has a Rust type for each item: `b`, `B`, `h`, `H`, `i`, `I`,
+/// `l`, `L`, `q` and `Q` the integer of the same size, `c` a `u8`, `?` a
+/// `bool`, `f` and `d` an `f32` and an `f64`, and `Ns` a `[u8; N]`. Padding
+/// bytes, `x`, are skipped. A leading `<` reads in little-endian, `>` and `!`
+/// in big-endian and `=` in the native endianness. `@`, or no prefix, also adds
+/// the padding that aligns each number to its size, as a C compiler would, and
+/// then rejects `l` and `L`, whose native size depends on the platform. The
+/// `e`, `n`, `N`, `p` and `P` specifiers are not supported.
+///
+/// Evaluates to a `bitconv::Result` of the tuple, failing with
+/// `ErrorKind::OutOfBounds` if the bytes are too short and with
+/// `ErrorKind::TrailingBytes` if they are longer than the format, like
+/// `struct.unpack`. See `unpack_from!` for reading from the middle of a buffer.
+/// # Example
+///
+/// ```
+/// use bitconv::unpack;
+///
+/// let record = [1, 0, 0, 0, 2, 0, 3, 0, 72, 69, 76, 76, 79, 0, 0, 0];
+/// let (id, kind, flags, name) = unpack!("<IHH8s", record).unwrap();
+/// assert_eq!((1, 2, 3), (id, kind, flags));
+/// assert_eq!(*b"HELLO\0\0\0", name);
+///
+/// // Native alignment puts a byte of padding before the `h`.
+/// let [low, high] = (-2i16).to_ne_bytes();
+/// assert_eq!(Ok((true, -2)), unpack!("?h", [1, 0, low, high]));
+/// ```
+///
+/// ```compile_fail
+/// let header = bitconv::unpack!("<Iz", [0; 5]);
+/// ```
+#[proc_macro]
+pub fn unpack(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as format::UnpackInput);
+    format::expand_unpack(input, false).unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
+/// Converts the bytes at an offset of a buffer laid out as described by a
+/// Python `struct` format string to a tuple, as `struct.unpack_from` does.
+///
+/// The format is interpreted as by `unpack!`, but bytes may follow the ones
+/// it describes.
+/// # Example
+///
+/// ```
+/// use bitconv::unpack_from;
+///
+/// let packet = [0xff, 0, 1, 0, 0, 0, 0x2a, 0xff];
+/// assert_eq!(Ok((1, 42)), unpack_from!(">HI", packet, 1));
+/// assert!(unpack_from!(">HI", packet, 3).is_err());
+/// ```
+#[proc_macro]
+pub fn unpack_from(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as format::UnpackInput);
+    format::expand_unpack(input, true).unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
+/// Converts values to a `Vec<u8>` laid out as described by a Python `struct`
+/// format string, as `struct.pack` does.
+///
+/// The format is interpreted as by `unpack!`, and the values, one for each
+/// item but padding, must have the types `unpack!` returns, except that `Ns`
+/// items take anything that is `AsRef<[u8]>` and, like Python, truncate it or
+/// pad it with zeros to N bytes. A wrong number of values fails the build.
+/// Needs the `alloc` feature of `bitconv`.
+/// # Example
+///
+/// ```
+/// use bitconv::{pack, unpack};
+///
+/// let record = pack!("<IHH8s", 1, 2, 3, b"HELLO");
+/// assert_eq!([1, 0, 0, 0, 2, 0, 3, 0, 72, 69, 76, 76, 79, 0, 0, 0], record[..]);
+/// assert_eq!(Ok((1, 2, 3, *b"HELLO\0\0\0")), unpack!("<IHH8s", record));
+/// assert_eq!([0, 0, 0, 0, 0, 1, 1], pack!(">H2xH?", 0, 1, true)[..]);
+/// ```
+///
+/// ```compile_fail
+/// let record = bitconv::pack!("<IH", 1);
+/// ```
+#[proc_macro]
+pub fn pack(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as format::PackInput);
+    format::expand_pack(input).unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
+/// Evaluates to the number of bytes described by a Python `struct` format
+/// string, as `struct.calcsize` does, as a `usize` constant.
+/// # Example
+///
+/// ```
+/// use bitconv::calcsize;
+///
+/// const RECORD_SIZE: usize = calcsize!("<IHH8s");
+/// assert_eq!(16, RECORD_SIZE);
+/// assert_eq!(5, calcsize!("=bi"));
+/// assert_eq!(8, calcsize!("bi"));
+/// ```
+#[proc_macro]
+pub fn calcsize(input: TokenStream) -> TokenStream {
+    let format = parse_macro_input!(input as LitStr);
+    format::expand_calcsize(format).unwrap_or_else(syn::Error::into_compile_error).into()
+}
@@ -0,0 +1,112 @@
+use crate::attr::{ContainerAttrs, VariantAttrs};
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{parse_quote, Data, DeriveInput, Error, Fields, Result};
+
+pub fn expand(input: &DeriveInput) -> Result<TokenStream> {
+    let attrs = ContainerAttrs::parse(&input.attrs)?;
+    let mut arms = Vec::new();
+    let mut lens = Vec::new();
+    match &input.data {
+        Data::Struct(data) => {
+            let (pattern, writes, len) = write_fields(&data.fields, quote!(Self), None);
+            arms.push(quote! { #pattern => { #writes } });
+            lens.push(quote! { #pattern => #len });
+        }
+        Data::Enum(data) => {
+            let tag = attrs.tag.as_ref().ok_or_else(|| {
+                Error::new_spanned(input, "enums need a `#[bitconv(tag = ...)]` discriminant type")
+            })?;
+            for variant in &data.variants {
+                let variant_attrs = VariantAttrs::parse(&variant.attrs)?;
+                let value = match (&variant_attrs.tag, &variant.discriminant) {
+                    (Some(value), _) | (None, Some((_, value))) => value,
+                    (None, None) => return Err(Error::new_spanned(
+                        variant,
+                        "variants need a `#[bitconv(tag = ...)]` value or an explicit discriminant",
+                    )),
+                };
+                let ident = &variant.ident;
+                let tag = quote! { { let __tag: #tag = #value; __tag } };
+                let (pattern, writes, len) =
+                    write_fields(&variant.fields, quote!(Self::#ident), Some(tag));
+                arms.push(quote! { #pattern => { #writes } });
+                lens.push(quote! { #pattern => #len });
+            }
+        }
+        Data::Union(_) => {
+            return Err(Error::new_spanned(input, "ToBytes cannot be derived for unions"))
+        }
+    }
+
+    let mut generics = input.generics.clone();
+    for param in generics.type_params_mut() {
+        param.bounds.push(parse_quote!(::bitconv::ToBytes));
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let name = &input.ident;
+
+    Ok(quote! {
+        impl #impl_generics ::bitconv::ToBytes for #name #ty_generics #where_clause {
+            fn to_bytes<__T: ::bitconv::BitConvEndian>(
+                &self,
+                __data: &mut [u8],
+                __start_index: usize,
+            ) -> ::bitconv::Result<usize> {
+                #[allow(unused_mut)]
+                let mut __offset = __start_index;
+                match self {
+                    #(#arms)*
+                }
+                ::core::result::Result::Ok(__offset - __start_index)
+            }
+
+            fn byte_len(&self) -> usize {
+                match self {
+                    #(#lens,)*
+                }
+            }
+        }
+    })
+}
+
+/// Generates the pattern binding `fields` of `path`, the statements writing
+/// them after the optional `tag`, and the expression adding up their lengths.
+fn write_fields(
+    fields: &Fields,
+    path: TokenStream,
+    tag: Option<TokenStream>,
+) -> (TokenStream, TokenStream, TokenStream) {
+    let mut bindings = Vec::new();
+    let mut writes = Vec::new();
+    let mut lens = Vec::new();
+    if let Some(tag) = tag {
+        writes.push(quote! {
+            __offset += ::bitconv::ToBytes::to_bytes::<__T>(&#tag, __data, __offset)?;
+        });
+        lens.push(quote! { ::bitconv::ToBytes::byte_len(&#tag) });
+    }
+    for (index, field) in fields.iter().enumerate() {
+        let binding = match &field.ident {
+            Some(ident) => ident.clone(),
+            None => format_ident!("__field{}", index),
+        };
+        let name = match &field.ident {
+            Some(ident) => ident.to_string(),
+            None => index.to_string(),
+        };
+        writes.push(quote! {
+            __offset += ::bitconv::ToBytes::to_bytes::<__T>(#binding, __data, __offset)
+                .map_err(|__error: ::bitconv::Error| __error.with_field(#name))?;
+        });
+        lens.push(quote! { ::bitconv::ToBytes::byte_len(#binding) });
+        bindings.push(binding);
+    }
+
+    let pattern = match fields {
+        Fields::Named(_) => quote! { #path { #(#bindings),* } },
+        Fields::Unnamed(_) => quote! { #path(#(#bindings),*) },
+        Fields::Unit => quote! { #path },
+    };
+    (pattern, quote! { #(#writes)* }, quote! { 0 #(+ #lens)* })
+}
@@ -0,0 +1,75 @@
+#![allow(dead_code)]
+
+use bitconv::{endian::Little, FixedSize, FromBytes};
+
+#[derive(Debug, PartialEq, FixedSize, FromBytes)]
+#[bitconv(size = 7)]
+struct Header {
+    magic: u32,
+    kind: u8,
+    length: i16,
+}
+
+#[derive(FixedSize)]
+struct Pair(u8, u16);
+
+#[derive(FixedSize)]
+struct Empty;
+
+const RECORD_SIZE: usize = 18;
+
+#[derive(FixedSize)]
+#[bitconv(size = RECORD_SIZE)]
+struct Record {
+    header: Header,
+    pair: Pair,
+    empty: Empty,
+    tail: (u32, u32),
+}
+
+#[derive(FixedSize)]
+struct Wrapper<T> {
+    value: T,
+    tag: u8,
+}
+
+#[test]
+fn derive_fixed_size_test() {
+    assert_eq!(7, Header::SIZE);
+    assert_eq!(3, Pair::SIZE);
+    assert_eq!(0, Empty::SIZE);
+    assert_eq!(18, Record::SIZE);
+    assert_eq!(3, Wrapper::<u16>::SIZE);
+    assert_eq!(4, Wrapper::<Pair>::SIZE);
+}
+
+#[test]
+fn derive_fixed_size_matches_read_test() {
+    let buffer = [15, 0, 0, 128, 16, 39, 240, 216];
+    let (_, read) = Header::from_bytes::<Little>(&buffer, 0).unwrap();
+    assert_eq!(Header::SIZE, read);
+}
+
+#[test]
+fn derive_offsets_test() {
+    assert_eq!([0, 4, 5], Header::OFFSETS);
+    assert_eq!(0, Header::MAGIC_OFFSET);
+    assert_eq!(4, Header::KIND_OFFSET);
+    assert_eq!(5, Header::LENGTH_OFFSET);
+    assert_eq!([0, 1], Pair::OFFSETS);
+    assert_eq!([0; 0], Empty::OFFSETS);
+    assert_eq!([0, 7, 10, 10], Record::OFFSETS);
+    assert_eq!(10, Record::TAIL_OFFSET);
+    assert_eq!([0, 8], Wrapper::<u64>::OFFSETS);
+    assert_eq!(3, Wrapper::<Pair>::TAG_OFFSET);
+}
+
+#[test]
+fn derive_offsets_patch_test() {
+    let mut buffer = [15, 0, 0, 128, 16, 39, 240, 216];
+    buffer[Header::KIND_OFFSET] = 7;
+    let (header, _) = Header::from_bytes::<Little>(&buffer, 0).unwrap();
+    assert_eq!(7, header.kind);
+    let (length, _) = i16::from_bytes::<Little>(&buffer, Header::LENGTH_OFFSET).unwrap();
+    assert_eq!(header.length, length);
+}
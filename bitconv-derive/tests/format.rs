@@ -0,0 +1,67 @@
+use bitconv::{calcsize, pack, unpack, unpack_from, Error, ErrorKind};
+
+fn sample() -> Vec<u8> {
+    [&[0xff, 0xfe, 1, 2, 0, 0, 0, 0][..], &1.5f64.to_le_bytes(), &(-1.5f32).to_le_bytes()].concat()
+}
+
+#[test]
+fn unpack_test() {
+    let buffer = sample();
+    assert_eq!(Ok((-1, 254, 0x0201, 1.5, -1.5)), unpack!("<bBh4xdf", buffer));
+    assert_eq!(Ok((0xfffe_0102, [0; 4])), unpack!(">I4s 12x", buffer));
+    assert_eq!(Ok((0xfffe, 0x0102)), unpack!("!HH16x", buffer));
+    assert_eq!(Ok(()), unpack!("<", []));
+    assert_eq!(Ok((true, false, 3, [], [7])), unpack!("<??c0s1s", [2, 0, 3, 7]));
+    assert_eq!(Ok((-1, 1)), unpack!("<qQ", [[0xff; 8], [1, 0, 0, 0, 0, 0, 0, 0]].concat()));
+    assert_eq!(Ok((1, 2, 3)), unpack!("<3h", [1, 0, 2, 0, 3, 0]));
+    assert_eq!(Ok((-2, 2)), unpack!(">lL", [255, 255, 255, 254, 0, 0, 0, 2]));
+}
+
+#[test]
+fn unpack_error_test() {
+    assert_eq!(
+        Err(Error::new(ErrorKind::OutOfBounds { start_index: 2, requested: 4, len: 5 })
+            .with_index(1)),
+        unpack!("<HI", [0; 5])
+    );
+    assert_eq!(
+        Err(Error::new(ErrorKind::TrailingBytes { start_index: 6, len: 1 })),
+        unpack!("<HI", [0; 7])
+    );
+    assert_eq!(
+        Err(Error::new(ErrorKind::OutOfBounds { start_index: 4, requested: 1, len: 4 })),
+        unpack!("<I2x", [0; 4])
+    );
+}
+
+#[test]
+fn unpack_from_test() {
+    let buffer = vec![9, 9, 0, 3, 97, 98, 99, 7];
+    assert_eq!(Ok((3, *b"abc")), unpack_from!(">H3s", buffer, 2));
+    assert_eq!(Ok((0x0909,)), unpack_from!("<H", &buffer[..], 0));
+    assert!(unpack_from!("<H", buffer, usize::MAX).is_err());
+}
+
+#[test]
+fn native_alignment_test() {
+    let buffer = [[7, 0, 0, 0], 0x0102_0304u32.to_ne_bytes()].concat();
+    assert_eq!(Ok((7, 0x0102_0304)), unpack!("BI", buffer));
+    assert_eq!(Ok((7, 0x0102_0304)), unpack!("@bi", buffer));
+    assert!(unpack!("=bi", buffer).is_err());
+    assert_eq!(buffer, pack!("BI", 7, 0x0102_0304));
+    assert_eq!(16, calcsize!("@?2h q"));
+    assert_eq!(6, calcsize!("@?2h"));
+    assert_eq!(16, calcsize!("@?q"));
+    assert_eq!(9, calcsize!("=?q"));
+    assert_eq!(0, calcsize!(""));
+}
+
+#[test]
+fn pack_test() {
+    assert_eq!(sample(), pack!("<bBh4xdf", -1, 254, 0x0201, 1.5, -1.5));
+    assert_eq!(vec![0, 2, 104, 105, 0, 97, 98, 1], pack!(">H3s2s?", 2, "hi", b"abc", true));
+    assert_eq!(vec![1, 0, 2, 0], pack!("<2h", 1, 2));
+    assert_eq!(Vec::<u8>::new(), pack!("<"));
+    let (kind, name) = unpack!("<B4s", pack!("<B4s", 3, [1, 2, 3, 4, 5])).unwrap();
+    assert_eq!((3, [1, 2, 3, 4]), (kind, name));
+}
@@ -0,0 +1,434 @@
+use bitconv::{
+    endian::{Big, Little}, ErrorKind, FromBytes, FromBytesLenient, FromReader, Reader
+};
+
+#[derive(Debug, PartialEq, FromBytes)]
+struct Header {
+    magic: u32,
+    kind: u8,
+    length: i16,
+}
+
+#[derive(Debug, PartialEq, FromBytes)]
+struct Pair(u8, u16);
+
+#[derive(Debug, PartialEq, FromBytes)]
+struct Empty;
+
+#[derive(Debug, PartialEq, FromBytes)]
+struct Nested {
+    header: Header,
+    pair: Pair,
+}
+
+#[derive(Debug, PartialEq, FromBytes)]
+struct Wrapper<T> {
+    value: T,
+}
+
+#[derive(Debug, PartialEq, FromBytes)]
+struct Save {
+    level: u8,
+    #[bitconv(since = 2)]
+    score: u32,
+    #[bitconv(since = 3)]
+    coins: u16,
+    checksum: u8,
+}
+
+#[derive(Debug, PartialEq, FromBytes)]
+struct SaveFile {
+    version: u16,
+    save: Save,
+    #[bitconv(since = 3)]
+    trailer: (u8, u8),
+}
+
+#[test]
+fn derive_struct_test_le() {
+    let buffer = [15, 0, 0, 128, 16, 39, 240, 216, 241, 255, 127];
+    assert_eq!(
+        Ok((Header { magic: 2147483663, kind: 16, length: -4057 }, 7)),
+        Header::from_bytes::<Little>(&buffer, 0)
+    );
+    assert_eq!(Ok((Pair(0, 32768), 3)), Pair::from_bytes::<Little>(&buffer, 1));
+    assert_eq!(Ok((Empty, 0)), Empty::from_bytes::<Little>(&buffer, 11));
+    assert_eq!(
+        Ok((Nested { header: Header { magic: 9, kind: 1, length: -1 }, pair: Pair(3, 10000) }, 10)),
+        Nested::from_bytes::<Little>(&[9, 0, 0, 0, 1, 255, 255, 3, 16, 39], 0)
+    );
+    assert_eq!(Ok((Wrapper { value: 10000 }, 2)), Wrapper::<u16>::from_bytes::<Little>(&buffer, 4));
+}
+
+#[test]
+fn derive_struct_test_be() {
+    let buffer = [15, 0, 0, 128, 16, 39, 240, 216, 241, 255, 127];
+    assert_eq!(
+        Ok((Header { magic: 251658368, kind: 16, length: 10224 }, 7)),
+        Header::from_bytes::<Big>(&buffer, 0)
+    );
+    assert_eq!(Ok((Pair(0, 128), 3)), Pair::from_bytes::<Big>(&buffer, 1));
+    assert_eq!(Ok((Wrapper { value: 4135 }, 2)), Wrapper::<u16>::from_bytes::<Big>(&buffer, 4));
+}
+
+#[test]
+fn derive_struct_short_buffer_test() {
+    let buffer = [15, 0, 0, 128, 16, 39, 240, 216, 241, 255, 127];
+    assert!(Header::from_bytes::<Little>(&buffer, 5).is_err());
+    assert!(Pair::from_bytes::<Big>(&buffer, 9).is_err());
+}
+
+#[test]
+fn derive_versioned_test() {
+    let buffer = [7, 232, 3, 0, 0, 5, 0, 9];
+    assert_eq!(
+        Ok((Save { level: 7, score: 0, coins: 0, checksum: 232 }, 2)),
+        Save::from_bytes_versioned::<Little>(&buffer, 0, 1)
+    );
+    assert_eq!(
+        Ok((Save { level: 7, score: 1000, coins: 0, checksum: 5 }, 6)),
+        Save::from_bytes_versioned::<Little>(&buffer, 0, 2)
+    );
+    assert_eq!(
+        Ok((Save { level: 7, score: 1000, coins: 5, checksum: 9 }, 8)),
+        Save::from_bytes_versioned::<Little>(&buffer, 0, 3)
+    );
+    assert_eq!(
+        Ok((Save { level: 7, score: 1000, coins: 5, checksum: 9 }, 8)),
+        Save::from_bytes::<Little>(&buffer, 0)
+    );
+    assert!(Save::from_bytes_versioned::<Little>(&buffer[..7], 0, 3).is_err());
+}
+
+#[test]
+fn derive_versioned_nested_test() {
+    let buffer = [0, 2, 7, 0, 0, 3, 232, 1, 2, 3];
+    assert_eq!(
+        Ok((
+            SaveFile {
+                version: 2,
+                save: Save { level: 7, score: 1000, coins: 0, checksum: 1 },
+                trailer: (0, 0)
+            },
+            8
+        )),
+        SaveFile::from_bytes_versioned::<Big>(&buffer, 0, 2)
+    );
+    assert!(SaveFile::from_bytes_versioned::<Big>(&buffer, 0, 3).is_err());
+}
+
+#[derive(Debug, PartialEq, FromBytes)]
+struct Telemetry {
+    id: u8,
+    #[bitconv(mask)]
+    present: u16,
+    #[bitconv(bit = 0)]
+    temperature: Option<i16>,
+    #[bitconv(bit = 3)]
+    pressure: Option<u32>,
+    #[bitconv(bit = 15)]
+    position: Option<(i16, i16)>,
+    #[bitconv(mask)]
+    extra: u8,
+    #[bitconv(bit = 7)]
+    battery: Option<u8>,
+    checksum: u8,
+}
+
+#[test]
+fn derive_presence_test() {
+    let buffer = [1, 0, 0, 0, 55];
+    assert_eq!(
+        Ok((
+            Telemetry {
+                id: 1,
+                present: 0,
+                temperature: None,
+                pressure: None,
+                position: None,
+                extra: 0,
+                battery: None,
+                checksum: 55
+            },
+            5
+        )),
+        Telemetry::from_bytes::<Little>(&buffer, 0)
+    );
+
+    let buffer = [1, 9, 128, 251, 255, 160, 134, 1, 0, 10, 0, 246, 255, 128, 80, 55];
+    assert_eq!(
+        Ok((
+            Telemetry {
+                id: 1,
+                present: 32777,
+                temperature: Some(-5),
+                pressure: Some(100000),
+                position: Some((10, -10)),
+                extra: 128,
+                battery: Some(80),
+                checksum: 55
+            },
+            16
+        )),
+        Telemetry::from_bytes::<Little>(&buffer, 0)
+    );
+
+    let buffer = [1, 0, 8, 0, 1, 134, 160, 1, 30, 55];
+    assert_eq!(
+        Ok((
+            Telemetry {
+                id: 1,
+                present: 8,
+                temperature: None,
+                pressure: Some(100000),
+                position: None,
+                extra: 1,
+                battery: None,
+                checksum: 30
+            },
+            9
+        )),
+        Telemetry::from_bytes::<Big>(&buffer, 0)
+    );
+    assert!(Telemetry::from_bytes::<Big>(&buffer[..6], 0).is_err());
+}
+
+#[derive(Debug, PartialEq, FromBytes)]
+#[bitconv(tag = u16)]
+enum Message {
+    #[bitconv(tag = 1)]
+    Ping { sequence: u32 },
+    #[bitconv(tag = 0x0200)]
+    Move(i16, i16),
+    #[bitconv(tag = 3)]
+    Data {
+        #[bitconv(mask)]
+        present: u8,
+        #[bitconv(bit = 1)]
+        value: Option<u8>,
+    },
+    #[bitconv(tag = 4)]
+    Quit,
+}
+
+#[derive(Debug, PartialEq, FromBytes)]
+#[bitconv(tag = u8)]
+enum Command {
+    Start = 0x10,
+    Stop = 0x20,
+}
+
+#[test]
+fn derive_enum_test_le() {
+    let buffer = [1, 0, 232, 3, 0, 0, 0, 2, 10, 0, 246, 255, 3, 0, 2, 77, 4, 0];
+    assert_eq!(
+        Ok((Message::Ping { sequence: 1000 }, 6)),
+        Message::from_bytes::<Little>(&buffer, 0)
+    );
+    assert_eq!(Ok((Message::Move(10, -10), 6)), Message::from_bytes::<Little>(&buffer, 6));
+    assert_eq!(
+        Ok((Message::Data { present: 2, value: Some(77) }, 4)),
+        Message::from_bytes::<Little>(&buffer, 12)
+    );
+    assert_eq!(Ok((Message::Quit, 2)), Message::from_bytes::<Little>(&buffer, 16));
+    assert!(Message::from_bytes::<Little>(&buffer, 1).is_err());
+    assert!(Message::from_bytes::<Little>(&buffer, 7).is_err());
+}
+
+#[test]
+fn derive_enum_test_be() {
+    let buffer = [2, 0, 0, 10, 255, 246, 0, 3, 0, 0, 4];
+    assert_eq!(Ok((Message::Move(10, -10), 6)), Message::from_bytes::<Big>(&buffer, 0));
+    assert_eq!(
+        Ok((Message::Data { present: 0, value: None }, 3)),
+        Message::from_bytes::<Big>(&buffer, 6)
+    );
+    assert_eq!(Ok((Message::Quit, 2)), Message::from_bytes::<Big>(&buffer, 9));
+    assert!(Message::from_bytes::<Big>(&buffer, 10).is_err());
+}
+
+#[test]
+fn derive_enum_discriminant_test() {
+    let buffer = [0x10, 0x20, 0x30];
+    assert_eq!(Ok((Command::Start, 1)), Command::from_bytes::<Little>(&buffer, 0));
+    assert_eq!(Ok((Command::Stop, 1)), Command::from_bytes::<Big>(&buffer, 1));
+    assert!(Command::from_bytes::<Little>(&buffer, 2).is_err());
+}
+
+#[derive(Debug, PartialEq, FromBytes)]
+struct DirectoryHeader {
+    num_entries: u16,
+    num_names: u8,
+}
+
+#[derive(Debug, PartialEq, FromBytes)]
+struct Entry {
+    offset: u32,
+    length: u16,
+}
+
+#[derive(Debug, PartialEq, FromBytes)]
+struct Directory {
+    header: DirectoryHeader,
+    #[bitconv(count = "header.num_entries")]
+    entries: Vec<Entry>,
+    #[bitconv(count = "header.num_names * 2")]
+    names: Vec<u8>,
+    #[bitconv(mask)]
+    present: u8,
+    #[bitconv(bit = 0, count = "entries.len()")]
+    checksums: Option<Vec<u8>>,
+}
+
+#[test]
+fn derive_count_test() {
+    let buffer = [2, 0, 1, 16, 0, 0, 0, 8, 0, 24, 0, 0, 0, 4, 0, 97, 98, 1, 200, 201];
+    assert_eq!(
+        Ok((
+            Directory {
+                header: DirectoryHeader { num_entries: 2, num_names: 1 },
+                entries: vec![Entry { offset: 16, length: 8 }, Entry { offset: 24, length: 4 }],
+                names: vec![97, 98],
+                present: 1,
+                checksums: Some(vec![200, 201])
+            },
+            20
+        )),
+        Directory::from_bytes::<Little>(&buffer, 0)
+    );
+
+    let buffer = [0, 0, 0, 0, 55];
+    assert_eq!(
+        Ok((
+            Directory {
+                header: DirectoryHeader { num_entries: 0, num_names: 0 },
+                entries: vec![],
+                names: vec![],
+                present: 0,
+                checksums: None
+            },
+            4
+        )),
+        Directory::from_bytes::<Big>(&buffer, 0)
+    );
+
+    let buffer = [255, 255, 0, 16, 0, 0, 0, 8, 0];
+    assert!(Directory::from_bytes::<Little>(&buffer, 0).is_err());
+}
+
+#[derive(Debug, PartialEq, FromBytes)]
+struct Limited {
+    num_items: u32,
+    #[bitconv(count = "num_items", max = 4)]
+    items: Vec<u8>,
+    #[bitconv(count = "u64::MAX", max = 2)]
+    markers: Vec<Empty>,
+}
+
+#[test]
+fn derive_count_limit_test() {
+    let error = Limited::from_bytes::<Little>(&[5, 0, 0, 0, 1, 2, 3, 4, 5], 0).unwrap_err();
+    assert_eq!(ErrorKind::LimitExceeded { start_index: 4, len: 5, max: 4 }, error.kind());
+    assert_eq!("items", error.context().to_string());
+    let error = Limited::from_bytes::<Big>(&[0, 0, 0, 4, 1, 2, 3, 4], 0).unwrap_err();
+    assert_eq!(
+        ErrorKind::LimitExceeded { start_index: 8, len: u64::MAX as usize, max: 2 },
+        error.kind()
+    );
+    assert_eq!("markers", error.context().to_string());
+}
+
+#[derive(Debug, PartialEq, FromBytes)]
+struct Archive {
+    directory: Directory,
+    messages: (Message, Message),
+    #[bitconv(count = "-1i8")]
+    invalid: Vec<u8>,
+}
+
+#[test]
+fn derive_error_context_test() {
+    let buffer = [3, 0, 0, 16, 0, 0, 0, 8, 0, 24, 0, 0, 0, 4];
+    let error = Directory::from_bytes::<Little>(&buffer, 0).unwrap_err();
+    assert_eq!(ErrorKind::OutOfBounds { start_index: 13, requested: 2, len: 14 }, error.kind());
+    assert_eq!("entries[1].length", error.context().to_string());
+    let error = Archive::from_bytes::<Little>(&buffer, 0).unwrap_err();
+    assert_eq!("directory.entries[1].length", error.context().to_string());
+
+    let buffer = [0, 0, 0, 0, 1, 0, 0, 0, 0];
+    let error = Archive::from_bytes::<Little>(&buffer, 0).unwrap_err();
+    assert_eq!(ErrorKind::OutOfBounds { start_index: 6, requested: 4, len: 9 }, error.kind());
+    assert_eq!("messages.0.sequence", error.context().to_string());
+
+    let buffer = [0, 0, 0, 0, 4, 0, 9, 0];
+    let error = Archive::from_bytes::<Little>(&buffer, 0).unwrap_err();
+    assert_eq!(ErrorKind::InvalidTag { start_index: 6 }, error.kind());
+    assert_eq!("messages.1", error.context().to_string());
+
+    let buffer = [0, 0, 0, 0, 4, 0, 4, 0];
+    let error = Archive::from_bytes::<Little>(&buffer, 0).unwrap_err();
+    assert_eq!(ErrorKind::InvalidCount { start_index: 8 }, error.kind());
+    assert_eq!("invalid", error.context().to_string());
+}
+
+#[derive(Debug, PartialEq, FromBytes)]
+#[bitconv(lenient)]
+struct Record {
+    kind: u8,
+    len: u8,
+    #[bitconv(count = "len")]
+    data: Vec<u16>,
+    checksum: u32,
+}
+
+#[derive(Debug, PartialEq, FromBytes)]
+#[bitconv(lenient)]
+struct Padded<T>(T, u8);
+
+#[test]
+fn derive_lenient_test() {
+    let buffer = [1, 2, 10, 0, 20, 0, 1, 0, 0, 0];
+    let (record, read, errors) = Record::from_bytes_lenient::<Little>(&buffer, 0);
+    assert_eq!(Record { kind: 1, len: 2, data: vec![10, 20], checksum: 1 }, record);
+    assert_eq!(10, read);
+    assert!(errors.is_empty());
+    assert_eq!(Ok((record, 10)), Record::from_bytes::<Little>(&buffer, 0));
+
+    let buffer = [1, 4, 10, 0, 20, 0, 1, 0, 0];
+    let (record, read, errors) = Record::from_bytes_lenient::<Little>(&buffer, 0);
+    assert_eq!(Record { kind: 1, len: 4, data: vec![], checksum: 1310730 }, record);
+    assert_eq!(6, read);
+    assert_eq!(1, errors.len());
+    assert_eq!(ErrorKind::OutOfBounds { start_index: 8, requested: 2, len: 9 }, errors[0].kind());
+    assert_eq!("data[3]", errors[0].context().to_string());
+
+    let (record, read, errors) = Record::from_bytes_lenient::<Big>(&[1], 0);
+    assert_eq!(Record { kind: 1, len: 0, data: vec![], checksum: 0 }, record);
+    assert_eq!(1, read);
+    assert_eq!(
+        ["len", "checksum"],
+        [errors[0].context().to_string(), errors[1].context().to_string()]
+    );
+}
+
+#[test]
+fn derive_lenient_generic_test() {
+    let (padded, read, errors) = Padded::<u32>::from_bytes_lenient::<Little>(&[5, 6], 1);
+    assert_eq!(Padded(0, 6), padded);
+    assert_eq!(1, read);
+    assert_eq!(ErrorKind::OutOfBounds { start_index: 1, requested: 4, len: 2 }, errors[0].kind());
+    assert_eq!("0", errors[0].context().to_string());
+}
+
+#[test]
+fn derive_from_reader_test() {
+    let buffer = [15, 0, 0, 128, 16, 39, 240, 216, 241, 255, 127];
+    let mut reader = Reader::<Little>::new(&buffer);
+    assert_eq!(
+        Ok(Header { magic: 2147483663, kind: 16, length: -4057 }),
+        Header::read_from(&mut reader)
+    );
+    assert_eq!(Ok(Pair(216, 65521)), Pair::read_from(&mut reader));
+    assert_eq!(10, reader.position());
+    assert!(Header::read_from(&mut reader).is_err());
+}
@@ -0,0 +1,107 @@
+use bitconv::{
+    endian::{Big, Little}, ErrorKind, FromBytes, ToBytes, ToWriter, Writer
+};
+
+#[derive(Debug, PartialEq, FromBytes, ToBytes)]
+struct Header {
+    magic: u32,
+    kind: u8,
+    length: i16,
+}
+
+#[derive(Debug, PartialEq, FromBytes, ToBytes)]
+struct Pair(u8, u16);
+
+#[derive(Debug, PartialEq, FromBytes, ToBytes)]
+struct Empty;
+
+#[derive(Debug, PartialEq, FromBytes, ToBytes)]
+struct Wrapper<T> {
+    value: T,
+}
+
+#[derive(Debug, PartialEq, FromBytes, ToBytes)]
+struct Telemetry {
+    #[bitconv(mask)]
+    present: u8,
+    #[bitconv(bit = 0)]
+    temperature: Option<i16>,
+    #[bitconv(bit = 1)]
+    pressure: Option<u32>,
+}
+
+#[derive(Debug, PartialEq, FromBytes, ToBytes)]
+struct Table {
+    num_entries: u8,
+    #[bitconv(count = "num_entries")]
+    entries: Vec<Pair>,
+}
+
+#[derive(Debug, PartialEq, FromBytes, ToBytes)]
+#[bitconv(tag = u16)]
+enum Message {
+    #[bitconv(tag = 1)]
+    Ping { sequence: u16 },
+    #[bitconv(tag = 2)]
+    Move(i16, i16),
+    #[bitconv(tag = 3)]
+    Quit,
+}
+
+#[derive(Debug, PartialEq, FromBytes, ToBytes)]
+#[bitconv(tag = u8)]
+enum Level {
+    Low = 1,
+    High = 7,
+}
+
+fn round_trip<V: FromBytes + ToBytes + PartialEq + core::fmt::Debug>(value: V, expected: &[u8]) {
+    let mut writer = Writer::<Little>::new();
+    value.write_to(&mut writer).unwrap();
+    assert_eq!(expected, writer.as_slice());
+    assert_eq!(expected.len(), value.byte_len());
+    assert_eq!(Ok((value, expected.len())), V::from_bytes::<Little>(expected, 0));
+}
+
+#[test]
+fn derive_struct_test_le() {
+    let header = Header { magic: 2147483663, kind: 16, length: -3856 };
+    round_trip(header, &[15, 0, 0, 128, 16, 240, 240]);
+    round_trip(Pair(7, 256), &[7, 0, 1]);
+    round_trip(Empty, &[]);
+    round_trip(Wrapper { value: Pair(1, 2) }, &[1, 2, 0]);
+}
+
+#[test]
+fn derive_struct_test_be() {
+    let mut buffer = [0; 9];
+    let header = Header { magic: 251658368, kind: 16, length: 10000 };
+    assert_eq!(Ok(7), header.to_bytes::<Big>(&mut buffer, 2));
+    assert_eq!([0, 0, 15, 0, 0, 128, 16, 39, 16], buffer);
+}
+
+#[test]
+fn derive_optional_and_count_test() {
+    let telemetry = Telemetry { present: 2, temperature: None, pressure: Some(100000) };
+    round_trip(telemetry, &[2, 160, 134, 1, 0]);
+    let table = Table { num_entries: 2, entries: vec![Pair(1, 10), Pair(2, 20)] };
+    round_trip(table, &[2, 1, 10, 0, 2, 20, 0]);
+}
+
+#[test]
+fn derive_enum_test() {
+    round_trip(Message::Ping { sequence: 300 }, &[1, 0, 44, 1]);
+    round_trip(Message::Move(10, -10), &[2, 0, 10, 0, 246, 255]);
+    round_trip(Message::Quit, &[3, 0]);
+    round_trip(Level::High, &[7]);
+    round_trip(Level::Low, &[1]);
+}
+
+#[test]
+fn derive_error_context_test() {
+    let table = Table { num_entries: 2, entries: vec![Pair(1, 10), Pair(2, 20)] };
+    let mut buffer = [0; 6];
+    let error = table.to_bytes::<Little>(&mut buffer, 0).unwrap_err();
+    assert_eq!(ErrorKind::OutOfBounds { start_index: 5, requested: 2, len: 6 }, error.kind());
+    assert_eq!("entries[1].1", error.context().to_string());
+}
@@ -0,0 +1,224 @@
+//! Instantiates every function annotated with `#[no_panic]` on inputs the
+//! optimizer cannot see through, so that linking fails if any of them can
+//! panic. Build it with:
+//!
+//! ```sh
+//! cargo build --release --features no-panic --example no_panic
+//! ```
+//!
+//! The proof only holds once the optimizer has removed the unreachable
+//! panics, so builds with debug assertions, where the functions are not
+//! checked either, leave the example empty.
+#[cfg(not(debug_assertions))]
+mod checks {
+    use bitconv::endian::{Big, Endian, Little, Native};
+    use bitconv::*;
+    use std::hint::black_box;
+
+    macro_rules! check {
+        ($($function:ident),+) => {$(
+            let _ = black_box($function::<Little>(black_box(&[0; 9]), black_box(3)));
+            let _ = black_box($function::<Big>(black_box(&[]), black_box(usize::MAX)));
+            let _ = black_box($function::<Native>(black_box(&[1]), black_box(0)));
+        )+};
+    }
+
+    macro_rules! check_split {
+        ($($function:ident),+) => {$(
+            let _ = black_box($function::<Little>(black_box(&[0; 9])));
+            let _ = black_box($function::<Big>(black_box(&[])));
+        )+};
+    }
+
+    macro_rules! check_write {
+        ($($function:ident),+) => {$(
+            let _ = black_box($function::<Little>(
+                black_box(&mut [0; 9]),
+                black_box(3),
+                black_box(7),
+            ));
+            let _ = black_box($function::<Big>(
+                black_box(&mut []),
+                black_box(usize::MAX),
+                black_box(7),
+            ));
+        )+};
+    }
+
+    pub fn run() {
+        check!(
+            try_to_int16,
+            try_to_int32,
+            try_to_int64,
+            try_to_uint16,
+            try_to_uint32,
+            try_to_uint64,
+            try_to_char,
+            to_int16_padded,
+            to_int32_padded,
+            to_int64_padded,
+            to_uint16_padded,
+            to_uint32_padded,
+            to_uint64_padded,
+            try_to_int16_wrapping,
+            try_to_int32_wrapping,
+            try_to_int64_wrapping,
+            try_to_uint16_wrapping,
+            try_to_uint32_wrapping,
+            try_to_uint64_wrapping
+        );
+        check_split!(
+            split_int8,
+            split_int16,
+            split_int32,
+            split_int64,
+            split_uint8,
+            split_uint16,
+            split_uint32,
+            split_uint64
+        );
+        check_write!(
+            try_write_int16_at,
+            try_write_int32_at,
+            try_write_int64_at,
+            try_write_uint16_at,
+            try_write_uint32_at,
+            try_write_uint64_at
+        );
+        let _ = black_box(try_to_boolean(black_box(&[0; 9]), black_box(3)));
+        let _ = black_box(try_to_boolean(black_box(&[]), black_box(usize::MAX)));
+        let _ = black_box(try_write_bytes::<Little, _>(black_box(&mut [0; 9]), black_box(7u32)));
+        let _ = black_box(try_write_bytes::<Big, _>(black_box(&mut []), black_box((7u16, 1.5f64))));
+        let _ = black_box(try_write_int_bytes(
+            black_box(-1),
+            black_box(&mut [0; 17]),
+            Endian::BE,
+            true,
+        ));
+        let _ = black_box(try_write_int_bytes(black_box(1), black_box(&mut []), Endian::NE, false));
+        let _ = black_box(read_array::<4>(black_box(&[0; 9]), black_box(6)));
+        let _ = black_box(read_array::<0>(black_box(&[]), black_box(usize::MAX)));
+        let _ = black_box(read_uleb128(black_box(&[0x80; 11]), black_box(1)));
+        let _ = black_box(write_uleb128(black_box(&mut [0; 9]), black_box(3), black_box(u64::MAX)));
+        let _ = black_box(read_sleb128(black_box(&[0xff; 11]), black_box(0)));
+        let _ = black_box(write_sleb128(black_box(&mut [0; 9]), black_box(3), black_box(i64::MIN)));
+        let _ = black_box(read_quic_varint(black_box(&[0xff; 7]), black_box(0)));
+        let _ =
+            black_box(write_quic_varint(black_box(&mut [0; 9]), black_box(3), black_box(u64::MAX)));
+        let _ = black_box(read_git_offset(black_box(&[0xff; 11]), black_box(0)));
+        let _ =
+            black_box(write_git_offset(black_box(&mut [0; 9]), black_box(3), black_box(u64::MAX)));
+        let _ = black_box(read_sqlite_varint(black_box(&[0xff; 11]), black_box(0)));
+        let _ = black_box(write_sqlite_varint(
+            black_box(&mut [0; 9]),
+            black_box(3),
+            black_box(u64::MAX),
+        ));
+        let _ = black_box(read_vlq(black_box(&[0xff; 11]), black_box(0)));
+        let _ = black_box(write_vlq(black_box(&mut [0; 9]), black_box(3), black_box(u64::MAX)));
+        let _ = black_box(read_ordered_varint(black_box(&[0xff; 11]), black_box(0)));
+        let _ = black_box(write_ordered_varint(
+            black_box(&mut [0; 9]),
+            black_box(3),
+            black_box(u64::MAX),
+        ));
+        let _ = black_box(read_elias_gamma::<Msb0>(black_box(&[0; 9]), black_box(3)));
+        let _ = black_box(write_elias_gamma::<Lsb0>(
+            black_box(&mut [0; 4]),
+            black_box(5),
+            black_box(u64::MAX),
+        ));
+        let _ = black_box(read_elias_delta::<Lsb0>(black_box(&[0x01; 11]), black_box(7)));
+        let _ = black_box(write_elias_delta::<Msb0>(
+            black_box(&mut [0; 4]),
+            black_box(usize::MAX),
+            black_box(9),
+        ));
+        let _ = black_box(read_rice::<Msb0>(black_box(&[0; 9]), black_box(3), black_box(70)));
+        let _ = black_box(write_rice::<Lsb0>(
+            black_box(&mut [0; 4]),
+            black_box(5),
+            black_box(99),
+            black_box(2),
+        ));
+        let _ = black_box(read_golomb::<Lsb0>(black_box(&[0x01; 11]), black_box(7), black_box(0)));
+        let _ = black_box(write_golomb::<Msb0>(
+            black_box(&mut [0; 4]),
+            black_box(1),
+            black_box(9),
+            black_box(3),
+        ));
+        let _ = black_box(pack_bits::<Msb0>(
+            black_box(&mut [0; 16]),
+            black_box(1),
+            black_box(&[1, 2, 3]),
+            black_box(13),
+        ));
+        let _ = black_box(unpack_bits::<Msb0>(
+            black_box(&[0; 16]),
+            black_box(2),
+            black_box(&mut [0; 4]),
+            black_box(24),
+        ));
+        let _ = black_box(pack_bits::<Lsb0>(
+            black_box(&mut [0; 16]),
+            black_box(1),
+            black_box(&[1, 2, 3]),
+            black_box(16),
+        ));
+        let _ = black_box(unpack_bits::<Lsb0>(
+            black_box(&[0; 16]),
+            black_box(2),
+            black_box(&mut [0; 4]),
+            black_box(13),
+        ));
+        reverse_bits_per_byte(black_box(&mut [0x12; 19]));
+        reverse_bit_order(black_box(&mut [0x12; 19]));
+        let _ = black_box(try_to_high_nibble(black_box(&[0x12]), black_box(1)));
+        let _ =
+            black_box(try_write_low_nibble_at(black_box(&mut [0x12]), black_box(0), black_box(7)));
+        swap_nibbles(black_box(&mut [0x12; 19]));
+        let tag = protobuf::Tag::new(1, protobuf::WireType::StartGroup).unwrap();
+        let _ = black_box(protobuf::read_tag(black_box(&[0xff; 6]), black_box(1)));
+        let _ =
+            black_box(protobuf::write_tag(black_box(&mut [0; 4]), black_box(1), black_box(tag)));
+        let _ = black_box(protobuf::skip_field(
+            black_box(&[0x0b, 0x0c, 0x12]),
+            black_box(0),
+            black_box(tag),
+        ));
+        #[cfg(feature = "alloc")]
+        {
+            let mut deque = std::collections::VecDeque::with_capacity(9);
+            deque.extend([0; 5]);
+            deque.push_front(1);
+            let _ = black_box(try_to_int16_deque::<Little>(black_box(&deque), black_box(5)));
+            let _ = black_box(try_to_int32_deque::<Big>(black_box(&deque), black_box(usize::MAX)));
+            let _ = black_box(try_to_int64_deque::<Native>(black_box(&deque), black_box(0)));
+            let _ = black_box(try_to_uint16_deque::<Little>(black_box(&deque), black_box(4)));
+            let _ = black_box(try_to_uint32_deque::<Big>(black_box(&deque), black_box(1)));
+            let _ = black_box(try_to_uint64_deque::<Native>(black_box(&deque), black_box(3)));
+        }
+        #[cfg(feature = "ffi")]
+        unsafe {
+            let mut value = 0;
+            let _ = black_box(ffi::bitconv_to_u32_le(
+                black_box([0; 9].as_ptr()),
+                9,
+                black_box(6),
+                &mut value,
+            ));
+            let _ = black_box(ffi::bitconv_write_f64_be(
+                black_box([0; 9].as_mut_ptr()),
+                9,
+                black_box(1),
+                1.5,
+            ));
+        }
+    }
+}
+
+fn main() {
+    #[cfg(not(debug_assertions))]
+    checks::run();
+}
@@ -0,0 +1,373 @@
+//! Base58 text for byte arrays, in the alphabet of Bitcoin, and its
+//! Base58Check variant with a checksum.
+use crate::{Error, ErrorKind, Result};
+#[cfg(feature = "alloc")]
+use alloc::{string::String, vec, vec::Vec};
+use core::{convert::TryInto, fmt, str};
+
+const BITCOIN_ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+const BITCOIN_VALUES: [u8; 256] = values(BITCOIN_ALPHABET);
+
+/// Returns the value of each character of alphabet, and 0xff for the others.
+const fn values(alphabet: &[u8; 58]) -> [u8; 256] {
+    let mut values = [0xff; 256];
+    let mut value = 0;
+    while value < 58 {
+        values[alphabet[value] as usize] = value as u8;
+        value += 1;
+    }
+    values
+}
+
+/// A variant of Base58: its alphabet and whether data is followed by the four
+/// byte checksum of Base58Check.
+///
+/// Base58 writes data as a single number in base 58, without the characters
+/// `0`, `O`, `I` and `l` that are easily mistaken for one another, and each
+/// leading zero byte as the first character of the alphabet, `1` for Bitcoin.
+/// It takes time quadratic in the length of data, which suits identifiers
+/// and keys rather than whole files.
+/// # Example
+///
+/// ```
+/// use bitconv::Base58;
+///
+/// let mut text = [0; 24];
+/// let len = Base58::BITCOIN.encode_into(b"Hello World!", &mut text).unwrap();
+/// assert_eq!(b"2NEpo7TZRRrLZSi2U", &text[..len]);
+///
+/// let mut data = [0; 16];
+/// assert_eq!(Ok(6), Base58::BITCOIN.decode_into("11233QC4", &mut data));
+/// assert_eq!([0, 0, 0x28, 0x7f, 0xb4, 0xcd], data[..6]);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Base58 {
+    alphabet: &'static [u8; 58],
+    values: &'static [u8; 256],
+    check: bool,
+}
+
+impl Base58 {
+    /// The alphabet of Bitcoin, also used by IPFS and Solana, without a
+    /// checksum.
+    pub const BITCOIN: Base58 =
+        Base58 { alphabet: BITCOIN_ALPHABET, values: &BITCOIN_VALUES, check: false };
+    /// The alphabet of Bitcoin with the checksum of Base58Check, as in
+    /// Bitcoin addresses and private keys in wallet import format.
+    pub const BITCOIN_CHECK: Base58 = Base58::BITCOIN.check(true);
+
+    /// Sets whether data is followed by the first four bytes of its double
+    /// SHA-256 hash, as Base58Check does.
+    pub const fn check(mut self, check: bool) -> Self {
+        self.check = check;
+        self
+    }
+
+    /// Returns the largest number of characters of the text of len bytes.
+    pub const fn max_encoded_len(&self, len: usize) -> usize {
+        let len = len.saturating_add(if self.check { 4 } else { 0 });
+        (len / 100).saturating_mul(138).saturating_add((len % 100 * 138).div_ceil(100))
+    }
+
+    /// Returns the largest number of bytes encoded by text of len characters,
+    /// which is also the room [`decode_into`](Base58::decode_into) needs.
+    pub const fn max_decoded_len(&self, len: usize) -> usize { len }
+
+    /// Writes the text of data to the start of out and returns the number of
+    /// characters written.
+    ///
+    /// Fails with `ErrorKind::OutOfBounds`, requesting
+    /// [`max_encoded_len`](Base58::max_encoded_len) characters, if out is too
+    /// short for the text, and may then have written part of out.
+    pub fn encode_into(&self, data: &[u8], out: &mut [u8]) -> Result<usize> {
+        let hashed;
+        let checksum: &[u8] = match self.check {
+            true => {
+                hashed = checksum(data);
+                &hashed
+            }
+            false => &[],
+        };
+        let requested = self.max_encoded_len(data.len());
+        let too_short =
+            Error::new(ErrorKind::OutOfBounds { start_index: 0, requested, len: out.len() });
+        let zeros = data.iter().chain(checksum).take_while(|&&byte| byte == 0).count();
+        if out.len() < zeros {
+            return Err(too_short);
+        }
+        let (ones, digits) = out.split_at_mut(zeros);
+        let mut len = 0;
+        for &byte in data.iter().chain(checksum).skip(zeros) {
+            let mut carry = u32::from(byte);
+            for digit in digits.iter_mut().take(len) {
+                carry += u32::from(*digit) << 8;
+                *digit = (carry % 58) as u8;
+                carry /= 58;
+            }
+            while carry > 0 {
+                *digits.get_mut(len).ok_or(too_short)? = (carry % 58) as u8;
+                len += 1;
+                carry /= 58;
+            }
+        }
+        ones.iter_mut().for_each(|one| *one = self.alphabet[0]);
+        let digits = &mut digits[..len];
+        digits.reverse();
+        digits.iter_mut().for_each(|digit| *digit = self.alphabet[usize::from(*digit)]);
+        Ok(zeros + len)
+    }
+
+    /// Returns the text of data as a new string.
+    #[cfg(feature = "alloc")]
+    pub fn encode(&self, data: &[u8]) -> String {
+        let mut text = vec![0; self.max_encoded_len(data.len())];
+        let len = self.encode_into(data, &mut text).unwrap_or_default();
+        text[..len].iter().copied().map(char::from).collect()
+    }
+
+    /// Parses text into the start of out and returns the number of bytes of
+    /// data written, not counting the checksum.
+    ///
+    /// Fails with `ErrorKind::InvalidEncoding` at the byte offset in text of
+    /// the first character outside the alphabet, or of the end of text if it
+    /// is too short for a checksum, with `ErrorKind::ChecksumMismatch` if the
+    /// checksum is not that of the data, and with `ErrorKind::OutOfBounds`,
+    /// requesting [`max_decoded_len`](Base58::max_decoded_len) bytes, if out
+    /// has no room for the data and checksum. Part of out may have been
+    /// written when decoding fails.
+    /// # Example
+    ///
+    /// ```
+    /// use bitconv::{Base58, ErrorKind};
+    ///
+    /// let mut data = [0; 34];
+    /// let address = "1BoatSLRHtKNngkdXEeobR76b53LETtpyT";
+    /// assert_eq!(Ok(21), Base58::BITCOIN_CHECK.decode_into(address, &mut data));
+    /// assert_eq!([0x00, 0x76, 0x80, 0xad], data[..4]);
+    ///
+    /// let typo = "1BoatSLRHtKNngkdXEeobR76b53LETtpyt";
+    /// let error = Base58::BITCOIN_CHECK.decode_into(typo, &mut data).unwrap_err();
+    /// assert_eq!(ErrorKind::ChecksumMismatch { start_index: 0 }, error.kind());
+    /// let error = Base58::BITCOIN.decode_into("1BoatSLRHtKNngkdXEeobR76b53LETtpy0", &mut data);
+    /// assert_eq!(ErrorKind::InvalidEncoding { start_index: 33 }, error.unwrap_err().kind());
+    /// ```
+    pub fn decode_into(&self, text: &str, out: &mut [u8]) -> Result<usize> {
+        let text = text.as_bytes();
+        let invalid = text.iter().position(|&digit| self.values[usize::from(digit)] == 0xff);
+        if let Some(start_index) = invalid {
+            return Err(Error::new(ErrorKind::InvalidEncoding { start_index }));
+        }
+        let requested = self.max_decoded_len(text.len());
+        let too_short =
+            Error::new(ErrorKind::OutOfBounds { start_index: 0, requested, len: out.len() });
+        let zeros = text.iter().take_while(|&&digit| digit == self.alphabet[0]).count();
+        if out.len() < zeros {
+            return Err(too_short);
+        }
+        let (zero_bytes, bytes) = out.split_at_mut(zeros);
+        let mut len = 0;
+        for &digit in &text[zeros..] {
+            let mut carry = u32::from(self.values[usize::from(digit)]);
+            for byte in bytes.iter_mut().take(len) {
+                carry += u32::from(*byte) * 58;
+                *byte = carry as u8;
+                carry >>= 8;
+            }
+            while carry > 0 {
+                *bytes.get_mut(len).ok_or(too_short)? = carry as u8;
+                len += 1;
+                carry >>= 8;
+            }
+        }
+        zero_bytes.iter_mut().for_each(|byte| *byte = 0);
+        bytes[..len].reverse();
+        let len = zeros + len;
+        if !self.check {
+            return Ok(len);
+        }
+        let data_len = match len.checked_sub(4) {
+            Some(data_len) => data_len,
+            None => return Err(Error::new(ErrorKind::InvalidEncoding { start_index: text.len() })),
+        };
+        let (data, stored) = out[..len].split_at(data_len);
+        match checksum(data) == stored {
+            true => Ok(data_len),
+            false => Err(Error::new(ErrorKind::ChecksumMismatch { start_index: 0 })),
+        }
+    }
+
+    /// Parses text into a new vector of its data, without the checksum,
+    /// failing as [`decode_into`](Base58::decode_into) does.
+    #[cfg(feature = "alloc")]
+    pub fn decode(&self, text: &str) -> Result<Vec<u8>> {
+        let mut data = vec![0; self.max_decoded_len(text.len())];
+        let len = self.decode_into(text, &mut data)?;
+        data.truncate(len);
+        Ok(data)
+    }
+}
+
+impl fmt::Debug for Base58 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Base58")
+            .field("alphabet", &str::from_utf8(self.alphabet).unwrap_or_default())
+            .field("check", &self.check)
+            .finish()
+    }
+}
+
+/// Returns the checksum of Base58Check, the first four bytes of the SHA-256
+/// hash of the SHA-256 hash of data.
+fn checksum(data: &[u8]) -> [u8; 4] { sha256(&sha256(data))[..4].try_into().unwrap_or_default() }
+
+/// Returns the SHA-256 hash of data, as specified in FIPS 180-4.
+fn sha256(data: &[u8]) -> [u8; 32] {
+    let mut state = [
+        0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab,
+        0x5be0cd19,
+    ];
+    let mut blocks = data.chunks_exact(64);
+    for block in &mut blocks {
+        sha256_block(&mut state, block);
+    }
+    // The rest of data, a one bit, zeros and the length of data in bits fill
+    // one or two more blocks.
+    let rest = blocks.remainder();
+    let mut tail = [0; 128];
+    tail[..rest.len()].copy_from_slice(rest);
+    tail[rest.len()] = 0x80;
+    let tail = &mut tail[..if rest.len() < 56 { 64 } else { 128 }];
+    let bits = (data.len() as u64).wrapping_mul(8).to_be_bytes();
+    let end = tail.len() - 8;
+    tail[end..].copy_from_slice(&bits);
+    for block in tail.chunks_exact(64) {
+        sha256_block(&mut state, block);
+    }
+    let mut hash = [0; 32];
+    for (bytes, word) in hash.chunks_exact_mut(4).zip(state) {
+        bytes.copy_from_slice(&word.to_be_bytes());
+    }
+    hash
+}
+
+fn sha256_block(state: &mut [u32; 8], block: &[u8]) {
+    const K: [u32; 64] = [
+        0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4,
+        0xab1c5ed5, 0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe,
+        0x9bdc06a7, 0xc19bf174, 0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f,
+        0x4a7484aa, 0x5cb0a9dc, 0x76f988da, 0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7,
+        0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967, 0x27b70a85, 0x2e1b2138, 0x4d2c6dfc,
+        0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85, 0xa2bfe8a1, 0xa81a664b,
+        0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070, 0x19a4c116,
+        0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+        0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7,
+        0xc67178f2,
+    ];
+    let mut w = [0u32; 64];
+    for (word, bytes) in w.iter_mut().zip(block.chunks_exact(4)) {
+        *word = u32::from_be_bytes(bytes.try_into().unwrap_or_default());
+    }
+    for i in 16..64 {
+        let s0 = w[i - 15].rotate_right(7) ^ w[i - 15].rotate_right(18) ^ w[i - 15] >> 3;
+        let s1 = w[i - 2].rotate_right(17) ^ w[i - 2].rotate_right(19) ^ w[i - 2] >> 10;
+        w[i] = w[i - 16].wrapping_add(s0).wrapping_add(w[i - 7]).wrapping_add(s1);
+    }
+    let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = *state;
+    for (&k, &w) in K.iter().zip(&w) {
+        let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+        let choice = (e & f) ^ (!e & g);
+        let t1 = h.wrapping_add(s1).wrapping_add(choice).wrapping_add(k).wrapping_add(w);
+        let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+        let majority = (a & b) ^ (a & c) ^ (b & c);
+        let t2 = s0.wrapping_add(majority);
+        (h, g, f, e, d, c, b, a) = (g, f, e, d.wrapping_add(t1), c, b, a, t1.wrapping_add(t2));
+    }
+    for (word, value) in state.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+        *word = word.wrapping_add(value);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn sha256_test() {
+        let empty = crate::hex!("e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855");
+        assert_eq!(empty, sha256(b""));
+        let abc = crate::hex!("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+        assert_eq!(abc, sha256(b"abc"));
+        let a56 = crate::hex!("b35439a4ac6f0948b6d6f9e3c6af0f5f590ce20f1bde7090ef7970686ec6738a");
+        assert_eq!(a56, sha256(&[b'a'; 56]));
+        let a64 = crate::hex!("ffe054fe7ae0cb6dc65c3af9b61d5209f439851db43d0ba5997337df154668eb");
+        assert_eq!(a64, sha256(&[b'a'; 64]));
+    }
+
+    #[test]
+    fn encode_into_test() {
+        let mut text = [0; 40];
+        let mut check = |base58: Base58, data: &[u8], expected: &str| {
+            assert_eq!(Ok(expected.len()), base58.encode_into(data, &mut text));
+            assert_eq!(expected.as_bytes(), &text[..expected.len()]);
+        };
+        check(Base58::BITCOIN, b"", "");
+        check(Base58::BITCOIN, b"\0", "1");
+        check(Base58::BITCOIN, &[0xff; 4], "7YXq9G");
+        check(Base58::BITCOIN, &crate::hex!("0000287fb4cd"), "11233QC4");
+        check(Base58::BITCOIN_CHECK, b"", "3QJmnh");
+        check(Base58::BITCOIN_CHECK, b"\0", "1Wh4bh");
+        let hash = crate::hex!("00 7680adec8eabcabac676be9e83854ade0bd22cdb");
+        check(Base58::BITCOIN_CHECK, &hash, "1BoatSLRHtKNngkdXEeobR76b53LETtpyT");
+        assert_eq!(
+            Err(Error::new(ErrorKind::OutOfBounds { start_index: 0, requested: 6, len: 5 })),
+            Base58::BITCOIN.encode_into(&[0xff; 4], &mut text[..5])
+        );
+        assert_eq!(
+            Err(Error::new(ErrorKind::OutOfBounds { start_index: 0, requested: 3, len: 1 })),
+            Base58::BITCOIN.encode_into(&[0; 2], &mut text[..1])
+        );
+    }
+
+    #[test]
+    fn decode_into_test() {
+        let mut data = [0; 8];
+        assert_eq!(Ok(0), Base58::BITCOIN.decode_into("", &mut data));
+        assert_eq!(Ok(4), Base58::BITCOIN.decode_into("7YXq9G", &mut data));
+        assert_eq!([0xff; 4], data[..4]);
+        assert_eq!(Ok(2), Base58::BITCOIN.decode_into("11", &mut data));
+        assert_eq!([0; 2], data[..2]);
+        assert_eq!(Ok(0), Base58::BITCOIN_CHECK.decode_into("3QJmnh", &mut data));
+        assert_eq!(Ok(1), Base58::BITCOIN_CHECK.decode_into("1Wh4bh", &mut data));
+        assert_eq!(
+            Err(Error::new(ErrorKind::InvalidEncoding { start_index: 2 })),
+            Base58::BITCOIN.decode_into("11O", &mut data)
+        );
+        assert_eq!(
+            Err(Error::new(ErrorKind::InvalidEncoding { start_index: 3 })),
+            Base58::BITCOIN_CHECK.decode_into("7YX", &mut data)
+        );
+        assert_eq!(
+            Err(Error::new(ErrorKind::ChecksumMismatch { start_index: 0 })),
+            Base58::BITCOIN_CHECK.decode_into("1Wh4bi", &mut data)
+        );
+        assert_eq!(
+            Err(Error::new(ErrorKind::OutOfBounds { start_index: 0, requested: 6, len: 3 })),
+            Base58::BITCOIN.decode_into("7YXq9G", &mut data[..3])
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn round_trip_test() {
+        let data: Vec<u8> = (0..=255).rev().collect();
+        for base58 in [Base58::BITCOIN, Base58::BITCOIN_CHECK] {
+            for len in [0, 1, 2, 7, 32, 100, 256] {
+                for zeros in [0, 1, 3] {
+                    let mut data = data[..len].to_vec();
+                    data.iter_mut().take(zeros).for_each(|byte| *byte = 0);
+                    assert_eq!(Ok(&data[..]), base58.decode(&base58.encode(&data)).as_deref());
+                }
+            }
+        }
+    }
+}
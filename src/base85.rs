@@ -0,0 +1,298 @@
+//! Base85 text for byte arrays, as the Ascii85 of PostScript and PDF and the
+//! Z85 of ZeroMQ write it.
+use crate::{Error, ErrorKind, Result};
+#[cfg(feature = "alloc")]
+use alloc::{string::String, vec, vec::Vec};
+use core::{convert::TryInto, fmt};
+
+const ASCII85_ALPHABET: &[u8; 85] =
+    b"!\"#$%&'()*+,-./0123456789:;<=>?@ABCDEFGHIJKLMNOPQRSTUVWXYZ[\\]^_`abcdefghijklmnopqrstu";
+const Z85_ALPHABET: &[u8; 85] =
+    b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ.-:+=^!/*?&<>()[]{}@%$#";
+const ASCII85_VALUES: [u8; 256] = values(ASCII85_ALPHABET);
+const Z85_VALUES: [u8; 256] = values(Z85_ALPHABET);
+
+/// Returns the value of each character of alphabet, and 0xff for the others.
+const fn values(alphabet: &[u8; 85]) -> [u8; 256] {
+    let mut values = [0xff; 256];
+    let mut value = 0;
+    while value < 85 {
+        values[alphabet[value] as usize] = value as u8;
+        value += 1;
+    }
+    values
+}
+
+/// A variant of Base85, which writes each group of four bytes as a number of
+/// five characters in base 85.
+///
+/// Ascii85 writes a group of four zero bytes as `z` and a final group of one
+/// to three bytes as two to four characters, and its decoding skips
+/// whitespace and the `<~` and `~>` delimiters around text. Z85 takes only
+/// data of a multiple of four bytes and text of a multiple of five
+/// characters, in an alphabet that can be quoted in source code.
+/// # Example
+///
+/// ```
+/// use bitconv::Base85;
+///
+/// let mut text = [0; 10];
+/// let key = [0x86, 0x4f, 0xd2, 0x6f, 0xb5, 0x59, 0xf7, 0x5b];
+/// assert_eq!(Ok(10), Base85::Z85.encode_into(&key, &mut text));
+/// assert_eq!(b"HelloWorld", &text);
+/// assert_eq!(Ok(7), Base85::ASCII85.encode_into(b"sure.", &mut text));
+/// assert_eq!(b"F*2M7/c", &text[..7]);
+///
+/// let mut data = [0; 8];
+/// assert_eq!(Ok(4), Base85::ASCII85.decode_into("<~9jqo^~>", &mut data));
+/// assert_eq!(b"Man ", &data[..4]);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Base85 {
+    alphabet: &'static [u8; 85],
+    values: &'static [u8; 256],
+    ascii85: bool,
+}
+
+impl Base85 {
+    /// Ascii85, of the characters `!` to `u` and `z`, as in PDF and PostScript
+    /// streams.
+    pub const ASCII85: Base85 =
+        Base85 { alphabet: ASCII85_ALPHABET, values: &ASCII85_VALUES, ascii85: true };
+    /// Z85, as in the keys of ZeroMQ's CURVE security.
+    pub const Z85: Base85 = Base85 { alphabet: Z85_ALPHABET, values: &Z85_VALUES, ascii85: false };
+
+    /// Returns the largest number of characters of the text of len bytes.
+    pub const fn max_encoded_len(&self, len: usize) -> usize {
+        let full = (len / 4).saturating_mul(5);
+        match len % 4 {
+            0 => full,
+            rest => full.saturating_add(rest + 1),
+        }
+    }
+
+    /// Returns the largest number of bytes encoded by text of len characters.
+    pub const fn max_decoded_len(&self, len: usize) -> usize {
+        match self.ascii85 {
+            true => len.saturating_mul(4),
+            false => len / 5 * 4,
+        }
+    }
+
+    /// Writes the text of data to the start of out, without delimiters, and
+    /// returns the number of characters written.
+    ///
+    /// Fails with `ErrorKind::InvalidEncoding` at the start of the last group
+    /// of data if Z85 is given fewer than four bytes in it, and with
+    /// `ErrorKind::OutOfBounds`, requesting
+    /// [`max_encoded_len`](Base85::max_encoded_len) characters, if out is too
+    /// short for the text, and may then have written part of out.
+    pub fn encode_into(&self, data: &[u8], out: &mut [u8]) -> Result<usize> {
+        if !self.ascii85 && data.len() & 3 != 0 {
+            let start_index = data.len() & !3;
+            return Err(Error::new(ErrorKind::InvalidEncoding { start_index }));
+        }
+        let requested = self.max_encoded_len(data.len());
+        let too_short =
+            Error::new(ErrorKind::OutOfBounds { start_index: 0, requested, len: out.len() });
+        let mut len = 0;
+        for bytes in data.chunks(4) {
+            if self.ascii85 && bytes == [0; 4] {
+                put(out, &mut len, b"z", too_short)?;
+                continue;
+            }
+            let mut group = [0; 4];
+            group[..bytes.len()].copy_from_slice(bytes);
+            let mut group = u32::from_be_bytes(group);
+            let mut digits = [0; 5];
+            for digit in digits.iter_mut().rev() {
+                *digit = self.alphabet[(group % 85) as usize];
+                group /= 85;
+            }
+            put(out, &mut len, &digits[..bytes.len() + 1], too_short)?;
+        }
+        Ok(len)
+    }
+
+    /// Returns the text of data as a new string, failing as
+    /// [`encode_into`](Base85::encode_into) does for an incomplete group of
+    /// Z85.
+    #[cfg(feature = "alloc")]
+    pub fn encode(&self, data: &[u8]) -> Result<String> {
+        let mut text = vec![0; self.max_encoded_len(data.len())];
+        let len = self.encode_into(data, &mut text)?;
+        Ok(text[..len].iter().copied().map(char::from).collect())
+    }
+
+    /// Parses text into the start of out and returns the number of bytes
+    /// written.
+    ///
+    /// Fails with `ErrorKind::InvalidEncoding` at the byte offset in text of
+    /// the first character outside the alphabet, of the `z` of Ascii85 inside
+    /// a group, of the last character of a group whose number does not fit in
+    /// four bytes, or of the last character if it ends a group of a single
+    /// character or, for Z85, of fewer than five, and with
+    /// `ErrorKind::OutOfBounds`, requesting
+    /// [`max_decoded_len`](Base85::max_decoded_len) bytes, if out is too short
+    /// for the data. Part of out may have been written when decoding fails.
+    pub fn decode_into(&self, text: &str, out: &mut [u8]) -> Result<usize> {
+        let text = text.as_bytes();
+        let mut start = 0;
+        let mut end = text.len();
+        if self.ascii85 {
+            if text.starts_with(b"<~") {
+                start = 2;
+            }
+            if text.ends_with(b"~>") && text.len() >= start + 2 {
+                end = text.len() - 2;
+            }
+        }
+        let requested = self.max_decoded_len(text.len());
+        let too_short =
+            Error::new(ErrorKind::OutOfBounds { start_index: 0, requested, len: out.len() });
+        let (mut group, mut digits, mut last, mut len) = (0u64, 0, 0, 0);
+        for (start_index, &digit) in text.iter().enumerate().take(end).skip(start) {
+            if self.ascii85 && digit.is_ascii_whitespace() {
+                continue;
+            }
+            if self.ascii85 && digit == b'z' && digits == 0 {
+                put(out, &mut len, &[0; 4], too_short)?;
+                continue;
+            }
+            let value = match self.values[usize::from(digit)] {
+                0xff => return Err(invalid(start_index)),
+                value => value,
+            };
+            group = group * 85 + u64::from(value);
+            digits += 1;
+            last = start_index;
+            if digits == 5 {
+                let bytes: u32 = group.try_into().map_err(|_| invalid(start_index))?;
+                put(out, &mut len, &bytes.to_be_bytes(), too_short)?;
+                group = 0;
+                digits = 0;
+            }
+        }
+        if digits > 0 {
+            if digits == 1 || !self.ascii85 {
+                return Err(invalid(last));
+            }
+            // The final group was written padded with zero bytes, whose
+            // digits rounded down, so it is read padded with the last digit.
+            for _ in digits..5 {
+                group = group * 85 + 84;
+            }
+            let bytes: u32 = group.try_into().map_err(|_| invalid(last))?;
+            put(out, &mut len, &bytes.to_be_bytes()[..digits - 1], too_short)?;
+        }
+        Ok(len)
+    }
+
+    /// Parses text into a new vector, failing as
+    /// [`decode_into`](Base85::decode_into) does.
+    #[cfg(feature = "alloc")]
+    pub fn decode(&self, text: &str) -> Result<Vec<u8>> {
+        let mut data = vec![0; self.max_decoded_len(text.len())];
+        let len = self.decode_into(text, &mut data)?;
+        data.truncate(len);
+        Ok(data)
+    }
+}
+
+impl fmt::Debug for Base85 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = if self.ascii85 { "ASCII85" } else { "Z85" };
+        f.debug_tuple("Base85").field(&name).finish()
+    }
+}
+
+fn invalid(start_index: usize) -> Error { Error::new(ErrorKind::InvalidEncoding { start_index }) }
+
+/// Appends bytes to out at len, or fails with too_short if they do not fit.
+fn put(out: &mut [u8], len: &mut usize, bytes: &[u8], too_short: Error) -> Result<()> {
+    let slots = out.get_mut(*len..).and_then(|rest| rest.get_mut(..bytes.len()));
+    slots.ok_or(too_short)?.copy_from_slice(bytes);
+    *len += bytes.len();
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn ascii85_test() {
+        let mut text = [0; 24];
+        let mut data = [0; 24];
+        let vectors: [(&[u8], &str); 9] = [
+            (b"", ""),
+            (b"Man ", "9jqo^"),
+            (b"Man", "9jqo"),
+            (b"sure.", "F*2M7/c"),
+            (b"\0\0\0\0", "z"),
+            (b"\0\0\0", "!!!!"),
+            (b"\0\0\0\0\x01", "z!<"),
+            (&[0xff; 4], "s8W-!"),
+            (b"Hello, world!\n", "87cURD_*#TDfTZ)+US"),
+        ];
+        for &(bytes, expected) in &vectors {
+            assert_eq!(Ok(expected.len()), Base85::ASCII85.encode_into(bytes, &mut text));
+            assert_eq!(expected.as_bytes(), &text[..expected.len()]);
+            assert_eq!(Ok(bytes.len()), Base85::ASCII85.decode_into(expected, &mut data));
+            assert_eq!(bytes, &data[..bytes.len()]);
+        }
+        let mut decode = |text| Base85::ASCII85.decode_into(text, &mut data);
+        assert_eq!(Ok(14), decode("<~87cUR D_*#T\nDfTZ)\r\n+US~>"));
+        assert_eq!(Ok(0), decode("<~~>"));
+        assert_eq!(Err(invalid(2)), decode("<~>"));
+        assert_eq!(Ok(8), decode("zz"));
+        assert_eq!(Err(invalid(2)), decode("9jzo^"));
+        assert_eq!(Err(invalid(4)), decode("s8W-\""));
+        assert_eq!(Err(invalid(3)), decode("9jqv^"));
+        assert_eq!(Err(invalid(7)), decode("<~9jqo^F~>"));
+        assert_eq!(Err(invalid(3)), decode("uuuu"));
+        assert_eq!(
+            Err(Error::new(ErrorKind::OutOfBounds { start_index: 0, requested: 5, len: 4 })),
+            Base85::ASCII85.encode_into(b"sure", &mut text[..4])
+        );
+        assert_eq!(
+            Err(Error::new(ErrorKind::OutOfBounds { start_index: 0, requested: 8, len: 7 })),
+            Base85::ASCII85.decode_into("zz", &mut data[..7])
+        );
+    }
+
+    #[test]
+    fn z85_test() {
+        let mut text = [0; 10];
+        let mut data = [0; 8];
+        assert_eq!(Ok(5), Base85::Z85.encode_into(&[0xff; 4], &mut text));
+        assert_eq!(b"%nSc0", &text[..5]);
+        assert_eq!(Ok(5), Base85::Z85.encode_into(&[0; 4], &mut text));
+        assert_eq!(b"00000", &text[..5]);
+        assert_eq!(Err(invalid(4)), Base85::Z85.encode_into(&[0; 6], &mut text));
+        assert_eq!(Ok(8), Base85::Z85.decode_into("HelloWorld", &mut data));
+        assert_eq!([0x86, 0x4f, 0xd2, 0x6f, 0xb5, 0x59, 0xf7, 0x5b], data);
+        assert_eq!(Err(invalid(3)), Base85::Z85.decode_into("Hell", &mut data));
+        assert_eq!(Err(invalid(5)), Base85::Z85.decode_into("Hello World", &mut data));
+        assert_eq!(Err(invalid(1)), Base85::Z85.decode_into("<~Hello~>", &mut data));
+        assert_eq!(Err(invalid(4)), Base85::Z85.decode_into("%nSc1", &mut data));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn round_trip_test() {
+        let data: Vec<u8> = (0..=255).chain([0; 9]).collect();
+        for len in [0, 1, 2, 3, 4, 5, 8, 100, 264, 265] {
+            let data = &data[..len];
+            let text = Base85::ASCII85.encode(data).unwrap();
+            assert_eq!(Ok(data), Base85::ASCII85.decode(&text).as_deref());
+            match len % 4 {
+                0 => {
+                    let text = Base85::Z85.encode(data).unwrap();
+                    assert_eq!(Ok(data), Base85::Z85.decode(&text).as_deref());
+                }
+                _ => assert!(Base85::Z85.encode(data).is_err()),
+            }
+        }
+    }
+}
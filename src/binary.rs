@@ -0,0 +1,417 @@
+//! Cursor and buffer with the semantics of .NET's `System.IO.BinaryReader`
+//! and `System.IO.BinaryWriter`.
+#[cfg(feature = "alloc")]
+use crate::Writer;
+use crate::{endian::Little, varint::decode_uleb128, Decimal, Error, ErrorKind, Reader, Result};
+#[cfg(feature = "alloc")]
+use alloc::{borrow::Cow, string::String};
+use core::{
+    convert::TryFrom, ops::{Deref, DerefMut}, str
+};
+
+/// [`Reader`] adding the conversions of .NET's `BinaryReader` that go beyond
+/// fixed-size integers, for reading files written by .NET code.
+///
+/// Like `BinaryReader`, it always reads in little-endian. The reader
+/// dereferences to a `Reader<Little>`, through which integers and everything
+/// else are read as usual. Unlike `BinaryReader`, a failed read leaves the
+/// position unchanged.
+/// # Example
+///
+/// ```
+/// use bitconv::BinaryReader;
+///
+/// // What `BinaryWriter` writes for `true`, `"hé"`, `300` and `1.5m`.
+/// let buffer =
+///     [1, 3, 104, 195, 169, 44, 1, 0, 0, 15, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0];
+/// let mut reader = BinaryReader::new(&buffer);
+/// assert_eq!(Ok(true), reader.read_boolean());
+/// assert_eq!(Ok("hé"), reader.read_string());
+/// assert_eq!(Ok(300), reader.read_int32());
+/// let value = reader.read_decimal().unwrap();
+/// assert_eq!((15, 1), (value.mantissa(), value.scale()));
+/// ```
+pub struct BinaryReader<'a> {
+    reader: Reader<'a, Little>,
+}
+
+impl<'a> BinaryReader<'a> {
+    /// Creates a reader positioned at the start of data.
+    pub fn new(data: &'a [u8]) -> Self { BinaryReader { reader: Reader::new(data) } }
+
+    /// Returns the underlying reader.
+    pub fn into_inner(self) -> Reader<'a, Little> { self.reader }
+
+    /// Reads a byte as a `bool`, with any byte other than 0 being `true`.
+    pub fn read_boolean(&mut self) -> Result<bool> {
+        self.reader.read_uint8().map(|byte| byte != 0)
+    }
+
+    /// Reads an `f32`, as `ReadSingle` does.
+    pub fn read_single(&mut self) -> Result<f32> { self.reader.read_uint32().map(f32::from_bits) }
+
+    /// Reads an `f64`, as `ReadDouble` does.
+    pub fn read_double(&mut self) -> Result<f64> { self.reader.read_uint64().map(f64::from_bits) }
+
+    /// Reads an `i32` stored 7 bits at a time, least significant group first,
+    /// with the high bit of each byte set when another byte follows.
+    ///
+    /// Fails with `ErrorKind::InvalidEncoding` if the value would not fit in
+    /// 32 bits, which `Read7BitEncodedInt` rejects as well.
+    /// # Example
+    ///
+    /// ```
+    /// use bitconv::BinaryReader;
+    ///
+    /// let mut reader = BinaryReader::new(&[172, 2, 255, 255, 255, 255, 15]);
+    /// assert_eq!(Ok(300), reader.read_7bit_encoded_int());
+    /// assert_eq!(Ok(-1), reader.read_7bit_encoded_int());
+    /// ```
+    pub fn read_7bit_encoded_int(&mut self) -> Result<i32> {
+        read_7bit_encoded(&mut self.reader, 32).map(|value| value as u32 as i32)
+    }
+
+    /// Reads an `i64` stored 7 bits at a time, as
+    /// [`read_7bit_encoded_int`](BinaryReader::read_7bit_encoded_int) does
+    /// for an `i32`.
+    pub fn read_7bit_encoded_int64(&mut self) -> Result<i64> {
+        read_7bit_encoded(&mut self.reader, 64).map(|value| value as i64)
+    }
+
+    /// Reads a string prefixed by its length in bytes, encoded as by
+    /// [`read_7bit_encoded_int`](BinaryReader::read_7bit_encoded_int), as
+    /// `ReadString` does with its default UTF-8 encoding.
+    ///
+    /// The string is borrowed from the underlying buffer. Fails with
+    /// `ErrorKind::InvalidCount` for a negative length and with
+    /// `ErrorKind::InvalidEncoding` if the bytes are not valid UTF-8, which
+    /// .NET replaces with U+FFFD instead, as
+    /// [`read_string_lossy`](BinaryReader::read_string_lossy) does.
+    pub fn read_string(&mut self) -> Result<&'a str> {
+        self.reader.transaction(|reader| {
+            let bytes = read_prefixed_bytes(reader)?;
+            str::from_utf8(bytes).map_err(|_| {
+                Error::new(ErrorKind::InvalidEncoding {
+                    start_index: reader.position() - bytes.len(),
+                })
+            })
+        })
+    }
+
+    /// Reads a length-prefixed string, replacing invalid UTF-8 with U+FFFD
+    /// exactly as `ReadString` does.
+    #[cfg(feature = "alloc")]
+    pub fn read_string_lossy(&mut self) -> Result<Cow<'a, str>> {
+        self.reader.transaction(read_prefixed_bytes).map(String::from_utf8_lossy)
+    }
+
+    /// Reads the 16 bytes of a [`Decimal`], as `ReadDecimal` does.
+    ///
+    /// Fails with `ErrorKind::InvalidEncoding` if the flags of the decimal are
+    /// not valid.
+    pub fn read_decimal(&mut self) -> Result<Decimal> { self.reader.read() }
+}
+
+/// Reads an integer of at most bits bits stored 7 bits at a time, failing if
+/// it has more.
+fn read_7bit_encoded(reader: &mut Reader<'_, Little>, bits: u32) -> Result<u64> {
+    let (value, len) = decode_uleb128(reader.get_ref(), reader.position(), bits)?;
+    reader.read_bytes(len)?;
+    Ok(value)
+}
+
+/// Reads bytes prefixed by a 7-bit encoded length.
+fn read_prefixed_bytes<'a>(reader: &mut Reader<'a, Little>) -> Result<&'a [u8]> {
+    let start_index = reader.position();
+    let len = read_7bit_encoded(reader, 32)? as u32 as i32;
+    let len =
+        usize::try_from(len).map_err(|_| Error::new(ErrorKind::InvalidCount { start_index }))?;
+    reader.read_bytes(len)
+}
+
+impl<'a> Deref for BinaryReader<'a> {
+    type Target = Reader<'a, Little>;
+
+    fn deref(&self) -> &Reader<'a, Little> { &self.reader }
+}
+
+impl<'a> DerefMut for BinaryReader<'a> {
+    fn deref_mut(&mut self) -> &mut Reader<'a, Little> { &mut self.reader }
+}
+
+/// [`Writer`] adding the conversions of .NET's `BinaryWriter` that go beyond
+/// fixed-size integers, for writing files that .NET code reads back, byte for
+/// byte what `BinaryWriter` would have written.
+///
+/// Like `BinaryWriter`, it always writes in little-endian, and it
+/// dereferences to a `Writer<Little>` for everything else.
+/// # Example
+///
+/// ```
+/// use bitconv::{BinaryReader, BinaryWriter, Decimal};
+///
+/// let mut writer = BinaryWriter::new();
+/// writer.write_boolean(true);
+/// writer.write_string("hé").unwrap();
+/// writer.write_int32(300);
+/// writer.write_decimal(Decimal::new(15, 0, 0, false, 1).unwrap());
+/// assert_eq!([1, 3, 104, 195, 169, 44, 1, 0, 0, 15], writer.as_slice()[..10]);
+///
+/// let mut reader = BinaryReader::new(writer.as_slice());
+/// assert_eq!(Ok(true), reader.read_boolean());
+/// assert_eq!(Ok("hé"), reader.read_string());
+/// ```
+#[cfg(feature = "alloc")]
+pub struct BinaryWriter {
+    writer: Writer<Little>,
+}
+
+#[cfg(feature = "alloc")]
+impl BinaryWriter {
+    pub fn new() -> Self { BinaryWriter { writer: Writer::new() } }
+
+    /// Returns the underlying writer.
+    pub fn into_inner(self) -> Writer<Little> { self.writer }
+
+    /// Appends a `bool` as a byte of 1 or 0.
+    pub fn write_boolean(&mut self, value: bool) { self.writer.write_uint8(value.into()) }
+
+    /// Appends an `f32`, as `Write(float)` does.
+    pub fn write_single(&mut self, value: f32) { self.writer.write_uint32(value.to_bits()) }
+
+    /// Appends an `f64`, as `Write(double)` does.
+    pub fn write_double(&mut self, value: f64) { self.writer.write_uint64(value.to_bits()) }
+
+    /// Appends an `i32` 7 bits at a time, as
+    /// [`BinaryReader::read_7bit_encoded_int`] reads it back.
+    ///
+    /// Negative values always take five bytes.
+    pub fn write_7bit_encoded_int(&mut self, value: i32) {
+        self.writer.write_uleb128(u64::from(value as u32))
+    }
+
+    /// Appends an `i64` 7 bits at a time, as
+    /// [`BinaryReader::read_7bit_encoded_int64`] reads it back.
+    pub fn write_7bit_encoded_int64(&mut self, value: i64) {
+        self.writer.write_uleb128(value as u64)
+    }
+
+    /// Appends a string as its UTF-8 bytes, prefixed by their number encoded
+    /// as by [`write_7bit_encoded_int`](BinaryWriter::write_7bit_encoded_int),
+    /// as `Write(string)` does with its default UTF-8 encoding.
+    ///
+    /// Fails with `ErrorKind::LengthOverflow`, writing nothing, for a string
+    /// longer than `i32::MAX` bytes, which .NET cannot represent.
+    pub fn write_string(&mut self, value: &str) -> Result<()> {
+        let start_index = self.writer.position();
+        let len = i32::try_from(value.len())
+            .map_err(|_| Error::new(ErrorKind::LengthOverflow { start_index, len: value.len() }))?;
+        self.write_7bit_encoded_int(len);
+        self.writer.write_bytes(value.as_bytes());
+        Ok(())
+    }
+
+    /// Appends the 16 bytes of a [`Decimal`], as `Write(decimal)` does.
+    pub fn write_decimal(&mut self, value: Decimal) { self.writer.write(&value); }
+}
+
+#[cfg(feature = "alloc")]
+impl Default for BinaryWriter {
+    fn default() -> Self { BinaryWriter::new() }
+}
+
+#[cfg(feature = "alloc")]
+impl Deref for BinaryWriter {
+    type Target = Writer<Little>;
+
+    fn deref(&self) -> &Writer<Little> { &self.writer }
+}
+
+#[cfg(feature = "alloc")]
+impl DerefMut for BinaryWriter {
+    fn deref_mut(&mut self) -> &mut Writer<Little> { &mut self.writer }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn binary_reader_test() {
+        let buffer = [1, 2, 0, 0, 0xc0, 0xbf, 0x18, 0x2d, 0x44, 0x54, 0xfb, 0x21, 0x09, 0x40, 7];
+        let mut reader = BinaryReader::new(&buffer);
+        assert_eq!(Ok(true), reader.read_boolean());
+        assert_eq!(Ok(true), reader.read_boolean());
+        assert_eq!(Ok(false), reader.read_boolean());
+        reader.set_position(2);
+        assert_eq!(Ok(-1.5), reader.read_single());
+        assert_eq!(Ok(core::f64::consts::PI), reader.read_double());
+        assert!(reader.read_double().is_err());
+        assert_eq!(14, reader.position());
+        assert_eq!(Ok(7), reader.into_inner().read_uint8());
+    }
+
+    #[test]
+    fn read_7bit_encoded_int_test() {
+        let cases: [(&[u8], i32); 6] = [
+            (&[0], 0),
+            (&[127], 127),
+            (&[128, 1], 128),
+            (&[255, 255, 255, 255, 7], i32::MAX),
+            (&[128, 128, 128, 128, 8], i32::MIN),
+            (&[128, 128, 0], 0),
+        ];
+        for (bytes, value) in cases {
+            let mut reader = BinaryReader::new(bytes);
+            assert_eq!(Ok(value), reader.read_7bit_encoded_int());
+            assert_eq!(0, reader.remaining());
+        }
+        let mut reader = BinaryReader::new(&[0, 255, 255, 255, 255, 16]);
+        reader.set_position(1);
+        assert_eq!(
+            Err(Error::new(ErrorKind::InvalidEncoding { start_index: 1 })),
+            reader.read_7bit_encoded_int()
+        );
+        assert_eq!(1, reader.position());
+        let mut reader = BinaryReader::new(&[128, 128]);
+        assert_eq!(
+            Err(Error::new(ErrorKind::OutOfBounds { start_index: 2, requested: 1, len: 2 })),
+            reader.read_7bit_encoded_int()
+        );
+        assert_eq!(0, reader.position());
+    }
+
+    #[test]
+    fn read_7bit_encoded_int64_test() {
+        let mut reader = BinaryReader::new(&[255, 255, 255, 255, 255, 255, 255, 255, 255, 1]);
+        assert_eq!(Ok(-1), reader.read_7bit_encoded_int64());
+        let mut reader = BinaryReader::new(&[128, 128, 128, 128, 16]);
+        assert_eq!(Ok(1 << 32), reader.read_7bit_encoded_int64());
+        let mut reader = BinaryReader::new(&[255, 255, 255, 255, 255, 255, 255, 255, 255, 2]);
+        assert_eq!(
+            Err(Error::new(ErrorKind::InvalidEncoding { start_index: 0 })),
+            reader.read_7bit_encoded_int64()
+        );
+    }
+
+    #[test]
+    fn read_string_test() {
+        let buffer = [0, 2, 104, 105, 3, 104, 195, 169, 2, 104, 255, 5, 104];
+        let mut reader = BinaryReader::new(&buffer);
+        assert_eq!(Ok(""), reader.read_string());
+        assert_eq!(Ok("hi"), reader.read_string());
+        assert_eq!(Ok("hé"), reader.read_string());
+        assert_eq!(
+            Err(Error::new(ErrorKind::InvalidEncoding { start_index: 9 })),
+            reader.read_string()
+        );
+        assert_eq!(8, reader.position());
+        reader.set_position(11);
+        assert_eq!(
+            Err(Error::new(ErrorKind::OutOfBounds { start_index: 12, requested: 5, len: 13 })),
+            reader.read_string()
+        );
+        assert_eq!(11, reader.position());
+        let mut reader = BinaryReader::new(&[255, 255, 255, 255, 15]);
+        assert_eq!(
+            Err(Error::new(ErrorKind::InvalidCount { start_index: 0 })),
+            reader.read_string()
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn read_string_lossy_test() {
+        let mut reader = BinaryReader::new(&[2, 104, 105, 2, 104, 255, 1]);
+        assert_eq!(Ok(Cow::Borrowed("hi")), reader.read_string_lossy());
+        assert_eq!(Ok(Cow::Owned(String::from("h\u{fffd}"))), reader.read_string_lossy());
+        assert!(reader.read_string_lossy().is_err());
+        assert_eq!(6, reader.position());
+    }
+
+    #[test]
+    fn read_decimal_test() {
+        let mut buffer = [0; 32];
+        buffer[0] = 15;
+        buffer[14] = 1;
+        buffer[15] = 128;
+        buffer[28] = 1;
+        let mut reader = BinaryReader::new(&buffer);
+        assert_eq!(Ok(Decimal::new(15, 0, 0, true, 1).unwrap()), reader.read_decimal());
+        assert_eq!(
+            Err(Error::new(ErrorKind::InvalidEncoding { start_index: 16 })),
+            reader.read_decimal()
+        );
+        assert_eq!(16, reader.position());
+        let mut reader = BinaryReader::new(&buffer[..15]);
+        assert!(reader.read_decimal().is_err());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn binary_writer_test() {
+        let mut writer = BinaryWriter::default();
+        writer.write_boolean(true);
+        writer.write_boolean(false);
+        writer.write_single(-1.5);
+        writer.write_double(core::f64::consts::PI);
+        writer.write_uint8(7);
+        assert_eq!(
+            [1, 0, 0, 0, 0xc0, 0xbf, 0x18, 0x2d, 0x44, 0x54, 0xfb, 0x21, 0x09, 0x40, 7],
+            writer.as_slice()
+        );
+        assert_eq!(15, writer.into_inner().position());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn write_7bit_encoded_int_test() {
+        let cases: [(&[u8], i32); 5] = [
+            (&[0], 0),
+            (&[127], 127),
+            (&[128, 1], 128),
+            (&[255, 255, 255, 255, 7], i32::MAX),
+            (&[255, 255, 255, 255, 15], -1),
+        ];
+        for (bytes, value) in cases {
+            let mut writer = BinaryWriter::new();
+            writer.write_7bit_encoded_int(value);
+            assert_eq!(bytes, writer.as_slice());
+        }
+        let mut writer = BinaryWriter::new();
+        writer.write_7bit_encoded_int64(-1);
+        writer.write_7bit_encoded_int64(1 << 32);
+        writer.write_7bit_encoded_int64(i64::MIN);
+        let mut reader = BinaryReader::new(writer.as_slice());
+        assert_eq!(10, reader.peek_bytes(10).unwrap().len());
+        assert_eq!(Ok(-1), reader.read_7bit_encoded_int64());
+        assert_eq!(Ok(1 << 32), reader.read_7bit_encoded_int64());
+        assert_eq!(Ok(i64::MIN), reader.read_7bit_encoded_int64());
+        assert_eq!(0, reader.remaining());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn write_string_test() {
+        let mut writer = BinaryWriter::new();
+        assert_eq!(Ok(()), writer.write_string(""));
+        assert_eq!(Ok(()), writer.write_string("hé"));
+        let long = "a".repeat(200);
+        assert_eq!(Ok(()), writer.write_string(&long));
+        assert_eq!([0, 3, 104, 195, 169, 200, 1, 97], writer.as_slice()[..8]);
+        let mut reader = BinaryReader::new(writer.as_slice());
+        assert_eq!(Ok(""), reader.read_string());
+        assert_eq!(Ok("hé"), reader.read_string());
+        assert_eq!(Ok(&long[..]), reader.read_string());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn write_decimal_test() {
+        let mut writer = BinaryWriter::new();
+        let value = Decimal::new(15, 2, 3, true, 1).unwrap();
+        writer.write_decimal(value);
+        assert_eq!([15, 0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0, 0, 0, 1, 128], writer.as_slice());
+        assert_eq!(Ok(value), BinaryReader::new(writer.as_slice()).read_decimal());
+    }
+}
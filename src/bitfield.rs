@@ -0,0 +1,451 @@
+//! Bitfields of C structs laid out the way a C compiler packs them, for
+//! exchanging structs with firmware that declares its registers and packets
+//! with bitfields.
+//!
+//! A [`Layout`] takes the members of a struct in declaration order and places
+//! each of them as the chosen [`Abi`] does, returning a [`Field`] that reads
+//! and writes the member at its place in a buffer holding the struct. Types
+//! are assumed to be aligned to their size, as they are on x86-64 and AArch64
+//! among others, and packed structs are not covered.
+//!
+//! The endianness `E` of the layout decides from which end of its storage
+//! bits are taken. On little-endian targets, the first bitfield is stored in
+//! the least significant bits of the first byte, and on big-endian ones in
+//! the most significant bits, each field starting from its most significant
+//! bit.
+use crate::bitstream::{has_bits, load_bits, no_bits, store_bits};
+use crate::{endian::Endian, extract_bits, sign_extend, BitConvEndian, BitNumbering};
+use crate::{Error, ErrorKind, Lsb0, Msb0, Result};
+use core::marker::PhantomData;
+
+/// Rules a compiler follows to pack bitfields into storage units.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Abi {
+    /// The rules of GCC, and of Clang targeting anything but Windows: a
+    /// bitfield goes right after the previous one, unless it would cross a
+    /// boundary of the alignment of its own type, in which case it starts at
+    /// the next such boundary. Bitfields of different types share bytes.
+    Gcc,
+    /// The rules of MSVC, and of Clang targeting Windows: consecutive
+    /// bitfields whose types have the same size share a storage unit of that
+    /// type while they fit in it, and any other member starts a new unit,
+    /// aligned to its type.
+    Msvc,
+}
+
+/// Integer types a C bitfield can be declared with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum CType {
+    /// `_Bool` or `bool`.
+    Bool,
+    /// `signed char` or `int8_t`, and `char` where it is signed.
+    I8,
+    /// `unsigned char` or `uint8_t`.
+    U8,
+    /// `short` or `int16_t`.
+    I16,
+    /// `unsigned short` or `uint16_t`.
+    U16,
+    /// `int` or `int32_t`, which both compilers treat as signed in
+    /// bitfields.
+    I32,
+    /// `unsigned int` or `uint32_t`.
+    U32,
+    /// `long long` or `int64_t`.
+    I64,
+    /// `unsigned long long` or `uint64_t`.
+    U64,
+}
+
+impl CType {
+    /// Returns the size of the type in bytes, which is also its alignment.
+    pub const fn size(self) -> usize {
+        match self {
+            CType::Bool | CType::I8 | CType::U8 => 1,
+            CType::I16 | CType::U16 => 2,
+            CType::I32 | CType::U32 => 4,
+            CType::I64 | CType::U64 => 8,
+        }
+    }
+
+    /// Returns the largest width a bitfield of the type can be declared with.
+    pub const fn max_bits(self) -> u32 {
+        match self {
+            CType::Bool => 1,
+            _ => self.size() as u32 * 8,
+        }
+    }
+
+    /// Returns whether the type is signed, so that its bitfields are
+    /// sign-extended when read.
+    pub const fn is_signed(self) -> bool {
+        matches!(self, CType::I8 | CType::I16 | CType::I32 | CType::I64)
+    }
+}
+
+/// Placement of the members of a C struct in the endianness `E`, computed one
+/// member at a time in declaration order.
+/// # Example
+///
+/// ```
+/// use bitconv::{
+///     bitfield::{Abi, CType, Layout}, endian::Little
+/// };
+///
+/// // struct status {
+/// //     unsigned int ready : 1;
+/// //     unsigned int mode : 3;
+/// //     int temp : 12;
+/// //     unsigned char flags : 4;
+/// //     unsigned short id : 10;
+/// // };
+/// let mut layout = Layout::<Little>::new(Abi::Gcc);
+/// let ready = layout.bitfield(CType::U32, 1).unwrap();
+/// let mode = layout.bitfield(CType::U32, 3).unwrap();
+/// let temp = layout.bitfield(CType::I32, 12).unwrap();
+/// let flags = layout.bitfield(CType::U8, 4).unwrap();
+/// let id = layout.bitfield(CType::U16, 10).unwrap();
+/// assert_eq!(4, layout.size());
+///
+/// let status = [0xcb, 0xf9, 0xba, 0x2a];
+/// assert_eq!(Ok(1), ready.get(&status, 0));
+/// assert_eq!(Ok(5), mode.get(&status, 0));
+/// assert_eq!(Ok(-100), temp.get_signed(&status, 0));
+/// assert_eq!(Ok(0xa), flags.get(&status, 0));
+/// assert_eq!(Ok(0x2ab), id.get(&status, 0));
+///
+/// // MSVC starts a new storage unit whenever the size of the type changes.
+/// let mut layout = Layout::<Little>::new(Abi::Msvc);
+/// layout.bitfield(CType::U32, 1).unwrap();
+/// layout.bitfield(CType::U32, 3).unwrap();
+/// layout.bitfield(CType::I32, 12).unwrap();
+/// assert_eq!(32, layout.bitfield(CType::U8, 4).unwrap().bit_offset());
+/// assert_eq!(48, layout.bitfield(CType::U16, 10).unwrap().bit_offset());
+/// assert_eq!(8, layout.size());
+/// ```
+#[derive(Clone, Debug)]
+pub struct Layout<E> {
+    abi: Abi,
+    offset: usize,
+    unit: Option<(usize, usize)>,
+    align: usize,
+    endian: PhantomData<E>,
+}
+
+impl<E: BitConvEndian> Layout<E> {
+    /// Creates the layout of an empty struct following abi.
+    pub fn new(abi: Abi) -> Self {
+        Layout { abi, offset: 0, unit: None, align: 1, endian: PhantomData }
+    }
+
+    /// Returns the number of bits taken by the members placed so far.
+    pub fn bit_len(&self) -> usize {
+        match (self.abi, self.unit) {
+            (Abi::Msvc, Some((start, size))) => start + size * 8,
+            _ => self.offset,
+        }
+    }
+
+    /// Returns the alignment of the struct in bytes, which is the largest size
+    /// of the types of its members.
+    pub fn align(&self) -> usize { self.align }
+
+    /// Returns the size of the struct in bytes, including the padding that
+    /// rounds it up to its alignment.
+    pub fn size(&self) -> usize { self.bit_len().div_ceil(8).div_ceil(self.align) * self.align }
+
+    /// Returns the field for bits bits at offset, in the bit numbering of `E`.
+    fn field(&self, offset: usize, bits: u32, signed: bool) -> Field {
+        let numbering = match E::ENDIANNESS {
+            Endian::LE => BitNumbering::Lsb0,
+            Endian::BE => BitNumbering::Msb0,
+            Endian::NE if cfg!(target_endian = "little") => BitNumbering::Lsb0,
+            Endian::NE => BitNumbering::Msb0,
+        };
+        Field { bit_offset: offset, bits, signed, numbering }
+    }
+
+    /// Places a bitfield of bits bits declared with ty, returning the field
+    /// reading and writing it.
+    ///
+    /// A width of 0 declares an unnamed zero-width bitfield, which moves the
+    /// next member to a new storage unit of ty, and the returned field then
+    /// holds no bits. Fails with `ErrorKind::LimitExceeded` where a compiler
+    /// would reject the declaration, if bits is wider than ty.
+    pub fn bitfield(&mut self, ty: CType, bits: u32) -> Result<Field> {
+        if bits > ty.max_bits() {
+            return Err(Error::new(ErrorKind::LimitExceeded {
+                start_index: self.bit_len() / 8,
+                len: bits as usize,
+                max: ty.max_bits() as usize,
+            }));
+        }
+        let unit_bits = ty.size() * 8;
+        let offset = match self.abi {
+            Abi::Gcc if bits == 0 => {
+                self.offset = self.offset.div_ceil(unit_bits) * unit_bits;
+                return Ok(self.field(self.offset, 0, ty.is_signed()));
+            }
+            Abi::Gcc => {
+                if self.offset % unit_bits + bits as usize > unit_bits {
+                    self.offset = self.offset.div_ceil(unit_bits) * unit_bits;
+                }
+                self.offset
+            }
+            Abi::Msvc if bits == 0 => {
+                self.close_unit();
+                return Ok(self.field(self.offset, 0, ty.is_signed()));
+            }
+            Abi::Msvc => match self.unit {
+                Some((start, size))
+                    if size == ty.size() && self.offset + bits as usize <= start + unit_bits =>
+                {
+                    self.offset
+                }
+                _ => {
+                    self.close_unit();
+                    let start = self.offset.div_ceil(unit_bits) * unit_bits;
+                    self.unit = Some((start, ty.size()));
+                    start
+                }
+            },
+        };
+        self.offset = offset + bits as usize;
+        self.align = self.align.max(ty.size());
+        Ok(self.field(offset, bits, ty.is_signed()))
+    }
+
+    /// Places a member of type ty that is not a bitfield, returning the field
+    /// reading and writing all of its bits.
+    ///
+    /// The member starts at the next byte aligned to its size, after the
+    /// storage unit of the previous bitfield for [`Abi::Msvc`].
+    pub fn member(&mut self, ty: CType) -> Field {
+        self.close_unit();
+        let unit_bits = ty.size() * 8;
+        let offset = self.offset.div_ceil(unit_bits) * unit_bits;
+        self.offset = offset + unit_bits;
+        self.align = self.align.max(ty.size());
+        self.field(offset, unit_bits as u32, ty.is_signed())
+    }
+
+    /// Moves past the storage unit of the previous bitfield, if any.
+    fn close_unit(&mut self) {
+        if let Some((start, size)) = self.unit.take() {
+            self.offset = start + size * 8;
+        }
+    }
+}
+
+/// Bitfield or member of a C struct, at a fixed bit offset from the start of
+/// the struct.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Field {
+    bit_offset: usize,
+    bits: u32,
+    signed: bool,
+    numbering: BitNumbering,
+}
+
+impl Field {
+    /// Returns the offset of the first bit of the field from the start of the
+    /// struct, counted in the bit numbering of the layout's endianness.
+    pub fn bit_offset(&self) -> usize { self.bit_offset }
+
+    /// Returns the width of the field in bits.
+    pub fn bits(&self) -> u32 { self.bits }
+
+    /// Returns whether the field was declared with a signed type.
+    pub fn is_signed(&self) -> bool { self.signed }
+
+    /// Returns the index of the first bit of the field in data holding the
+    /// struct at start_index.
+    fn bit_index(&self, data: &[u8], start_index: usize) -> Result<usize> {
+        start_index
+            .checked_mul(8)
+            .and_then(|index| index.checked_add(self.bit_offset))
+            .ok_or_else(|| no_bits(data, usize::MAX, self.bits as usize))
+    }
+
+    /// Returns the bits of the field in the struct at start_index, as the low
+    /// bits of a `u64` and without sign extension.
+    ///
+    /// Fails with `ErrorKind::OutOfBounds` if the field does not fit in data.
+    pub fn get(&self, data: &[u8], start_index: usize) -> Result<u64> {
+        let bit_index = self.bit_index(data, start_index)?;
+        let value = match self.numbering {
+            BitNumbering::Msb0 => load_bits::<Msb0>(data, bit_index, self.bits),
+            BitNumbering::Lsb0 => load_bits::<Lsb0>(data, bit_index, self.bits),
+        };
+        value.ok_or_else(|| no_bits(data, bit_index, self.bits as usize))
+    }
+
+    /// Returns the field in the struct at start_index as a two's complement
+    /// number sign-extended from its highest bit, as a compiler reads a
+    /// bitfield of a signed type, failing as [`get`](Field::get) does.
+    pub fn get_signed(&self, data: &[u8], start_index: usize) -> Result<i64> {
+        self.get(data, start_index).map(|value| sign_extend(value, self.bits))
+    }
+
+    /// Writes value to the field in the struct at start_index, leaving the
+    /// other bits of data as they are.
+    ///
+    /// Fails with `ErrorKind::InvalidEncoding` if value does not fit in the
+    /// width of the field, and with `ErrorKind::OutOfBounds` if the field
+    /// does not fit in data, leaving data untouched either way.
+    pub fn set(&self, data: &mut [u8], start_index: usize, value: u64) -> Result<()> {
+        let bit_index = self.bit_index(data, start_index)?;
+        if value.checked_shr(self.bits).unwrap_or(0) != 0 {
+            return Err(Error::new(ErrorKind::InvalidEncoding { start_index: bit_index / 8 }));
+        } else if !has_bits(data, bit_index, self.bits as usize) {
+            return Err(no_bits(data, bit_index, self.bits as usize));
+        }
+        match self.numbering {
+            BitNumbering::Msb0 => store_bits::<Msb0>(data, bit_index, self.bits, value),
+            BitNumbering::Lsb0 => store_bits::<Lsb0>(data, bit_index, self.bits, value),
+        }
+        Ok(())
+    }
+
+    /// Writes value to the field in two's complement, failing as
+    /// [`set`](Field::set) does if value is out of the range of a signed
+    /// number of the width of the field.
+    pub fn set_signed(&self, data: &mut [u8], start_index: usize, value: i64) -> Result<()> {
+        let bits = extract_bits(value as u64, 0, self.bits);
+        if sign_extend(bits, self.bits) != value {
+            let bit_index = self.bit_index(data, start_index)?;
+            return Err(Error::new(ErrorKind::InvalidEncoding { start_index: bit_index / 8 }));
+        }
+        self.set(data, start_index, bits)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::endian::{Big, Little};
+
+    const STATUS: [(CType, u32); 5] =
+        [(CType::U32, 1), (CType::U32, 3), (CType::I32, 12), (CType::U8, 4), (CType::U16, 10)];
+
+    #[test]
+    fn gcc_layout_test() {
+        let mut layout = Layout::<Little>::new(Abi::Gcc);
+        let offsets = STATUS.map(|(ty, bits)| layout.bitfield(ty, bits).unwrap().bit_offset());
+        assert_eq!([0, 1, 4, 16, 20], offsets);
+        assert_eq!((30, 4, 4), (layout.bit_len(), layout.align(), layout.size()));
+
+        // struct mixed {
+        //     unsigned char a : 7;
+        //     unsigned char b : 3;
+        //     unsigned long long c : 40;
+        //     unsigned short d;
+        //     unsigned int : 0;
+        //     _Bool e : 1;
+        //     unsigned short f : 15;
+        //     unsigned short g : 2;
+        // };
+        let mut layout = Layout::<Little>::new(Abi::Gcc);
+        let a = layout.bitfield(CType::U8, 7).unwrap();
+        let b = layout.bitfield(CType::U8, 3).unwrap();
+        let c = layout.bitfield(CType::U64, 40).unwrap();
+        let d = layout.member(CType::U16);
+        assert_eq!(0, layout.bitfield(CType::U32, 0).unwrap().bits());
+        let e = layout.bitfield(CType::Bool, 1).unwrap();
+        let f = layout.bitfield(CType::U16, 15).unwrap();
+        let g = layout.bitfield(CType::U16, 2).unwrap();
+        let offsets = [a, b, c, d, e, f, g].map(|field| field.bit_offset());
+        assert_eq!([0, 8, 11, 64, 96, 97, 112], offsets);
+        assert_eq!((8, 16), (layout.align(), layout.size()));
+
+        // As laid out by GCC on x86-64.
+        let mixed =
+            [0x55, 0xd6, 0xc4, 0xb3, 0xa2, 0x91, 0, 0, 0xef, 0xbe, 0, 0, 0x79, 0xf5, 0x03, 0];
+        let mut buffer = [0; 16];
+        for (field, value) in
+            [a, b, c, d, e, f, g].iter().zip([0x55, 6, 0x12_3456_789a, 0xbeef, 1, 0x7abc, 3])
+        {
+            assert_eq!(Ok(()), field.set(&mut buffer, 0, value));
+            assert_eq!(Ok(value), field.get(&mixed, 0));
+        }
+        assert_eq!(mixed, buffer);
+    }
+
+    #[test]
+    fn gcc_big_endian_test() {
+        let mut layout = Layout::<Big>::new(Abi::Gcc);
+        let fields = STATUS.map(|(ty, bits)| layout.bitfield(ty, bits).unwrap());
+        let status = [0xdf, 0x9c, 0xaa, 0xac];
+        let mut buffer = [0; 4];
+        for (field, value) in fields.iter().zip([1, 5, 0xf9c, 0xa, 0x2ab]) {
+            assert_eq!(Ok(()), field.set(&mut buffer, 0, value));
+            assert_eq!(Ok(value), field.get(&status, 0));
+        }
+        assert_eq!(status, buffer);
+        assert_eq!(Ok(-100), fields[2].get_signed(&status, 0));
+    }
+
+    #[test]
+    fn msvc_layout_test() {
+        let mut layout = Layout::<Little>::new(Abi::Msvc);
+        let fields = STATUS.map(|(ty, bits)| layout.bitfield(ty, bits).unwrap());
+        assert_eq!([0, 1, 4, 32, 48], fields.map(|field| field.bit_offset()));
+        assert_eq!((64, 4, 8), (layout.bit_len(), layout.align(), layout.size()));
+        let status = [0xcb, 0xf9, 0, 0, 0x0a, 0, 0xab, 0x02];
+        assert_eq!(Ok(-100), fields[2].get_signed(&status, 0));
+        assert_eq!(Ok(0x2ab), fields[4].get(&status, 0));
+
+        let mut layout = Layout::<Little>::new(Abi::Msvc);
+        let offsets = [
+            layout.bitfield(CType::U8, 7),
+            layout.bitfield(CType::U8, 3),
+            layout.bitfield(CType::U64, 40),
+            Ok(layout.member(CType::U16)),
+            layout.bitfield(CType::U32, 0),
+            layout.bitfield(CType::Bool, 1),
+            layout.bitfield(CType::U16, 15),
+            layout.bitfield(CType::U16, 2),
+        ]
+        .map(|field| field.unwrap().bit_offset());
+        assert_eq!([0, 8, 64, 128, 144, 144, 160, 176], offsets);
+        assert_eq!((8, 24), (layout.align(), layout.size()));
+
+        // A zero-width bitfield closes the unit of the previous bitfield.
+        let mut layout = Layout::<Little>::new(Abi::Msvc);
+        layout.bitfield(CType::U32, 3).unwrap();
+        layout.bitfield(CType::U32, 0).unwrap();
+        assert_eq!(32, layout.bitfield(CType::U32, 3).unwrap().bit_offset());
+    }
+
+    #[test]
+    fn field_error_test() {
+        let mut layout = Layout::<Little>::new(Abi::Gcc);
+        layout.member(CType::U8);
+        assert_eq!(
+            Err(Error::new(ErrorKind::LimitExceeded { start_index: 1, len: 9, max: 8 })),
+            layout.bitfield(CType::I8, 9)
+        );
+        assert!(layout.bitfield(CType::Bool, 2).is_err());
+        let field = layout.bitfield(CType::I16, 5).unwrap();
+        let mut buffer = [0xff; 2];
+        assert_eq!(
+            Err(Error::new(ErrorKind::OutOfBounds { start_index: 2, requested: 1, len: 2 })),
+            field.get(&buffer, 1)
+        );
+        assert_eq!(
+            Err(Error::new(ErrorKind::InvalidEncoding { start_index: 1 })),
+            field.set(&mut buffer, 0, 32)
+        );
+        assert!(field.set_signed(&mut buffer, 0, 16).is_err());
+        assert!(field.set_signed(&mut buffer, 0, -17).is_err());
+        assert!(field.set(&mut buffer, usize::MAX, 0).is_err());
+        assert_eq!([0xff; 2], buffer);
+        assert_eq!(Ok(()), field.set_signed(&mut buffer, 0, -16));
+        assert_eq!([0xff, 0xf0], buffer);
+        assert_eq!(Ok(-16), field.get_signed(&buffer, 0));
+        assert_eq!(Ok(()), field.set_signed(&mut buffer, 0, 15));
+        assert_eq!(Ok(15), field.get_signed(&buffer, 0));
+    }
+}
@@ -0,0 +1,133 @@
+//! Reinterpretations of floating-point values as integers of the same width,
+//! mirroring the bit conversions of C#'s `BitConverter`.
+
+/// Returns the IEEE 754 bits of a double-precision floating-point number as a
+/// 64-bit signed integer.
+///
+/// The bits are copied as they are, so NaN payloads and the sign of zero
+/// survive a round trip through [`int64_bits_to_double`], as they do in C#.
+/// # Example
+///
+/// ```
+/// use bitconv::{double_to_int64_bits, int64_bits_to_double};
+///
+/// assert_eq!(4607182418800017408, double_to_int64_bits(1.0));
+/// assert_eq!(i64::MIN, double_to_int64_bits(-0.0));
+/// assert_eq!(-2.5, int64_bits_to_double(double_to_int64_bits(-2.5)));
+/// ```
+#[inline]
+pub const fn double_to_int64_bits(value: f64) -> i64 { value.to_bits() as i64 }
+
+/// Returns the double-precision floating-point number whose IEEE 754 bits are
+/// a 64-bit signed integer.
+#[inline]
+pub const fn int64_bits_to_double(value: i64) -> f64 { f64::from_bits(value as u64) }
+
+/// Returns the IEEE 754 bits of a single-precision floating-point number as a
+/// 32-bit signed integer, copying them as they are like
+/// [`double_to_int64_bits`].
+/// # Example
+///
+/// ```
+/// use bitconv::{int32_bits_to_single, single_to_int32_bits};
+///
+/// assert_eq!(1065353216, single_to_int32_bits(1.0));
+/// assert_eq!(-1.5, int32_bits_to_single(single_to_int32_bits(-1.5)));
+/// ```
+#[inline]
+pub const fn single_to_int32_bits(value: f32) -> i32 { value.to_bits() as i32 }
+
+/// Returns the single-precision floating-point number whose IEEE 754 bits are
+/// a 32-bit signed integer.
+#[inline]
+pub const fn int32_bits_to_single(value: i32) -> f32 { f32::from_bits(value as u32) }
+
+/// Returns the IEEE 754 bits of a half-precision floating-point number as a
+/// 16-bit signed integer, copying them as they are like
+/// [`double_to_int64_bits`].
+///
+/// Half-precision numbers are represented by the `f16` type of the `half`
+/// crate, as Rust's own is not yet stable.
+/// # Example
+///
+/// ```
+/// use bitconv::{half_to_int16_bits, int16_bits_to_half};
+/// use half::f16;
+///
+/// assert_eq!(15360, half_to_int16_bits(f16::ONE));
+/// assert_eq!(f16::NEG_INFINITY, int16_bits_to_half(-1024));
+/// ```
+#[cfg(feature = "half")]
+#[inline]
+pub const fn half_to_int16_bits(value: ::half::f16) -> i16 { value.to_bits() as i16 }
+
+/// Returns the half-precision floating-point number whose IEEE 754 bits are a
+/// 16-bit signed integer.
+#[cfg(feature = "half")]
+#[inline]
+pub const fn int16_bits_to_half(value: i16) -> ::half::f16 { ::half::f16::from_bits(value as u16) }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn double_bits_test() {
+        let cases = [
+            (0.0, 0),
+            (-0.0, i64::MIN),
+            (1.0, 0x3ff0_0000_0000_0000),
+            (-2.0, -0x3fff_ffff_ffff_ffff - 1),
+            (f64::INFINITY, 0x7ff0_0000_0000_0000),
+            (f64::MIN_POSITIVE, 0x0010_0000_0000_0000),
+            (f64::MAX, 0x7fef_ffff_ffff_ffff),
+        ];
+        for (value, bits) in cases {
+            assert_eq!(bits, double_to_int64_bits(value));
+            assert_eq!(value.to_bits(), int64_bits_to_double(bits).to_bits());
+        }
+        let nan = int64_bits_to_double(-0x0007_ffff_ffff_fff1);
+        assert!(nan.is_nan());
+        assert_eq!(-0x0007_ffff_ffff_fff1, double_to_int64_bits(nan));
+    }
+
+    #[test]
+    fn single_bits_test() {
+        let cases = [
+            (0.0, 0),
+            (-0.0, i32::MIN),
+            (1.0, 0x3f80_0000),
+            (-2.0, -0x4000_0000),
+            (f32::NEG_INFINITY, -0x0080_0000),
+            (f32::MIN_POSITIVE, 0x0080_0000),
+            (f32::MAX, 0x7f7f_ffff),
+        ];
+        for (value, bits) in cases {
+            assert_eq!(bits, single_to_int32_bits(value));
+            assert_eq!(value.to_bits(), int32_bits_to_single(bits).to_bits());
+        }
+        let nan = int32_bits_to_single(0x7fc0_0001);
+        assert!(nan.is_nan());
+        assert_eq!(0x7fc0_0001, single_to_int32_bits(nan));
+    }
+
+    #[cfg(feature = "half")]
+    #[test]
+    fn half_bits_test() {
+        use ::half::f16;
+
+        let cases = [
+            (f16::ZERO, 0),
+            (f16::NEG_ZERO, i16::MIN),
+            (f16::ONE, 0x3c00),
+            (f16::from_f32(-2.0), -0x4000),
+            (f16::INFINITY, 0x7c00),
+            (f16::MAX, 0x7bff),
+        ];
+        for (value, bits) in cases {
+            assert_eq!(bits, half_to_int16_bits(value));
+            assert_eq!(value.to_bits(), int16_bits_to_half(bits).to_bits());
+        }
+        assert_eq!(0x7e01, half_to_int16_bits(int16_bits_to_half(0x7e01)));
+    }
+}
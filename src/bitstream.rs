@@ -0,0 +1,1674 @@
+//! Codes stored at arbitrary bit positions rather than whole bytes.
+//!
+//! Functions here take a bit_index counting bits from the start of the first
+//! byte in the [`BitOrder`] `O`, and return lengths in bits. With [`Msb0`],
+//! bit 0 is the most significant bit of the first byte and bit 8 that of the
+//! second, and with [`Lsb0`] they are the least significant bits instead.
+//! Bits outside the code are left untouched when writing. The indices in
+//! errors are those of bytes, of the byte holding the first bit of the code.
+//!
+//! [`BitReader`] reads the same codes, and fields of any width up to 64 bits,
+//! one after the other from a cursor, and [`BitWriter`] appends them to a
+//! [`ByteSink`].
+use crate::{ByteSink, Error, ErrorKind, Result};
+use core::marker::PhantomData;
+
+/// Bit that comes first in each byte, and so first in each field.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum BitNumbering {
+    /// The most significant bit comes first, and fields are stored from their
+    /// most significant bit, as in the diagrams of network protocols.
+    Msb0,
+    /// The least significant bit comes first, and fields are stored from
+    /// their least significant bit, as in DEFLATE and the documentation of
+    /// many hardware registers.
+    Lsb0,
+}
+
+/// Marker types selecting the bit numbering of bit-level conversions, as
+/// [`BitConvEndian`](crate::BitConvEndian) selects their byte order.
+///
+/// With [`Lsb0`], a field of n bits at bit_index holds the same value as bits
+/// bit_index to bit_index + n - 1 of the slice read as a little-endian
+/// integer, which is how registers are documented when bit 0 is the least
+/// significant one. With [`Msb0`], the slice reads as a big-endian integer
+/// instead, with bit 0 the most significant one, as in protocol diagrams.
+/// # Example
+///
+/// ```
+/// use bitconv::{BitReader, Lsb0, Msb0};
+///
+/// let buffer = [0b1100_0101, 0b0000_0011];
+/// assert_eq!(Ok(0b1100), BitReader::<Msb0>::new(&buffer).read_bits(4));
+/// assert_eq!(Ok(0b0101), BitReader::<Lsb0>::new(&buffer).read_bits(4));
+/// let mut reader = BitReader::<Lsb0>::new(&buffer);
+/// reader.set_position(6);
+/// assert_eq!(Ok(0b1111), reader.read_bits(4));
+/// ```
+pub trait BitOrder {
+    const NUMBERING: BitNumbering;
+}
+
+/// Bit numbering from the most significant bit of each byte.
+#[derive(Clone, Copy, Debug)]
+pub struct Msb0;
+/// Bit numbering from the least significant bit of each byte.
+#[derive(Clone, Copy, Debug)]
+pub struct Lsb0;
+
+impl BitOrder for Msb0 {
+    const NUMBERING: BitNumbering = BitNumbering::Msb0;
+}
+
+impl BitOrder for Lsb0 {
+    const NUMBERING: BitNumbering = BitNumbering::Lsb0;
+}
+
+/// Returns the error for a code of count bits that does not fit in data at
+/// bit_index.
+pub(crate) fn no_bits(data: &[u8], bit_index: usize, count: usize) -> Error {
+    Error::new(ErrorKind::OutOfBounds {
+        start_index: bit_index / 8,
+        requested: (bit_index % 8).wrapping_add(count).wrapping_add(7) / 8,
+        len: data.len(),
+    })
+}
+
+/// Returns whether count bits at bit_index fit in data.
+#[inline]
+pub(crate) fn has_bits(data: &[u8], bit_index: usize, count: usize) -> bool {
+    match bit_index.checked_add(count) {
+        Some(end) => end <= data.len().saturating_mul(8),
+        None => false,
+    }
+}
+
+/// Returns the count bits at bit_index, at most 64, as the low bits of an
+/// integer, or `None` if they do not all fit in data.
+#[inline]
+pub(crate) fn load_bits<O: BitOrder>(data: &[u8], bit_index: usize, count: u32) -> Option<u64> {
+    let mut value = 0u64;
+    let mut index = bit_index;
+    let mut left = count;
+    while left > 0 {
+        let byte = u32::from(*data.get(index / 8)?);
+        let offset = (index % 8) as u32;
+        let take = 8u32.wrapping_sub(offset).min(left);
+        let mask = !(!0 << take);
+        match O::NUMBERING {
+            BitNumbering::Msb0 => {
+                let bits = byte.wrapping_shr(8u32.wrapping_sub(offset).wrapping_sub(take)) & mask;
+                value = value.wrapping_shl(take) | u64::from(bits);
+            }
+            BitNumbering::Lsb0 => {
+                let bits = byte.wrapping_shr(offset) & mask;
+                value |= u64::from(bits).wrapping_shl(count.wrapping_sub(left));
+            }
+        }
+        index = index.wrapping_add(take as usize);
+        left = left.wrapping_sub(take);
+    }
+    Some(value)
+}
+
+/// Stores the low count bits of value, at most 64, at bit_index, which the
+/// caller has checked they fit at.
+#[inline]
+pub(crate) fn store_bits<O: BitOrder>(data: &mut [u8], bit_index: usize, count: u32, value: u64) {
+    let mut index = bit_index;
+    let mut left = count;
+    while left > 0 {
+        let byte = match data.get_mut(index / 8) {
+            Some(byte) => byte,
+            None => return,
+        };
+        let offset = (index % 8) as u32;
+        let take = 8u32.wrapping_sub(offset).min(left);
+        let (shift, bits) = match O::NUMBERING {
+            BitNumbering::Msb0 => (
+                8u32.wrapping_sub(offset).wrapping_sub(take),
+                value.wrapping_shr(left.wrapping_sub(take)),
+            ),
+            BitNumbering::Lsb0 => (offset, value.wrapping_shr(count.wrapping_sub(left))),
+        };
+        let mask = (!(!0u32 << take) << shift) as u8;
+        let bits = (bits as u32).wrapping_shl(shift) as u8;
+        *byte = *byte & !mask | bits & mask;
+        index = index.wrapping_add(take as usize);
+        left = left.wrapping_sub(take);
+    }
+}
+
+/// Stores count zero bits at bit_index, which the caller has checked they fit
+/// at, filling whole bytes at once.
+#[inline]
+fn store_zeros<O: BitOrder>(data: &mut [u8], bit_index: usize, count: usize) {
+    let head = (8 - bit_index % 8) % 8;
+    if count <= head {
+        store_bits::<O>(data, bit_index, count as u32, 0);
+        return;
+    }
+    store_bits::<O>(data, bit_index, head as u32, 0);
+    let start = (bit_index.wrapping_add(head)) / 8;
+    let bytes = count.wrapping_sub(head) / 8;
+    if let Some(bytes) = data.get_mut(start..).and_then(|data| data.get_mut(..bytes)) {
+        bytes.fill(0);
+    }
+    let tail = count.wrapping_sub(head) % 8;
+    store_bits::<O>(data, bit_index.wrapping_add(count).wrapping_sub(tail), tail as u32, 0);
+}
+
+/// Stores the unary code of count, as count zero bits followed by a one bit,
+/// at bit_index, which the caller has checked it fits at.
+#[inline]
+fn store_unary<O: BitOrder>(data: &mut [u8], bit_index: usize, count: usize) {
+    store_zeros::<O>(data, bit_index, count);
+    store_bits::<O>(data, bit_index.wrapping_add(count), 1, 1);
+}
+
+/// Returns the number of zero bits at bit_index, counting no further than
+/// max + 1 of them, along with whether a one bit ends them before data does.
+#[inline]
+fn zero_run<O: BitOrder>(data: &[u8], bit_index: usize, max: usize) -> (usize, bool) {
+    let mut zeros = 0usize;
+    loop {
+        let index = bit_index.wrapping_add(zeros);
+        let byte = match data.get(index / 8) {
+            Some(&byte) => byte,
+            None => return (zeros, false),
+        };
+        let leading = match O::NUMBERING {
+            BitNumbering::Msb0 => (byte << (index % 8)).leading_zeros(),
+            BitNumbering::Lsb0 => (byte >> (index % 8)).trailing_zeros(),
+        };
+        let available = 8 - index % 8;
+        let leading = (leading as usize).min(available);
+        zeros = zeros.saturating_add(leading);
+        if leading < available {
+            return (zeros, true);
+        }
+        if zeros > max {
+            return (zeros, false);
+        }
+    }
+}
+
+/// Returns the number of bits the Elias gamma code of value takes, from 1 to
+/// 127, or `None` for 0, which has none.
+/// # Example
+///
+/// ```
+/// use bitconv::elias_gamma_len;
+///
+/// assert_eq!(Some(1), elias_gamma_len(1));
+/// assert_eq!(Some(5), elias_gamma_len(4));
+/// assert_eq!(Some(127), elias_gamma_len(u64::MAX));
+/// assert_eq!(None, elias_gamma_len(0));
+/// ```
+#[inline]
+pub const fn elias_gamma_len(value: u64) -> Option<usize> {
+    match value {
+        0 => None,
+        _ => Some(2 * (63 - value.leading_zeros() as usize) + 1),
+    }
+}
+
+/// Returns the Elias gamma code at bit_index, along with the number of bits
+/// it takes.
+///
+/// The code of a value from 1 stores its number of significant bits less one
+/// in unary, as that many zero bits followed by a one bit standing for the
+/// most significant bit of the value, then the other bits of the value as a
+/// field. Fails with `ErrorKind::OutOfBounds` if data ends
+/// before the last bit, and with `ErrorKind::InvalidEncoding` for a value of
+/// more than 64 bits.
+/// # Example
+///
+/// ```
+/// use bitconv::{read_elias_gamma, Msb0};
+///
+/// // The codes of 1, 2 and 5: 1, 010 and 00101, padded with zero bits.
+/// let buffer = [0b1010_0010, 0b1000_0000];
+/// assert_eq!(Ok((1, 1)), read_elias_gamma::<Msb0>(&buffer, 0));
+/// assert_eq!(Ok((2, 3)), read_elias_gamma::<Msb0>(&buffer, 1));
+/// assert_eq!(Ok((5, 5)), read_elias_gamma::<Msb0>(&buffer, 4));
+/// ```
+#[inline]
+#[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
+pub fn read_elias_gamma<O: BitOrder>(data: &[u8], bit_index: usize) -> Result<(u64, usize)> {
+    let (zeros, ended) = zero_run::<O>(data, bit_index, 63);
+    let len = zeros.wrapping_mul(2).wrapping_add(1);
+    if zeros > 63 {
+        return Err(Error::new(ErrorKind::InvalidEncoding { start_index: bit_index / 8 }));
+    } else if !ended {
+        return Err(no_bits(data, bit_index, len));
+    }
+    match load_bits::<O>(data, bit_index.wrapping_add(zeros).wrapping_add(1), zeros as u32) {
+        Some(low) => Ok((1u64.wrapping_shl(zeros as u32) | low, len)),
+        None => Err(no_bits(data, bit_index, len)),
+    }
+}
+
+/// Writes the Elias gamma code of value at bit_index, returning the number of
+/// bits written.
+///
+/// Fails with `ErrorKind::InvalidEncoding` for 0, which has no code, and with
+/// `ErrorKind::OutOfBounds` if the code does not fit, leaving data untouched
+/// either way.
+/// # Example
+///
+/// ```
+/// use bitconv::{write_elias_gamma, Msb0};
+///
+/// let mut buffer = [0xff; 2];
+/// assert_eq!(Ok(5), write_elias_gamma::<Msb0>(&mut buffer, 2, 6));
+/// assert_eq!([0b1100_1101, 0xff], buffer);
+/// ```
+#[inline]
+#[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
+pub fn write_elias_gamma<O: BitOrder>(
+    data: &mut [u8],
+    bit_index: usize,
+    value: u64,
+) -> Result<usize> {
+    let len = match elias_gamma_len(value) {
+        Some(len) => len,
+        None => return Err(Error::new(ErrorKind::InvalidEncoding { start_index: bit_index / 8 })),
+    };
+    if !has_bits(data, bit_index, len) {
+        return Err(no_bits(data, bit_index, len));
+    }
+    let low_bits = 63 - value.leading_zeros();
+    store_unary::<O>(data, bit_index, low_bits as usize);
+    store_bits::<O>(
+        data,
+        bit_index.wrapping_add(low_bits as usize).wrapping_add(1),
+        low_bits,
+        value,
+    );
+    Ok(len)
+}
+
+/// Returns the number of bits the Elias delta code of value takes, from 1 to
+/// 76, or `None` for 0, which has none.
+/// # Example
+///
+/// ```
+/// use bitconv::elias_delta_len;
+///
+/// assert_eq!(Some(1), elias_delta_len(1));
+/// assert_eq!(Some(9), elias_delta_len(16));
+/// assert_eq!(Some(76), elias_delta_len(u64::MAX));
+/// ```
+#[inline]
+pub const fn elias_delta_len(value: u64) -> Option<usize> {
+    if value == 0 {
+        return None;
+    }
+    let low_bits = 63 - value.leading_zeros() as u64;
+    match elias_gamma_len(low_bits + 1) {
+        Some(len) => Some(len + low_bits as usize),
+        None => None,
+    }
+}
+
+/// Returns the Elias delta code at bit_index, along with the number of bits
+/// it takes.
+///
+/// The code of a value from 1 stores its number of significant bits as an
+/// Elias gamma code, followed by the value without its most significant one
+/// bit, which makes it shorter than the gamma code for values from 32. Fails
+/// with `ErrorKind::OutOfBounds` if data ends before the last bit, and with
+/// `ErrorKind::InvalidEncoding` for a value of more than 64 bits.
+/// # Example
+///
+/// ```
+/// use bitconv::{read_elias_delta, Msb0};
+///
+/// // The codes of 1 and 10: 1 and 00100010, padded with zero bits.
+/// let buffer = [0b1001_0001, 0];
+/// assert_eq!(Ok((1, 1)), read_elias_delta::<Msb0>(&buffer, 0));
+/// assert_eq!(Ok((10, 8)), read_elias_delta::<Msb0>(&buffer, 1));
+/// ```
+#[inline]
+#[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
+pub fn read_elias_delta<O: BitOrder>(data: &[u8], bit_index: usize) -> Result<(u64, usize)> {
+    let (bits, prefix) = read_elias_gamma::<O>(data, bit_index)?;
+    if bits > 64 {
+        return Err(Error::new(ErrorKind::InvalidEncoding { start_index: bit_index / 8 }));
+    }
+    let low_bits = bits.wrapping_sub(1) as u32;
+    let len = prefix.wrapping_add(low_bits as usize);
+    match load_bits::<O>(data, bit_index.wrapping_add(prefix), low_bits) {
+        Some(low) => Ok((1u64.wrapping_shl(low_bits) | low, len)),
+        None => Err(no_bits(data, bit_index, len)),
+    }
+}
+
+/// Writes the Elias delta code of value at bit_index, returning the number of
+/// bits written.
+///
+/// Fails with `ErrorKind::InvalidEncoding` for 0, which has no code, and with
+/// `ErrorKind::OutOfBounds` if the code does not fit, leaving data untouched
+/// either way.
+/// # Example
+///
+/// ```
+/// use bitconv::{write_elias_delta, Msb0};
+///
+/// let mut buffer = [0; 2];
+/// assert_eq!(Ok(8), write_elias_delta::<Msb0>(&mut buffer, 4, 10));
+/// assert_eq!([0b0000_0010, 0b0010_0000], buffer);
+/// ```
+#[inline]
+#[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
+pub fn write_elias_delta<O: BitOrder>(
+    data: &mut [u8],
+    bit_index: usize,
+    value: u64,
+) -> Result<usize> {
+    let len = match elias_delta_len(value) {
+        Some(len) => len,
+        None => return Err(Error::new(ErrorKind::InvalidEncoding { start_index: bit_index / 8 })),
+    };
+    if !has_bits(data, bit_index, len) {
+        return Err(no_bits(data, bit_index, len));
+    }
+    let low_bits = 63 - value.leading_zeros();
+    let prefix = write_elias_gamma::<O>(data, bit_index, u64::from(low_bits) + 1)?;
+    store_bits::<O>(data, bit_index.wrapping_add(prefix), low_bits, value);
+    Ok(len)
+}
+
+/// Returns the number of bits the Rice code of value with parameter k takes,
+/// or `None` if k is larger than 64 or the length does not fit in a `usize`.
+/// # Example
+///
+/// ```
+/// use bitconv::rice_len;
+///
+/// assert_eq!(Some(4), rice_len(5, 2));
+/// assert_eq!(Some(65), rice_len(u64::MAX, 64));
+/// assert_eq!(None, rice_len(0, 65));
+/// ```
+#[inline]
+pub const fn rice_len(value: u64, k: u32) -> Option<usize> {
+    if k > 64 {
+        return None;
+    }
+    let quotient = match value.checked_shr(k) {
+        Some(quotient) => quotient,
+        None => 0,
+    };
+    match (quotient as usize).checked_add(1 + k as usize) {
+        Some(len) if quotient <= usize::MAX as u64 => Some(len),
+        _ => None,
+    }
+}
+
+/// Returns the Rice code with parameter k at bit_index, along with the number
+/// of bits it takes.
+///
+/// The code stores the value shifted right by k in unary, as that many zero
+/// bits followed by a one bit, then its k low bits as a field, as in the
+/// residuals of FLAC. The Golomb codes of [`read_golomb`] generalize it to
+/// divisors other than powers of two. Signed values can be mapped to unsigned
+/// ones with [`zigzag_encode_int64`](crate::zigzag_encode_int64) first.
+///
+/// Fails with `ErrorKind::OutOfBounds` if data ends before the last bit, and
+/// with `ErrorKind::InvalidEncoding` if k is larger than 64 or the value does
+/// not fit in a `u64`.
+/// # Example
+///
+/// ```
+/// use bitconv::{read_rice, Msb0};
+///
+/// // The codes of 5 and 2 with k = 2: 0 1 01 and 1 10.
+/// let buffer = [0b0101_1100];
+/// assert_eq!(Ok((5, 4)), read_rice::<Msb0>(&buffer, 0, 2));
+/// assert_eq!(Ok((2, 3)), read_rice::<Msb0>(&buffer, 4, 2));
+/// ```
+#[inline]
+#[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
+pub fn read_rice<O: BitOrder>(data: &[u8], bit_index: usize, k: u32) -> Result<(u64, usize)> {
+    let max = match u64::MAX.checked_shr(k) {
+        Some(max) if k <= 64 => max.min(usize::MAX as u64) as usize,
+        _ if k == 64 => 0,
+        _ => return Err(Error::new(ErrorKind::InvalidEncoding { start_index: bit_index / 8 })),
+    };
+    let (quotient, ended) = zero_run::<O>(data, bit_index, max);
+    let prefix = quotient.wrapping_add(1);
+    let len = prefix.wrapping_add(k as usize);
+    if quotient > max {
+        return Err(Error::new(ErrorKind::InvalidEncoding { start_index: bit_index / 8 }));
+    } else if !ended || !has_bits(data, bit_index, len) {
+        return Err(no_bits(data, bit_index, len));
+    }
+    let low = load_bits::<O>(data, bit_index.wrapping_add(prefix), k).unwrap_or(0);
+    Ok(((quotient as u64).checked_shl(k).unwrap_or(0) | low, len))
+}
+
+/// Writes the Rice code of value with parameter k at bit_index, returning the
+/// number of bits written.
+///
+/// Fails with `ErrorKind::InvalidEncoding` where [`rice_len`] returns `None`,
+/// and with `ErrorKind::OutOfBounds` if the code does not fit, leaving data
+/// untouched either way.
+/// # Example
+///
+/// ```
+/// use bitconv::{write_rice, Msb0};
+///
+/// let mut buffer = [0; 2];
+/// assert_eq!(Ok(4), write_rice::<Msb0>(&mut buffer, 0, 5, 2));
+/// assert_eq!(Ok(5), write_rice::<Msb0>(&mut buffer, 4, 9, 3));
+/// assert_eq!([0b0101_0100, 0b1000_0000], buffer);
+/// ```
+#[inline]
+#[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
+pub fn write_rice<O: BitOrder>(
+    data: &mut [u8],
+    bit_index: usize,
+    value: u64,
+    k: u32,
+) -> Result<usize> {
+    let len = match rice_len(value, k) {
+        Some(len) => len,
+        None => return Err(Error::new(ErrorKind::InvalidEncoding { start_index: bit_index / 8 })),
+    };
+    if !has_bits(data, bit_index, len) {
+        return Err(no_bits(data, bit_index, len));
+    }
+    let prefix = len.wrapping_sub(k as usize);
+    store_unary::<O>(data, bit_index, prefix.wrapping_sub(1));
+    store_bits::<O>(data, bit_index.wrapping_add(prefix), k, value);
+    Ok(len)
+}
+
+/// Returns the number of bits of the truncated binary remainders of the
+/// Golomb codes with parameter m, and the number of remainders stored in one
+/// bit less.
+#[inline]
+const fn golomb_remainder(m: u64) -> (u32, u64) {
+    let bits = 64 - m.wrapping_sub(1).leading_zeros();
+    let short = match 1u64.checked_shl(bits) {
+        Some(power) => power.wrapping_sub(m),
+        None => m.wrapping_neg(),
+    };
+    (bits, short)
+}
+
+/// Returns the number of bits the Golomb code of value with parameter m takes,
+/// or `None` if m is 0 or the length does not fit in a `usize`.
+/// # Example
+///
+/// ```
+/// use bitconv::golomb_len;
+///
+/// assert_eq!(Some(2), golomb_len(0, 3));
+/// assert_eq!(Some(3), golomb_len(2, 3));
+/// assert_eq!(Some(4), golomb_len(4, 3));
+/// assert_eq!(None, golomb_len(4, 0));
+/// ```
+#[inline]
+pub const fn golomb_len(value: u64, m: u64) -> Option<usize> {
+    if m == 0 || value / m > usize::MAX as u64 {
+        return None;
+    }
+    let (bits, short) = golomb_remainder(m);
+    let remainder = match value % m < short {
+        true => bits - 1,
+        false => bits,
+    };
+    ((value / m) as usize).checked_add(1 + remainder as usize)
+}
+
+/// Returns the Golomb code with parameter m at bit_index, along with the
+/// number of bits it takes.
+///
+/// The code stores the quotient of the value by m in unary, as that many zero
+/// bits followed by a one bit, then the remainder in truncated binary, which
+/// takes one bit less for the smallest remainders when m is not a power of
+/// two: a field of one bit less than needed for m - 1, followed for the
+/// largest remainders by their lowest bit. With m a power of two, it is the
+/// Rice code of [`read_rice`].
+///
+/// Fails with `ErrorKind::OutOfBounds` if data ends before the last bit, and
+/// with `ErrorKind::InvalidEncoding` if m is 0 or the value does not fit in a
+/// `u64`.
+/// # Example
+///
+/// ```
+/// use bitconv::{read_golomb, Msb0};
+///
+/// // The codes of 2 and 3 with m = 3: 1 11 and 01 0.
+/// let buffer = [0b1110_1000];
+/// assert_eq!(Ok((2, 3)), read_golomb::<Msb0>(&buffer, 0, 3));
+/// assert_eq!(Ok((3, 3)), read_golomb::<Msb0>(&buffer, 3, 3));
+/// ```
+#[inline]
+#[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
+pub fn read_golomb<O: BitOrder>(data: &[u8], bit_index: usize, m: u64) -> Result<(u64, usize)> {
+    let invalid = Error::new(ErrorKind::InvalidEncoding { start_index: bit_index / 8 });
+    let max = match u64::MAX.checked_div(m) {
+        Some(max) => max.min(usize::MAX as u64) as usize,
+        None => return Err(invalid),
+    };
+    let (quotient, ended) = zero_run::<O>(data, bit_index, max);
+    let prefix = quotient.wrapping_add(1);
+    let (bits, short) = golomb_remainder(m);
+    let truncated = |bits: u32| no_bits(data, bit_index, prefix.wrapping_add(bits as usize));
+    if quotient > max {
+        return Err(invalid);
+    } else if !ended {
+        return Err(truncated(bits.saturating_sub(1)));
+    }
+    let remainder_index = bit_index.wrapping_add(prefix);
+    let mut remainder = match bits {
+        0 => 0,
+        _ => load_bits::<O>(data, remainder_index, bits - 1).ok_or_else(|| truncated(bits - 1))?,
+    };
+    let mut len = prefix.wrapping_add(bits.saturating_sub(1) as usize);
+    if bits > 0 && remainder >= short {
+        let low = load_bits::<O>(data, remainder_index.wrapping_add(bits as usize - 1), 1)
+            .ok_or_else(|| truncated(bits))?;
+        remainder = (remainder << 1 | low).wrapping_sub(short);
+        len = len.wrapping_add(1);
+    }
+    (quotient as u64)
+        .checked_mul(m)
+        .and_then(|value| value.checked_add(remainder))
+        .map(|value| (value, len))
+        .ok_or(invalid)
+}
+
+/// Writes the Golomb code of value with parameter m at bit_index, returning
+/// the number of bits written.
+///
+/// Fails with `ErrorKind::InvalidEncoding` where [`golomb_len`] returns
+/// `None`, and with `ErrorKind::OutOfBounds` if the code does not fit, leaving
+/// data untouched either way.
+/// # Example
+///
+/// ```
+/// use bitconv::{write_golomb, Msb0};
+///
+/// let mut buffer = [0; 1];
+/// assert_eq!(Ok(3), write_golomb::<Msb0>(&mut buffer, 0, 2, 3));
+/// assert_eq!(Ok(4), write_golomb::<Msb0>(&mut buffer, 3, 7, 5));
+/// assert_eq!([0b1110_1100], buffer);
+/// ```
+#[inline]
+#[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
+pub fn write_golomb<O: BitOrder>(
+    data: &mut [u8],
+    bit_index: usize,
+    value: u64,
+    m: u64,
+) -> Result<usize> {
+    let len = match golomb_len(value, m) {
+        Some(len) => len,
+        None => return Err(Error::new(ErrorKind::InvalidEncoding { start_index: bit_index / 8 })),
+    };
+    if !has_bits(data, bit_index, len) {
+        return Err(no_bits(data, bit_index, len));
+    }
+    let (quotient, remainder) = match (value.checked_div(m), value.checked_rem(m)) {
+        (Some(quotient), Some(remainder)) => (quotient as usize, remainder),
+        _ => return Err(Error::new(ErrorKind::InvalidEncoding { start_index: bit_index / 8 })),
+    };
+    let (bits, short) = golomb_remainder(m);
+    store_unary::<O>(data, bit_index, quotient);
+    let remainder_index = bit_index.wrapping_add(quotient).wrapping_add(1);
+    let short_bits = bits.saturating_sub(1);
+    if bits > 0 && remainder >= short {
+        let remainder = remainder.wrapping_add(short);
+        store_bits::<O>(data, remainder_index, short_bits, remainder >> 1);
+        store_bits::<O>(data, remainder_index.wrapping_add(short_bits as usize), 1, remainder);
+    } else {
+        store_bits::<O>(data, remainder_index, short_bits, remainder);
+    }
+    Ok(len)
+}
+
+/// Returns the number of bytes count values of bits bits each take once
+/// packed, or `None` if bits is larger than 64 or the length does not fit in
+/// a `usize`.
+/// # Example
+///
+/// ```
+/// use bitconv::packed_len;
+///
+/// assert_eq!(Some(1625), packed_len(1000, 13));
+/// assert_eq!(Some(0), packed_len(1000, 0));
+/// assert_eq!(None, packed_len(1, 65));
+/// ```
+#[inline]
+pub const fn packed_len(count: usize, bits: u32) -> Option<usize> {
+    if bits > 64 {
+        return None;
+    }
+    match count.checked_mul(bits as usize) {
+        Some(total) => Some(total / 8 + (total % 8 != 0) as usize),
+        None => None,
+    }
+}
+
+/// Packs values of bits bits each at start_index, one right after the other
+/// as fields in the bit numbering O, returning the number of bytes written.
+///
+/// The last byte is padded with zero bits. Widths that are a whole number of
+/// bytes are copied byte by byte as big-endian integers for [`Msb0`] and
+/// little-endian ones for [`Lsb0`], and others go through a 128-bit
+/// accumulator, either way without a call per value.
+///
+/// Fails with `ErrorKind::InvalidEncoding` if bits is larger than 64, or with
+/// the index of the offending value as context if a value does not fit in
+/// bits bits, and with `ErrorKind::OutOfBounds` if the packed values do not
+/// fit, leaving data untouched in every case.
+/// # Example
+///
+/// ```
+/// use bitconv::{pack_bits, Lsb0, Msb0};
+///
+/// let mut buffer = [0; 3];
+/// assert_eq!(Ok(3), pack_bits::<Msb0>(&mut buffer, 0, &[1, 2, 3, 4, 5], 4));
+/// assert_eq!([0x12, 0x34, 0x50], buffer);
+/// assert_eq!(Ok(3), pack_bits::<Lsb0>(&mut buffer, 0, &[1, 2, 3, 4, 5], 4));
+/// assert_eq!([0x21, 0x43, 0x05], buffer);
+/// assert!(pack_bits::<Msb0>(&mut buffer, 0, &[16], 4).is_err());
+/// ```
+#[inline]
+#[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
+pub fn pack_bits<O: BitOrder>(
+    data: &mut [u8],
+    start_index: usize,
+    values: &[u64],
+    bits: u32,
+) -> Result<usize> {
+    let len = match packed_len(values.len(), bits) {
+        Some(len) => len,
+        None => return Err(Error::new(ErrorKind::InvalidEncoding { start_index })),
+    };
+    let limit = (!0u64).checked_shl(bits).map_or(u64::MAX, |high| !high);
+    if let Some(index) = values.iter().position(|&value| value & !limit != 0) {
+        let start_index = start_index.wrapping_add(index.wrapping_mul(bits as usize) / 8);
+        return Err(Error::new(ErrorKind::InvalidEncoding { start_index }).with_index(index));
+    }
+    let data_len = data.len();
+    let bytes = match data.get_mut(start_index..).and_then(|bytes| bytes.get_mut(..len)) {
+        Some(bytes) => bytes,
+        None => {
+            return Err(Error::new(ErrorKind::OutOfBounds {
+                start_index,
+                requested: len,
+                len: data_len,
+            }))
+        }
+    };
+    if bits & 7 == 0 {
+        let width = bits as usize / 8;
+        for (chunk, value) in bytes.chunks_mut(width.max(1)).zip(values) {
+            let (value, skip) = match O::NUMBERING {
+                BitNumbering::Msb0 => (value.to_be_bytes(), 8usize.wrapping_sub(width)),
+                BitNumbering::Lsb0 => (value.to_le_bytes(), 0),
+            };
+            let value = value.get(skip..).unwrap_or_default();
+            chunk.iter_mut().zip(value).for_each(|(byte, &value)| *byte = value);
+        }
+        return Ok(len);
+    }
+    let mut out = bytes.iter_mut();
+    let mut acc = 0u128;
+    let mut filled = 0u32;
+    for &value in values {
+        match O::NUMBERING {
+            BitNumbering::Msb0 => acc = acc << bits | u128::from(value),
+            BitNumbering::Lsb0 => acc |= u128::from(value) << filled,
+        }
+        filled = filled.wrapping_add(bits);
+        while filled >= 8 {
+            filled = filled.wrapping_sub(8);
+            let byte = match O::NUMBERING {
+                BitNumbering::Msb0 => (acc >> filled) as u8,
+                BitNumbering::Lsb0 => {
+                    let byte = acc as u8;
+                    acc >>= 8;
+                    byte
+                }
+            };
+            if let Some(out) = out.next() {
+                *out = byte;
+            }
+        }
+        acc &= !(!0u128 << filled);
+    }
+    if let Some(byte) = out.next() {
+        *byte = match O::NUMBERING {
+            BitNumbering::Msb0 => (acc << 8u32.wrapping_sub(filled)) as u8,
+            BitNumbering::Lsb0 => acc as u8,
+        };
+    }
+    Ok(len)
+}
+
+/// Unpacks values of bits bits each from start_index, as [`pack_bits`]
+/// stores them, filling values and returning the number of bytes read.
+///
+/// Fails with `ErrorKind::InvalidEncoding` if bits is larger than 64, and
+/// with `ErrorKind::OutOfBounds` if data ends before the last value, leaving
+/// values untouched either way.
+/// # Example
+///
+/// ```
+/// use bitconv::{unpack_bits, Msb0};
+///
+/// // 1000 values of 13 bits each.
+/// let buffer = [0xab; 1625];
+/// let mut values = [0; 1000];
+/// assert_eq!(Ok(1625), unpack_bits::<Msb0>(&buffer, 0, &mut values, 13));
+/// assert_eq!([0x1575, 0x0eae], values[..2]);
+/// ```
+#[inline]
+#[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
+pub fn unpack_bits<O: BitOrder>(
+    data: &[u8],
+    start_index: usize,
+    values: &mut [u64],
+    bits: u32,
+) -> Result<usize> {
+    let len = match packed_len(values.len(), bits) {
+        Some(len) => len,
+        None => return Err(Error::new(ErrorKind::InvalidEncoding { start_index })),
+    };
+    let bytes = match data.get(start_index..).and_then(|bytes| bytes.get(..len)) {
+        Some(bytes) => bytes,
+        None => {
+            return Err(Error::new(ErrorKind::OutOfBounds {
+                start_index,
+                requested: len,
+                len: data.len(),
+            }))
+        }
+    };
+    if bits & 7 == 0 {
+        let width = bits as usize / 8;
+        for (value, chunk) in values.iter_mut().zip(bytes.chunks(width.max(1))) {
+            let fold = |value, &byte| value << 8 | u64::from(byte);
+            *value = match O::NUMBERING {
+                BitNumbering::Msb0 => chunk.iter().fold(0, fold),
+                BitNumbering::Lsb0 => chunk.iter().rev().fold(0, fold),
+            };
+        }
+        if bits == 0 {
+            values.fill(0);
+        }
+        return Ok(len);
+    }
+    let mut input = bytes.iter();
+    let mut acc = 0u128;
+    let mut filled = 0u32;
+    for value in values {
+        while filled < bits {
+            let byte = u128::from(*input.next().unwrap_or(&0));
+            match O::NUMBERING {
+                BitNumbering::Msb0 => acc = acc << 8 | byte,
+                BitNumbering::Lsb0 => acc |= byte << filled,
+            }
+            filled = filled.wrapping_add(8);
+        }
+        filled = filled.wrapping_sub(bits);
+        *value = match O::NUMBERING {
+            BitNumbering::Msb0 => (acc >> filled) as u64,
+            BitNumbering::Lsb0 => {
+                let value = acc as u64;
+                acc >>= bits;
+                value
+            }
+        } & !(!0u64 << bits);
+        acc &= !(!0u128 << filled);
+    }
+    Ok(len)
+}
+
+/// Returns the error for a field of count bits, more than the 64 of a `u64`.
+fn too_wide(bit_index: usize, count: u32) -> Error {
+    Error::new(ErrorKind::LimitExceeded {
+        start_index: bit_index / 8,
+        len: count as usize,
+        max: 64,
+    })
+}
+
+/// Cursor over a byte slice tracking its position in bits, for the sub-byte
+/// fields of compressed headers, codec bitstreams and CAN payloads.
+///
+/// Bits are read in the bit numbering O, as by the functions of this module,
+/// and positions count bits from the start of the slice. A failed read leaves
+/// the position unchanged.
+/// # Example
+///
+/// ```
+/// use bitconv::{BitReader, Msb0};
+///
+/// // An MPEG audio frame header: sync word, version, layer and no CRC.
+/// let mut reader = BitReader::<Msb0>::new(&[0xff, 0xfb, 0x90, 0x64]);
+/// assert_eq!(Ok(0x7ff), reader.read_bits(11));
+/// assert_eq!(Ok(3), reader.read_bits(2));
+/// assert_eq!(Ok(1), reader.read_bits(2));
+/// assert_eq!(Ok(true), reader.read_bit());
+/// assert_eq!(Ok(9), reader.read_bits(4));
+/// assert_eq!(20, reader.position());
+/// reader.align();
+/// assert_eq!(Ok(0x64), reader.read_bits(8));
+/// assert!(reader.read_bit().is_err());
+/// ```
+#[derive(Clone, Debug)]
+pub struct BitReader<'a, O> {
+    data: &'a [u8],
+    position: usize,
+    order: PhantomData<O>,
+}
+
+impl<'a, O: BitOrder> BitReader<'a, O> {
+    /// Creates a reader positioned at the first bit of data.
+    pub fn new(data: &'a [u8]) -> Self { BitReader { data, position: 0, order: PhantomData } }
+
+    /// Returns the underlying byte slice.
+    pub fn get_ref(&self) -> &'a [u8] { self.data }
+
+    /// Returns the index of the next bit to be read.
+    pub fn position(&self) -> usize { self.position }
+
+    /// Moves to the bit at position, which may lie past the end of the
+    /// slice. Reads from there fail until the reader is moved back.
+    pub fn set_position(&mut self, position: usize) { self.position = position; }
+
+    /// Returns the number of bits left after the current position.
+    pub fn remaining(&self) -> usize {
+        self.data.len().saturating_mul(8).saturating_sub(self.position)
+    }
+
+    /// Returns the index of the byte holding the next bit, which is the
+    /// position to hand over to a byte-oriented [`Reader`](crate::Reader)
+    /// once aligned.
+    pub fn byte_position(&self) -> usize { self.position / 8 }
+
+    /// Returns whether the next bit is the first bit of a byte.
+    pub fn is_aligned(&self) -> bool { self.position & 7 == 0 }
+
+    /// Skips the bits left in the current byte, if any, returning how many
+    /// were skipped.
+    pub fn align(&mut self) -> u32 {
+        let padding = (8 - self.position % 8) % 8;
+        self.position = self.position.saturating_add(padding);
+        padding as u32
+    }
+
+    /// Advances past count bits, failing with `ErrorKind::OutOfBounds` if
+    /// fewer remain.
+    pub fn skip_bits(&mut self, count: usize) -> Result<()> {
+        match has_bits(self.data, self.position, count) {
+            true => {
+                self.position += count;
+                Ok(())
+            }
+            false => Err(no_bits(self.data, self.position, count)),
+        }
+    }
+
+    /// Returns the next count bits, at most 64, as the low bits of a `u64`
+    /// without advancing.
+    ///
+    /// Fails with `ErrorKind::LimitExceeded` if count is larger than 64, and
+    /// with `ErrorKind::OutOfBounds` if fewer bits remain.
+    pub fn peek_bits(&self, count: u32) -> Result<u64> {
+        if count > 64 {
+            return Err(too_wide(self.position, count));
+        }
+        match has_bits(self.data, self.position, count as usize) {
+            true => Ok(load_bits::<O>(self.data, self.position, count).unwrap_or(0)),
+            false => Err(no_bits(self.data, self.position, count as usize)),
+        }
+    }
+
+    /// Reads count bits, at most 64, as the low bits of a `u64` and advances
+    /// past them, failing as [`peek_bits`](BitReader::peek_bits) does.
+    pub fn read_bits(&mut self, count: u32) -> Result<u64> {
+        let value = self.peek_bits(count)?;
+        self.position += count as usize;
+        Ok(value)
+    }
+
+    /// Reads a single bit and advances past it.
+    pub fn read_bit(&mut self) -> Result<bool> { self.read_bits(1).map(|bit| bit != 0) }
+
+    /// Decodes a code at the current position with decode and advances past
+    /// it.
+    fn read_code(
+        &mut self,
+        decode: impl FnOnce(&[u8], usize) -> Result<(u64, usize)>,
+    ) -> Result<u64> {
+        let (value, len) = decode(self.data, self.position)?;
+        self.position += len;
+        Ok(value)
+    }
+
+    /// Reads an Elias gamma code and advances past it, as
+    /// [`read_elias_gamma`] does.
+    pub fn read_elias_gamma(&mut self) -> Result<u64> { self.read_code(read_elias_gamma::<O>) }
+
+    /// Reads an Elias delta code and advances past it, as
+    /// [`read_elias_delta`] does.
+    pub fn read_elias_delta(&mut self) -> Result<u64> { self.read_code(read_elias_delta::<O>) }
+
+    /// Reads a Rice code with parameter k and advances past it, as
+    /// [`read_rice`] does.
+    pub fn read_rice(&mut self, k: u32) -> Result<u64> {
+        self.read_code(|data, bit_index| read_rice::<O>(data, bit_index, k))
+    }
+
+    /// Reads a Golomb code with parameter m and advances past it, as
+    /// [`read_golomb`] does.
+    pub fn read_golomb(&mut self, m: u64) -> Result<u64> {
+        self.read_code(|data, bit_index| read_golomb::<O>(data, bit_index, m))
+    }
+}
+
+/// Writer appending fields of any width up to 64 bits to a [`ByteSink`], for
+/// building bitstreams such as test inputs for a decoder.
+///
+/// Bits are written in the bit numbering O, as [`BitReader`] reads them. The
+/// bits of an unfinished byte are kept in the last byte of the sink, padded
+/// with zero bits, so the sink always holds everything written and there is
+/// nothing left to flush: [`align`] only moves on to the next byte, and
+/// [`align_with`] pads with one bits instead. Writes that do not fit in the
+/// sink fail with `ErrorKind::OutOfBounds`, leaving it unchanged.
+///
+/// [`align`]: BitWriter::align
+/// [`align_with`]: BitWriter::align_with
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "alloc")] {
+/// use bitconv::{BitWriter, Msb0};
+///
+/// let mut writer = BitWriter::<_, Msb0>::new(Vec::new());
+/// writer.write_bits(0x7ff, 11).unwrap();
+/// writer.write_bits(0b11, 2).unwrap();
+/// writer.write_bit(true).unwrap();
+/// assert_eq!([0xff, 0xfc], writer.as_slice());
+/// assert_eq!(2, writer.align_with(true));
+/// writer.write_bits(0x64, 8).unwrap();
+/// assert_eq!([0xff, 0xff, 0x64], writer.into_inner()[..]);
+/// # }
+/// ```
+pub struct BitWriter<S, O> {
+    sink: S,
+    position: usize,
+    order: PhantomData<O>,
+}
+
+impl<S: ByteSink, O: BitOrder> BitWriter<S, O> {
+    /// Creates a writer appending to the bytes already held by sink.
+    pub fn new(sink: S) -> Self {
+        let position = sink.as_bytes().len().saturating_mul(8);
+        BitWriter { sink, position, order: PhantomData }
+    }
+
+    /// Returns a reference to the underlying sink.
+    pub fn get_ref(&self) -> &S { &self.sink }
+
+    /// Returns the underlying sink, whose last byte is padded with zero bits
+    /// if the writer is not aligned.
+    pub fn into_inner(self) -> S { self.sink }
+
+    /// Returns the bytes held by the sink.
+    pub fn as_slice(&self) -> &[u8] { self.sink.as_bytes() }
+
+    /// Returns the number of bits held by the sink, which is the index of the
+    /// next bit to be written.
+    pub fn position(&self) -> usize { self.position }
+
+    /// Returns whether the next bit starts a new byte.
+    pub fn is_aligned(&self) -> bool { self.position & 7 == 0 }
+
+    /// Pads the current byte with zero bits, if it is unfinished, returning
+    /// how many were added.
+    pub fn align(&mut self) -> u32 {
+        let padding = (8 - self.position % 8) % 8;
+        self.position += padding;
+        padding as u32
+    }
+
+    /// Pads the current byte with one bits or zero bits, if it is unfinished,
+    /// returning how many were added.
+    pub fn align_with(&mut self, bit: bool) -> u32 {
+        let position = self.position;
+        let padding = self.align();
+        store_bits::<O>(self.sink.as_bytes_mut(), position, padding, u64::from(bit).wrapping_neg());
+        padding
+    }
+
+    /// Appends a code of len bits, or of none if it has no encoding, with
+    /// encode, making room for it first and removing that room again if it
+    /// fails.
+    fn write_code(
+        &mut self,
+        len: Option<usize>,
+        encode: impl FnOnce(&mut [u8], usize) -> Result<usize>,
+    ) -> Result<()> {
+        let start_len = self.sink.as_bytes().len();
+        let requested = match len.and_then(|len| self.position.checked_add(len)) {
+            Some(end) => end / 8 + usize::from(end % 8 != 0) - start_len,
+            None => 0,
+        };
+        if !self.sink.try_grow(requested) {
+            return Err(Error::new(ErrorKind::OutOfBounds {
+                start_index: start_len,
+                requested,
+                len: start_len,
+            }));
+        }
+        match encode(self.sink.as_bytes_mut(), self.position) {
+            Ok(len) => {
+                self.position += len;
+                Ok(())
+            }
+            Err(error) => {
+                self.sink.truncate(start_len);
+                Err(error)
+            }
+        }
+    }
+
+    /// Appends the count low bits of value, at most 64.
+    ///
+    /// Fails with `ErrorKind::LimitExceeded` if count is larger than 64, and
+    /// with `ErrorKind::InvalidEncoding` if value does not fit in count bits.
+    pub fn write_bits(&mut self, value: u64, count: u32) -> Result<()> {
+        if count > 64 {
+            return Err(too_wide(self.position, count));
+        } else if value.checked_shr(count).unwrap_or(0) != 0 {
+            return Err(Error::new(ErrorKind::InvalidEncoding { start_index: self.position / 8 }));
+        }
+        self.write_code(Some(count as usize), |data, bit_index| {
+            store_bits::<O>(data, bit_index, count, value);
+            Ok(count as usize)
+        })
+    }
+
+    /// Appends a single bit.
+    pub fn write_bit(&mut self, bit: bool) -> Result<()> { self.write_bits(bit.into(), 1) }
+
+    /// Appends the Elias gamma code of value, failing as
+    /// [`write_elias_gamma`] does.
+    pub fn write_elias_gamma(&mut self, value: u64) -> Result<()> {
+        self.write_code(elias_gamma_len(value), |data, bit_index| {
+            write_elias_gamma::<O>(data, bit_index, value)
+        })
+    }
+
+    /// Appends the Elias delta code of value, failing as
+    /// [`write_elias_delta`] does.
+    pub fn write_elias_delta(&mut self, value: u64) -> Result<()> {
+        self.write_code(elias_delta_len(value), |data, bit_index| {
+            write_elias_delta::<O>(data, bit_index, value)
+        })
+    }
+
+    /// Appends the Rice code of value with parameter k, failing as
+    /// [`write_rice`] does.
+    pub fn write_rice(&mut self, value: u64, k: u32) -> Result<()> {
+        self.write_code(rice_len(value, k), |data, bit_index| {
+            write_rice::<O>(data, bit_index, value, k)
+        })
+    }
+
+    /// Appends the Golomb code of value with parameter m, failing as
+    /// [`write_golomb`] does.
+    pub fn write_golomb(&mut self, value: u64, m: u64) -> Result<()> {
+        self.write_code(golomb_len(value, m), |data, bit_index| {
+            write_golomb::<O>(data, bit_index, value, m)
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::sink::test::Frame;
+
+    #[test]
+    fn bits_test() {
+        let buffer = [0b1011_0011, 0b0101_1100, 0xff];
+        assert_eq!(Some(0b1011), load_bits::<Msb0>(&buffer, 0, 4));
+        assert_eq!(Some(0b0_0110_1011), load_bits::<Msb0>(&buffer, 4, 9));
+        assert_eq!(Some(0), load_bits::<Msb0>(&buffer, 24, 0));
+        assert_eq!(None, load_bits::<Msb0>(&buffer, 20, 5));
+        assert_eq!(Some(0xb35cff), load_bits::<Msb0>(&buffer, 0, 24));
+        let mut buffer = [0u8; 9];
+        store_bits::<Msb0>(&mut buffer, 3, 64, u64::MAX);
+        assert_eq!([0x1f, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xe0], buffer);
+        assert_eq!(Some(u64::MAX), load_bits::<Msb0>(&buffer, 3, 64));
+        store_bits::<Msb0>(&mut buffer, 6, 5, 0b10101);
+        assert_eq!([0x1e, 0xbf], buffer[..2]);
+    }
+
+    #[test]
+    fn lsb0_bits_test() {
+        let buffer = [0b1011_0011, 0b0101_1100, 0xff];
+        assert_eq!(Some(0b0011), load_bits::<Lsb0>(&buffer, 0, 4));
+        assert_eq!(Some(0b1_1100_1011), load_bits::<Lsb0>(&buffer, 4, 9));
+        assert_eq!(Some(0), load_bits::<Lsb0>(&buffer, 24, 0));
+        assert_eq!(None, load_bits::<Lsb0>(&buffer, 20, 5));
+        assert_eq!(Some(0xff5cb3), load_bits::<Lsb0>(&buffer, 0, 24));
+        let mut buffer = [0u8; 9];
+        store_bits::<Lsb0>(&mut buffer, 3, 64, u64::MAX);
+        assert_eq!([0xf8, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x07], buffer);
+        assert_eq!(Some(u64::MAX), load_bits::<Lsb0>(&buffer, 3, 64));
+        store_bits::<Lsb0>(&mut buffer, 6, 5, 0b10101);
+        assert_eq!([0x78, 0xfd], buffer[..2]);
+    }
+
+    #[test]
+    fn lsb0_codes_test() {
+        let mut buffer = [0; 1];
+        assert_eq!(Ok(5), write_elias_gamma::<Lsb0>(&mut buffer, 0, 7));
+        assert_eq!([0b0001_1100], buffer);
+        let mut buffer = [0x55; 24];
+        for value in (1..300).chain([u64::MAX >> 1, u64::MAX]) {
+            for bit_index in [0, 3, 8] {
+                let len = write_elias_gamma::<Lsb0>(&mut buffer, bit_index, value).unwrap();
+                assert_eq!(Ok((value, len)), read_elias_gamma::<Lsb0>(&buffer, bit_index));
+                let len = write_elias_delta::<Lsb0>(&mut buffer, bit_index, value).unwrap();
+                assert_eq!(Ok((value, len)), read_elias_delta::<Lsb0>(&buffer, bit_index));
+                let len = write_rice::<Lsb0>(&mut buffer, bit_index, value, 60).unwrap();
+                assert_eq!(Ok((value, len)), read_rice::<Lsb0>(&buffer, bit_index, 60));
+                let m = u64::MAX / 9;
+                let len = write_golomb::<Lsb0>(&mut buffer, bit_index, value, m).unwrap();
+                assert_eq!(Ok((value, len)), read_golomb::<Lsb0>(&buffer, bit_index, m));
+            }
+        }
+        let mut writer = BitWriter::<_, Lsb0>::new(Frame::default());
+        assert_eq!(Ok(()), writer.write_bits(0b101, 3));
+        assert_eq!(Ok(()), writer.write_golomb(9, 10));
+        assert_eq!(Ok(()), writer.write_rice(5, 2));
+        assert_eq!(4, writer.align_with(true));
+        let mut reader = BitReader::<Lsb0>::new(writer.as_slice());
+        assert_eq!(Ok(0b101), reader.read_bits(3));
+        assert_eq!(Ok(9), reader.read_golomb(10));
+        assert_eq!(Ok(5), reader.read_rice(2));
+        assert_eq!(Ok(0b1111), reader.read_bits(4));
+        assert_eq!(0, reader.remaining());
+    }
+
+    #[test]
+    fn elias_gamma_test() {
+        let cases: [(u64, usize, u64); 5] = [
+            (1, 1, 0b1),
+            (2, 3, 0b010),
+            (7, 5, 0b00111),
+            (17, 9, 0b0000_10001),
+            (1 << 31, 63, 1 << 31),
+        ];
+        for (value, len, code) in cases {
+            assert_eq!(Some(len), elias_gamma_len(value));
+            for bit_index in [0, 5, 8] {
+                let mut buffer = [0xaa; 10];
+                let mut expected = [0xaa; 10];
+                store_bits::<Msb0>(&mut expected, bit_index, len as u32, code);
+                assert_eq!(Ok(len), write_elias_gamma::<Msb0>(&mut buffer, bit_index, value));
+                assert_eq!(expected, buffer);
+                assert_eq!(Ok((value, len)), read_elias_gamma::<Msb0>(&buffer, bit_index));
+            }
+        }
+        let mut buffer = [0xaa; 17];
+        assert_eq!(Ok(127), write_elias_gamma::<Msb0>(&mut buffer, 1, u64::MAX));
+        assert_eq!(Ok((u64::MAX, 127)), read_elias_gamma::<Msb0>(&buffer, 1));
+        assert_eq!([0x80, 0, 0, 0, 0, 0, 0, 0, 0xff], buffer[..9]);
+    }
+
+    #[test]
+    fn elias_gamma_error_test() {
+        assert_eq!(
+            Err(Error::new(ErrorKind::InvalidEncoding { start_index: 0 })),
+            read_elias_gamma::<Msb0>(&[0; 9], 0)
+        );
+        assert_eq!(
+            Err(Error::new(ErrorKind::OutOfBounds { start_index: 0, requested: 2, len: 1 })),
+            read_elias_gamma::<Msb0>(&[0b0000_0011], 4)
+        );
+        assert_eq!(
+            Err(Error::new(ErrorKind::OutOfBounds { start_index: 1, requested: 2, len: 2 })),
+            read_elias_gamma::<Msb0>(&[0xff, 0], 12)
+        );
+        let mut buffer = [0xaa; 2];
+        assert_eq!(
+            Err(Error::new(ErrorKind::InvalidEncoding { start_index: 0 })),
+            write_elias_gamma::<Msb0>(&mut buffer, 0, 0)
+        );
+        assert_eq!(
+            Err(Error::new(ErrorKind::OutOfBounds { start_index: 1, requested: 2, len: 2 })),
+            write_elias_gamma::<Msb0>(&mut buffer, 12, 4)
+        );
+        assert!(write_elias_gamma::<Msb0>(&mut buffer, usize::MAX, 1).is_err());
+        assert_eq!([0xaa; 2], buffer);
+    }
+
+    #[test]
+    fn elias_delta_test() {
+        let cases: [(u64, usize, u128); 5] = [
+            (1, 1, 0b1),
+            (2, 4, 0b0100),
+            (10, 8, 0b0010_0010),
+            (16, 9, 0b0_0101_0000),
+            (u64::MAX, 76, 0b000000_1000000 << 63 | u128::from(u64::MAX >> 1)),
+        ];
+        for (value, len, code) in cases {
+            assert_eq!(Some(len), elias_delta_len(value));
+            for bit_index in [0, 3] {
+                let mut buffer = [0x55; 11];
+                let mut expected = [0x55; 11];
+                let high = len.saturating_sub(64);
+                store_bits::<Msb0>(&mut expected, bit_index, high as u32, (code >> 64) as u64);
+                store_bits::<Msb0>(
+                    &mut expected,
+                    bit_index + high,
+                    (len - high) as u32,
+                    code as u64,
+                );
+                assert_eq!(Ok(len), write_elias_delta::<Msb0>(&mut buffer, bit_index, value));
+                assert_eq!(expected, buffer);
+                assert_eq!(Ok((value, len)), read_elias_delta::<Msb0>(&buffer, bit_index));
+            }
+        }
+        assert_eq!(None, elias_delta_len(0));
+    }
+
+    #[test]
+    fn elias_delta_error_test() {
+        // The gamma code of 65 bits.
+        let mut buffer = [0; 2];
+        store_bits::<Msb0>(&mut buffer, 0, 13, 65);
+        assert_eq!(
+            Err(Error::new(ErrorKind::InvalidEncoding { start_index: 0 })),
+            read_elias_delta::<Msb0>(&buffer, 0)
+        );
+        assert_eq!(
+            Err(Error::new(ErrorKind::OutOfBounds { start_index: 0, requested: 2, len: 1 })),
+            read_elias_delta::<Msb0>(&[0b0011_1111], 0)
+        );
+        let mut buffer = [0; 1];
+        assert_eq!(
+            Err(Error::new(ErrorKind::OutOfBounds { start_index: 0, requested: 2, len: 1 })),
+            write_elias_delta::<Msb0>(&mut buffer, 1, 10)
+        );
+        assert_eq!(
+            Err(Error::new(ErrorKind::InvalidEncoding { start_index: 0 })),
+            write_elias_delta::<Msb0>(&mut buffer, 0, 0)
+        );
+        assert_eq!([0], buffer);
+    }
+
+    fn check_store_zeros<O: BitOrder>() {
+        for (bit_index, count) in [(0, 0), (3, 4), (3, 5), (5, 20), (0, 24), (7, 1), (1, 30)] {
+            let mut buffer = [0xff; 5];
+            store_zeros::<O>(&mut buffer, bit_index, count);
+            for index in 0..40 {
+                let zero = (bit_index..bit_index + count).contains(&index);
+                assert_eq!(Some(u64::from(!zero)), load_bits::<O>(&buffer, index, 1));
+            }
+            assert_eq!((count, bit_index + count < 40), zero_run::<O>(&buffer, bit_index, 40));
+        }
+    }
+
+    #[test]
+    fn store_zeros_test() {
+        check_store_zeros::<Msb0>();
+        check_store_zeros::<Lsb0>();
+    }
+
+    #[test]
+    fn rice_test() {
+        let cases: [(u64, u32, usize, u64); 6] = [
+            (0, 0, 1, 0b1),
+            (3, 0, 4, 0b0001),
+            (5, 2, 4, 0b0101),
+            (7, 2, 4, 0b0111),
+            (0xff, 8, 9, 0x1ff),
+            (u64::MAX, 63, 65, u64::MAX),
+        ];
+        for (value, k, len, code) in cases {
+            assert_eq!(Some(len), rice_len(value, k));
+            for bit_index in [0, 6] {
+                let mut buffer = [0xaa; 10];
+                let mut expected = [0xaa; 10];
+                store_bits::<Msb0>(&mut expected, bit_index, (len as u32).saturating_sub(64), 0);
+                store_bits::<Msb0>(
+                    &mut expected,
+                    bit_index + len.saturating_sub(64),
+                    len.min(64) as u32,
+                    code,
+                );
+                assert_eq!(Ok(len), write_rice::<Msb0>(&mut buffer, bit_index, value, k));
+                assert_eq!(expected, buffer);
+                assert_eq!(Ok((value, len)), read_rice::<Msb0>(&buffer, bit_index, k));
+            }
+        }
+        // A quotient of 100 spans several bytes of zero bits.
+        let mut buffer = [0xff; 16];
+        assert_eq!(Ok(104), write_rice::<Msb0>(&mut buffer, 5, 803, 3));
+        assert_eq!([0xf8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x5f, 0xff, 0xff], buffer);
+        assert_eq!(Ok((803, 104)), read_rice::<Msb0>(&buffer, 5, 3));
+        let mut buffer = [0; 9];
+        assert_eq!(Ok(65), write_rice::<Msb0>(&mut buffer, 0, u64::MAX, 64));
+        assert_eq!(Ok((u64::MAX, 65)), read_rice::<Msb0>(&buffer, 0, 64));
+    }
+
+    #[test]
+    fn rice_error_test() {
+        assert_eq!(
+            Err(Error::new(ErrorKind::InvalidEncoding { start_index: 0 })),
+            read_rice::<Msb0>(&[0xff], 0, 65)
+        );
+        assert_eq!(
+            Err(Error::new(ErrorKind::InvalidEncoding { start_index: 0 })),
+            read_rice::<Msb0>(&[0, 0x80], 0, 61)
+        );
+        assert_eq!(
+            Err(Error::new(ErrorKind::OutOfBounds { start_index: 0, requested: 2, len: 1 })),
+            read_rice::<Msb0>(&[0b0001_1000], 0, 5)
+        );
+        assert_eq!(
+            Err(Error::new(ErrorKind::OutOfBounds { start_index: 1, requested: 2, len: 2 })),
+            read_rice::<Msb0>(&[0xff, 0], 10, 0)
+        );
+        let mut buffer = [0xaa; 2];
+        assert_eq!(
+            Err(Error::new(ErrorKind::OutOfBounds { start_index: 1, requested: 2, len: 2 })),
+            write_rice::<Msb0>(&mut buffer, 9, 16, 1)
+        );
+        assert_eq!(
+            Err(Error::new(ErrorKind::InvalidEncoding { start_index: 0 })),
+            write_rice::<Msb0>(&mut buffer, 0, 0, 65)
+        );
+        assert!(write_rice::<Msb0>(&mut buffer, 0, u64::MAX, 0).is_err());
+        assert_eq!([0xaa; 2], buffer);
+    }
+
+    #[test]
+    fn golomb_test() {
+        let cases: [(u64, u64, usize, u128); 8] = [
+            (0, 1, 1, 0b1),
+            (2, 1, 3, 0b001),
+            (0, 3, 2, 0b10),
+            (1, 3, 3, 0b110),
+            (2, 3, 3, 0b111),
+            (5, 3, 4, 0b0111),
+            (9, 10, 5, 0b1_1111),
+            (u64::MAX, u64::MAX, 65, 0b01 << 63),
+        ];
+        for (value, m, len, code) in cases {
+            assert_eq!(Some(len), golomb_len(value, m));
+            for bit_index in [0, 7] {
+                let mut buffer = [0x55; 10];
+                let mut expected = [0x55; 10];
+                store_bits::<Msb0>(
+                    &mut expected,
+                    bit_index,
+                    (len as u32).saturating_sub(64),
+                    (code >> 64) as u64,
+                );
+                store_bits::<Msb0>(
+                    &mut expected,
+                    bit_index + len.saturating_sub(64),
+                    len.min(64) as u32,
+                    code as u64,
+                );
+                assert_eq!(Ok(len), write_golomb::<Msb0>(&mut buffer, bit_index, value, m));
+                assert_eq!(expected, buffer);
+                assert_eq!(Ok((value, len)), read_golomb::<Msb0>(&buffer, bit_index, m));
+            }
+        }
+        for value in 0..40 {
+            let (mut rice, mut golomb) = ([0; 8], [0; 8]);
+            assert_eq!(
+                write_rice::<Msb0>(&mut rice, 3, value, 2),
+                write_golomb::<Msb0>(&mut golomb, 3, value, 4)
+            );
+            assert_eq!(rice, golomb);
+        }
+    }
+
+    #[test]
+    fn golomb_error_test() {
+        assert_eq!(
+            Err(Error::new(ErrorKind::InvalidEncoding { start_index: 0 })),
+            read_golomb::<Msb0>(&[0xff], 0, 0)
+        );
+        // A quotient of 1 and the largest remainder overflow with m = 2^63 + 1.
+        assert_eq!(
+            Err(Error::new(ErrorKind::InvalidEncoding { start_index: 0 })),
+            read_golomb::<Msb0>(
+                &[0x7f, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff],
+                0,
+                (1 << 63) + 1
+            )
+        );
+        assert_eq!(
+            Err(Error::new(ErrorKind::OutOfBounds { start_index: 0, requested: 2, len: 1 })),
+            read_golomb::<Msb0>(&[0b0000_0011], 0, 3)
+        );
+        assert_eq!(
+            Err(Error::new(ErrorKind::OutOfBounds { start_index: 0, requested: 2, len: 1 })),
+            read_golomb::<Msb0>(&[0b0000_0011], 4, 5)
+        );
+        assert_eq!(
+            Err(Error::new(ErrorKind::OutOfBounds { start_index: 0, requested: 2, len: 1 })),
+            read_golomb::<Msb0>(&[0b0000_0001], 1, 3)
+        );
+        let mut buffer = [0xaa; 1];
+        assert_eq!(
+            Err(Error::new(ErrorKind::InvalidEncoding { start_index: 0 })),
+            write_golomb::<Msb0>(&mut buffer, 0, 1, 0)
+        );
+        assert_eq!(
+            Err(Error::new(ErrorKind::OutOfBounds { start_index: 0, requested: 2, len: 1 })),
+            write_golomb::<Msb0>(&mut buffer, 3, 12, 3)
+        );
+        assert_eq!([0xaa], buffer);
+    }
+
+    fn check_pack_bits<O: BitOrder>() {
+        let mut seed = 0x2545_f491_4f6c_dd1du64;
+        for bits in 0..=64 {
+            let mut values = [0u64; 37];
+            for value in values.iter_mut() {
+                seed ^= seed << 13;
+                seed ^= seed >> 7;
+                seed ^= seed << 17;
+                *value = seed.checked_shr(64 - bits).unwrap_or(0);
+            }
+            let len = packed_len(values.len(), bits).unwrap();
+            let mut expected = [0xaa; 300];
+            store_zeros::<O>(&mut expected, 8, len * 8);
+            for (index, &value) in values.iter().enumerate() {
+                store_bits::<O>(&mut expected, 8 + index * bits as usize, bits, value);
+            }
+            let mut buffer = [0xaa; 300];
+            assert_eq!(Ok(len), pack_bits::<O>(&mut buffer, 1, &values, bits));
+            assert_eq!(expected, buffer);
+            let mut unpacked = [0xaa; 37];
+            assert_eq!(Ok(len), unpack_bits::<O>(&buffer, 1, &mut unpacked, bits));
+            assert_eq!(values, unpacked);
+        }
+    }
+
+    #[test]
+    fn pack_bits_test() {
+        check_pack_bits::<Msb0>();
+        check_pack_bits::<Lsb0>();
+        let mut buffer = [0; 4];
+        assert_eq!(Ok(4), pack_bits::<Lsb0>(&mut buffer, 0, &[0x1234, 0xabcd], 16));
+        assert_eq!([0x34, 0x12, 0xcd, 0xab], buffer);
+        assert_eq!(Ok(3), pack_bits::<Lsb0>(&mut buffer, 0, &[0x5, 0x1ff], 9));
+        assert_eq!([0x05, 0xfe, 0x03], buffer[..3]);
+    }
+
+    #[test]
+    fn pack_bits_error_test() {
+        let mut buffer = [0xaa; 4];
+        assert_eq!(
+            Err(Error::new(ErrorKind::InvalidEncoding { start_index: 2 }).with_index(3)),
+            pack_bits::<Msb0>(&mut buffer, 1, &[1, 2, 3, 8, 4], 3)
+        );
+        assert_eq!(
+            Err(Error::new(ErrorKind::OutOfBounds { start_index: 1, requested: 4, len: 4 })),
+            pack_bits::<Msb0>(&mut buffer, 1, &[0; 3], 9)
+        );
+        assert_eq!(
+            Err(Error::new(ErrorKind::InvalidEncoding { start_index: 0 })),
+            pack_bits::<Msb0>(&mut buffer, 0, &[0], 65)
+        );
+        assert!(pack_bits::<Msb0>(&mut buffer, 0, &[1], 0).is_err());
+        assert!(pack_bits::<Msb0>(&mut buffer, usize::MAX, &[], 1).is_err());
+        assert_eq!([0xaa; 4], buffer);
+        assert_eq!(Ok(0), pack_bits::<Msb0>(&mut buffer, 4, &[], 7));
+
+        let mut values = [7; 3];
+        assert_eq!(
+            Err(Error::new(ErrorKind::OutOfBounds { start_index: 1, requested: 4, len: 4 })),
+            unpack_bits::<Msb0>(&buffer, 1, &mut values, 9)
+        );
+        assert_eq!(
+            Err(Error::new(ErrorKind::InvalidEncoding { start_index: 0 })),
+            unpack_bits::<Msb0>(&buffer, 0, &mut values, 65)
+        );
+        assert_eq!([7; 3], values);
+    }
+
+    #[test]
+    fn bit_reader_test() {
+        let buffer = [0b1011_0011, 0b0101_1100, 0xff];
+        let mut reader = BitReader::<Msb0>::new(&buffer);
+        assert_eq!(24, reader.remaining());
+        assert_eq!(Ok(true), reader.read_bit());
+        assert_eq!(Ok(0b011), reader.read_bits(3));
+        assert!(!reader.is_aligned());
+        assert_eq!(Ok(0b0011_0101_1100), reader.peek_bits(12));
+        assert_eq!(Ok(0), reader.read_bits(0));
+        assert_eq!(Ok(0b00_1101), reader.read_bits(6));
+        assert_eq!((10, 1), (reader.position(), reader.byte_position()));
+        assert_eq!(6, reader.align());
+        assert_eq!(0, reader.align());
+        assert!(reader.is_aligned());
+        assert_eq!(
+            Err(Error::new(ErrorKind::OutOfBounds { start_index: 2, requested: 2, len: 3 })),
+            reader.read_bits(9)
+        );
+        assert_eq!(
+            Err(Error::new(ErrorKind::LimitExceeded { start_index: 2, len: 65, max: 64 })),
+            reader.peek_bits(65)
+        );
+        assert_eq!(16, reader.position());
+        assert!(reader.skip_bits(9).is_err());
+        assert_eq!(Ok(()), reader.skip_bits(7));
+        assert_eq!(Ok(true), reader.read_bit());
+        assert_eq!(0, reader.remaining());
+        reader.set_position(usize::MAX);
+        assert_eq!(0, reader.remaining());
+        assert!(reader.read_bit().is_err());
+        assert!(reader.skip_bits(1).is_err());
+
+        let buffer = [0xff; 9];
+        let mut reader = BitReader::<Msb0>::new(&buffer);
+        reader.set_position(4);
+        assert_eq!(Ok(u64::MAX), reader.read_bits(64));
+        assert_eq!(Ok(0xf), reader.read_bits(4));
+    }
+
+    #[test]
+    fn bit_reader_codes_test() {
+        // 3 as a gamma code, 10 as a delta code, 5 as a Rice code with k = 2
+        // and 2 as a Golomb code with m = 3.
+        let buffer = [0b0110_0100, 0b0100_1011, 0b1100_0000];
+        let mut reader = BitReader::<Msb0>::new(&buffer);
+        assert_eq!(Ok(3), reader.read_elias_gamma());
+        assert_eq!(Ok(10), reader.read_elias_delta());
+        assert_eq!(Ok(5), reader.read_rice(2));
+        assert_eq!(Ok(2), reader.read_golomb(3));
+        assert_eq!(18, reader.position());
+        assert!(reader.read_elias_gamma().is_err());
+        assert_eq!(18, reader.position());
+    }
+
+    #[test]
+    fn bit_writer_test() {
+        let mut writer = BitWriter::<_, Msb0>::new(Frame::default());
+        assert_eq!(Ok(()), writer.write_bit(true));
+        assert_eq!(Ok(()), writer.write_bits(0b011, 3));
+        assert_eq!(Ok(()), writer.write_bits(0, 0));
+        assert!(!writer.is_aligned());
+        assert_eq!([0b1011_0000], writer.as_slice());
+        assert_eq!(Ok(()), writer.write_bits(0b00_1101, 6));
+        assert_eq!(10, writer.position());
+        assert_eq!(6, writer.align());
+        assert_eq!(0, writer.align());
+        assert_eq!(Ok(()), writer.write_bits(0x5, 3));
+        assert_eq!(5, writer.align_with(true));
+        assert_eq!(
+            Err(Error::new(ErrorKind::InvalidEncoding { start_index: 3 })),
+            writer.write_bits(8, 3)
+        );
+        assert_eq!(
+            Err(Error::new(ErrorKind::LimitExceeded { start_index: 3, len: 65, max: 64 })),
+            writer.write_bits(0, 65)
+        );
+        assert_eq!(Ok(()), writer.write_bits(u64::MAX >> 28, 36));
+        assert_eq!(
+            Err(Error::new(ErrorKind::OutOfBounds { start_index: 8, requested: 1, len: 8 })),
+            writer.write_bits(0, 5)
+        );
+        assert_eq!(60, writer.position());
+        assert_eq!(Ok(()), writer.write_bits(0x3, 4));
+        assert!(writer.write_bit(false).is_err());
+        assert_eq!(
+            [0b1011_0011, 0b0100_0000, 0b1011_1111, 0xff, 0xff, 0xff, 0xff, 0xf3],
+            writer.into_inner().bytes
+        );
+
+        let mut writer = BitWriter::<_, Msb0>::new(Frame::default());
+        assert_eq!(Ok(()), writer.write_bits(u64::MAX, 64));
+        assert_eq!(64, writer.position());
+    }
+
+    #[test]
+    fn bit_writer_codes_test() {
+        let mut writer = BitWriter::<_, Msb0>::new(Frame::default());
+        assert_eq!(Ok(()), writer.write_elias_gamma(3));
+        assert_eq!(Ok(()), writer.write_elias_delta(10));
+        assert_eq!(Ok(()), writer.write_rice(5, 2));
+        assert_eq!(Ok(()), writer.write_golomb(2, 3));
+        assert_eq!([0b0110_0100, 0b0100_1011, 0b1100_0000], writer.as_slice());
+        assert_eq!(
+            Err(Error::new(ErrorKind::InvalidEncoding { start_index: 2 })),
+            writer.write_elias_gamma(0)
+        );
+        assert!(writer.write_golomb(1, 0).is_err());
+        assert!(writer.write_rice(0, 65).is_err());
+        assert_eq!(
+            Err(Error::new(ErrorKind::OutOfBounds { start_index: 3, requested: 37, len: 3 })),
+            writer.write_rice(300, 0)
+        );
+        assert_eq!(Ok(()), writer.write_rice(40, 0));
+        assert_eq!(Ok(()), writer.write_elias_delta(u64::MAX >> 61));
+        assert!(writer.write_elias_gamma(1).is_err());
+        assert_eq!(64, writer.position());
+        let mut reader = BitReader::<Msb0>::new(writer.as_slice());
+        reader.set_position(18);
+        assert_eq!(Ok(40), reader.read_rice(0));
+        assert_eq!(Ok(7), reader.read_elias_delta());
+    }
+}
@@ -0,0 +1,114 @@
+//! Chainable construction of byte buffers.
+use alloc::vec::Vec;
+
+/// Builder assembling a byte buffer in a single expression, with the
+/// endianness chosen for each value.
+///
+/// It suits test vectors and small packets, where spelling out every field
+/// inline reads better than a sequence of writes.
+/// # Example
+///
+/// ```
+/// use bitconv::BytesBuilder;
+///
+/// let payload = [104, 105];
+/// let packet = BytesBuilder::new().uint16_le(1).uint32_be(0xDEAD).bytes(&payload).build();
+/// assert_eq!([1, 0, 0, 0, 222, 173, 104, 105], packet[..]);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[must_use = "a builder does nothing until `build` is called"]
+pub struct BytesBuilder {
+    buffer: Vec<u8>,
+}
+
+macro_rules! BytesBuilderImpl {
+    ($($le:ident $be:ident $ne:ident $type:ty),+) => {$(
+        #[doc = concat!("Appends a `", stringify!($type), "` in little-endian order.")]
+        #[inline]
+        pub fn $le(self, value: $type) -> Self { self.bytes(&value.to_le_bytes()) }
+
+        #[doc = concat!("Appends a `", stringify!($type), "` in big-endian order.")]
+        #[inline]
+        pub fn $be(self, value: $type) -> Self { self.bytes(&value.to_be_bytes()) }
+
+        #[doc = concat!("Appends a `", stringify!($type), "` in native order.")]
+        #[inline]
+        pub fn $ne(self, value: $type) -> Self { self.bytes(&value.to_ne_bytes()) }
+    )+};
+}
+
+impl BytesBuilder {
+    pub fn new() -> Self { BytesBuilder::default() }
+
+    /// Appends raw bytes.
+    pub fn bytes(mut self, bytes: &[u8]) -> Self {
+        self.buffer.extend_from_slice(bytes);
+        self
+    }
+
+    /// Appends a signed byte.
+    pub fn int8(self, value: i8) -> Self { self.bytes(&value.to_ne_bytes()) }
+
+    /// Appends a byte.
+    pub fn uint8(self, value: u8) -> Self { self.bytes(&[value]) }
+
+    BytesBuilderImpl!(
+        int16_le int16_be int16_ne i16,
+        int32_le int32_be int32_ne i32,
+        int64_le int64_be int64_ne i64,
+        uint16_le uint16_be uint16_ne u16,
+        uint32_le uint32_be uint32_ne u32,
+        uint64_le uint64_be uint64_ne u64
+    );
+
+    /// Returns the assembled bytes.
+    pub fn build(self) -> Vec<u8> { self.buffer }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn bytes_builder_test() {
+        let buffer = BytesBuilder::new()
+            .uint32_le(2147483663)
+            .int16_le(10000)
+            .int16_be(-3880)
+            .int8(-15)
+            .uint8(255)
+            .bytes(&[])
+            .bytes(&[127])
+            .build();
+        assert_eq!([15, 0, 0, 128, 16, 39, 240, 216, 241, 255, 127], buffer[..]);
+        let buffer = BytesBuilder::new()
+            .uint64_be(1080864460595785944)
+            .int64_le(-2)
+            .int32_be(-2)
+            .uint16_be(4135)
+            .build();
+        assert_eq!(
+            [
+                15, 0, 0, 128, 16, 39, 240, 216, 254, 255, 255, 255, 255, 255, 255, 255, 255, 255,
+                255, 254, 16, 39
+            ],
+            buffer[..]
+        );
+        let native = BytesBuilder::new()
+            .uint16_ne(1)
+            .int32_ne(2)
+            .uint32_ne(3)
+            .int64_ne(4)
+            .uint64_ne(5)
+            .int16_ne(6);
+        let mut expected = Vec::new();
+        expected.extend_from_slice(&1u16.to_ne_bytes());
+        expected.extend_from_slice(&2i32.to_ne_bytes());
+        expected.extend_from_slice(&3u32.to_ne_bytes());
+        expected.extend_from_slice(&4i64.to_ne_bytes());
+        expected.extend_from_slice(&5u64.to_ne_bytes());
+        expected.extend_from_slice(&6i16.to_ne_bytes());
+        assert_eq!(expected, native.build());
+        assert!(BytesBuilder::new().build().is_empty());
+    }
+}
@@ -0,0 +1,87 @@
+//! Interoperability with the endianness markers of the `byteorder` crate, for
+//! codebases that use both while migrating from one to the other.
+//!
+//! The markers of `byteorder` can be used wherever this crate expects an
+//! endianness, and [`AsByteOrder`] maps the markers of this crate to theirs.
+//! `byteorder::NativeEndian` is an alias of one of the other two, so it
+//! converts with the fixed endianness of the target rather than as
+//! [`Native`].
+use crate::{
+    endian::{Big, Endian, Little, Native}, BitConvEndian
+};
+use ::byteorder::{BigEndian, ByteOrder, LittleEndian, NativeEndian};
+
+impl BitConvEndian for LittleEndian {
+    const ENDIANNESS: Endian = Endian::LE;
+}
+
+impl BitConvEndian for BigEndian {
+    const ENDIANNESS: Endian = Endian::BE;
+}
+
+/// Endianness markers with a `byteorder` counterpart, so code generic over
+/// the one can call into code generic over the other.
+/// # Example
+///
+/// ```
+/// use bitconv::{byteorder::AsByteOrder, endian::Big, BitConvEndian};
+/// use byteorder::{ByteOrder, LittleEndian};
+///
+/// fn checksum<E: AsByteOrder>(data: &[u8]) -> u16 {
+///     E::ByteOrder::read_u16(data) ^ bitconv::to_uint16::<E>(data, 2)
+/// }
+///
+/// assert_eq!(0x0102 ^ 0x0304, checksum::<Big>(&[1, 2, 3, 4]));
+/// assert_eq!(0x0201 ^ 0x0403, checksum::<LittleEndian>(&[1, 2, 3, 4]));
+/// ```
+pub trait AsByteOrder: BitConvEndian {
+    /// The `byteorder` marker of the same endianness.
+    type ByteOrder: ByteOrder;
+}
+
+impl AsByteOrder for Little {
+    type ByteOrder = LittleEndian;
+}
+
+impl AsByteOrder for Big {
+    type ByteOrder = BigEndian;
+}
+
+impl AsByteOrder for Native {
+    type ByteOrder = NativeEndian;
+}
+
+impl AsByteOrder for LittleEndian {
+    type ByteOrder = LittleEndian;
+}
+
+impl AsByteOrder for BigEndian {
+    type ByteOrder = BigEndian;
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::*;
+
+    fn round_trip<E: AsByteOrder>(value: u32) -> u32 {
+        let mut buffer = [0; 4];
+        E::ByteOrder::write_u32(&mut buffer, value);
+        to_uint32::<E>(&buffer, 0)
+    }
+
+    #[test]
+    fn byteorder_test() {
+        let buffer = [15, 0, 0, 128, 16, 39, 240, 216, 241, 255, 127];
+        assert_eq!(2147483663, to_uint32::<LittleEndian>(&buffer, 0));
+        assert_eq!(-3880, to_int16::<BigEndian>(&buffer, 6));
+        assert_eq!(to_uint64::<Native>(&buffer, 0), to_uint64::<NativeEndian>(&buffer, 0));
+        assert_eq!(
+            <Little as AsByteOrder>::ByteOrder::read_u32(&buffer),
+            to_uint32::<Little>(&buffer, 0)
+        );
+        assert_eq!(0xdeadbeef, round_trip::<Big>(0xdeadbeef));
+        assert_eq!(0xdeadbeef, round_trip::<Native>(0xdeadbeef));
+        assert_eq!(0xdeadbeef, round_trip::<LittleEndian>(0xdeadbeef));
+    }
+}
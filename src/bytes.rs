@@ -0,0 +1,113 @@
+//! Extension traits converting values to and from the buffers of the `bytes`
+//! crate.
+use crate::{BitConvEndian, Error, ErrorKind, FromBytes, Result, ToBytes};
+use ::bytes::{Buf, BufMut};
+use alloc::vec;
+
+/// Extension of [`Buf`] taking values in the endianness `E` from the front of
+/// the buffer, such as tuples and derived types.
+///
+/// A value is converted from the current [`chunk`](Buf::chunk) of the buffer,
+/// which for `Bytes`, `BytesMut` and byte slices holds all of it. A value
+/// spanning the chunks of a buffer made of several, such as one returned by
+/// [`Buf::chain`], fails with `ErrorKind::OutOfBounds`.
+/// # Example
+///
+/// ```
+/// use bitconv::{bytes::BufExt, endian::Big};
+/// use bytes::Bytes;
+///
+/// let mut buf = Bytes::from_static(&[0, 7, 0, 0, 1, 0, 9]);
+/// assert_eq!(Ok((7, 256)), buf.get_value::<(u16, u32), Big>());
+/// assert_eq!(1, buf.len());
+/// assert!(buf.get_value::<u16, Big>().is_err());
+/// ```
+pub trait BufExt: Buf {
+    /// Converts a value from the front of the buffer and advances past it.
+    ///
+    /// Nothing is consumed if the conversion fails.
+    fn get_value<V: FromBytes, E: BitConvEndian>(&mut self) -> Result<V> {
+        let (value, read) = V::from_bytes::<E>(self.chunk(), 0)?;
+        self.advance(read);
+        Ok(value)
+    }
+}
+
+impl<B: Buf + ?Sized> BufExt for B {}
+
+/// Extension of [`BufMut`] appending values in the endianness `E`, such as
+/// tuples and derived types.
+/// # Example
+///
+/// ```
+/// use bitconv::{bytes::BufMutExt, endian::Little};
+/// use bytes::BytesMut;
+///
+/// let mut buf = BytesMut::new();
+/// buf.put_value::<_, Little>(&(7u8, 256u16)).unwrap();
+/// assert_eq!([7, 0, 1], buf[..]);
+/// ```
+pub trait BufMutExt: BufMut {
+    /// Appends a value to the buffer.
+    ///
+    /// Fails with `ErrorKind::OutOfBounds`, writing nothing, if the buffer
+    /// cannot grow to hold the value.
+    fn put_value<V: ToBytes + ?Sized, E: BitConvEndian>(&mut self, value: &V) -> Result<()> {
+        let len = value.byte_len();
+        if len > self.remaining_mut() {
+            return Err(Error::new(ErrorKind::OutOfBounds {
+                start_index: 0,
+                requested: len,
+                len: self.remaining_mut(),
+            }));
+        }
+        let mut bytes = vec![0; len];
+        value.to_bytes::<E>(&mut bytes, 0)?;
+        self.put_slice(&bytes);
+        Ok(())
+    }
+}
+
+impl<B: BufMut + ?Sized> BufMutExt for B {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::endian::*;
+    use ::bytes::{Bytes, BytesMut};
+
+    #[test]
+    fn buf_test() {
+        let mut buf = Bytes::from_static(&[15, 0, 0, 128, 16, 39, 240, 216, 241, 255, 127]);
+        assert_eq!(Ok(2147483663), buf.get_value::<u32, Little>());
+        assert_eq!(Ok((10000, -10000)), buf.get_value::<(i16, i16), Little>());
+        assert_eq!(Ok(-3585), buf.get_value::<i16, Big>());
+        assert_eq!(
+            Err(Error::new(ErrorKind::OutOfBounds { start_index: 0, requested: 4, len: 1 })),
+            buf.get_value::<u32, Big>()
+        );
+        assert_eq!(Ok(127), buf.get_value::<u8, Native>());
+        let mut chained = (&[1u8][..]).chain(&[2u8][..]);
+        assert!(chained.get_value::<u16, Little>().is_err());
+        assert_eq!(2, chained.remaining());
+    }
+
+    #[test]
+    fn buf_mut_test() {
+        let mut buf = BytesMut::new();
+        assert_eq!(Ok(()), buf.put_value::<_, Little>(&2147483663u32));
+        assert_eq!(Ok(()), buf.put_value::<_, Big>(&(-10000i16, [1u8, 2])));
+        assert_eq!([15, 0, 0, 128, 216, 240, 1, 2], buf[..]);
+        let mut bytes = [0; 3];
+        let mut slice = &mut bytes[..];
+        assert_eq!(Ok(()), slice.put_value::<_, Big>(&258u16));
+        assert_eq!(
+            Err(Error::new(ErrorKind::OutOfBounds { start_index: 0, requested: 2, len: 1 })),
+            slice.put_value::<_, Big>(&1u16)
+        );
+        assert_eq!([1, 2, 0], bytes);
+        let mut buf = buf.freeze();
+        assert_eq!(Ok(2147483663), buf.get_value::<u32, Little>());
+        assert_eq!(Ok((-10000, [1, 2])), buf.get_value::<(i16, [u8; 2]), Big>());
+    }
+}
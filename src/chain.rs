@@ -0,0 +1,135 @@
+//! Cursor over a value split across several byte slices.
+use crate::{BitConvEndian, Error, ErrorKind, FromBytes, Result};
+use core::{marker::PhantomData, mem};
+
+/// Cursor over a sequence of byte slices read as if they were one contiguous
+/// buffer, converting values in the endianness `E`.
+///
+/// Values spanning the seam between two slices, such as the two halves of a
+/// ring buffer or the segments of a scattered receive, are read without
+/// copying the slices into a temporary buffer first. Positions in errors
+/// count from the start of the first slice.
+/// # Example
+///
+/// ```
+/// use bitconv::{endian::Big, ChainReader};
+///
+/// let (head, tail) = ([0, 0, 1], [0, 7]);
+/// let segments = [&head[..], &tail[..]];
+/// let mut reader = ChainReader::<Big>::new(&segments);
+/// assert_eq!(Ok(256), reader.read_uint32());
+/// assert_eq!(Ok(7), reader.read_uint8());
+/// assert!(reader.read_uint8().is_err());
+/// ```
+pub struct ChainReader<'a, E> {
+    segments: &'a [&'a [u8]],
+    segment: usize,
+    offset: usize,
+    position: usize,
+    len: usize,
+    endian: PhantomData<E>,
+}
+
+macro_rules! ReadExactImpl {
+    ($($read:ident $type:ty),+) => {$(
+        #[doc = concat!("Reads a `", stringify!($type), "` and advances past it.")]
+        pub fn $read(&mut self) -> Result<$type> {
+            let mut bytes = [0; mem::size_of::<$type>()];
+            self.read_exact(&mut bytes)?;
+            <$type>::from_bytes::<E>(&bytes, 0).map(|(value, _)| value)
+        }
+    )+};
+}
+
+impl<'a, E: BitConvEndian> ChainReader<'a, E> {
+    /// Creates a reader positioned at the start of the first segment.
+    pub fn new(segments: &'a [&'a [u8]]) -> Self {
+        let len = segments.iter().map(|segment| segment.len()).sum();
+        ChainReader { segments, segment: 0, offset: 0, position: 0, len, endian: PhantomData }
+    }
+
+    /// Returns the index of the next byte to be read, counting from the start
+    /// of the first segment.
+    pub fn position(&self) -> usize { self.position }
+
+    /// Returns the number of bytes left across all segments.
+    pub fn remaining(&self) -> usize { self.len - self.position }
+
+    /// Fills buf with the next bytes and advances past them.
+    ///
+    /// Errors without advancing if fewer than `buf.len()` bytes remain.
+    pub fn read_exact(&mut self, buf: &mut [u8]) -> Result<()> {
+        if buf.len() > self.remaining() {
+            return Err(Error::new(ErrorKind::OutOfBounds {
+                start_index: self.position,
+                requested: buf.len(),
+                len: self.len,
+            }));
+        }
+        let mut filled = 0;
+        while filled < buf.len() {
+            let segment = &self.segments[self.segment][self.offset..];
+            let n = segment.len().min(buf.len() - filled);
+            buf[filled..filled + n].copy_from_slice(&segment[..n]);
+            filled += n;
+            self.offset += n;
+            if self.offset == self.segments[self.segment].len() {
+                self.segment += 1;
+                self.offset = 0;
+            }
+        }
+        self.position += buf.len();
+        Ok(())
+    }
+
+    ReadExactImpl!(
+        read_int8 i8,
+        read_int16 i16,
+        read_int32 i32,
+        read_int64 i64,
+        read_uint8 u8,
+        read_uint16 u16,
+        read_uint32 u32,
+        read_uint64 u64
+    );
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::endian::*;
+
+    #[test]
+    fn chain_reader_test_le() {
+        let buffer = [15, 0, 0, 128, 16, 39, 240, 216, 241, 255, 127];
+        let segments = [&buffer[..1], &[][..], &buffer[1..3], &buffer[3..4], &buffer[4..]];
+        let mut reader = ChainReader::<Little>::new(&segments);
+        assert_eq!(11, reader.remaining());
+        assert_eq!(Ok(2147483663), reader.read_uint32());
+        assert_eq!(Ok(10000), reader.read_int16());
+        assert_eq!(Ok(-10000), reader.read_int16());
+        assert_eq!(8, reader.position());
+        assert_eq!(
+            Err(Error::new(ErrorKind::OutOfBounds { start_index: 8, requested: 8, len: 11 })),
+            reader.read_uint64()
+        );
+        assert_eq!(Ok(-15), reader.read_int8());
+        assert_eq!(Ok(255), reader.read_uint8());
+        assert_eq!(Ok(127), reader.read_int8());
+        assert_eq!(0, reader.remaining());
+        assert!(reader.read_uint8().is_err());
+    }
+
+    #[test]
+    fn chain_reader_test_be() {
+        let buffer = [15, 0, 0, 128, 16, 39, 240, 216, 241, 255, 127];
+        let segments = [&buffer[..3], &buffer[3..]];
+        let mut reader = ChainReader::<Big>::new(&segments);
+        assert_eq!(Ok(1080864460595785944), reader.read_uint64());
+        let mut bytes = [0; 3];
+        assert_eq!(Ok(()), reader.read_exact(&mut bytes));
+        assert_eq!([241, 255, 127], bytes);
+        assert!(ChainReader::<Big>::new(&[]).read_int32().is_err());
+        assert_eq!(Ok(()), ChainReader::<Big>::new(&[]).read_exact(&mut []));
+    }
+}
@@ -0,0 +1,269 @@
+//! The 128-bit decimal type of .NET, `System.Decimal`.
+use crate::{BitConvEndian, Error, ErrorKind, FixedSize, FromBytes, Result, ToBytes};
+use core::{fmt, str};
+
+/// Raw parts of a .NET `System.Decimal`, a 96-bit unsigned integer scaled
+/// down by a power of ten between 0 and 28, along with a sign.
+///
+/// The parts are kept as .NET lays them out in memory and in files, so a value
+/// read from bytes writes back unchanged, including negative zero and the
+/// trailing zeros of a value such as `1.50`.
+///
+/// As bytes, a decimal is the four integers of
+/// [`to_bits`](Decimal::to_bits) one after another, which in little-endian
+/// is the layout of `BinaryWriter.Write(decimal)` and of decimals in memory.
+/// Converting bytes whose flags are not valid fails with
+/// `ErrorKind::InvalidEncoding`. With the `rust_decimal` feature, decimals
+/// convert to and from `rust_decimal::Decimal` for arithmetic.
+/// # Example
+///
+/// ```
+/// use bitconv::{endian::Little, Decimal, FromBytes};
+///
+/// // 1234.50m, as exported by a .NET service.
+/// let bytes = [0x3a, 0xe2, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 2, 0];
+/// let (price, _) = Decimal::from_bytes::<Little>(&bytes, 0).unwrap();
+/// assert_eq!("1234.50", price.to_string());
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Decimal {
+    lo: u32,
+    mid: u32,
+    hi: u32,
+    flags: u32,
+}
+
+const SIGN_MASK: u32 = 0x8000_0000;
+const SCALE_MASK: u32 = 0x00ff_0000;
+const SCALE_SHIFT: u32 = 16;
+
+impl Decimal {
+    /// Largest scale a decimal can have.
+    pub const MAX_SCALE: u32 = 28;
+
+    /// Creates a decimal from the 96 bits of its integer, its sign and its
+    /// scale, returning `None` if the scale is larger than
+    /// [`MAX_SCALE`](Decimal::MAX_SCALE).
+    /// # Example
+    ///
+    /// ```
+    /// use bitconv::Decimal;
+    ///
+    /// // -1.50
+    /// let value = Decimal::new(150, 0, 0, true, 2).unwrap();
+    /// assert_eq!((150, true, 2), (value.mantissa(), value.is_negative(), value.scale()));
+    /// assert!(Decimal::new(1, 0, 0, false, 29).is_none());
+    /// ```
+    pub const fn new(lo: u32, mid: u32, hi: u32, negative: bool, scale: u32) -> Option<Self> {
+        if scale > Decimal::MAX_SCALE {
+            return None;
+        }
+        let sign = if negative { SIGN_MASK } else { 0 };
+        Some(Decimal { lo, mid, hi, flags: sign | scale << SCALE_SHIFT })
+    }
+
+    /// Creates a decimal from the four integers returned by .NET's
+    /// `decimal.GetBits`, in the order lo, mid, hi and flags, returning `None`
+    /// if the flags are not valid, as `new decimal(int[])` would throw.
+    pub const fn from_bits(bits: [i32; 4]) -> Option<Self> {
+        let flags = bits[3] as u32;
+        if flags & !(SIGN_MASK | SCALE_MASK) != 0 || (flags & SCALE_MASK) >> SCALE_SHIFT > 28 {
+            return None;
+        }
+        Some(Decimal { lo: bits[0] as u32, mid: bits[1] as u32, hi: bits[2] as u32, flags })
+    }
+
+    /// Returns the four integers of the decimal in the order of .NET's
+    /// `decimal.GetBits`.
+    pub const fn to_bits(self) -> [i32; 4] {
+        [self.lo as i32, self.mid as i32, self.hi as i32, self.flags as i32]
+    }
+
+    /// Returns the 96-bit unsigned integer of the decimal.
+    pub const fn mantissa(self) -> u128 {
+        (self.hi as u128) << 64 | (self.mid as u128) << 32 | self.lo as u128
+    }
+
+    /// Returns the power of ten the mantissa is divided by.
+    pub const fn scale(self) -> u32 { (self.flags & SCALE_MASK) >> SCALE_SHIFT }
+
+    /// Returns whether the sign bit is set, which it can be for zero as well.
+    pub const fn is_negative(self) -> bool { self.flags & SIGN_MASK != 0 }
+}
+
+impl FromBytes for Decimal {
+    #[inline]
+    fn from_bytes<T: BitConvEndian>(data: &[u8], start_index: usize) -> Result<(Self, usize)> {
+        let ((lo, mid, hi, flags), read) =
+            <(i32, i32, i32, i32)>::from_bytes::<T>(data, start_index)?;
+        match Decimal::from_bits([lo, mid, hi, flags]) {
+            Some(value) => Ok((value, read)),
+            None => Err(Error::new(ErrorKind::InvalidEncoding { start_index })),
+        }
+    }
+}
+
+impl ToBytes for Decimal {
+    #[inline]
+    fn to_bytes<T: BitConvEndian>(&self, data: &mut [u8], start_index: usize) -> Result<usize> {
+        let [lo, mid, hi, flags] = self.to_bits();
+        let len = data.len();
+        data.get_mut(start_index..)
+            .and_then(|data| data.get_mut(..Decimal::SIZE))
+            .ok_or_else(|| {
+                Error::new(ErrorKind::OutOfBounds { start_index, requested: Decimal::SIZE, len })
+            })
+            .and_then(|data| (lo, mid, hi, flags).to_bytes::<T>(data, 0))
+    }
+
+    #[inline]
+    fn byte_len(&self) -> usize { Decimal::SIZE }
+}
+
+impl FixedSize for Decimal {
+    const SIZE: usize = 16;
+}
+
+/// Displays the decimal as .NET's `ToString` does with the invariant culture,
+/// keeping trailing zeros, such as `-1.50`.
+impl fmt::Display for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // 29 digits for the mantissa, or the zero before the point and the 28
+        // digits after it for the largest scale, and the point.
+        let mut buffer = [b'0'; 30];
+        let mut mantissa = self.mantissa();
+        let scale = self.scale() as usize;
+        let mut start = buffer.len();
+        let mut digits = 0;
+        while mantissa != 0 || digits <= scale {
+            if digits == scale && scale != 0 {
+                start -= 1;
+                buffer[start] = b'.';
+            }
+            start -= 1;
+            buffer[start] = b'0' + (mantissa % 10) as u8;
+            mantissa /= 10;
+            digits += 1;
+        }
+        let digits = str::from_utf8(&buffer[start..]).map_err(|_| fmt::Error)?;
+        let negative = self.is_negative() && self.mantissa() != 0;
+        f.pad_integral(!negative, "", digits)
+    }
+}
+
+#[cfg(feature = "rust_decimal")]
+impl From<Decimal> for ::rust_decimal::Decimal {
+    /// Converts the parts of a decimal, with negative zero becoming zero.
+    fn from(value: Decimal) -> Self {
+        ::rust_decimal::Decimal::from_parts(
+            value.lo,
+            value.mid,
+            value.hi,
+            value.is_negative(),
+            value.scale(),
+        )
+    }
+}
+
+#[cfg(feature = "rust_decimal")]
+impl From<::rust_decimal::Decimal> for Decimal {
+    fn from(value: ::rust_decimal::Decimal) -> Self {
+        let mantissa = value.mantissa().unsigned_abs();
+        Decimal {
+            lo: mantissa as u32,
+            mid: (mantissa >> 32) as u32,
+            hi: (mantissa >> 64) as u32,
+            flags: if value.is_sign_negative() { SIGN_MASK } else { 0 }
+                | value.scale() << SCALE_SHIFT,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    extern crate alloc;
+
+    use super::*;
+    use crate::endian::*;
+    use alloc::{format, string::ToString};
+
+    #[test]
+    fn decimal_test() {
+        let value = Decimal::new(u32::MAX, 1, 2, false, 28).unwrap();
+        assert_eq!(0x2_0000_0001_ffff_ffff, value.mantissa());
+        assert_eq!((28, false), (value.scale(), value.is_negative()));
+        assert_eq!([-1, 1, 2, 28 << 16], value.to_bits());
+        assert_eq!(Some(value), Decimal::from_bits(value.to_bits()));
+        assert_eq!(Decimal::new(15, 0, 0, true, 1), Decimal::from_bits([15, 0, 0, -2147418112]));
+        assert_eq!(None, Decimal::from_bits([0, 0, 0, 29 << 16]));
+        assert_eq!(None, Decimal::from_bits([0, 0, 0, 1]));
+        assert_eq!(None, Decimal::from_bits([0, 0, 0, 1 << 24]));
+        assert_eq!(None, Decimal::new(0, 0, 0, false, 29));
+        assert_eq!(Decimal::default(), Decimal::new(0, 0, 0, false, 0).unwrap());
+    }
+
+    #[test]
+    fn decimal_bytes_test() {
+        let mut buffer = [0; 20];
+        let value = Decimal::new(15, 2, 3, true, 1).unwrap();
+        assert_eq!(Ok(16), value.to_bytes::<Little>(&mut buffer, 4));
+        assert_eq!([15, 0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0, 0, 0, 1, 128], buffer[4..]);
+        assert_eq!(Ok((value, 16)), Decimal::from_bytes::<Little>(&buffer, 4));
+        assert_eq!(Ok(16), value.to_bytes::<Big>(&mut buffer, 0));
+        assert_eq!([0, 0, 0, 15, 0, 0, 0, 2, 0, 0, 0, 3, 128, 1, 0, 0], buffer[..16]);
+        assert_eq!(Ok((value, 16)), Decimal::from_bytes::<Big>(&buffer, 0));
+        assert_eq!(
+            Err(Error::new(ErrorKind::InvalidEncoding { start_index: 1 })),
+            Decimal::from_bytes::<Big>(&buffer, 1)
+        );
+        assert_eq!(
+            Err(Error::new(ErrorKind::OutOfBounds { start_index: 5, requested: 16, len: 20 })),
+            value.to_bytes::<Little>(&mut buffer, 5)
+        );
+        assert_eq!([0, 0, 0, 15, 0], buffer[..5]);
+        assert!(Decimal::from_bytes::<Little>(&buffer, 5).is_err());
+        assert_eq!(16, value.byte_len());
+    }
+
+    #[test]
+    fn decimal_display_test() {
+        let cases = [
+            (Decimal::new(0, 0, 0, false, 0), "0"),
+            (Decimal::new(0, 0, 0, true, 0), "0"),
+            (Decimal::new(0, 0, 0, false, 2), "0.00"),
+            (Decimal::new(150, 0, 0, true, 2), "-1.50"),
+            (Decimal::new(5, 0, 0, false, 3), "0.005"),
+            (Decimal::new(123450, 0, 0, false, 1), "12345.0"),
+            (Decimal::new(u32::MAX, u32::MAX, u32::MAX, false, 0), "79228162514264337593543950335"),
+            (
+                Decimal::new(u32::MAX, u32::MAX, u32::MAX, true, 28),
+                "-7.9228162514264337593543950335",
+            ),
+            (Decimal::new(1, 0, 0, false, 28), "0.0000000000000000000000000001"),
+        ];
+        for (value, text) in cases {
+            assert_eq!(text, value.unwrap().to_string());
+        }
+        assert_eq!("   -42", format!("{:>6}", Decimal::new(42, 0, 0, true, 0).unwrap()));
+        assert_eq!("       1.5", format!("{:>10}", Decimal::new(15, 0, 0, false, 1).unwrap()));
+        assert_eq!("-1.50*", format!("{:*<6}", Decimal::new(150, 0, 0, true, 2).unwrap()));
+        assert_eq!("-001.50", format!("{:07}", Decimal::new(150, 0, 0, true, 2).unwrap()));
+    }
+
+    #[cfg(feature = "rust_decimal")]
+    #[test]
+    fn rust_decimal_test() {
+        use core::str::FromStr;
+
+        let value = Decimal::new(150, 0, 0, true, 2).unwrap();
+        let converted = ::rust_decimal::Decimal::from(value);
+        assert_eq!(::rust_decimal::Decimal::from_str("-1.50").unwrap(), converted);
+        assert_eq!(2, converted.scale());
+        assert_eq!(value, Decimal::from(converted));
+        let max = ::rust_decimal::Decimal::MAX;
+        assert_eq!(Decimal::new(u32::MAX, u32::MAX, u32::MAX, false, 0).unwrap(), max.into());
+        let zero = ::rust_decimal::Decimal::from(Decimal::new(0, 0, 0, true, 3).unwrap());
+        assert!(!zero.is_sign_negative());
+    }
+}
@@ -0,0 +1,132 @@
+//! Push-based decoding of values from bytes arriving in chunks.
+use crate::{BitConvEndian, ErrorKind, FromBytes, Result};
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+
+/// Outcome of a call to [`Decoder::decode`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Decoded<V> {
+    /// A complete value, whose bytes have been removed from the decoder.
+    Value(V),
+    /// At least this many more bytes must be fed before a value can be
+    /// decoded.
+    NeedMoreData(usize),
+}
+
+/// Decoder that is fed bytes as they arrive and hands out values of type `V`
+/// converted in the endianness `E` once enough of them have been buffered.
+///
+/// The decoder performs no IO of its own, so it fits any event loop or async
+/// runtime: feed it whatever chunk was just received, then call
+/// [`decode`](Decoder::decode) until it asks for more data.
+/// # Example
+///
+/// ```
+/// use bitconv::{
+///     decoder::{Decoded, Decoder}, endian::Little
+/// };
+///
+/// let mut decoder = Decoder::<(u8, u32), Little>::new();
+/// decoder.feed(&[1, 64]);
+/// assert_eq!(Ok(Decoded::NeedMoreData(3)), decoder.decode());
+/// decoder.feed(&[66, 15, 0, 2]);
+/// assert_eq!(Ok(Decoded::Value((1, 1000000))), decoder.decode());
+/// assert_eq!(Ok(Decoded::NeedMoreData(4)), decoder.decode());
+/// ```
+pub struct Decoder<V, E> {
+    buffer: Vec<u8>,
+    marker: PhantomData<(V, E)>,
+}
+
+impl<V: FromBytes, E: BitConvEndian> Decoder<V, E> {
+    pub fn new() -> Self { Decoder { buffer: Vec::new(), marker: PhantomData } }
+
+    /// Appends chunk to the bytes waiting to be decoded.
+    pub fn feed(&mut self, chunk: &[u8]) { self.buffer.extend_from_slice(chunk); }
+
+    /// Returns the number of bytes fed but not yet decoded.
+    pub fn buffered(&self) -> usize { self.buffer.len() }
+
+    /// Decodes the next value from the buffered bytes.
+    ///
+    /// The number of bytes returned by `Decoded::NeedMoreData` is a lower
+    /// bound: a value whose length depends on its contents, such as one with a
+    /// count-prefixed field, may ask for more again once those bytes have
+    /// been fed. Failures other than running out of bytes are returned as
+    /// errors and leave the buffered bytes in place.
+    pub fn decode(&mut self) -> Result<Decoded<V>> {
+        match V::from_bytes::<E>(&self.buffer, 0) {
+            Ok((value, read)) => {
+                self.buffer.drain(..read);
+                Ok(Decoded::Value(value))
+            }
+            Err(error) => match error.kind() {
+                ErrorKind::OutOfBounds { start_index, requested, len } => {
+                    Ok(Decoded::NeedMoreData(
+                        start_index.saturating_add(requested).saturating_sub(len).max(1),
+                    ))
+                }
+                _ => Err(error),
+            },
+        }
+    }
+}
+
+impl<V: FromBytes, E: BitConvEndian> Default for Decoder<V, E> {
+    fn default() -> Self { Decoder::new() }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{endian::*, Error};
+
+    #[test]
+    fn decoder_test_le() {
+        let mut decoder = Decoder::<(u16, i64), Little>::new();
+        assert_eq!(Ok(Decoded::NeedMoreData(2)), decoder.decode());
+        decoder.feed(&[15]);
+        assert_eq!(Ok(Decoded::NeedMoreData(1)), decoder.decode());
+        decoder.feed(&[0, 240, 216]);
+        assert_eq!(Ok(Decoded::NeedMoreData(6)), decoder.decode());
+        assert_eq!(4, decoder.buffered());
+        decoder.feed(&[241, 255, 255, 255, 255, 255, 1, 0, 0]);
+        assert_eq!(Ok(Decoded::Value((15, -927504))), decoder.decode());
+        assert_eq!(3, decoder.buffered());
+        assert_eq!(Ok(Decoded::NeedMoreData(7)), decoder.decode());
+        decoder.feed(&[0; 7]);
+        assert_eq!(Ok(Decoded::Value((1, 0))), decoder.decode());
+        assert_eq!(0, decoder.buffered());
+    }
+
+    #[test]
+    fn decoder_test_be() {
+        let mut decoder = Decoder::<u32, Big>::default();
+        decoder.feed(&[0, 0, 1, 0, 0, 0, 0, 2]);
+        assert_eq!(Ok(Decoded::Value(256)), decoder.decode());
+        assert_eq!(Ok(Decoded::Value(2)), decoder.decode());
+        assert_eq!(Ok(Decoded::NeedMoreData(4)), decoder.decode());
+    }
+
+    struct Tagged(u8);
+
+    impl FromBytes for Tagged {
+        fn from_bytes<T: BitConvEndian>(data: &[u8], start_index: usize) -> Result<(Self, usize)> {
+            match u8::from_bytes::<T>(data, start_index)? {
+                (0, _) => Err(Error::new(ErrorKind::InvalidTag { start_index })),
+                (tag, read) => Ok((Tagged(tag), read)),
+            }
+        }
+    }
+
+    #[test]
+    fn decoder_error_test() {
+        let mut decoder = Decoder::<Tagged, Native>::new();
+        decoder.feed(&[3, 0]);
+        assert!(matches!(decoder.decode(), Ok(Decoded::Value(Tagged(3)))));
+        let error = decoder.decode().map(|_| ()).unwrap_err();
+        assert_eq!(ErrorKind::InvalidTag { start_index: 0 }, error.kind());
+        assert_eq!(1, decoder.buffered());
+    }
+}
@@ -0,0 +1,167 @@
+//! Conversions from the bytes of a `VecDeque`.
+use crate::{endian::Endian, error, BitConvEndian, Error, ErrorKind, Result};
+use alloc::collections::VecDeque;
+use core::mem;
+
+macro_rules! DequeImpl {
+    ($($(#[$attr:meta])* $name:ident $try_name:ident $type:ty),+) => {$(
+        #[doc = concat!(
+            "Returns a `", stringify!($type), "` converted from the bytes at a specified ",
+            "position in a `VecDeque`, reading across the gap between its two halves ",
+            "without making it contiguous.\n",
+            "# Panics\n\n",
+            "Panics if the deque does not hold enough bytes from start_index. See [`",
+            stringify!($try_name), "`] for a version that returns an error instead."
+        )]
+        $(#[$attr])*
+        #[inline]
+        #[track_caller]
+        pub fn $name<T: BitConvEndian>(data: &VecDeque<u8>, start_index: usize) -> $type {
+            match $try_name::<T>(data, start_index) {
+                Ok(value) => value,
+                Err(error) => error::conversion_failed("read", stringify!($type), error),
+            }
+        }
+
+        #[doc = concat!(
+            "Returns a `", stringify!($type), "` converted from the bytes at a specified ",
+            "position in a `VecDeque`, or an `ErrorKind::OutOfBounds` error if the deque does ",
+            "not hold enough bytes from start_index.\n\n",
+            "This is the fallible counterpart of [`", stringify!($name), "`]."
+        )]
+        #[inline]
+        #[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
+        pub fn $try_name<T: BitConvEndian>(
+            data: &VecDeque<u8>,
+            start_index: usize,
+        ) -> Result<$type> {
+            let mut bytes = [0; mem::size_of::<$type>()];
+            let (front, back) = data.as_slices();
+            match start_index.checked_add(bytes.len()) {
+                Some(end) if end <= data.len() => {}
+                _ => {
+                    return Err(Error::new(ErrorKind::OutOfBounds {
+                        start_index,
+                        requested: bytes.len(),
+                        len: data.len(),
+                    }))
+                }
+            }
+            for (i, byte) in bytes.iter_mut().enumerate() {
+                let index = start_index + i;
+                *byte = match front.get(index) {
+                    Some(byte) => *byte,
+                    None => back.get(index - front.len()).copied().unwrap_or_default(),
+                };
+            }
+            Ok(match T::ENDIANNESS {
+                Endian::LE => <$type>::from_le_bytes(bytes),
+                Endian::BE => <$type>::from_be_bytes(bytes),
+                Endian::NE => <$type>::from_ne_bytes(bytes),
+            })
+        }
+    )+};
+}
+
+DequeImpl!(
+    to_int16_deque try_to_int16_deque i16,
+    to_int32_deque try_to_int32_deque i32,
+    to_int64_deque try_to_int64_deque i64,
+    to_uint16_deque try_to_uint16_deque u16,
+    /// # Example
+    ///
+    /// ```
+    /// use bitconv::{endian::Big, to_uint32_deque};
+    /// use std::collections::VecDeque;
+    ///
+    /// // Pushing to the front wraps around the buffer, splitting the deque in two.
+    /// let mut received = VecDeque::from(vec![1, 0]);
+    /// received.push_front(0);
+    /// received.push_front(0);
+    /// assert!(!received.as_slices().1.is_empty());
+    /// assert_eq!(256, to_uint32_deque::<Big>(&received, 0));
+    /// ```
+    to_uint32_deque try_to_uint32_deque u32,
+    to_uint64_deque try_to_uint64_deque u64
+);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{endian::*, to_int16, to_int32, to_int64, to_uint16, to_uint32, to_uint64};
+    use alloc::vec::Vec;
+
+    fn split_deque(buffer: &[u8], at: usize) -> VecDeque<u8> {
+        let mut deque = VecDeque::with_capacity(buffer.len());
+        deque.extend(&buffer[at..]);
+        for byte in buffer[..at].iter().rev() {
+            deque.push_front(*byte);
+        }
+        deque
+    }
+
+    #[test]
+    fn to_deque_test() {
+        let buffer = [15, 0, 0, 128, 16, 39, 240, 216, 241, 255, 127];
+        for at in 0..buffer.len() {
+            let deque = split_deque(&buffer, at);
+            assert_eq!(buffer[..], deque.iter().copied().collect::<Vec<_>>()[..]);
+            for start_index in 0..buffer.len() - 1 {
+                assert_eq!(
+                    to_int16::<Little>(&buffer, start_index),
+                    to_int16_deque::<Little>(&deque, start_index)
+                );
+                assert_eq!(
+                    to_uint16::<Big>(&buffer, start_index),
+                    to_uint16_deque::<Big>(&deque, start_index)
+                );
+            }
+            for start_index in 0..buffer.len() - 3 {
+                assert_eq!(
+                    to_int32::<Big>(&buffer, start_index),
+                    to_int32_deque::<Big>(&deque, start_index)
+                );
+                assert_eq!(
+                    to_uint32::<Little>(&buffer, start_index),
+                    to_uint32_deque::<Little>(&deque, start_index)
+                );
+            }
+            for start_index in 0..buffer.len() - 7 {
+                assert_eq!(
+                    to_int64::<Little>(&buffer, start_index),
+                    to_int64_deque::<Little>(&deque, start_index)
+                );
+                assert_eq!(
+                    to_uint64::<Native>(&buffer, start_index),
+                    to_uint64_deque::<Native>(&deque, start_index)
+                );
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn to_deque_panic_test() {
+        let deque = split_deque(&[15, 0, 0, 128, 16], 2);
+        to_uint32_deque::<Little>(&deque, 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "Failed to read u16: 2 bytes requested at index")]
+    fn to_deque_overflow_panic_test() { to_uint16_deque::<Little>(&VecDeque::new(), usize::MAX); }
+
+    #[test]
+    fn try_to_deque_test() {
+        let deque = split_deque(&[15, 0, 0, 128, 16], 2);
+        assert_eq!(Ok(-2147483633), try_to_int32_deque::<Little>(&deque, 0));
+        assert_eq!(Ok(0x8010), try_to_uint16_deque::<Big>(&deque, 3));
+        assert_eq!(
+            ErrorKind::OutOfBounds { start_index: 2, requested: 4, len: 5 },
+            try_to_uint32_deque::<Little>(&deque, 2).unwrap_err().kind()
+        );
+        assert_eq!(
+            ErrorKind::OutOfBounds { start_index: usize::MAX, requested: 8, len: 5 },
+            try_to_int64_deque::<Native>(&deque, usize::MAX).unwrap_err().kind()
+        );
+    }
+}
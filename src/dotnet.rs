@@ -0,0 +1,100 @@
+//! Aliases named after the members of C#'s `BitConverter`, for porting C#
+//! code mechanically before renaming its calls to the functions they stand
+//! for.
+//!
+//! Importing the module as `BitConverter` keeps call sites as they were in
+//! C#, but for the endianness, which is a type parameter as everywhere else in
+//! the crate. `Native` is the one `BitConverter` itself uses.
+//! # Example
+//!
+//! ```
+//! # #[cfg(feature = "alloc")] {
+//! use bitconv::{dotnet as BitConverter, endian::Native};
+//!
+//! let buffer = BitConverter::GetBytes::<Native, _>(999999);
+//! assert_eq!(999999, BitConverter::ToInt32::<Native>(&buffer, 0));
+//! assert_eq!(1.5, BitConverter::Int64BitsToDouble(BitConverter::DoubleToInt64Bits(1.5)));
+//! # }
+//! ```
+#![allow(non_snake_case)]
+
+#[cfg(feature = "alloc")]
+pub use crate::to_hex_string as ToString;
+use crate::{int32_bits_to_single, int64_bits_to_double, to_int32, to_int64, BitConvEndian};
+#[cfg(feature = "alloc")]
+use crate::{ToBytes, Writer};
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+pub use crate::{
+    double_to_int64_bits as DoubleToInt64Bits, int32_bits_to_single as Int32BitsToSingle, int64_bits_to_double as Int64BitsToDouble, single_to_int32_bits as SingleToInt32Bits, to_boolean as ToBoolean, to_char as ToChar, to_int16 as ToInt16, to_int32 as ToInt32, to_int64 as ToInt64, to_uint16 as ToUInt16, to_uint32 as ToUInt32, to_uint64 as ToUInt64, try_write_bytes as TryWriteBytes
+};
+
+/// Returns a single-precision floating-point number converted from four bytes
+/// at a specified position in a byte array, as `BitConverter.ToSingle` does.
+/// # Panics
+///
+/// Panics if data is shorter than four bytes from start_index.
+#[inline]
+#[track_caller]
+pub fn ToSingle<T: BitConvEndian>(data: &[u8], start_index: usize) -> f32 {
+    int32_bits_to_single(to_int32::<T>(data, start_index))
+}
+
+/// Returns a double-precision floating-point number converted from eight
+/// bytes at a specified position in a byte array, as `BitConverter.ToDouble`
+/// does.
+/// # Panics
+///
+/// Panics if data is shorter than eight bytes from start_index.
+#[inline]
+#[track_caller]
+pub fn ToDouble<T: BitConvEndian>(data: &[u8], start_index: usize) -> f64 {
+    int64_bits_to_double(to_int64::<T>(data, start_index))
+}
+
+/// Returns the bytes of a value as a new vector, as `BitConverter.GetBytes`
+/// does.
+///
+/// The value can be any [`ToBytes`] type, which covers every primitive of
+/// `GetBytes` but `char`.
+#[cfg(feature = "alloc")]
+#[track_caller]
+pub fn GetBytes<T: BitConvEndian, V: ToBytes>(value: V) -> Vec<u8> {
+    let mut writer = Writer::<T>::with_capacity(value.byte_len());
+    writer.write(&value);
+    writer.into_inner()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::endian::*;
+
+    #[test]
+    fn dotnet_names_test() {
+        let buffer = [15, 0, 0, 128, 16, 39, 240, 216, 241, 255, 127, 1];
+        assert_eq!(15, ToInt16::<Little>(&buffer, 0));
+        assert_eq!(-2146424848, ToInt32::<Big>(&buffer, 3));
+        assert_eq!(61479, ToUInt16::<Little>(&buffer, 5));
+        assert_eq!(2147483663, ToUInt32::<Little>(&buffer, 0));
+        assert_eq!(ToUInt64::<Big>(&buffer, 1) as i64, ToInt64::<Big>(&buffer, 1));
+        assert!(ToBoolean(&buffer, 11));
+        assert_eq!('\u{0f}', ToChar::<Little>(&buffer, 0));
+        assert_eq!(1.5, ToSingle::<Big>(&[63, 192, 0, 0], 0));
+        assert_eq!(-2.0, ToDouble::<Little>(&[0, 0, 0, 0, 0, 0, 0, 192], 0));
+        assert_eq!(-1, SingleToInt32Bits(Int32BitsToSingle(-1)));
+        let mut destination = [0; 2];
+        assert!(TryWriteBytes::<Big, _>(&mut destination, 258u16));
+        assert_eq!([1, 2], destination);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn get_bytes_test() {
+        assert_eq!([192, 0, 0, 0], GetBytes::<Big, _>(-2.0f32)[..]);
+        assert_eq!([15, 0, 0, 128], GetBytes::<Little, _>(2147483663u32)[..]);
+        assert_eq!([1], GetBytes::<Native, _>(true)[..]);
+        assert_eq!("0F-00-00-80", ToString(&GetBytes::<Little, _>(2147483663u32)));
+    }
+}
@@ -0,0 +1,199 @@
+//! Adapters converting values to and from the blocking streams of the
+//! `embedded-io` crate, such as UART and SPI drivers, without needing `std`.
+//!
+//! These mirror `EndianReader` and `EndianWriter` of the `std` feature.
+//! Without an allocator to convert into, only the integer primitives and
+//! byte arrays are supported.
+use crate::{endian::Endian, BitConvEndian};
+use core::{marker::PhantomData, mem, result::Result};
+use embedded_io::{Read, ReadExactError, Write};
+
+/// Adapter converting values in the endianness `E` from the bytes of any
+/// `embedded_io` [`Read`] source.
+///
+/// A source that ends early fails with `ReadExactError::UnexpectedEof`.
+/// # Example
+///
+/// ```
+/// use bitconv::{embedded::EndianReader, endian::Little};
+///
+/// let uart: &[u8] = &[1, 0, 0, 1, 7];
+/// let mut reader = EndianReader::<_, Little>::new(uart);
+/// assert_eq!(Ok(16777217), reader.read_uint32());
+/// assert_eq!(Ok(7), reader.read_uint8());
+/// assert!(reader.read_uint8().is_err());
+/// ```
+pub struct EndianReader<R, E> {
+    inner: R,
+    endian: PhantomData<E>,
+}
+
+macro_rules! EmbeddedReadImpl {
+    ($($read:ident $type:ty),+) => {$(
+        #[doc = concat!("Reads a `", stringify!($type), "` from the underlying reader.")]
+        pub fn $read(&mut self) -> Result<$type, ReadExactError<R::Error>> {
+            let mut bytes = [0; mem::size_of::<$type>()];
+            self.inner.read_exact(&mut bytes)?;
+            Ok(match E::ENDIANNESS {
+                Endian::LE => <$type>::from_le_bytes(bytes),
+                Endian::BE => <$type>::from_be_bytes(bytes),
+                Endian::NE => <$type>::from_ne_bytes(bytes),
+            })
+        }
+    )+};
+}
+
+impl<R: Read, E: BitConvEndian> EndianReader<R, E> {
+    pub fn new(inner: R) -> Self { EndianReader { inner, endian: PhantomData } }
+
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R { &self.inner }
+
+    /// Returns a mutable reference to the underlying reader.
+    pub fn get_mut(&mut self) -> &mut R { &mut self.inner }
+
+    /// Returns the underlying reader.
+    pub fn into_inner(self) -> R { self.inner }
+
+    /// Fills buf with the next bytes of the underlying reader.
+    pub fn read_exact(&mut self, buf: &mut [u8]) -> Result<(), ReadExactError<R::Error>> {
+        self.inner.read_exact(buf)
+    }
+
+    /// Reads the next N bytes of the underlying reader into an array.
+    pub fn read_array<const N: usize>(&mut self) -> Result<[u8; N], ReadExactError<R::Error>> {
+        let mut bytes = [0; N];
+        self.inner.read_exact(&mut bytes)?;
+        Ok(bytes)
+    }
+
+    EmbeddedReadImpl!(
+        read_int8 i8,
+        read_int16 i16,
+        read_int32 i32,
+        read_int64 i64,
+        read_uint8 u8,
+        read_uint16 u16,
+        read_uint32 u32,
+        read_uint64 u64
+    );
+}
+
+/// Adapter writing values in the endianness `E` to any `embedded_io`
+/// [`Write`] sink.
+/// # Example
+///
+/// ```
+/// use bitconv::{embedded::EndianWriter, endian::Big};
+///
+/// let mut frame = [0; 6];
+/// let mut writer = EndianWriter::<_, Big>::new(&mut frame[..]);
+/// writer.write_uint16(0xcafe).unwrap();
+/// writer.write_uint32(1).unwrap();
+/// assert!(writer.write_uint8(0).is_err());
+/// assert_eq!([0xca, 0xfe, 0, 0, 0, 1], frame);
+/// ```
+pub struct EndianWriter<W, E> {
+    inner: W,
+    endian: PhantomData<E>,
+}
+
+macro_rules! EmbeddedWriteImpl {
+    ($($write:ident $type:ty),+) => {$(
+        #[doc = concat!("Writes a `", stringify!($type), "` to the underlying writer.")]
+        pub fn $write(&mut self, value: $type) -> Result<(), W::Error> {
+            let bytes = match E::ENDIANNESS {
+                Endian::LE => value.to_le_bytes(),
+                Endian::BE => value.to_be_bytes(),
+                Endian::NE => value.to_ne_bytes(),
+            };
+            self.inner.write_all(&bytes)
+        }
+    )+};
+}
+
+impl<W: Write, E: BitConvEndian> EndianWriter<W, E> {
+    pub fn new(inner: W) -> Self { EndianWriter { inner, endian: PhantomData } }
+
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W { &self.inner }
+
+    /// Returns a mutable reference to the underlying writer.
+    pub fn get_mut(&mut self) -> &mut W { &mut self.inner }
+
+    /// Returns the underlying writer.
+    pub fn into_inner(self) -> W { self.inner }
+
+    /// Writes raw bytes to the underlying writer.
+    pub fn write_bytes(&mut self, bytes: &[u8]) -> Result<(), W::Error> {
+        self.inner.write_all(bytes)
+    }
+
+    /// Flushes the underlying writer.
+    pub fn flush(&mut self) -> Result<(), W::Error> { self.inner.flush() }
+
+    EmbeddedWriteImpl!(
+        write_int8 i8,
+        write_int16 i16,
+        write_int32 i32,
+        write_int64 i64,
+        write_uint8 u8,
+        write_uint16 u16,
+        write_uint32 u32,
+        write_uint64 u64
+    );
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::endian::*;
+
+    #[test]
+    fn endian_reader_test() {
+        let buffer = [15, 0, 0, 128, 16, 39, 240, 216, 241, 255, 127];
+        let mut reader = EndianReader::<_, Little>::new(&buffer[..]);
+        assert_eq!(Ok(2147483663), reader.read_uint32());
+        assert_eq!(Ok(10000), reader.read_int16());
+        assert_eq!(Ok(-10000), reader.read_int16());
+        assert_eq!(Ok(-15), reader.read_int8());
+        assert_eq!(Ok([255]), reader.read_array());
+        assert_eq!(Err(ReadExactError::UnexpectedEof), reader.read_uint16());
+        let mut reader = EndianReader::<_, Big>::new(&buffer[..]);
+        assert_eq!(Ok(1080864460595785944), reader.read_uint64());
+        assert_eq!(Ok(241), reader.read_uint8());
+        let mut bytes = [0; 2];
+        assert_eq!(Ok(()), reader.read_exact(&mut bytes));
+        assert_eq!([255, 127], bytes);
+        assert!(reader.get_ref().is_empty());
+        let mut reader = EndianReader::<_, Big>::new(&buffer[..]);
+        assert_eq!(Ok(251658368), reader.read_int32());
+    }
+
+    #[test]
+    fn endian_writer_test() {
+        let mut buffer = [0; 11];
+        let mut writer = EndianWriter::<_, Little>::new(&mut buffer[..]);
+        assert_eq!(Ok(()), writer.write_uint32(2147483663));
+        assert_eq!(Ok(()), writer.write_int16(10000));
+        assert_eq!(Ok(()), writer.write_int16(-10000));
+        assert_eq!(Ok(()), writer.write_int8(-15));
+        assert_eq!(Ok(()), writer.write_uint8(255));
+        assert_eq!(Ok(()), writer.write_bytes(&[127]));
+        assert_eq!(Ok(()), writer.flush());
+        assert!(writer.write_uint8(0).is_err());
+        assert_eq!([15, 0, 0, 128, 16, 39, 240, 216, 241, 255, 127], buffer);
+        let mut buffer = [0; 22];
+        let mut writer = EndianWriter::<_, Big>::new(&mut buffer[..]);
+        assert_eq!(Ok(()), writer.write_int64(-2));
+        assert_eq!(Ok(()), writer.write_uint64(1));
+        assert_eq!(Ok(()), writer.write_int32(-3));
+        assert_eq!(Ok(()), writer.write_uint16(4));
+        assert!(writer.into_inner().is_empty());
+        let mut reader = EndianReader::<_, Big>::new(&buffer[..]);
+        assert_eq!(Ok(-2), reader.read_int64());
+        assert_eq!(Ok(1), reader.read_uint64());
+        assert_eq!(Ok(-3), reader.read_int32());
+        assert_eq!(Ok(4), reader.read_uint16());
+    }
+}
@@ -0,0 +1,225 @@
+//! Adapters converting values to and from the asynchronous streams of the
+//! `embedded-io-async` crate, such as the drivers of embedded executors,
+//! without needing `std`.
+//!
+//! These mirror the blocking adapters of [`embedded`](crate::embedded), with
+//! every conversion being an `async` method instead.
+use crate::{endian::Endian, BitConvEndian};
+use core::{marker::PhantomData, mem, result::Result};
+use embedded_io_async::{Read, ReadExactError, Write};
+
+/// Adapter converting values in the endianness `E` from the bytes of any
+/// `embedded_io_async` [`Read`] source.
+///
+/// A source that ends early fails with `ReadExactError::UnexpectedEof`.
+/// # Example
+///
+/// ```
+/// use bitconv::{embedded_async::EndianReader, endian::Little};
+/// use embedded_io_async::{Read, ReadExactError};
+///
+/// async fn read_frame<R: Read>(uart: R) -> Result<(u8, u32), ReadExactError<R::Error>> {
+///     let mut reader = EndianReader::<_, Little>::new(uart);
+///     Ok((reader.read_uint8().await?, reader.read_uint32().await?))
+/// }
+/// ```
+pub struct EndianReader<R, E> {
+    inner: R,
+    endian: PhantomData<E>,
+}
+
+macro_rules! EmbeddedAsyncReadImpl {
+    ($($read:ident $type:ty),+) => {$(
+        #[doc = concat!("Reads a `", stringify!($type), "` from the underlying reader.")]
+        pub async fn $read(&mut self) -> Result<$type, ReadExactError<R::Error>> {
+            let mut bytes = [0; mem::size_of::<$type>()];
+            self.inner.read_exact(&mut bytes).await?;
+            Ok(match E::ENDIANNESS {
+                Endian::LE => <$type>::from_le_bytes(bytes),
+                Endian::BE => <$type>::from_be_bytes(bytes),
+                Endian::NE => <$type>::from_ne_bytes(bytes),
+            })
+        }
+    )+};
+}
+
+impl<R: Read, E: BitConvEndian> EndianReader<R, E> {
+    pub fn new(inner: R) -> Self { EndianReader { inner, endian: PhantomData } }
+
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R { &self.inner }
+
+    /// Returns a mutable reference to the underlying reader.
+    pub fn get_mut(&mut self) -> &mut R { &mut self.inner }
+
+    /// Returns the underlying reader.
+    pub fn into_inner(self) -> R { self.inner }
+
+    /// Fills buf with the next bytes of the underlying reader.
+    pub async fn read_exact(&mut self, buf: &mut [u8]) -> Result<(), ReadExactError<R::Error>> {
+        self.inner.read_exact(buf).await
+    }
+
+    /// Reads the next N bytes of the underlying reader into an array.
+    pub async fn read_array<const N: usize>(
+        &mut self,
+    ) -> Result<[u8; N], ReadExactError<R::Error>> {
+        let mut bytes = [0; N];
+        self.inner.read_exact(&mut bytes).await?;
+        Ok(bytes)
+    }
+
+    EmbeddedAsyncReadImpl!(
+        read_int8 i8,
+        read_int16 i16,
+        read_int32 i32,
+        read_int64 i64,
+        read_uint8 u8,
+        read_uint16 u16,
+        read_uint32 u32,
+        read_uint64 u64
+    );
+}
+
+/// Adapter writing values in the endianness `E` to any `embedded_io_async`
+/// [`Write`] sink.
+/// # Example
+///
+/// ```
+/// use bitconv::{embedded_async::EndianWriter, endian::Big};
+/// use embedded_io_async::Write;
+///
+/// async fn write_frame<W: Write>(spi: W, payload: &[u8]) -> Result<(), W::Error> {
+///     let mut writer = EndianWriter::<_, Big>::new(spi);
+///     writer.write_uint16(0xcafe).await?;
+///     writer.write_bytes(payload).await?;
+///     writer.flush().await
+/// }
+/// ```
+pub struct EndianWriter<W, E> {
+    inner: W,
+    endian: PhantomData<E>,
+}
+
+macro_rules! EmbeddedAsyncWriteImpl {
+    ($($write:ident $type:ty),+) => {$(
+        #[doc = concat!("Writes a `", stringify!($type), "` to the underlying writer.")]
+        pub async fn $write(&mut self, value: $type) -> Result<(), W::Error> {
+            let bytes = match E::ENDIANNESS {
+                Endian::LE => value.to_le_bytes(),
+                Endian::BE => value.to_be_bytes(),
+                Endian::NE => value.to_ne_bytes(),
+            };
+            self.inner.write_all(&bytes).await
+        }
+    )+};
+}
+
+impl<W: Write, E: BitConvEndian> EndianWriter<W, E> {
+    pub fn new(inner: W) -> Self { EndianWriter { inner, endian: PhantomData } }
+
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W { &self.inner }
+
+    /// Returns a mutable reference to the underlying writer.
+    pub fn get_mut(&mut self) -> &mut W { &mut self.inner }
+
+    /// Returns the underlying writer.
+    pub fn into_inner(self) -> W { self.inner }
+
+    /// Writes raw bytes to the underlying writer.
+    pub async fn write_bytes(&mut self, bytes: &[u8]) -> Result<(), W::Error> {
+        self.inner.write_all(bytes).await
+    }
+
+    /// Flushes the underlying writer.
+    pub async fn flush(&mut self) -> Result<(), W::Error> { self.inner.flush().await }
+
+    EmbeddedAsyncWriteImpl!(
+        write_int8 i8,
+        write_int16 i16,
+        write_int32 i32,
+        write_int64 i64,
+        write_uint8 u8,
+        write_uint16 u16,
+        write_uint32 u32,
+        write_uint64 u64
+    );
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::endian::*;
+    use core::{
+        future::Future, pin::pin, task::{Context, Poll, RawWaker, RawWakerVTable, Waker}
+    };
+
+    const VTABLE: RawWakerVTable =
+        RawWakerVTable::new(|_| RawWaker::new(core::ptr::null(), &VTABLE), |_| {}, |_| {}, |_| {});
+
+    /// Drives a future over in-memory streams, which never have to wait.
+    fn block_on<F: Future>(future: F) -> F::Output {
+        // Safety: the vtable functions do nothing, so any data pointer is
+        // valid for them.
+        let waker = unsafe { Waker::from_raw(RawWaker::new(core::ptr::null(), &VTABLE)) };
+        let mut future = pin!(future);
+        loop {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut Context::from_waker(&waker)) {
+                return output;
+            }
+        }
+    }
+
+    #[test]
+    fn endian_reader_test() {
+        block_on(async {
+            let buffer = [15, 0, 0, 128, 16, 39, 240, 216, 241, 255, 127];
+            let mut reader = EndianReader::<_, Little>::new(&buffer[..]);
+            assert_eq!(Ok(2147483663), reader.read_uint32().await);
+            assert_eq!(Ok(10000), reader.read_int16().await);
+            assert_eq!(Ok(-10000), reader.read_int16().await);
+            assert_eq!(Ok(-15), reader.read_int8().await);
+            assert_eq!(Ok([255]), reader.read_array().await);
+            assert_eq!(Err(ReadExactError::UnexpectedEof), reader.read_uint16().await);
+            let mut reader = EndianReader::<_, Big>::new(&buffer[..]);
+            assert_eq!(Ok(1080864460595785944), reader.read_uint64().await);
+            assert_eq!(Ok(241), reader.read_uint8().await);
+            let mut bytes = [0; 2];
+            assert_eq!(Ok(()), reader.read_exact(&mut bytes).await);
+            assert_eq!([255, 127], bytes);
+            assert!(reader.get_ref().is_empty());
+            let mut reader = EndianReader::<_, Big>::new(&buffer[..]);
+            assert_eq!(Ok(251658368), reader.read_int32().await);
+        });
+    }
+
+    #[test]
+    fn endian_writer_test() {
+        block_on(async {
+            let mut buffer = [0; 11];
+            let mut writer = EndianWriter::<_, Little>::new(&mut buffer[..]);
+            assert_eq!(Ok(()), writer.write_uint32(2147483663).await);
+            assert_eq!(Ok(()), writer.write_int16(10000).await);
+            assert_eq!(Ok(()), writer.write_int16(-10000).await);
+            assert_eq!(Ok(()), writer.write_int8(-15).await);
+            assert_eq!(Ok(()), writer.write_uint8(255).await);
+            assert_eq!(Ok(()), writer.write_bytes(&[127]).await);
+            assert_eq!(Ok(()), writer.flush().await);
+            assert!(writer.write_uint8(0).await.is_err());
+            assert_eq!([15, 0, 0, 128, 16, 39, 240, 216, 241, 255, 127], buffer);
+            let mut buffer = [0; 22];
+            let mut writer = EndianWriter::<_, Big>::new(&mut buffer[..]);
+            assert_eq!(Ok(()), writer.write_int64(-2).await);
+            assert_eq!(Ok(()), writer.write_uint64(1).await);
+            assert_eq!(Ok(()), writer.write_int32(-3).await);
+            assert_eq!(Ok(()), writer.write_uint16(4).await);
+            assert!(writer.into_inner().is_empty());
+            let mut reader = EndianReader::<_, Big>::new(&buffer[..]);
+            assert_eq!(Ok(-2), reader.read_int64().await);
+            assert_eq!(Ok(1), reader.read_uint64().await);
+            assert_eq!(Ok(-3), reader.read_int32().await);
+            assert_eq!(Ok(4), reader.read_uint16().await);
+        });
+    }
+}
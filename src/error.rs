@@ -0,0 +1,324 @@
+//! Error type returned by the fallible conversions.
+use core::fmt;
+
+/// Maximum number of segments an error [`Context`] keeps track of.
+pub const MAX_CONTEXT_DEPTH: usize = 5;
+
+/// Error returned when a sequence of bytes cannot be converted to a value.
+///
+/// Besides the [`ErrorKind`] describing what went wrong, an error carries the
+/// [`Context`] it happened in, such as `header.entries[3].length`, which
+/// composed reads fill in as the error travels back up to the caller.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Error {
+    kind: ErrorKind,
+    context: Context,
+}
+
+/// What went wrong in a failed conversion.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// The buffer does not hold the requested number of bytes at start_index.
+    OutOfBounds { start_index: usize, requested: usize, len: usize },
+    /// The discriminant at start_index does not select any variant.
+    InvalidTag { start_index: usize },
+    /// The element count of a collection starting at start_index is negative
+    /// or does not fit in a `usize`.
+    InvalidCount { start_index: usize },
+    /// A seek by offset from position would move before the start of the
+    /// buffer or past `usize::MAX`.
+    InvalidSeek { position: usize, offset: isize },
+    /// Parsing finished at start_index, leaving len bytes unread.
+    TrailingBytes { start_index: usize, len: usize },
+    /// A length of len bytes does not fit in the field at start_index.
+    LengthOverflow { start_index: usize, len: usize },
+    /// The offset stored at start_index points past the end of the buffer, or
+    /// ends a region before it starts.
+    InvalidOffset { start_index: usize },
+    /// The count or length len read for the value at start_index is larger
+    /// than the maximum of max allowed for it.
+    LimitExceeded { start_index: usize, len: usize, max: usize },
+    /// The bytes at start_index are not a valid encoding of the value, such
+    /// as malformed UTF-8 or a variable-length integer that runs too long.
+    InvalidEncoding { start_index: usize },
+    /// The checksum of the value at start_index does not match its contents.
+    ChecksumMismatch { start_index: usize },
+}
+
+impl Error {
+    pub fn new(kind: ErrorKind) -> Self { Error { kind, context: Context::new() } }
+
+    pub fn kind(&self) -> ErrorKind { self.kind }
+
+    pub fn context(&self) -> &Context { &self.context }
+
+    /// Records that the error happened while converting the field `name`.
+    pub fn with_field(mut self, name: &'static str) -> Self {
+        self.context.push(Segment::Field(name));
+        self
+    }
+
+    /// Records that the error happened while converting the element at
+    /// `index` of a collection.
+    pub fn with_index(mut self, index: usize) -> Self {
+        self.context.push(Segment::Index(index));
+        self
+    }
+}
+
+/// Panics with a message naming what failed to convert and, for a buffer
+/// that is too short, where the bytes were requested.
+///
+/// Kept out of line so the conversions calling it stay small enough to be
+/// inlined, and reported at the location of the conversion's caller.
+#[cold]
+#[inline(never)]
+#[track_caller]
+pub(crate) fn conversion_failed(action: &str, what: &str, error: Error) -> ! {
+    panic!("Failed to {} {}: {}.", action, what, error)
+}
+
+/// Displays the kind of the error, preceded by its context when there is one,
+/// such as `header.entries[3]: invalid tag at index 40`.
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.context.is_empty() {
+            true => write!(f, "{}", self.kind),
+            false => write!(f, "{}: {}", self.context, self.kind),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for Error {}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for Error {
+    fn format(&self, f: defmt::Formatter<'_>) {
+        match self.context.is_empty() {
+            true => defmt::write!(f, "{}", self.kind),
+            false => defmt::write!(f, "{}: {}", self.context, self.kind),
+        }
+    }
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            ErrorKind::OutOfBounds { start_index, requested, len } => write!(
+                f,
+                "{} bytes requested at index {} of a buffer of {} bytes",
+                requested, start_index, len
+            ),
+            ErrorKind::InvalidTag { start_index } => {
+                write!(f, "invalid tag at index {}", start_index)
+            }
+            ErrorKind::InvalidCount { start_index } => {
+                write!(f, "invalid count at index {}", start_index)
+            }
+            ErrorKind::InvalidSeek { position, offset } => {
+                write!(f, "invalid seek by {} from position {}", offset, position)
+            }
+            ErrorKind::TrailingBytes { start_index, len } => {
+                write!(f, "{} trailing bytes at index {}", len, start_index)
+            }
+            ErrorKind::LengthOverflow { start_index, len } => write!(
+                f,
+                "length of {} bytes does not fit in the field at index {}",
+                len, start_index
+            ),
+            ErrorKind::InvalidOffset { start_index } => {
+                write!(f, "invalid offset at index {}", start_index)
+            }
+            ErrorKind::LimitExceeded { start_index, len, max } => write!(
+                f,
+                "length of {} at index {} exceeds the maximum of {}",
+                len, start_index, max
+            ),
+            ErrorKind::InvalidEncoding { start_index } => {
+                write!(f, "invalid encoding at index {}", start_index)
+            }
+            ErrorKind::ChecksumMismatch { start_index } => {
+                write!(f, "checksum mismatch at index {}", start_index)
+            }
+        }
+    }
+}
+
+/// A single step of the path to a failed conversion.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum Segment {
+    Field(&'static str),
+    Index(usize),
+}
+
+/// Path from the outermost value being converted to the one that failed.
+///
+/// Up to [`MAX_CONTEXT_DEPTH`] segments are kept. When a path is deeper than
+/// that, the outermost segments are dropped and the path is displayed with a
+/// leading `...`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Context {
+    // Stored innermost first, in the order the segments are pushed.
+    segments: [Segment; MAX_CONTEXT_DEPTH],
+    len: u8,
+    truncated: bool,
+}
+
+impl Context {
+    const fn new() -> Self {
+        Context { segments: [Segment::Index(0); MAX_CONTEXT_DEPTH], len: 0, truncated: false }
+    }
+
+    fn push(&mut self, segment: Segment) {
+        match self.segments.get_mut(usize::from(self.len)) {
+            Some(slot) => {
+                *slot = segment;
+                self.len += 1;
+            }
+            None => self.truncated = true,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool { self.len == 0 }
+
+    /// Whether outer segments were dropped because the path was too deep.
+    pub fn is_truncated(&self) -> bool { self.truncated }
+
+    /// Returns the segments of the path, from the outermost to the innermost.
+    pub fn iter(&self) -> impl Iterator<Item = Segment> + '_ {
+        self.segments[..usize::from(self.len)].iter().rev().copied()
+    }
+}
+
+impl fmt::Display for Context {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut first = true;
+        if self.truncated {
+            f.write_str("...")?;
+        }
+        for segment in self.iter() {
+            match segment {
+                Segment::Field(name) if first => f.write_str(name)?,
+                Segment::Field(name) => write!(f, ".{}", name)?,
+                Segment::Index(index) => write!(f, "[{}]", index)?,
+            }
+            first = false;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for Context {
+    fn format(&self, f: defmt::Formatter<'_>) {
+        let mut first = true;
+        if self.truncated {
+            defmt::write!(f, "...");
+        }
+        for segment in self.iter() {
+            match segment {
+                Segment::Field(name) if first => defmt::write!(f, "{=str}", name),
+                Segment::Field(name) => defmt::write!(f, ".{=str}", name),
+                Segment::Index(index) => defmt::write!(f, "[{=usize}]", index),
+            }
+            first = false;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    extern crate alloc;
+
+    use super::*;
+    use alloc::string::ToString;
+
+    fn out_of_bounds() -> Error {
+        Error::new(ErrorKind::OutOfBounds { start_index: 4, requested: 4, len: 6 })
+    }
+
+    #[test]
+    fn context_test() {
+        assert!(out_of_bounds().context().is_empty());
+        assert_eq!("", out_of_bounds().context().to_string());
+        let error = out_of_bounds().with_field("length").with_index(3).with_field("entries");
+        let error = error.with_field("header");
+        assert_eq!(ErrorKind::OutOfBounds { start_index: 4, requested: 4, len: 6 }, error.kind());
+        assert!(!error.context().is_truncated());
+        assert_eq!("header.entries[3].length", error.context().to_string());
+        let mut segments = error.context().iter();
+        assert_eq!(Some(Segment::Field("header")), segments.next());
+        assert_eq!(Some(Segment::Field("entries")), segments.next());
+        assert_eq!(Some(Segment::Index(3)), segments.next());
+        assert_eq!(Some(Segment::Field("length")), segments.next());
+        assert_eq!(None, segments.next());
+        assert_eq!("[0][1]", out_of_bounds().with_index(1).with_index(0).context().to_string());
+    }
+
+    #[test]
+    fn display_test() {
+        assert_eq!(
+            "4 bytes requested at index 4 of a buffer of 6 bytes",
+            out_of_bounds().to_string()
+        );
+        let error = Error::new(ErrorKind::InvalidTag { start_index: 40 });
+        let error = error.with_index(3).with_field("entries").with_field("header");
+        assert_eq!("header.entries[3]: invalid tag at index 40", error.to_string());
+        let kinds = [
+            (ErrorKind::InvalidCount { start_index: 1 }, "invalid count at index 1"),
+            (
+                ErrorKind::InvalidSeek { position: 5, offset: -6 },
+                "invalid seek by -6 from position 5",
+            ),
+            (ErrorKind::TrailingBytes { start_index: 2, len: 3 }, "3 trailing bytes at index 2"),
+            (
+                ErrorKind::LengthOverflow { start_index: 1, len: 256 },
+                "length of 256 bytes does not fit in the field at index 1",
+            ),
+            (ErrorKind::InvalidOffset { start_index: 7 }, "invalid offset at index 7"),
+            (
+                ErrorKind::LimitExceeded { start_index: 4, len: 5, max: 4 },
+                "length of 5 at index 4 exceeds the maximum of 4",
+            ),
+            (ErrorKind::InvalidEncoding { start_index: 3 }, "invalid encoding at index 3"),
+            (ErrorKind::ChecksumMismatch { start_index: 0 }, "checksum mismatch at index 0"),
+        ];
+        for (kind, message) in kinds {
+            assert_eq!(message, Error::new(kind).to_string());
+        }
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn std_error_test() {
+        fn parse() -> Result<u8, std::boxed::Box<dyn std::error::Error>> {
+            Ok(crate::try_to_uint16::<crate::endian::Little>(&[1], 0)? as u8)
+        }
+        assert_eq!(
+            "2 bytes requested at index 0 of a buffer of 1 bytes",
+            parse().unwrap_err().to_string()
+        );
+    }
+
+    #[test]
+    fn error_size_test() {
+        // Keeps `Result<T, Error>` below the size at which clippy's
+        // `result_large_err` lint fires in callers.
+        assert!(core::mem::size_of::<Error>() < 128);
+    }
+
+    #[test]
+    fn context_truncated_test() {
+        let error = (0..MAX_CONTEXT_DEPTH).fold(out_of_bounds(), Error::with_index);
+        assert!(!error.context().is_truncated());
+        assert_eq!("[4][3][2][1][0]", error.context().to_string());
+        let error = error.with_field("outer").with_field("outermost");
+        assert!(error.context().is_truncated());
+        assert_eq!("...[4][3][2][1][0]", error.context().to_string());
+        assert_eq!(MAX_CONTEXT_DEPTH, error.context().iter().count());
+    }
+}
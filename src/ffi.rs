@@ -0,0 +1,192 @@
+//! C ABI for the conversions, for sharing them with C and C++ code.
+//!
+//! The functions take a buffer as a pointer and a length, and report the
+//! outcome as a [`BitconvStatus`] rather than through a `Result`. A header can
+//! be generated with `cbindgen`, whose configuration is at the root of the
+//! repository.
+use crate::{
+    endian::{Big, Little}, FromBytes, ToBytes
+};
+use core::slice;
+
+/// Outcome of a conversion through the C ABI.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BitconvStatus {
+    /// The conversion succeeded.
+    Ok = 0,
+    /// The buffer was null but not empty, or the output pointer was null.
+    NullPointer = 1,
+    /// The value does not fit in the buffer at the offset, as reported by
+    /// `ErrorKind::OutOfBounds`.
+    OutOfBounds = 2,
+}
+
+macro_rules! FfiImpl {
+    ($($to:ident $write:ident $type:ty $(as $bits:ty)?, $endian:ty;)+) => {$(
+        #[doc = concat!(
+            "Reads a `", stringify!($type), "` from the bytes at off in the buffer of len bytes ",
+            "at ptr into out, leaving out untouched on failure."
+        )]
+        ///
+        /// # Safety
+        ///
+        /// ptr must point to len readable bytes, or be null with len 0, and out
+        /// must be null or point to a writable value, which may be unaligned.
+        #[no_mangle]
+        #[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
+        pub unsafe extern "C" fn $to(
+            ptr: *const u8,
+            len: usize,
+            off: usize,
+            out: *mut $type,
+        ) -> BitconvStatus {
+            if (ptr.is_null() && len != 0) || out.is_null() {
+                return BitconvStatus::NullPointer;
+            }
+            let data = match ptr.is_null() {
+                true => &[],
+                false => slice::from_raw_parts(ptr, len),
+            };
+            FfiImpl!(@read data, off, out, $type $(as $bits)?, $endian)
+        }
+
+        #[doc = concat!(
+            "Writes a `", stringify!($type), "` to the bytes at off in the buffer of len bytes ",
+            "at ptr, leaving the buffer untouched on failure."
+        )]
+        ///
+        /// # Safety
+        ///
+        /// ptr must point to len writable bytes, or be null with len 0.
+        #[no_mangle]
+        #[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
+        pub unsafe extern "C" fn $write(
+            ptr: *mut u8,
+            len: usize,
+            off: usize,
+            value: $type,
+        ) -> BitconvStatus {
+            if ptr.is_null() && len != 0 {
+                return BitconvStatus::NullPointer;
+            }
+            let data = match ptr.is_null() {
+                true => &mut [],
+                false => slice::from_raw_parts_mut(ptr, len),
+            };
+            FfiImpl!(@write data, off, value, $type $(as $bits)?, $endian)
+        }
+    )+};
+    (@read $data:ident, $off:ident, $out:ident, $type:ty, $endian:ty) => {
+        match <$type>::from_bytes::<$endian>($data, $off) {
+            Ok((value, _)) => {
+                $out.write_unaligned(value);
+                BitconvStatus::Ok
+            }
+            Err(_) => BitconvStatus::OutOfBounds,
+        }
+    };
+    (@read $data:ident, $off:ident, $out:ident, $type:ty as $bits:ty, $endian:ty) => {
+        match <$bits>::from_bytes::<$endian>($data, $off) {
+            Ok((bits, _)) => {
+                $out.write_unaligned(<$type>::from_bits(bits));
+                BitconvStatus::Ok
+            }
+            Err(_) => BitconvStatus::OutOfBounds,
+        }
+    };
+    (@write $data:ident, $off:ident, $value:ident, $type:ty $(as $bits:ty)?, $endian:ty) => {
+        match $value.to_bytes::<$endian>($data, $off) {
+            Ok(_) => BitconvStatus::Ok,
+            Err(_) => BitconvStatus::OutOfBounds,
+        }
+    };
+}
+
+FfiImpl!(
+    bitconv_to_u16_le bitconv_write_u16_le u16, Little;
+    bitconv_to_u16_be bitconv_write_u16_be u16, Big;
+    bitconv_to_i16_le bitconv_write_i16_le i16, Little;
+    bitconv_to_i16_be bitconv_write_i16_be i16, Big;
+    bitconv_to_u32_le bitconv_write_u32_le u32, Little;
+    bitconv_to_u32_be bitconv_write_u32_be u32, Big;
+    bitconv_to_i32_le bitconv_write_i32_le i32, Little;
+    bitconv_to_i32_be bitconv_write_i32_be i32, Big;
+    bitconv_to_u64_le bitconv_write_u64_le u64, Little;
+    bitconv_to_u64_be bitconv_write_u64_be u64, Big;
+    bitconv_to_i64_le bitconv_write_i64_le i64, Little;
+    bitconv_to_i64_be bitconv_write_i64_be i64, Big;
+    bitconv_to_f32_le bitconv_write_f32_le f32 as u32, Little;
+    bitconv_to_f32_be bitconv_write_f32_be f32 as u32, Big;
+    bitconv_to_f64_le bitconv_write_f64_le f64 as u64, Little;
+    bitconv_to_f64_be bitconv_write_f64_be f64 as u64, Big;
+);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use core::ptr;
+
+    #[test]
+    fn ffi_to_test() {
+        let buffer = [0xff, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08];
+        let mut value = 0u32;
+        unsafe {
+            assert_eq!(BitconvStatus::Ok, bitconv_to_u32_le(buffer.as_ptr(), 9, 1, &mut value));
+            assert_eq!(0x04030201, value);
+            assert_eq!(BitconvStatus::Ok, bitconv_to_u32_be(buffer.as_ptr(), 9, 5, &mut value));
+            assert_eq!(0x05060708, value);
+            assert_eq!(
+                BitconvStatus::OutOfBounds,
+                bitconv_to_u32_le(buffer.as_ptr(), 9, 6, &mut value)
+            );
+            assert_eq!(
+                BitconvStatus::OutOfBounds,
+                bitconv_to_u32_le(buffer.as_ptr(), 9, usize::MAX, &mut value)
+            );
+            assert_eq!(0x05060708, value);
+            assert_eq!(
+                BitconvStatus::NullPointer,
+                bitconv_to_u32_le(ptr::null(), 9, 0, &mut value)
+            );
+            assert_eq!(
+                BitconvStatus::OutOfBounds,
+                bitconv_to_u32_le(ptr::null(), 0, 0, &mut value)
+            );
+            assert_eq!(
+                BitconvStatus::NullPointer,
+                bitconv_to_u32_le(buffer.as_ptr(), 9, 0, ptr::null_mut())
+            );
+
+            let mut value = 0i16;
+            assert_eq!(BitconvStatus::Ok, bitconv_to_i16_be(buffer.as_ptr(), 9, 0, &mut value));
+            assert_eq!(-255, value);
+            let mut value = 0.0f64;
+            let bytes = (-2.5f64).to_be_bytes();
+            assert_eq!(BitconvStatus::Ok, bitconv_to_f64_be(bytes.as_ptr(), 8, 0, &mut value));
+            assert_eq!(-2.5, value);
+        }
+    }
+
+    #[test]
+    fn ffi_write_test() {
+        let mut buffer = [0u8; 8];
+        unsafe {
+            assert_eq!(
+                BitconvStatus::Ok,
+                bitconv_write_u32_le(buffer.as_mut_ptr(), 8, 1, 0x04030201)
+            );
+            assert_eq!(BitconvStatus::Ok, bitconv_write_i16_be(buffer.as_mut_ptr(), 8, 5, -2));
+            assert_eq!([0, 1, 2, 3, 4, 255, 254, 0], buffer);
+            assert_eq!(
+                BitconvStatus::OutOfBounds,
+                bitconv_write_u64_be(buffer.as_mut_ptr(), 8, 1, 0)
+            );
+            assert_eq!([0, 1, 2, 3, 4, 255, 254, 0], buffer);
+            assert_eq!(BitconvStatus::Ok, bitconv_write_f32_le(buffer.as_mut_ptr(), 8, 4, 1.0));
+            assert_eq!([0, 1, 2, 3, 0, 0, 0x80, 0x3f], buffer);
+            assert_eq!(BitconvStatus::NullPointer, bitconv_write_u16_le(ptr::null_mut(), 2, 0, 1));
+            assert_eq!(BitconvStatus::OutOfBounds, bitconv_write_u16_le(ptr::null_mut(), 0, 0, 1));
+        }
+    }
+}
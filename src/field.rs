@@ -0,0 +1,274 @@
+//! Bit ranges of integers that were already read, such as the fields and
+//! flags of a register after a call to [`to_uint32`](crate::to_uint32).
+//!
+//! Bits are numbered from the least significant one, so the range of len bits
+//! at start holds the value `(value >> start) & ((1 << len) - 1)`, which is
+//! how datasheets lay out register fields.
+
+/// Unsigned integer types whose bit ranges can be extracted and replaced.
+pub trait BitField: Copy {
+    /// The signed integer type of the same width, which sign-extended fields
+    /// are returned as.
+    type Signed;
+
+    /// The number of bits of the type.
+    const BITS: u32;
+
+    /// Returns the len bits of self from start as the low bits of the
+    /// result, as [`extract_bits`] does.
+    fn extract_bits(self, start: u32, len: u32) -> Self;
+
+    /// Returns the len bits of self from start sign-extended from their
+    /// highest bit, as [`extract_bits_signed`] does.
+    fn extract_bits_signed(self, start: u32, len: u32) -> Self::Signed;
+
+    /// Returns self with its len bits from start replaced by the low bits of
+    /// field, as [`insert_bits`] does.
+    fn insert_bits(self, start: u32, len: u32, field: Self) -> Self;
+}
+
+#[cold]
+#[track_caller]
+fn range_failed(start: u32, len: u32, bits: u32) -> ! {
+    panic!(
+        "Failed to access bits {} to {} of a {}-bit integer.",
+        start,
+        u64::from(start) + u64::from(len),
+        bits
+    )
+}
+
+macro_rules! BitFieldImpl {
+    ($($type:ty => $signed:ty),+) => {$(
+        impl BitField for $type {
+            type Signed = $signed;
+
+            const BITS: u32 = <$type>::BITS;
+
+            #[inline]
+            #[track_caller]
+            fn extract_bits(self, start: u32, len: u32) -> Self {
+                if len > Self::BITS || start > Self::BITS - len {
+                    range_failed(start, len, Self::BITS);
+                }
+                match len {
+                    0 => 0,
+                    _ => self >> start & <$type>::MAX >> (Self::BITS - len),
+                }
+            }
+
+            #[inline]
+            #[track_caller]
+            fn extract_bits_signed(self, start: u32, len: u32) -> Self::Signed {
+                let field = BitField::extract_bits(self, start, len);
+                match len {
+                    0 => 0,
+                    _ => (field << (Self::BITS - len)) as $signed >> (Self::BITS - len),
+                }
+            }
+
+            #[inline]
+            #[track_caller]
+            fn insert_bits(self, start: u32, len: u32, field: Self) -> Self {
+                if len > Self::BITS || start > Self::BITS - len {
+                    range_failed(start, len, Self::BITS);
+                }
+                match len {
+                    0 => self,
+                    _ => {
+                        let mask = <$type>::MAX >> (Self::BITS - len) << start;
+                        self & !mask | field << start & mask
+                    }
+                }
+            }
+        }
+    )+};
+}
+
+BitFieldImpl!(u8 => i8, u16 => i16, u32 => i32, u64 => i64, u128 => i128, usize => isize);
+
+/// Returns the len bits of value from start, counting from its least
+/// significant bit, as the low bits of the result.
+///
+/// A len of 0 returns 0.
+/// # Panics
+///
+/// Panics if the range runs past the highest bit of value.
+/// # Example
+///
+/// ```
+/// use bitconv::{endian::Little, extract_bits, to_uint32};
+///
+/// // The mode and prescaler fields of a timer control register.
+/// let register = to_uint32::<Little>(&[0x5a, 0x03, 0, 0x80], 0);
+/// assert_eq!(0b11, extract_bits(register, 3, 2));
+/// assert_eq!(0x35, extract_bits(register, 4, 8));
+/// assert_eq!(1, extract_bits(register, 31, 1));
+/// ```
+#[inline]
+#[track_caller]
+pub fn extract_bits<T: BitField>(value: T, start: u32, len: u32) -> T {
+    BitField::extract_bits(value, start, len)
+}
+
+/// Returns the len bits of value from start as a two's complement number,
+/// sign-extended from the highest bit of the range.
+///
+/// A len of 0 returns 0.
+/// # Panics
+///
+/// Panics if the range runs past the highest bit of value.
+/// # Example
+///
+/// ```
+/// use bitconv::extract_bits_signed;
+///
+/// // A 12-bit ADC reading in the middle of a 32-bit sample.
+/// let sample = 0x00ff_e0c0u32;
+/// assert_eq!(-500, extract_bits_signed(sample, 4, 12));
+/// assert_eq!(255, extract_bits_signed(sample, 16, 9));
+/// assert_eq!(-1, extract_bits_signed(sample, 16, 8));
+/// ```
+#[inline]
+#[track_caller]
+pub fn extract_bits_signed<T: BitField>(value: T, start: u32, len: u32) -> T::Signed {
+    BitField::extract_bits_signed(value, start, len)
+}
+
+/// Returns value with its len bits from start replaced by the len low bits of
+/// field, leaving the other bits as they are.
+///
+/// Bits of field above the lowest len are ignored, so a negative field can be
+/// inserted as its unsigned counterpart, such as `-3i8 as u8`.
+/// # Panics
+///
+/// Panics if the range runs past the highest bit of value.
+/// # Example
+///
+/// ```
+/// use bitconv::{extract_bits, insert_bits};
+///
+/// let register = insert_bits(0x8000_035au32, 4, 8, 0xc7);
+/// assert_eq!(0x8000_0c7a, register);
+/// assert_eq!(0xc7, extract_bits(register, 4, 8));
+/// assert_eq!(0x0f, insert_bits(0xffu8, 4, 4, 0x10));
+/// ```
+#[inline]
+#[track_caller]
+pub fn insert_bits<T: BitField>(value: T, start: u32, len: u32, field: T) -> T {
+    BitField::insert_bits(value, start, len, field)
+}
+
+macro_rules! SignExtendImpl {
+    ($($(#[$attr:meta])* $name:ident $type:ty => $signed:ty),+) => {$(
+        #[doc = concat!(
+            "Returns the bits low bits of a `", stringify!($type), "` as a two's complement ",
+            "number sign-extended from the highest of them, ignoring the bits above.\n\n",
+            "A bits of 0 returns 0, and a bits of ", stringify!($signed), "::BITS ",
+            "reinterprets value as it is.\n",
+            "# Panics\n\n",
+            "Panics if bits is larger than ", stringify!($signed), "::BITS."
+        )]
+        $(#[$attr])*
+        #[inline]
+        #[track_caller]
+        pub const fn $name(value: $type, bits: u32) -> $signed {
+            assert!(bits <= <$type>::BITS, "sign extension from more bits than the integer has");
+            match bits {
+                0 => 0,
+                _ => (value << (<$type>::BITS - bits)) as $signed >> (<$type>::BITS - bits),
+            }
+        }
+    )+};
+}
+
+SignExtendImpl!(
+    sign_extend8 u8 => i8,
+    sign_extend16 u16 => i16,
+    sign_extend32 u32 => i32,
+    /// # Example
+    ///
+    /// ```
+    /// use bitconv::{extract_bits, sign_extend};
+    ///
+    /// // A 12-bit ADC reading and a 20-bit branch offset.
+    /// assert_eq!(-500, sign_extend(0xe0c, 12));
+    /// assert_eq!(2047, sign_extend(0x7ff, 12));
+    /// assert_eq!(-4, sign_extend(extract_bits(0x8fff_ffc0u64, 4, 20), 20));
+    /// assert_eq!(-1, sign_extend(u64::MAX, 64));
+    /// ```
+    sign_extend u64 => i64,
+    sign_extend128 u128 => i128
+);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn extract_bits_test() {
+        assert_eq!(0b1011, extract_bits(0b1011_0110u8, 4, 4));
+        assert_eq!(0, extract_bits(0xffu8, 8, 0));
+        assert_eq!(0xff, extract_bits(0xffu8, 0, 8));
+        assert_eq!(u128::MAX >> 1, extract_bits(u128::MAX, 1, 127));
+        assert_eq!(0x1234, extract_bits(0x0123_4000u32, 12, 16));
+        assert_eq!(1, extract_bits(1usize << (usize::BITS - 1), usize::BITS - 1, 1));
+    }
+
+    #[test]
+    fn extract_bits_signed_test() {
+        assert_eq!(-1i8, extract_bits_signed(0b1100_0000u8, 6, 2));
+        assert_eq!(1, extract_bits_signed(0b0100_0000u8, 6, 2));
+        assert_eq!(-2048, extract_bits_signed(0x0080_0000u32, 12, 12));
+        assert_eq!(2047, extract_bits_signed(0x007f_f000u32, 12, 12));
+        assert_eq!(0, extract_bits_signed(u64::MAX, 64, 0));
+        assert_eq!(i64::MIN, extract_bits_signed(1u64 << 63, 0, 64));
+        assert_eq!(-1i16, extract_bits_signed(0x8000u16, 15, 1));
+    }
+
+    #[test]
+    fn insert_bits_test() {
+        assert_eq!(0b1010_0101, insert_bits(0b1111_0101u8, 4, 4, 0b1010));
+        assert_eq!(0x5a, insert_bits(0x5au8, 3, 0, 0xff));
+        assert_eq!(0xab, insert_bits(0x12u8, 0, 8, 0xab));
+        assert_eq!(0b0111_1111, insert_bits(0xffu8, 7, 1, 0));
+        assert_eq!(0x0000_0005_ffff_ffff, insert_bits(u64::MAX, 32, 32, 5));
+        assert_eq!(0xfd << 8, insert_bits(0u16, 8, 8, -3i8 as u8 as u16));
+        for start in 0..=32 {
+            for len in 0..=32 - start {
+                let value = insert_bits(0xdead_beefu32, start, len, 0x1234_5678);
+                assert_eq!(extract_bits(0x1234_5678, 0, len), extract_bits(value, start, len));
+            }
+        }
+    }
+
+    #[test]
+    fn sign_extend_test() {
+        assert_eq!(-1, sign_extend8(1, 1));
+        assert_eq!(0, sign_extend8(0xfe, 1));
+        assert_eq!(i8::MIN, sign_extend8(0x80, 8));
+        assert_eq!(0, sign_extend16(0xffff, 0));
+        assert_eq!(-0x8000, sign_extend16(0x8000, 16));
+        assert_eq!(0x3fff, sign_extend16(0xbfff, 15));
+        assert_eq!(-0x8_0000, sign_extend32(0xfff8_0000, 20));
+        assert_eq!(0x7_ffff, sign_extend32(0x0007_ffff, 20));
+        assert_eq!(-2, sign_extend(0b110, 3));
+        assert_eq!(i64::MAX, sign_extend(u64::MAX >> 1, 64));
+        assert_eq!(i64::MIN >> 1, sign_extend(1 << 62, 63));
+        assert_eq!(-1, sign_extend128(u128::MAX, 100));
+        const OFFSET: i32 = sign_extend32(0xf_ff00, 20);
+        assert_eq!(-256, OFFSET);
+    }
+
+    #[test]
+    #[should_panic]
+    fn sign_extend_range_test() { sign_extend(0, 65); }
+
+    #[test]
+    #[should_panic]
+    fn extract_bits_range_test() { extract_bits(0u32, 30, 3); }
+
+    #[test]
+    #[should_panic]
+    fn insert_bits_range_test() { insert_bits(0u8, 9, 0, 0); }
+}
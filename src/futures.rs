@@ -0,0 +1,268 @@
+//! Adapters converting values to and from the runtime-agnostic asynchronous
+//! streams of the `futures-io` crate, as used by smol and async-std.
+//!
+//! These mirror [`EndianReader`](crate::EndianReader) and
+//! [`EndianWriter`](crate::EndianWriter), with every conversion being an
+//! `async` method instead.
+use crate::{endian::Endian, BitConvEndian, ToBytes};
+use core::{future::poll_fn, marker::PhantomData, mem, pin::Pin};
+use futures_io::{AsyncRead, AsyncWrite};
+use std::{io, vec};
+
+/// Fills buf from reader, retrying reads that were interrupted.
+async fn read_exact<R: AsyncRead + Unpin>(reader: &mut R, mut buf: &mut [u8]) -> io::Result<()> {
+    while !buf.is_empty() {
+        match poll_fn(|cx| Pin::new(&mut *reader).poll_read(cx, buf)).await {
+            Ok(0) => return Err(io::ErrorKind::UnexpectedEof.into()),
+            Ok(read) => buf = &mut mem::take(&mut buf)[read..],
+            Err(error) if error.kind() == io::ErrorKind::Interrupted => {}
+            Err(error) => return Err(error),
+        }
+    }
+    Ok(())
+}
+
+/// Writes all of buf to writer, retrying writes that were interrupted.
+async fn write_all<W: AsyncWrite + Unpin>(writer: &mut W, mut buf: &[u8]) -> io::Result<()> {
+    while !buf.is_empty() {
+        match poll_fn(|cx| Pin::new(&mut *writer).poll_write(cx, buf)).await {
+            Ok(0) => return Err(io::ErrorKind::WriteZero.into()),
+            Ok(written) => buf = &buf[written..],
+            Err(error) if error.kind() == io::ErrorKind::Interrupted => {}
+            Err(error) => return Err(error),
+        }
+    }
+    Ok(())
+}
+
+/// Adapter converting values in the endianness `E` from the bytes of any
+/// [`AsyncRead`] source.
+///
+/// A source that ends early fails with `io::ErrorKind::UnexpectedEof`.
+/// # Example
+///
+/// ```
+/// use bitconv::{endian::Big, futures::EndianReader};
+/// use futures_io::AsyncRead;
+///
+/// async fn read_header<R: AsyncRead + Unpin>(stream: R) -> std::io::Result<(u32, u16)> {
+///     let mut reader = EndianReader::<_, Big>::new(stream);
+///     Ok((reader.read_uint32().await?, reader.read_uint16().await?))
+/// }
+/// ```
+pub struct EndianReader<R, E> {
+    inner: R,
+    endian: PhantomData<E>,
+}
+
+macro_rules! AsyncEndianReadImpl {
+    ($($read:ident $type:ty),+) => {$(
+        #[doc = concat!("Reads a `", stringify!($type), "` from the underlying reader.")]
+        pub async fn $read(&mut self) -> io::Result<$type> {
+            let mut bytes = [0; mem::size_of::<$type>()];
+            read_exact(&mut self.inner, &mut bytes).await?;
+            Ok(match E::ENDIANNESS {
+                Endian::LE => <$type>::from_le_bytes(bytes),
+                Endian::BE => <$type>::from_be_bytes(bytes),
+                Endian::NE => <$type>::from_ne_bytes(bytes),
+            })
+        }
+    )+};
+}
+
+impl<R: AsyncRead + Unpin, E: BitConvEndian> EndianReader<R, E> {
+    pub fn new(inner: R) -> Self { EndianReader { inner, endian: PhantomData } }
+
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R { &self.inner }
+
+    /// Returns a mutable reference to the underlying reader.
+    pub fn get_mut(&mut self) -> &mut R { &mut self.inner }
+
+    /// Returns the underlying reader.
+    pub fn into_inner(self) -> R { self.inner }
+
+    /// Fills buf with the next bytes of the underlying reader.
+    pub async fn read_exact(&mut self, buf: &mut [u8]) -> io::Result<()> {
+        read_exact(&mut self.inner, buf).await
+    }
+
+    /// Reads the next N bytes of the underlying reader into an array.
+    pub async fn read_array<const N: usize>(&mut self) -> io::Result<[u8; N]> {
+        let mut bytes = [0; N];
+        read_exact(&mut self.inner, &mut bytes).await?;
+        Ok(bytes)
+    }
+
+    AsyncEndianReadImpl!(
+        read_int8 i8,
+        read_int16 i16,
+        read_int32 i32,
+        read_int64 i64,
+        read_uint8 u8,
+        read_uint16 u16,
+        read_uint32 u32,
+        read_uint64 u64
+    );
+}
+
+/// Adapter writing values in the endianness `E` to any [`AsyncWrite`]
+/// sink.
+/// # Example
+///
+/// ```
+/// use bitconv::{endian::Little, futures::EndianWriter};
+/// use futures_io::AsyncWrite;
+///
+/// async fn write_header<W: AsyncWrite + Unpin>(sink: W) -> std::io::Result<()> {
+///     let mut writer = EndianWriter::<_, Little>::new(sink);
+///     writer.write_uint32(0x4d534721).await?;
+///     writer.write(&(1u8, 2u16)).await?;
+///     writer.flush().await
+/// }
+/// ```
+pub struct EndianWriter<W, E> {
+    inner: W,
+    endian: PhantomData<E>,
+}
+
+macro_rules! AsyncEndianWriteImpl {
+    ($($write:ident $type:ty),+) => {$(
+        #[doc = concat!("Writes a `", stringify!($type), "` to the underlying writer.")]
+        pub async fn $write(&mut self, value: $type) -> io::Result<()> {
+            let bytes = match E::ENDIANNESS {
+                Endian::LE => value.to_le_bytes(),
+                Endian::BE => value.to_be_bytes(),
+                Endian::NE => value.to_ne_bytes(),
+            };
+            write_all(&mut self.inner, &bytes).await
+        }
+    )+};
+}
+
+impl<W: AsyncWrite + Unpin, E: BitConvEndian> EndianWriter<W, E> {
+    pub fn new(inner: W) -> Self { EndianWriter { inner, endian: PhantomData } }
+
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W { &self.inner }
+
+    /// Returns a mutable reference to the underlying writer.
+    pub fn get_mut(&mut self) -> &mut W { &mut self.inner }
+
+    /// Returns the underlying writer.
+    pub fn into_inner(self) -> W { self.inner }
+
+    /// Writes a value, such as a tuple or a derived type, to the underlying
+    /// writer.
+    ///
+    /// The value is converted into a temporary buffer first, so nothing is
+    /// written if the conversion fails.
+    pub async fn write<V: ToBytes + ?Sized>(&mut self, value: &V) -> io::Result<()> {
+        let mut buffer = vec![0; value.byte_len()];
+        value
+            .to_bytes::<E>(&mut buffer, 0)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+        write_all(&mut self.inner, &buffer).await
+    }
+
+    /// Writes raw bytes to the underlying writer.
+    pub async fn write_bytes(&mut self, bytes: &[u8]) -> io::Result<()> {
+        write_all(&mut self.inner, bytes).await
+    }
+
+    /// Flushes the underlying writer.
+    pub async fn flush(&mut self) -> io::Result<()> {
+        poll_fn(|cx| Pin::new(&mut self.inner).poll_flush(cx)).await
+    }
+
+    AsyncEndianWriteImpl!(
+        write_int8 i8,
+        write_int16 i16,
+        write_int32 i32,
+        write_int64 i64,
+        write_uint8 u8,
+        write_uint16 u16,
+        write_uint32 u32,
+        write_uint64 u64
+    );
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::endian::*;
+    use core::{
+        future::Future, pin::Pin, task::{Context, Poll}
+    };
+    use std::{
+        boxed::Box, sync::Arc, task::{Wake, Waker}, vec::Vec
+    };
+
+    struct NoopWaker;
+
+    impl Wake for NoopWaker {
+        fn wake(self: Arc<Self>) {}
+    }
+
+    /// Drives a future over in-memory streams, which never have to wait.
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let waker = Waker::from(Arc::new(NoopWaker));
+        let mut future = Box::pin(future);
+        loop {
+            if let Poll::Ready(output) =
+                Pin::as_mut(&mut future).poll(&mut Context::from_waker(&waker))
+            {
+                return output;
+            }
+        }
+    }
+
+    #[test]
+    fn endian_reader_test() {
+        block_on(async {
+            let buffer = [15, 0, 0, 128, 16, 39, 240, 216, 241, 255, 127];
+            let mut reader = EndianReader::<_, Little>::new(&buffer[..]);
+            assert_eq!(2147483663, reader.read_uint32().await.unwrap());
+            assert_eq!(10000, reader.read_int16().await.unwrap());
+            assert_eq!(-10000, reader.read_int16().await.unwrap());
+            assert_eq!(-15, reader.read_int8().await.unwrap());
+            assert_eq!([255], reader.read_array().await.unwrap());
+            let error = reader.read_uint16().await.unwrap_err();
+            assert_eq!(io::ErrorKind::UnexpectedEof, error.kind());
+            let mut reader = EndianReader::<_, Big>::new(&buffer[..]);
+            assert_eq!(1080864460595785944, reader.read_uint64().await.unwrap());
+            assert_eq!(241, reader.read_uint8().await.unwrap());
+            let mut bytes = [0; 2];
+            reader.read_exact(&mut bytes).await.unwrap();
+            assert_eq!([255, 127], bytes);
+            assert!(reader.into_inner().is_empty());
+        });
+    }
+
+    #[test]
+    fn endian_writer_test() {
+        block_on(async {
+            let mut writer = EndianWriter::<_, Little>::new(Vec::new());
+            writer.write_uint32(2147483663).await.unwrap();
+            writer.write_int16(10000).await.unwrap();
+            writer.write(&(-10000i16, -15i8)).await.unwrap();
+            writer.write_uint8(255).await.unwrap();
+            writer.write_int8(127).await.unwrap();
+            writer.flush().await.unwrap();
+            assert_eq!([15, 0, 0, 128, 16, 39, 240, 216, 241, 255, 127], writer.get_ref()[..]);
+            let mut writer = EndianWriter::<_, Big>::new(Vec::new());
+            writer.write_int64(-2).await.unwrap();
+            writer.write_uint64(1).await.unwrap();
+            writer.write_int32(-3).await.unwrap();
+            writer.write_uint16(4).await.unwrap();
+            writer.write_bytes(&[5]).await.unwrap();
+            let bytes = writer.into_inner();
+            let mut reader = EndianReader::<_, Big>::new(&bytes[..]);
+            assert_eq!(-2, reader.read_int64().await.unwrap());
+            assert_eq!(1, reader.read_uint64().await.unwrap());
+            assert_eq!(-3, reader.read_int32().await.unwrap());
+            assert_eq!(4, reader.read_uint16().await.unwrap());
+            assert_eq!(5, reader.read_uint8().await.unwrap());
+        });
+    }
+}
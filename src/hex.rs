@@ -0,0 +1,529 @@
+//! Hexadecimal text for byte arrays, in the format of C#'s
+//! `BitConverter.ToString` by default, and its parsing back into bytes.
+use crate::{Error, ErrorKind, Result};
+use core::fmt;
+#[cfg(feature = "alloc")]
+use {
+    crate::error, alloc::{string::String, vec::Vec}
+};
+
+const UPPER: &[u8; 16] = b"0123456789ABCDEF";
+const LOWER: &[u8; 16] = b"0123456789abcdef";
+
+/// Layout of hexadecimal text: the separator between bytes and the case of
+/// the digits.
+///
+/// The default is that of C#'s `BitConverter.ToString`, two uppercase digits
+/// per byte separated by dashes, such as `0F-00-FF`. An empty separator gives
+/// the format of `Convert.ToHexString` instead.
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "alloc")] {
+/// use bitconv::HexFormat;
+///
+/// let format = HexFormat::new().separator(" ").lowercase(true);
+/// assert_eq!("0f 00 ff", format.format(&[15, 0, 255]));
+/// assert_eq!("0F00FF", HexFormat::new().separator("").format(&[15, 0, 255]));
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HexFormat {
+    separator: &'static str,
+    lowercase: bool,
+}
+
+impl HexFormat {
+    /// Creates the format of `BitConverter.ToString`.
+    pub const fn new() -> Self { HexFormat { separator: "-", lowercase: false } }
+
+    /// Sets the text written between bytes.
+    pub const fn separator(mut self, separator: &'static str) -> Self {
+        self.separator = separator;
+        self
+    }
+
+    /// Sets whether digits above 9 are written in lowercase.
+    pub const fn lowercase(mut self, lowercase: bool) -> Self {
+        self.lowercase = lowercase;
+        self
+    }
+
+    /// Writes the hexadecimal text of data to out, without allocating.
+    pub fn write<W: fmt::Write + ?Sized>(&self, out: &mut W, data: &[u8]) -> fmt::Result {
+        let digits = match self.lowercase {
+            true => LOWER,
+            false => UPPER,
+        };
+        for (index, &byte) in data.iter().enumerate() {
+            if index > 0 {
+                out.write_str(self.separator)?;
+            }
+            out.write_char(digits[usize::from(byte >> 4)].into())?;
+            out.write_char(digits[usize::from(byte & 0xf)].into())?;
+        }
+        Ok(())
+    }
+
+    /// Returns the hexadecimal text of data as a new string.
+    #[cfg(feature = "alloc")]
+    pub fn format(&self, data: &[u8]) -> String {
+        let len = data.len().saturating_mul(2 + self.separator.len());
+        let mut text = String::with_capacity(len.saturating_sub(self.separator.len()));
+        let _ = self.write(&mut text, data);
+        text
+    }
+}
+
+impl Default for HexFormat {
+    fn default() -> Self { HexFormat::new() }
+}
+
+/// Displays a byte array as hexadecimal text in the format of
+/// `BitConverter.ToString`, without allocating, for logging in `no_std` code.
+///
+/// `Debug` writes the same text, so it also reads well inside derived `Debug`
+/// output, and with the `defmt` feature so does `defmt::Format`.
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "alloc")] {
+/// use bitconv::Hex;
+///
+/// assert_eq!("frame 0F-00-FF", format!("frame {}", Hex(&[15, 0, 255])));
+/// assert_eq!("Some()", format!("{:?}", Some(Hex(&[]))));
+/// # }
+/// ```
+#[derive(Clone, Copy)]
+pub struct Hex<'a>(pub &'a [u8]);
+
+impl fmt::Display for Hex<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { HexFormat::new().write(f, self.0) }
+}
+
+impl fmt::Debug for Hex<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { fmt::Display::fmt(self, f) }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for Hex<'_> {
+    fn format(&self, f: defmt::Formatter<'_>) {
+        for (index, &byte) in self.0.iter().enumerate() {
+            match index {
+                0 => defmt::write!(f, "{=u8:02X}", byte),
+                _ => defmt::write!(f, "-{=u8:02X}", byte),
+            }
+        }
+    }
+}
+
+/// Displays at most the number of bytes in its second field of a byte array as
+/// [`Hex`] does, followed by `...` and the length of the array if it is
+/// longer, so large buffers keep log lines short.
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "alloc")] {
+/// use bitconv::HexLimited;
+///
+/// let data = [1, 2, 3, 4, 5];
+/// assert_eq!("01-02-... (5 bytes)", format!("{}", HexLimited(&data, 2)));
+/// assert_eq!("01-02-03-04-05", format!("{:?}", HexLimited(&data, 8)));
+/// # }
+/// ```
+#[derive(Clone, Copy)]
+pub struct HexLimited<'a>(pub &'a [u8], pub usize);
+
+impl HexLimited<'_> {
+    fn shown(&self) -> &[u8] { self.0.get(..self.1).unwrap_or(self.0) }
+}
+
+impl fmt::Display for HexLimited<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let shown = self.shown();
+        HexFormat::new().write(f, shown)?;
+        match (shown.len() < self.0.len(), shown.is_empty()) {
+            (false, _) => Ok(()),
+            (true, true) => write!(f, "... ({} bytes)", self.0.len()),
+            (true, false) => write!(f, "-... ({} bytes)", self.0.len()),
+        }
+    }
+}
+
+impl fmt::Debug for HexLimited<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { fmt::Display::fmt(self, f) }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for HexLimited<'_> {
+    fn format(&self, f: defmt::Formatter<'_>) {
+        let shown = self.shown();
+        defmt::write!(f, "{}", Hex(shown));
+        match (shown.len() < self.0.len(), shown.is_empty()) {
+            (false, _) => {}
+            (true, true) => defmt::write!(f, "... ({=usize} bytes)", self.0.len()),
+            (true, false) => defmt::write!(f, "-... ({=usize} bytes)", self.0.len()),
+        }
+    }
+}
+
+/// Returns the hexadecimal text of data as C#'s `BitConverter.ToString` does,
+/// as pairs of uppercase digits separated by dashes.
+///
+/// Logs written with it compare equal to those of the .NET code being
+/// ported. [`HexFormat`] changes the separator and the case.
+/// # Example
+///
+/// ```
+/// use bitconv::to_hex_string;
+///
+/// assert_eq!("0F-00-FF", to_hex_string(&[15, 0, 255]));
+/// assert_eq!("", to_hex_string(&[]));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn to_hex_string(data: &[u8]) -> String { HexFormat::new().format(data) }
+
+/// Returns the hexadecimal text of len bytes of data from start_index, as
+/// `BitConverter.ToString(value, startIndex, length)` does.
+/// # Panics
+///
+/// Panics if the range does not fit in data, where C# throws an
+/// `ArgumentOutOfRangeException`.
+/// # Example
+///
+/// ```
+/// use bitconv::to_hex_string_at;
+///
+/// assert_eq!("00-FF", to_hex_string_at(&[15, 0, 255, 7], 1, 2));
+/// ```
+#[cfg(feature = "alloc")]
+#[track_caller]
+pub fn to_hex_string_at(data: &[u8], start_index: usize, len: usize) -> String {
+    match data.get(start_index..).and_then(|bytes| bytes.get(..len)) {
+        Some(bytes) => to_hex_string(bytes),
+        None => error::conversion_failed(
+            "format",
+            "hex string",
+            Error::new(ErrorKind::OutOfBounds { start_index, requested: len, len: data.len() }),
+        ),
+    }
+}
+
+/// Returns whether byte may be written between the bytes of hexadecimal text.
+const fn is_separator(byte: u8) -> bool {
+    byte.is_ascii_whitespace() || matches!(byte, b'-' | b':' | b',')
+}
+
+const fn digit(byte: u8) -> Option<u8> {
+    match byte {
+        b'0'..=b'9' => Some(byte - b'0'),
+        b'a'..=b'f' => Some(byte - b'a' + 10),
+        b'A'..=b'F' => Some(byte - b'A' + 10),
+        _ => None,
+    }
+}
+
+/// Parses text into bytes, passing each to emit, and fails with the byte
+/// offset in text of the first character that is not where it may be.
+fn decode<F: FnMut(u8) -> Result<()>>(text: &str, mut emit: F) -> Result<()> {
+    let text = text.as_bytes();
+    let invalid = |start_index| Err(Error::new(ErrorKind::InvalidEncoding { start_index }));
+    let mut index = 0;
+    let mut token_start = true;
+    while let Some(&byte) = text.get(index) {
+        if is_separator(byte) {
+            index += 1;
+            token_start = true;
+            continue;
+        }
+        if token_start && byte == b'0' && matches!(text.get(index + 1), Some(b'x' | b'X')) {
+            index += 2;
+            token_start = false;
+            match text.get(index).copied().and_then(digit) {
+                Some(_) => continue,
+                None => return invalid(index),
+            }
+        }
+        token_start = false;
+        let high = match digit(byte) {
+            Some(high) => high,
+            None => return invalid(index),
+        };
+        let low = match text.get(index + 1) {
+            Some(&byte) if !is_separator(byte) => digit(byte).ok_or(index + 1),
+            // A lone digit before a separator or the end is the odd one out.
+            _ => Err(index),
+        };
+        match low {
+            Ok(low) => emit(high << 4 | low)?,
+            Err(start_index) => return invalid(start_index),
+        }
+        index += 2;
+    }
+    Ok(())
+}
+
+/// Parses hexadecimal text into bytes, as `Convert.FromHexString` does but
+/// more leniently, for tests and configuration files.
+///
+/// Pairs of digits of either case may be separated by whitespace, dashes,
+/// colons or commas, and each group of them may start with `0x`, so the
+/// output of [`to_hex_string`], `xxd -p` and C array initializers all parse.
+/// # Panics
+///
+/// Panics if text is not valid hexadecimal. See [`try_from_hex_string`] for a
+/// version that returns an error instead.
+/// # Example
+///
+/// ```
+/// use bitconv::from_hex_string;
+///
+/// assert_eq!([15, 0, 255], from_hex_string("0F-00-FF")[..]);
+/// assert_eq!([15, 0, 255], from_hex_string("0x0f, 0x00, 0xff")[..]);
+/// assert_eq!([0xde, 0xad, 0xbe, 0xef], from_hex_string("dead beef\n")[..]);
+/// ```
+#[cfg(feature = "alloc")]
+#[track_caller]
+pub fn from_hex_string(text: &str) -> Vec<u8> {
+    match try_from_hex_string(text) {
+        Ok(bytes) => bytes,
+        Err(error) => error::conversion_failed("parse", "hex string", error),
+    }
+}
+
+/// Parses hexadecimal text into bytes as [`from_hex_string`] does, or fails
+/// with `ErrorKind::InvalidEncoding` at the byte offset in text of the first
+/// character that is not a digit, a separator or a `0x` prefix where one may
+/// be, or of the lone digit of a group with an odd number of them.
+/// # Example
+///
+/// ```
+/// use bitconv::{try_from_hex_string, ErrorKind};
+///
+/// let error = try_from_hex_string("0f 0g").unwrap_err();
+/// assert_eq!(ErrorKind::InvalidEncoding { start_index: 4 }, error.kind());
+/// let error = try_from_hex_string("0f 0").unwrap_err();
+/// assert_eq!(ErrorKind::InvalidEncoding { start_index: 3 }, error.kind());
+/// ```
+#[cfg(feature = "alloc")]
+pub fn try_from_hex_string(text: &str) -> Result<Vec<u8>> {
+    let mut bytes = Vec::with_capacity(text.len() / 2);
+    decode(text, |byte| {
+        bytes.push(byte);
+        Ok(())
+    })?;
+    Ok(bytes)
+}
+
+/// Parses hexadecimal text as [`from_hex_string`] does into out, without
+/// allocating, and returns the number of bytes written to its start.
+///
+/// Fails as [`try_from_hex_string`] does, and with `ErrorKind::OutOfBounds`
+/// at the index of the first byte that does not fit if out is too small.
+/// # Example
+///
+/// ```
+/// use bitconv::try_from_hex_string_into;
+///
+/// let mut buffer = [0; 4];
+/// assert_eq!(Ok(3), try_from_hex_string_into("0F-00-FF", &mut buffer));
+/// assert_eq!([15, 0, 255, 0], buffer);
+/// assert!(try_from_hex_string_into("0102030405", &mut buffer).is_err());
+/// ```
+pub fn try_from_hex_string_into(text: &str, out: &mut [u8]) -> Result<usize> {
+    let len = out.len();
+    let mut written = 0;
+    decode(text, |byte| match out.get_mut(written) {
+        Some(slot) => {
+            *slot = byte;
+            written += 1;
+            Ok(())
+        }
+        None => Err(Error::new(ErrorKind::OutOfBounds { start_index: written, requested: 1, len })),
+    })?;
+    Ok(written)
+}
+
+/// Returns a byte array of the hexadecimal text of one or more string
+/// literals, parsed at compile time with the leniency of [`from_hex_string`].
+///
+/// Invalid text fails to compile. The result is a `[u8; N]` constant, so it
+/// can initialize other constants and statics.
+/// # Example
+///
+/// ```
+/// use bitconv::hex;
+///
+/// const MAGIC: [u8; 4] = hex!("7f 45 4c 46");
+/// assert_eq!(*b"\x7fELF", MAGIC);
+/// assert_eq!([0xde, 0xad, 0xbe, 0xef, 0, 1], hex!("0xdead" "BE:EF" "00 01"));
+/// ```
+#[macro_export]
+macro_rules! hex {
+    ($($text:literal)+) => {{
+        const TEXT: &[&str] = &[$($text),+];
+        const BYTES: [u8; $crate::__private::hex_scan::<0>(TEXT).0] =
+            $crate::__private::hex_scan(TEXT).1;
+        BYTES
+    }};
+}
+
+/// Parses the hexadecimal text of hex! into up to N bytes, returning them and
+/// the number of bytes in all of the text, or panics at compile time.
+#[doc(hidden)]
+pub const fn hex_scan<const N: usize>(texts: &[&str]) -> (usize, [u8; N]) {
+    let mut bytes = [0; N];
+    let mut len = 0;
+    let mut part = 0;
+    while part < texts.len() {
+        let text = texts[part].as_bytes();
+        let mut index = 0;
+        let mut token_start = true;
+        while index < text.len() {
+            let byte = text[index];
+            if is_separator(byte) {
+                index += 1;
+                token_start = true;
+                continue;
+            }
+            let prefix = index + 1 < text.len() && matches!(text[index + 1], b'x' | b'X');
+            if token_start && byte == b'0' && prefix {
+                index += 2;
+                token_start = false;
+                if index == text.len() || digit(text[index]).is_none() {
+                    panic!("hex! needs digits after a 0x prefix");
+                }
+                continue;
+            }
+            token_start = false;
+            if index + 1 == text.len() || is_separator(text[index + 1]) {
+                panic!("hex! needs an even number of digits in each group");
+            }
+            let byte = match (digit(byte), digit(text[index + 1])) {
+                (Some(high), Some(low)) => high << 4 | low,
+                _ => panic!("hex! only takes hexadecimal digits and separators"),
+            };
+            if len < N {
+                bytes[len] = byte;
+            }
+            len += 1;
+            index += 2;
+        }
+        part += 1;
+    }
+    (len, bytes)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn invalid(start_index: usize) -> Result<usize> {
+        Err(Error::new(ErrorKind::InvalidEncoding { start_index }))
+    }
+
+    #[test]
+    fn hex_macro_test() {
+        const EMPTY: [u8; 0] = crate::hex!("");
+        assert_eq!([0u8; 0], EMPTY);
+        assert_eq!([0x0f, 0x00, 0xff], crate::hex!("0f 00 ff"));
+        assert_eq!([0x0f, 0x00, 0xff], crate::hex!("0x0F," "0x00," "0xFF"));
+        assert_eq!([1, 2, 3, 4, 5, 6], crate::hex!("01-02:03\n0405" "06"));
+        assert_eq!((2, [0xab]), hex_scan::<1>(&["ab", "cd"]));
+    }
+
+    #[test]
+    #[should_panic]
+    fn hex_scan_odd_test() { hex_scan::<1>(&["0f0"]); }
+
+    #[test]
+    fn try_from_hex_string_into_test() {
+        let mut buffer = [0; 8];
+        let parse = |text| try_from_hex_string_into(text, &mut [0; 8]);
+        assert_eq!(Ok(0), try_from_hex_string_into("", &mut buffer));
+        assert_eq!(Ok(0), try_from_hex_string_into(" \t\r\n", &mut buffer));
+        assert_eq!(Ok(4), try_from_hex_string_into("  0xDEAD:be-EF, ", &mut buffer));
+        assert_eq!([0xde, 0xad, 0xbe, 0xef], buffer[..4]);
+        assert_eq!(Ok(8), try_from_hex_string_into("0001020304050607", &mut buffer));
+        assert_eq!(Ok(2), parse("0X0a 0x0b"));
+        assert_eq!(invalid(5), parse("0X0a0x0b"));
+        assert_eq!(Ok(1), parse("00"));
+        assert_eq!(invalid(2), parse("0x"));
+        assert_eq!(invalid(7), parse("0x0f 0x 10"));
+        assert_eq!(invalid(3), parse("000x01"));
+        assert_eq!(invalid(0), parse("x0"));
+        assert_eq!(invalid(0), parse("0"));
+        assert_eq!(invalid(3), parse("0f 0 ff"));
+        assert_eq!(invalid(5), parse("0f 0fz"));
+        assert_eq!(invalid(2), parse("0f\u{e9}"));
+        assert_eq!(
+            Err(Error::new(ErrorKind::OutOfBounds { start_index: 2, requested: 1, len: 2 })),
+            try_from_hex_string_into("01 02 03", &mut buffer[..2])
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn to_hex_string_test() {
+        assert_eq!("00", to_hex_string(&[0]));
+        assert_eq!("01-23-45-67-89-AB-CD-EF", to_hex_string(&[1, 35, 69, 103, 137, 171, 205, 239]));
+        assert_eq!("", to_hex_string_at(&[1, 2], 2, 0));
+        assert_eq!("02", to_hex_string_at(&[1, 2], 1, 1));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    #[should_panic]
+    fn to_hex_string_at_range_test() { to_hex_string_at(&[1, 2], 1, 2); }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn hex_format_test() {
+        let data = [0xde, 0xad, 0xbe, 0xef];
+        assert_eq!(
+            "de, ad, be, ef",
+            HexFormat::new().separator(", ").lowercase(true).format(&data)
+        );
+        assert_eq!("DEADBEEF", HexFormat::new().separator("").format(&data));
+        assert_eq!("", HexFormat::default().lowercase(true).format(&[]));
+        assert_eq!("A5", HexFormat::new().separator(":").format(&[0xa5]));
+        let mut text = String::from("key=");
+        assert_eq!(Ok(()), HexFormat::new().write(&mut text, &data[..2]));
+        assert_eq!("key=DE-AD", text);
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn hex_display_test() {
+        use alloc::format;
+
+        let data = [0xde, 0xad, 0xbe, 0xef];
+        assert_eq!("DE-AD-BE-EF", format!("{}", Hex(&data)));
+        assert_eq!("[DE-AD]", format!("{:?}", [Hex(&data[..2])]));
+        assert_eq!("DE-AD-BE-EF", format!("{}", HexLimited(&data, 4)));
+        assert_eq!("DE-AD-BE-... (4 bytes)", format!("{}", HexLimited(&data, 3)));
+        assert_eq!("... (4 bytes)", format!("{:?}", HexLimited(&data, 0)));
+        assert_eq!("", format!("{}", HexLimited(&[], 0)));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn from_hex_string_test() {
+        let data: Vec<u8> = (0..=255).collect();
+        assert_eq!(data, from_hex_string(&to_hex_string(&data)));
+        assert_eq!(
+            data,
+            from_hex_string(&HexFormat::new().separator("").lowercase(true).format(&data))
+        );
+        assert_eq!(Ok(Vec::new()), try_from_hex_string(""));
+        assert_eq!(
+            Err(Error::new(ErrorKind::InvalidEncoding { start_index: 0 })),
+            try_from_hex_string("a")
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    #[should_panic]
+    fn from_hex_string_invalid_test() { from_hex_string("0f-0g"); }
+}
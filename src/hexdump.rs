@@ -0,0 +1,156 @@
+//! Hexdumps in the layout of `xxd`, with an offset, the bytes in hexadecimal
+//! and their ASCII text on each line.
+use core::fmt::{self, Write};
+
+/// A hexdump of a byte array in the layout of `xxd`, written without
+/// allocating through `Display` or line by line through [`lines`].
+///
+/// Each line holds 16 bytes by default, in groups of two, and starts with the
+/// offset of its first byte, counted from 0 unless [`offset`] gives the
+/// address of the first byte of data.
+///
+/// [`lines`]: HexDump::lines
+/// [`offset`]: HexDump::offset
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "alloc")] {
+/// use bitconv::HexDump;
+///
+/// let dump = HexDump::new(b"Hello, world.\n").width(5).offset(0x1000);
+/// assert_eq!(
+///     "00001000: 4865 6c6c 6f  Hello\n\
+///      00001005: 2c20 776f 72  , wor\n\
+///      0000100a: 6c64 2e0a     ld..\n",
+///     dump.to_string()
+/// );
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct HexDump<'a> {
+    data: &'a [u8],
+    width: usize,
+    offset: usize,
+}
+
+impl<'a> HexDump<'a> {
+    /// Creates a dump of data with 16 bytes per line and offsets from 0.
+    pub fn new(data: &'a [u8]) -> Self { HexDump { data, width: 16, offset: 0 } }
+
+    /// Sets the number of bytes per line, at least 1.
+    pub fn width(mut self, width: usize) -> Self {
+        self.width = width.max(1);
+        self
+    }
+
+    /// Sets the offset shown for the first byte of data, such as its address
+    /// in memory or its position in a file, in place of 0.
+    pub fn offset(mut self, offset: usize) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Returns the lines of the dump, each displayed without a line break.
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// use bitconv::HexDump;
+    ///
+    /// let mut lines = HexDump::new(&[0, 0x41, 0x7f, 0x20, 0xff]).width(4).lines();
+    /// assert_eq!("00000000: 0041 7f20  .A. ", lines.next().unwrap().to_string());
+    /// assert_eq!("00000004: ff         .", lines.next().unwrap().to_string());
+    /// assert!(lines.next().is_none());
+    /// # }
+    /// ```
+    pub fn lines(&self) -> impl Iterator<Item = HexDumpLine<'a>> {
+        let (width, offset) = (self.width, self.offset);
+        self.data.chunks(width).enumerate().map(move |(index, bytes)| HexDumpLine {
+            offset: offset.wrapping_add(index.wrapping_mul(width)),
+            bytes,
+            width,
+        })
+    }
+}
+
+impl fmt::Display for HexDump<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for line in self.lines() {
+            writeln!(f, "{}", line)?;
+        }
+        Ok(())
+    }
+}
+
+/// A line of a [`HexDump`], as `xxd` writes it but for the line break.
+#[derive(Clone, Copy, Debug)]
+pub struct HexDumpLine<'a> {
+    offset: usize,
+    bytes: &'a [u8],
+    width: usize,
+}
+
+impl<'a> HexDumpLine<'a> {
+    /// Returns the offset shown for the first byte of the line.
+    pub fn offset(&self) -> usize { self.offset }
+
+    /// Returns the bytes of the line.
+    pub fn bytes(&self) -> &'a [u8] { self.bytes }
+}
+
+impl fmt::Display for HexDumpLine<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:08x}:", self.offset)?;
+        for index in 0..self.width {
+            if index % 2 == 0 {
+                f.write_str(" ")?;
+            }
+            match self.bytes.get(index) {
+                Some(byte) => write!(f, "{:02x}", byte)?,
+                None => f.write_str("  ")?,
+            }
+        }
+        f.write_str("  ")?;
+        for &byte in self.bytes {
+            let printable = byte == b' ' || byte.is_ascii_graphic();
+            f.write_char(if printable { byte.into() } else { '.' })?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod test {
+    use super::*;
+    use alloc::string::ToString;
+
+    #[test]
+    fn hexdump_test() {
+        let data = b"Hello, world.\n\x00\x01\xff abcdefghijklmnop";
+        assert_eq!(
+            "00000000: 4865 6c6c 6f2c 2077 6f72 6c64 2e0a 0001  Hello, world....\n\
+             00000010: ff20 6162 6364 6566 6768 696a 6b6c 6d6e  . abcdefghijklmn\n\
+             00000020: 6f70                                     op\n",
+            HexDump::new(data).to_string()
+        );
+        assert_eq!("", HexDump::new(&[]).to_string());
+        assert_eq!(
+            "00000007: 61  a\n00000008: 62  b\n",
+            HexDump::new(b"ab").width(0).offset(7).to_string()
+        );
+        let dots = [0x2e; 3];
+        assert_eq!(
+            "fffffffe: 2e2e 2e  ...\n",
+            HexDump::new(&dots).width(3).offset(0xffff_fffe).to_string()
+        );
+    }
+
+    #[test]
+    fn hexdump_lines_test() {
+        let data = [0u8; 40];
+        let lines: alloc::vec::Vec<_> = HexDump::new(&data).offset(0x100).lines().collect();
+        assert_eq!(3, lines.len());
+        assert_eq!(0x120, lines[2].offset());
+        assert_eq!(8, lines[2].bytes().len());
+    }
+}
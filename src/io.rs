@@ -0,0 +1,658 @@
+//! Adapters converting values to and from `std::io` streams.
+use crate::{
+    endian::Endian, from_bytes_with, to_bytes_with, BitConvEndian, FixedSize, FromBytes, ToBytes, Writer
+};
+use core::{
+    marker::PhantomData, mem, ops::{Deref, DerefMut}
+};
+use std::{
+    io::{self, IoSlice, Read, Write}, vec, vec::Vec
+};
+
+/// Names of the endianness markers as used by the `byteorder` crate, for use
+/// with [`ReadBytesExt`] and [`WriteBytesExt`].
+pub use crate::endian::{
+    Big as BigEndian, Big as NetworkEndian, Little as LittleEndian, Native as NativeEndian
+};
+
+/// Adapter converting values in the endianness `E` from the bytes of any
+/// [`Read`] source, such as a file or a socket, without reading all of it
+/// into memory first.
+///
+/// Every conversion reads exactly the bytes of its value, so unbuffered
+/// sources like `File` or `TcpStream` are best wrapped in a
+/// [`BufReader`](std::io::BufReader) to avoid a system call per value. A
+/// source that ends early fails with `io::ErrorKind::UnexpectedEof`.
+/// # Example
+///
+/// ```
+/// use bitconv::{endian::Big, EndianReader};
+///
+/// let stream: &[u8] = &[0, 0, 1, 0, 7, 255];
+/// let mut reader = EndianReader::<_, Big>::new(stream);
+/// assert_eq!(256, reader.read_uint32().unwrap());
+/// assert_eq!(7, reader.read_uint8().unwrap());
+/// assert!(reader.read_uint16().is_err());
+/// ```
+pub struct EndianReader<R, E> {
+    inner: R,
+    endian: PhantomData<E>,
+}
+
+macro_rules! EndianReadImpl {
+    ($($read:ident $type:ty),+) => {$(
+        #[doc = concat!("Reads a `", stringify!($type), "` from the underlying reader.")]
+        pub fn $read(&mut self) -> io::Result<$type> {
+            let mut bytes = [0; mem::size_of::<$type>()];
+            self.inner.read_exact(&mut bytes)?;
+            Ok(match E::ENDIANNESS {
+                Endian::LE => <$type>::from_le_bytes(bytes),
+                Endian::BE => <$type>::from_be_bytes(bytes),
+                Endian::NE => <$type>::from_ne_bytes(bytes),
+            })
+        }
+    )+};
+}
+
+impl<R: Read, E: BitConvEndian> EndianReader<R, E> {
+    pub fn new(inner: R) -> Self { EndianReader { inner, endian: PhantomData } }
+
+    /// Returns a reference to the underlying reader.
+    pub fn get_ref(&self) -> &R { &self.inner }
+
+    /// Returns a mutable reference to the underlying reader.
+    ///
+    /// Reading from it directly skips those bytes for this adapter as well.
+    pub fn get_mut(&mut self) -> &mut R { &mut self.inner }
+
+    /// Returns the underlying reader.
+    pub fn into_inner(self) -> R { self.inner }
+
+    /// Fills buf with the next bytes of the underlying reader.
+    pub fn read_exact(&mut self, buf: &mut [u8]) -> io::Result<()> { self.inner.read_exact(buf) }
+
+    /// Reads the next N bytes of the underlying reader into an array.
+    pub fn read_array<const N: usize>(&mut self) -> io::Result<[u8; N]> {
+        let mut bytes = [0; N];
+        self.inner.read_exact(&mut bytes)?;
+        Ok(bytes)
+    }
+
+    EndianReadImpl!(
+        read_int8 i8,
+        read_int16 i16,
+        read_int32 i32,
+        read_int64 i64,
+        read_uint8 u8,
+        read_uint16 u16,
+        read_uint32 u32,
+        read_uint64 u64
+    );
+}
+
+/// Adapter writing values in the endianness `E` to any [`Write`] sink, such
+/// as a file or a socket.
+///
+/// As with [`EndianReader`], each value is a separate small write, so
+/// unbuffered sinks are best wrapped in a [`BufWriter`](std::io::BufWriter).
+/// # Example
+///
+/// ```
+/// use bitconv::{endian::Little, EndianWriter};
+///
+/// let mut writer = EndianWriter::<_, Little>::new(Vec::new());
+/// writer.write_uint16(256).unwrap();
+/// writer.write(&(7u8, -1i16)).unwrap();
+/// assert_eq!([0, 1, 7, 255, 255], writer.into_inner()[..]);
+/// ```
+pub struct EndianWriter<W, E> {
+    inner: W,
+    endian: PhantomData<E>,
+}
+
+macro_rules! EndianWriteImpl {
+    ($($write:ident $type:ty),+) => {$(
+        #[doc = concat!("Writes a `", stringify!($type), "` to the underlying writer.")]
+        pub fn $write(&mut self, value: $type) -> io::Result<()> {
+            let bytes = match E::ENDIANNESS {
+                Endian::LE => value.to_le_bytes(),
+                Endian::BE => value.to_be_bytes(),
+                Endian::NE => value.to_ne_bytes(),
+            };
+            self.inner.write_all(&bytes)
+        }
+    )+};
+}
+
+impl<W: Write, E: BitConvEndian> EndianWriter<W, E> {
+    pub fn new(inner: W) -> Self { EndianWriter { inner, endian: PhantomData } }
+
+    /// Returns a reference to the underlying writer.
+    pub fn get_ref(&self) -> &W { &self.inner }
+
+    /// Returns a mutable reference to the underlying writer.
+    pub fn get_mut(&mut self) -> &mut W { &mut self.inner }
+
+    /// Returns the underlying writer.
+    pub fn into_inner(self) -> W { self.inner }
+
+    /// Writes a value, such as a tuple or a derived type, to the underlying
+    /// writer.
+    ///
+    /// The value is converted into a temporary buffer of
+    /// [`byte_len`](ToBytes::byte_len) bytes first, so nothing is written if
+    /// the conversion fails.
+    pub fn write<V: ToBytes + ?Sized>(&mut self, value: &V) -> io::Result<()> {
+        let mut buffer = vec![0; value.byte_len()];
+        value
+            .to_bytes::<E>(&mut buffer, 0)
+            .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+        self.inner.write_all(&buffer)
+    }
+
+    /// Writes raw bytes to the underlying writer.
+    pub fn write_bytes(&mut self, bytes: &[u8]) -> io::Result<()> { self.inner.write_all(bytes) }
+
+    /// Flushes the underlying writer.
+    pub fn flush(&mut self) -> io::Result<()> { self.inner.flush() }
+
+    EndianWriteImpl!(
+        write_int8 i8,
+        write_int16 i16,
+        write_int32 i32,
+        write_int64 i64,
+        write_uint8 u8,
+        write_uint16 u16,
+        write_uint32 u32,
+        write_uint64 u64
+    );
+}
+
+/// Fills value with the next bytes of reader in the given endianness, as Go's
+/// `binary.Read` does for a fixed-size struct.
+///
+/// Exactly `V::SIZE` bytes are read, failing with
+/// `io::ErrorKind::UnexpectedEof` if reader ends first, and value is only
+/// overwritten once all of them have been converted. A conversion error, such
+/// as an invalid enum tag, fails with `io::ErrorKind::InvalidData`.
+/// # Example
+///
+/// ```
+/// use bitconv::{binary_read, binary_write, endian::Endian};
+///
+/// // type header struct { Magic uint16; Length uint32 }
+/// let mut header = (0u16, 0u32);
+/// let mut stream: &[u8] = &[0xca, 0xfe, 0, 0, 1, 0, 7];
+/// binary_read(&mut stream, Endian::BE, &mut header).unwrap();
+/// assert_eq!((0xcafe, 256), header);
+/// assert_eq!([7], stream);
+///
+/// let mut sink = Vec::new();
+/// binary_write(&mut sink, Endian::LE, &header).unwrap();
+/// assert_eq!([0xfe, 0xca, 0, 1, 0, 0], sink[..]);
+/// ```
+pub fn binary_read<R: Read + ?Sized, V: FromBytes + FixedSize>(
+    reader: &mut R,
+    endian: Endian,
+    value: &mut V,
+) -> io::Result<()> {
+    let mut buffer = vec![0; V::SIZE];
+    reader.read_exact(&mut buffer)?;
+    let (converted, _) = from_bytes_with::<V>(endian, &buffer, 0)
+        .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+    *value = converted;
+    Ok(())
+}
+
+/// Writes value to writer in the given endianness with a single `write_all`,
+/// as Go's `binary.Write` does.
+///
+/// Nothing is written if the conversion fails, which it does with
+/// `io::ErrorKind::InvalidData`.
+pub fn binary_write<W: Write + ?Sized, V: ToBytes + ?Sized>(
+    writer: &mut W,
+    endian: Endian,
+    value: &V,
+) -> io::Result<()> {
+    let mut buffer = vec![0; value.byte_len()];
+    to_bytes_with(endian, value, &mut buffer, 0)
+        .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+    writer.write_all(&buffer)
+}
+
+/// [`Writer`] that can also hold borrowed byte slices, which are handed to a
+/// sink as they are through [`Write::write_vectored`] instead of being copied
+/// into the buffer.
+///
+/// The writer dereferences to the `Writer` holding the bytes between the
+/// borrowed slices, through which headers and other values are written as
+/// usual. Its positions and lengths only count those buffered bytes, so a
+/// length covering a borrowed slice is best written from the slice itself.
+/// # Example
+///
+/// ```
+/// use bitconv::{endian::Big, VectoredWriter};
+///
+/// let payload = vec![104, 105];
+/// let mut writer = VectoredWriter::<Big>::new();
+/// writer.write_uint16(payload.len() as u16);
+/// writer.write_borrowed(&payload);
+/// writer.write_uint8(0);
+/// assert_eq!(3, writer.io_slices().len());
+///
+/// let mut sink = Vec::new();
+/// writer.write_vectored(&mut sink).unwrap();
+/// assert_eq!([0, 2, 104, 105, 0], sink[..]);
+/// ```
+pub struct VectoredWriter<'a, E> {
+    writer: Writer<E>,
+    borrowed: Vec<(usize, &'a [u8])>,
+}
+
+impl<'a, E: BitConvEndian> VectoredWriter<'a, E> {
+    pub fn new() -> Self { VectoredWriter { writer: Writer::new(), borrowed: Vec::new() } }
+
+    /// Appends bytes that are kept borrowed rather than copied.
+    pub fn write_borrowed(&mut self, bytes: &'a [u8]) {
+        self.borrowed.push((self.writer.position(), bytes));
+    }
+
+    /// Returns the total number of bytes written, borrowed ones included.
+    pub fn len(&self) -> usize {
+        self.writer.position() + self.borrowed.iter().map(|(_, bytes)| bytes.len()).sum::<usize>()
+    }
+
+    pub fn is_empty(&self) -> bool { self.len() == 0 }
+
+    /// Returns the written bytes in order as a batch of slices, leaving out
+    /// empty ones.
+    pub fn io_slices(&self) -> Vec<IoSlice<'_>> {
+        let buffer = self.writer.as_slice();
+        let mut slices = Vec::with_capacity(self.borrowed.len() * 2 + 1);
+        let mut start = 0;
+        for &(position, bytes) in &self.borrowed {
+            slices.push(IoSlice::new(&buffer[start..position]));
+            slices.push(IoSlice::new(bytes));
+            start = position;
+        }
+        slices.push(IoSlice::new(&buffer[start..]));
+        slices.retain(|slice| !slice.is_empty());
+        slices
+    }
+
+    /// Writes all of the bytes to sink, using as few calls to
+    /// [`Write::write_vectored`] as it allows.
+    pub fn write_vectored<W: Write + ?Sized>(&self, sink: &mut W) -> io::Result<()> {
+        let mut slices = self.io_slices();
+        let mut slices = &mut slices[..];
+        while !slices.is_empty() {
+            match sink.write_vectored(slices) {
+                Ok(0) => return Err(io::ErrorKind::WriteZero.into()),
+                Ok(written) => IoSlice::advance_slices(&mut slices, written),
+                Err(error) if error.kind() == io::ErrorKind::Interrupted => {}
+                Err(error) => return Err(error),
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns all of the bytes copied into a single buffer.
+    pub fn to_vec(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(self.len());
+        for slice in self.io_slices() {
+            bytes.extend_from_slice(&slice);
+        }
+        bytes
+    }
+}
+
+impl<E: BitConvEndian> Default for VectoredWriter<'_, E> {
+    fn default() -> Self { VectoredWriter::new() }
+}
+
+impl<E> Deref for VectoredWriter<'_, E> {
+    type Target = Writer<E>;
+
+    fn deref(&self) -> &Writer<E> { &self.writer }
+}
+
+impl<E> DerefMut for VectoredWriter<'_, E> {
+    fn deref_mut(&mut self) -> &mut Writer<E> { &mut self.writer }
+}
+
+macro_rules! ReadBytesExtImpl {
+    ($($name:ident $read:ident $type:ty),+) => {$(
+        #[doc = concat!("Reads a `", stringify!($type), "` in the endianness `E`.")]
+        #[inline]
+        fn $name<E: BitConvEndian>(&mut self) -> io::Result<$type> {
+            EndianReader::<_, E>::new(self).$read()
+        }
+    )+};
+}
+
+/// Extension trait with the method names of `byteorder::ReadBytesExt`,
+/// implemented for every [`Read`] source, so code using `byteorder` can
+/// switch over by changing its imports.
+///
+/// This module also provides the `LittleEndian`, `BigEndian`,
+/// `NetworkEndian` and `NativeEndian` names for the endianness markers.
+/// # Example
+///
+/// ```
+/// use bitconv::io::{BigEndian, LittleEndian, ReadBytesExt};
+///
+/// let mut stream: &[u8] = &[2, 5, 0, 0, 1, 0x3f, 0x80, 0, 0];
+/// assert_eq!(2, stream.read_u8().unwrap());
+/// assert_eq!(5, stream.read_u16::<LittleEndian>().unwrap());
+/// assert_eq!(1, stream.read_u16::<BigEndian>().unwrap());
+/// assert_eq!(1.0, stream.read_f32::<BigEndian>().unwrap());
+/// ```
+pub trait ReadBytesExt: Read {
+    /// Reads a `u8`.
+    #[inline]
+    fn read_u8(&mut self) -> io::Result<u8> {
+        EndianReader::<_, NativeEndian>::new(self).read_uint8()
+    }
+
+    /// Reads an `i8`.
+    #[inline]
+    fn read_i8(&mut self) -> io::Result<i8> {
+        EndianReader::<_, NativeEndian>::new(self).read_int8()
+    }
+
+    ReadBytesExtImpl!(
+        read_i16 read_int16 i16,
+        read_i32 read_int32 i32,
+        read_i64 read_int64 i64,
+        read_u16 read_uint16 u16,
+        read_u32 read_uint32 u32,
+        read_u64 read_uint64 u64
+    );
+
+    /// Reads an `f32` from its IEEE 754 bits in the endianness `E`.
+    #[inline]
+    fn read_f32<E: BitConvEndian>(&mut self) -> io::Result<f32> {
+        self.read_u32::<E>().map(f32::from_bits)
+    }
+
+    /// Reads an `f64` from its IEEE 754 bits in the endianness `E`.
+    #[inline]
+    fn read_f64<E: BitConvEndian>(&mut self) -> io::Result<f64> {
+        self.read_u64::<E>().map(f64::from_bits)
+    }
+}
+
+impl<R: Read + ?Sized> ReadBytesExt for R {}
+
+macro_rules! WriteBytesExtImpl {
+    ($($name:ident $write:ident $type:ty),+) => {$(
+        #[doc = concat!("Writes a `", stringify!($type), "` in the endianness `E`.")]
+        #[inline]
+        fn $name<E: BitConvEndian>(&mut self, value: $type) -> io::Result<()> {
+            EndianWriter::<_, E>::new(self).$write(value)
+        }
+    )+};
+}
+
+/// Extension trait with the method names of `byteorder::WriteBytesExt`,
+/// implemented for every [`Write`] sink.
+/// # Example
+///
+/// ```
+/// use bitconv::io::{LittleEndian, NetworkEndian, WriteBytesExt};
+///
+/// let mut sink = Vec::new();
+/// sink.write_u8(2).unwrap();
+/// sink.write_i16::<LittleEndian>(-2).unwrap();
+/// sink.write_u32::<NetworkEndian>(1).unwrap();
+/// assert_eq!([2, 254, 255, 0, 0, 0, 1], sink[..]);
+/// ```
+pub trait WriteBytesExt: Write {
+    /// Writes a `u8`.
+    #[inline]
+    fn write_u8(&mut self, value: u8) -> io::Result<()> {
+        EndianWriter::<_, NativeEndian>::new(self).write_uint8(value)
+    }
+
+    /// Writes an `i8`.
+    #[inline]
+    fn write_i8(&mut self, value: i8) -> io::Result<()> {
+        EndianWriter::<_, NativeEndian>::new(self).write_int8(value)
+    }
+
+    WriteBytesExtImpl!(
+        write_i16 write_int16 i16,
+        write_i32 write_int32 i32,
+        write_i64 write_int64 i64,
+        write_u16 write_uint16 u16,
+        write_u32 write_uint32 u32,
+        write_u64 write_uint64 u64
+    );
+
+    /// Writes the IEEE 754 bits of an `f32` in the endianness `E`.
+    #[inline]
+    fn write_f32<E: BitConvEndian>(&mut self, value: f32) -> io::Result<()> {
+        self.write_u32::<E>(value.to_bits())
+    }
+
+    /// Writes the IEEE 754 bits of an `f64` in the endianness `E`.
+    #[inline]
+    fn write_f64<E: BitConvEndian>(&mut self, value: f64) -> io::Result<()> {
+        self.write_u64::<E>(value.to_bits())
+    }
+}
+
+impl<W: Write + ?Sized> WriteBytesExt for W {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::endian::*;
+    use std::{io::Cursor, vec::Vec};
+
+    #[test]
+    fn endian_reader_test_le() {
+        let buffer = [15, 0, 0, 128, 16, 39, 240, 216, 241, 255, 127];
+        let mut reader = EndianReader::<_, Little>::new(&buffer[..]);
+        assert_eq!(2147483663, reader.read_uint32().unwrap());
+        assert_eq!(10000, reader.read_int16().unwrap());
+        assert_eq!(-10000, reader.read_int16().unwrap());
+        assert_eq!(-15, reader.read_int8().unwrap());
+        assert_eq!([255], reader.read_array().unwrap());
+        assert_eq!(io::ErrorKind::UnexpectedEof, reader.read_uint16().unwrap_err().kind());
+        let mut reader = EndianReader::<_, Little>::new(Cursor::new([0; 22]));
+        assert_eq!(0, reader.read_int64().unwrap());
+        assert_eq!(0, reader.read_uint64().unwrap());
+        assert_eq!(16, reader.get_ref().position());
+        reader.get_mut().set_position(20);
+        assert_eq!(0, reader.read_uint16().unwrap());
+        assert!(reader.read_uint8().is_err());
+    }
+
+    #[test]
+    fn endian_reader_test_be() {
+        let buffer = [15, 0, 0, 128, 16, 39, 240, 216, 241, 255, 127];
+        let mut reader = EndianReader::<_, Big>::new(Cursor::new(buffer));
+        assert_eq!(1080864460595785944, reader.read_uint64().unwrap());
+        assert_eq!(241, reader.read_uint8().unwrap());
+        let mut bytes = [0; 2];
+        reader.read_exact(&mut bytes).unwrap();
+        assert_eq!([255, 127], bytes);
+        assert_eq!(11, reader.into_inner().position());
+        let mut reader = EndianReader::<_, Big>::new(&buffer[2..]);
+        assert_eq!(8392743, reader.read_int32().unwrap());
+        assert_eq!(61656, reader.read_uint16().unwrap());
+    }
+
+    #[test]
+    fn endian_writer_test_le() {
+        let mut writer = EndianWriter::<_, Little>::new(Cursor::new(Vec::new()));
+        writer.write_uint32(2147483663).unwrap();
+        writer.write_int16(10000).unwrap();
+        writer.write(&(-10000i16, -15i8)).unwrap();
+        writer.write_uint8(255).unwrap();
+        writer.write_int8(127).unwrap();
+        writer.flush().unwrap();
+        assert_eq!(11, writer.get_ref().position());
+        assert_eq!(
+            [15, 0, 0, 128, 16, 39, 240, 216, 241, 255, 127],
+            writer.get_ref().get_ref()[..]
+        );
+        writer.get_mut().set_position(0);
+        writer.write_int64(-1).unwrap();
+        writer.write_uint64(1).unwrap();
+        assert_eq!(16, writer.into_inner().into_inner().len());
+    }
+
+    #[test]
+    fn endian_writer_test_be() {
+        let mut buffer = [0; 11];
+        let mut writer = EndianWriter::<_, Big>::new(&mut buffer[..]);
+        writer.write_uint64(1080864460595785944).unwrap();
+        writer.write_bytes(&[241]).unwrap();
+        writer.write(&[255u8, 127][..]).unwrap();
+        assert_eq!(io::ErrorKind::WriteZero, writer.write_uint32(0).unwrap_err().kind());
+        assert_eq!([15, 0, 0, 128, 16, 39, 240, 216, 241, 255, 127], buffer);
+        let mut writer = EndianWriter::<_, Big>::new(Vec::new());
+        writer.write_int32(-2).unwrap();
+        writer.write_uint16(1).unwrap();
+        let bytes = writer.into_inner();
+        let mut reader = EndianReader::<_, Big>::new(&bytes[..]);
+        assert_eq!((-2, 1), (reader.read_int32().unwrap(), reader.read_uint16().unwrap()));
+    }
+
+    /// Sink accepting at most limit bytes per call, interrupting every other
+    /// call.
+    struct Trickle {
+        bytes: Vec<u8>,
+        limit: usize,
+        interrupt: bool,
+    }
+
+    impl Write for Trickle {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.interrupt = !self.interrupt;
+            if self.interrupt {
+                return Err(io::ErrorKind::Interrupted.into());
+            }
+            let len = buf.len().min(self.limit);
+            self.bytes.extend_from_slice(&buf[..len]);
+            Ok(len)
+        }
+
+        fn flush(&mut self) -> io::Result<()> { Ok(()) }
+    }
+
+    #[test]
+    fn vectored_writer_test() {
+        let payload = [16, 39, 240, 216];
+        let mut writer = VectoredWriter::<Little>::default();
+        assert!(writer.is_empty());
+        writer.write_borrowed(&[]);
+        writer.write_uint32(2147483663);
+        writer.write_borrowed(&payload);
+        writer.write_borrowed(&payload[..1]);
+        writer.write(&(-15i8, 255u8));
+        writer.write_borrowed(&[127]);
+        assert_eq!(12, writer.len());
+        assert_eq!(6, writer.position());
+        let slices = writer.io_slices();
+        assert_eq!(5, slices.len());
+        assert_eq!([241, 255], *slices[3]);
+        let expected = [15, 0, 0, 128, 16, 39, 240, 216, 16, 241, 255, 127];
+        assert_eq!(expected, writer.to_vec()[..]);
+        let mut sink = Trickle { bytes: Vec::new(), limit: 3, interrupt: false };
+        writer.write_vectored(&mut sink).unwrap();
+        assert_eq!(expected, sink.bytes[..]);
+        let mut full = [0; 4];
+        assert_eq!(
+            io::ErrorKind::WriteZero,
+            writer.write_vectored(&mut &mut full[..]).unwrap_err().kind()
+        );
+        assert_eq!([15, 0, 0, 128], full);
+    }
+
+    #[test]
+    fn read_bytes_ext_test() {
+        let buffer = [15, 0, 0, 128, 16, 39, 240, 216, 241, 255, 127];
+        let mut stream = &buffer[..];
+        assert_eq!(2147483663, stream.read_u32::<LittleEndian>().unwrap());
+        assert_eq!(10000, stream.read_i16::<LittleEndian>().unwrap());
+        assert_eq!(-3880, stream.read_i16::<BigEndian>().unwrap());
+        assert_eq!(-15, stream.read_i8().unwrap());
+        assert_eq!(255, stream.read_u8().unwrap());
+        assert_eq!(
+            io::ErrorKind::UnexpectedEof,
+            stream.read_u16::<NetworkEndian>().unwrap_err().kind()
+        );
+        let mut stream = Cursor::new(buffer);
+        assert_eq!(1080864460595785944, stream.read_u64::<BigEndian>().unwrap());
+        stream.set_position(0);
+        assert_eq!(1080864460595785944, stream.read_i64::<NetworkEndian>().unwrap());
+        stream.set_position(0);
+        assert_eq!(i32::from_ne_bytes([15, 0, 0, 128]), stream.read_i32::<NativeEndian>().unwrap());
+        assert_eq!(10000, stream.read_u16::<LittleEndian>().unwrap());
+        let mut stream: &[u8] = &[0x40, 0x09, 0x21, 0xfb, 0x54, 0x44, 0x2d, 0x18, 0, 0, 0xc0, 0xbf];
+        assert_eq!(core::f64::consts::PI, stream.read_f64::<BigEndian>().unwrap());
+        assert_eq!(-1.5, stream.read_f32::<LittleEndian>().unwrap());
+    }
+
+    #[test]
+    fn write_bytes_ext_test() {
+        let mut sink = Vec::new();
+        sink.write_u32::<LittleEndian>(2147483663).unwrap();
+        sink.write_i16::<LittleEndian>(10000).unwrap();
+        sink.write_u16::<BigEndian>(61656).unwrap();
+        sink.write_i8(-15).unwrap();
+        sink.write_u8(255).unwrap();
+        sink.write_f32::<LittleEndian>(-1.5).unwrap();
+        sink.write_f64::<NetworkEndian>(core::f64::consts::PI).unwrap();
+        sink.write_i32::<BigEndian>(-2).unwrap();
+        sink.write_i64::<LittleEndian>(-1).unwrap();
+        sink.write_u64::<BigEndian>(1).unwrap();
+        let mut stream = &sink[..];
+        assert_eq!(2147483663, stream.read_u32::<LittleEndian>().unwrap());
+        assert_eq!(10000, stream.read_i16::<LittleEndian>().unwrap());
+        assert_eq!([240, 216, 241, 255], stream[..4]);
+        stream = &stream[4..];
+        assert_eq!(-1.5, stream.read_f32::<LittleEndian>().unwrap());
+        assert_eq!(core::f64::consts::PI, stream.read_f64::<BigEndian>().unwrap());
+        assert_eq!(-2, stream.read_i32::<BigEndian>().unwrap());
+        assert_eq!(-1, stream.read_i64::<LittleEndian>().unwrap());
+        assert_eq!(1, stream.read_u64::<BigEndian>().unwrap());
+        let mut full = [0; 1];
+        assert_eq!(
+            io::ErrorKind::WriteZero,
+            (&mut full[..]).write_u16::<BigEndian>(1).unwrap_err().kind()
+        );
+    }
+
+    #[test]
+    fn binary_read_test() {
+        let mut stream: &[u8] = &[1, 0, 0, 0, 2, 0, 3, 0, 0, 0, 0, 4];
+        let mut record = (0u32, 0i16);
+        assert!(binary_read(&mut stream, Endian::LE, &mut record).is_ok());
+        assert_eq!((1, 2), record);
+        assert!(binary_read(&mut stream, Endian::BE, &mut record).is_ok());
+        assert_eq!((0x0300_0000, 4), record);
+        let mut stream: &[u8] = &[1, 2, 3];
+        assert_eq!(
+            io::ErrorKind::UnexpectedEof,
+            binary_read(&mut stream, Endian::NE, &mut record).unwrap_err().kind()
+        );
+        assert_eq!((0x0300_0000, 4), record);
+    }
+
+    #[test]
+    fn binary_write_test() {
+        let mut sink = Vec::new();
+        assert!(binary_write(&mut sink, Endian::BE, &(1u32, -2i16)).is_ok());
+        assert!(binary_write(&mut sink, Endian::LE, &258u16).is_ok());
+        assert_eq!([0, 0, 0, 1, 255, 254, 2, 1], sink[..]);
+        let mut sink = [0u8; 3];
+        assert_eq!(
+            io::ErrorKind::WriteZero,
+            binary_write(&mut &mut sink[..], Endian::NE, &0u32).unwrap_err().kind()
+        );
+    }
+}
@@ -0,0 +1,236 @@
+//! Readers pulling bytes from an iterator.
+use crate::{BitConvEndian, Error, ErrorKind, FromBytes, Result};
+use core::{fmt, marker::PhantomData, mem};
+
+/// Reader converting values in the endianness `E` from bytes pulled out of an
+/// iterator, for sources that produce bytes one at a time rather than as a
+/// slice, such as decompression streams.
+///
+/// Sources yielding `Result<u8, _>`, such as the `bytes()` of a `std::io`
+/// reader, are read with [`TryIterReader`] instead, which returns their errors.
+/// # Example
+///
+/// ```
+/// use bitconv::{endian::Little, IterReader};
+///
+/// let bytes = (1..=6).map(|byte| byte * 16);
+/// let mut reader = IterReader::<_, Little>::new(bytes);
+/// assert_eq!(Ok(8208), reader.read_uint16());
+/// assert_eq!(Ok(1615872048), reader.read_uint32());
+/// assert!(reader.read_uint8().is_err());
+/// ```
+pub struct IterReader<I, E> {
+    iter: I,
+    position: usize,
+    endian: PhantomData<E>,
+}
+
+impl<I: Iterator<Item = u8>, E: BitConvEndian> IterReader<I, E> {
+    /// Creates a reader pulling bytes from iter, with none pulled so far.
+    pub fn new(iter: I) -> Self { IterReader { iter, position: 0, endian: PhantomData } }
+
+    /// Returns the underlying iterator.
+    pub fn into_inner(self) -> I { self.iter }
+
+    /// Returns the number of bytes pulled from the iterator so far.
+    pub fn position(&self) -> usize { self.position }
+
+    /// Fills buf with the next bytes of the iterator.
+    ///
+    /// If the iterator ends first, the bytes it did yield are consumed and an
+    /// `ErrorKind::OutOfBounds` error reports how many there were.
+    pub fn read_exact(&mut self, buf: &mut [u8]) -> Result<()> {
+        let start_index = self.position;
+        for (filled, slot) in buf.iter_mut().enumerate() {
+            match self.iter.next() {
+                Some(byte) => *slot = byte,
+                None => {
+                    self.position += filled;
+                    return Err(Error::new(ErrorKind::OutOfBounds {
+                        start_index,
+                        requested: buf.len(),
+                        len: self.position,
+                    }));
+                }
+            }
+        }
+        self.position += buf.len();
+        Ok(())
+    }
+
+    ReadExactImpl!(
+        read_int8 i8,
+        read_int16 i16,
+        read_int32 i32,
+        read_int64 i64,
+        read_uint8 u8,
+        read_uint16 u16,
+        read_uint32 u32,
+        read_uint64 u64
+    );
+}
+
+/// Error returned by [`TryIterReader`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReadError<X> {
+    /// The iterator ended before the read was complete, which fails with
+    /// `ErrorKind::OutOfBounds` as it does for [`IterReader`].
+    Conversion(Error),
+    /// An error yielded by the iterator, as it was yielded.
+    Source(X),
+}
+
+impl<X> From<Error> for ReadError<X> {
+    fn from(error: Error) -> Self { ReadError::Conversion(error) }
+}
+
+impl<X: fmt::Display> fmt::Display for ReadError<X> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReadError::Conversion(error) => write!(f, "{}", error),
+            ReadError::Source(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<X: fmt::Debug + fmt::Display> std::error::Error for ReadError<X> {}
+
+/// Reader converting values in the endianness `E` from the bytes of an
+/// iterator yielding `Result<u8, X>`, such as the `bytes()` of a `std::io`
+/// reader or a driver reporting framing errors byte by byte.
+///
+/// A read stops at the first error the iterator yields and returns it as
+/// [`ReadError::Source`], keeping the bytes pulled before it consumed.
+/// # Example
+///
+/// ```
+/// use bitconv::{endian::Little, iter::ReadError, TryIterReader};
+///
+/// let received = [Ok(1), Ok(1), Ok(7), Err("parity error"), Ok(9)];
+/// let mut reader = TryIterReader::<_, Little>::new(received.iter().copied());
+/// assert_eq!(Ok(257), reader.read_uint16());
+/// assert_eq!(Err(ReadError::Source("parity error")), reader.read_uint16());
+/// assert_eq!(3, reader.position());
+/// assert_eq!(Ok(9), reader.read_uint8());
+/// ```
+pub struct TryIterReader<I, E> {
+    iter: I,
+    position: usize,
+    endian: PhantomData<E>,
+}
+
+macro_rules! TryReadExactImpl {
+    ($($read:ident $type:ty),+) => {$(
+        #[doc = concat!("Reads a `", stringify!($type), "` and advances past it.")]
+        pub fn $read(&mut self) -> core::result::Result<$type, ReadError<X>> {
+            let mut bytes = [0; mem::size_of::<$type>()];
+            self.read_exact(&mut bytes)?;
+            Ok(<$type>::from_bytes::<E>(&bytes, 0)?.0)
+        }
+    )+};
+}
+
+impl<I: Iterator<Item = core::result::Result<u8, X>>, X, E: BitConvEndian> TryIterReader<I, E> {
+    /// Creates a reader pulling bytes from iter, with none pulled so far.
+    pub fn new(iter: I) -> Self { TryIterReader { iter, position: 0, endian: PhantomData } }
+
+    /// Returns the underlying iterator.
+    pub fn into_inner(self) -> I { self.iter }
+
+    /// Returns the number of bytes pulled from the iterator so far, leaving
+    /// out the errors it yielded.
+    pub fn position(&self) -> usize { self.position }
+
+    /// Fills buf with the next bytes of the iterator.
+    ///
+    /// If the iterator yields an error or ends first, the bytes it did yield
+    /// are consumed, and the error is returned as it is or as an
+    /// `ErrorKind::OutOfBounds` error reporting how many bytes there were.
+    pub fn read_exact(&mut self, buf: &mut [u8]) -> core::result::Result<(), ReadError<X>> {
+        let start_index = self.position;
+        for (filled, slot) in buf.iter_mut().enumerate() {
+            match self.iter.next() {
+                Some(Ok(byte)) => *slot = byte,
+                Some(Err(error)) => {
+                    self.position += filled;
+                    return Err(ReadError::Source(error));
+                }
+                None => {
+                    self.position += filled;
+                    return Err(ReadError::Conversion(Error::new(ErrorKind::OutOfBounds {
+                        start_index,
+                        requested: buf.len(),
+                        len: self.position,
+                    })));
+                }
+            }
+        }
+        self.position += buf.len();
+        Ok(())
+    }
+
+    TryReadExactImpl!(
+        read_int8 i8,
+        read_int16 i16,
+        read_int32 i32,
+        read_int64 i64,
+        read_uint8 u8,
+        read_uint16 u16,
+        read_uint32 u32,
+        read_uint64 u64
+    );
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::endian::*;
+
+    #[test]
+    fn iter_reader_test_le() {
+        let buffer = [15, 0, 0, 128, 16, 39, 240, 216, 241, 255, 127];
+        let mut reader = IterReader::<_, Little>::new(buffer.iter().copied());
+        assert_eq!(Ok(2147483663), reader.read_uint32());
+        assert_eq!(Ok(10000), reader.read_int16());
+        assert_eq!(Ok(-10000), reader.read_int16());
+        assert_eq!(Ok(-15), reader.read_int8());
+        assert_eq!(
+            Err(Error::new(ErrorKind::OutOfBounds { start_index: 9, requested: 4, len: 11 })),
+            reader.read_uint32()
+        );
+        assert_eq!(11, reader.position());
+        assert_eq!(None, reader.into_inner().next());
+    }
+
+    #[test]
+    fn iter_reader_test_be() {
+        let buffer = [15, 0, 0, 128, 16, 39, 240, 216, 241, 255, 127];
+        let mut reader = IterReader::<_, Big>::new(buffer.iter().copied());
+        assert_eq!(Ok(1080864460595785944), reader.read_uint64());
+        assert_eq!(Ok(-15), reader.read_int8());
+        assert_eq!(Ok(65407), reader.read_uint16());
+        let mut bytes = [0; 0];
+        assert_eq!(Ok(()), reader.read_exact(&mut bytes));
+        assert!(reader.read_int64().is_err());
+    }
+
+    #[test]
+    fn try_iter_reader_test() {
+        let buffer = [Ok(15), Ok(0), Ok(0), Ok(128), Ok(16), Err('x'), Ok(39), Ok(240)];
+        let mut reader = TryIterReader::<_, Big>::new(buffer.iter().copied());
+        assert_eq!(Ok(251658368), reader.read_uint32());
+        assert_eq!(Err(ReadError::Source('x')), reader.read_int32());
+        assert_eq!(5, reader.position());
+        assert_eq!(Ok(10224), reader.read_uint16());
+        assert_eq!(
+            Err(ReadError::Conversion(Error::new(ErrorKind::OutOfBounds {
+                start_index: 7,
+                requested: 1,
+                len: 7
+            }))),
+            reader.read_int8()
+        );
+        assert_eq!(None, reader.into_inner().next());
+    }
+}
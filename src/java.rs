@@ -0,0 +1,344 @@
+//! Cursor and buffer with the semantics of Java's `java.io.DataInputStream`
+//! and `java.io.DataOutputStream`.
+#[cfg(feature = "alloc")]
+use crate::Writer;
+use crate::{endian::Big, Reader, Result};
+#[cfg(feature = "alloc")]
+use crate::{Error, ErrorKind};
+#[cfg(feature = "alloc")]
+use alloc::{borrow::Cow, string::String};
+use core::ops::{Deref, DerefMut};
+#[cfg(feature = "alloc")]
+use core::{convert::TryFrom, str};
+
+/// [`Reader`] adding the conversions of Java's `DataInput` that go beyond
+/// fixed-size integers, for reading files written by Java code.
+///
+/// Like `DataInputStream`, it always reads in big-endian. The reader
+/// dereferences to a `Reader<Big>`, through which integers and everything
+/// else are read as usual: `read_int32` for `readInt`, `read_uint16` for
+/// `readUnsignedShort`, `read_bytes` for `readFully` and so on. A failed read
+/// leaves the position unchanged.
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "alloc")] {
+/// use bitconv::DataInput;
+///
+/// // What `DataOutputStream` writes for `true`, `300` and `"a\0😀"`.
+/// let buffer = [1, 0, 0, 1, 44, 0, 9, 97, 0xc0, 0x80, 0xed, 0xa0, 0xbd, 0xed, 0xb8, 0x80];
+/// let mut input = DataInput::new(&buffer);
+/// assert_eq!(Ok(true), input.read_boolean());
+/// assert_eq!(Ok(300), input.read_int32());
+/// assert_eq!("a\0😀", input.read_utf().unwrap());
+/// # }
+/// ```
+pub struct DataInput<'a> {
+    reader: Reader<'a, Big>,
+}
+
+impl<'a> DataInput<'a> {
+    /// Creates a reader positioned at the start of data.
+    pub fn new(data: &'a [u8]) -> Self { DataInput { reader: Reader::new(data) } }
+
+    /// Returns the underlying reader.
+    pub fn into_inner(self) -> Reader<'a, Big> { self.reader }
+
+    /// Reads a byte as a `bool`, with any byte other than 0 being `true`.
+    pub fn read_boolean(&mut self) -> Result<bool> {
+        self.reader.read_uint8().map(|byte| byte != 0)
+    }
+
+    /// Reads a Java `char`, a UTF-16 code unit that may be half of a
+    /// surrogate pair, as `readChar` does.
+    pub fn read_char(&mut self) -> Result<u16> { self.reader.read_uint16() }
+
+    /// Reads an `f32`, as `readFloat` does.
+    pub fn read_float(&mut self) -> Result<f32> { self.reader.read_uint32().map(f32::from_bits) }
+
+    /// Reads an `f64`, as `readDouble` does.
+    pub fn read_double(&mut self) -> Result<f64> { self.reader.read_uint64().map(f64::from_bits) }
+
+    /// Reads a string in the modified UTF-8 of `readUTF`, prefixed by its
+    /// length in bytes as a `u16`.
+    ///
+    /// Modified UTF-8 differs from UTF-8 in storing U+0000 as the two bytes
+    /// `C0 80` and characters beyond U+FFFF as the three-byte encodings of
+    /// the two halves of their surrogate pair. Strings holding neither are
+    /// plain UTF-8 and borrowed from the underlying buffer. Fails with
+    /// `ErrorKind::InvalidEncoding` at the offending byte where `readUTF`
+    /// throws a `UTFDataFormatException`, and also for a surrogate that is not
+    /// part of a pair, which a Rust string cannot hold.
+    #[cfg(feature = "alloc")]
+    pub fn read_utf(&mut self) -> Result<Cow<'a, str>> {
+        self.reader.transaction(|reader| {
+            let len = reader.read_uint16()?;
+            let start_index = reader.position();
+            let bytes = reader.read_bytes(usize::from(len))?;
+            match str::from_utf8(bytes) {
+                Ok(value) if bytes.iter().all(|&byte| byte < 0xf0) => Ok(Cow::Borrowed(value)),
+                _ => decode_modified_utf8(bytes, start_index).map(Cow::Owned),
+            }
+        })
+    }
+}
+
+/// Decodes modified UTF-8, reporting errors at their index in the buffer the
+/// bytes start at start_index of.
+#[cfg(feature = "alloc")]
+fn decode_modified_utf8(bytes: &[u8], start_index: usize) -> Result<String> {
+    let mut value = String::with_capacity(bytes.len());
+    // A high surrogate waiting for its low half, with the index of its bytes.
+    let mut high: Option<(u16, usize)> = None;
+    let invalid =
+        |offset| Error::new(ErrorKind::InvalidEncoding { start_index: start_index + offset });
+    let continuation = |offset: usize| match bytes.get(offset) {
+        Some(&byte) if byte & 0xc0 == 0x80 => Ok(u16::from(byte & 0x3f)),
+        _ => Err(invalid(offset)),
+    };
+    let mut index = 0;
+    while index < bytes.len() {
+        let lead = bytes[index];
+        let (unit, len) = match lead >> 4 {
+            0..=7 => (u16::from(lead), 1),
+            12 | 13 => (u16::from(lead & 0x1f) << 6 | continuation(index + 1)?, 2),
+            14 => (
+                u16::from(lead & 0x0f) << 12
+                    | continuation(index + 1)? << 6
+                    | continuation(index + 2)?,
+                3,
+            ),
+            _ => return Err(invalid(index)),
+        };
+        match (high.take(), unit) {
+            (Some((first, _)), 0xdc00..=0xdfff) => {
+                let code =
+                    0x10000 + ((u32::from(first) - 0xd800) << 10 | (u32::from(unit) - 0xdc00));
+                value.extend(char::from_u32(code));
+            }
+            (Some((_, offset)), _) => return Err(invalid(offset)),
+            (None, 0xd800..=0xdbff) => high = Some((unit, index)),
+            (None, 0xdc00..=0xdfff) => return Err(invalid(index)),
+            (None, _) => value.extend(char::from_u32(unit.into())),
+        }
+        index += len;
+    }
+    match high {
+        Some((_, offset)) => Err(invalid(offset)),
+        None => Ok(value),
+    }
+}
+
+impl<'a> Deref for DataInput<'a> {
+    type Target = Reader<'a, Big>;
+
+    fn deref(&self) -> &Reader<'a, Big> { &self.reader }
+}
+
+impl<'a> DerefMut for DataInput<'a> {
+    fn deref_mut(&mut self) -> &mut Reader<'a, Big> { &mut self.reader }
+}
+
+/// [`Writer`] adding the conversions of Java's `DataOutput` that go beyond
+/// fixed-size integers, writing byte for byte what `DataOutputStream` would
+/// have written.
+///
+/// Like `DataOutputStream`, it always writes in big-endian, and it
+/// dereferences to a `Writer<Big>` for everything else.
+/// # Example
+///
+/// ```
+/// use bitconv::{DataInput, DataOutput};
+///
+/// let mut output = DataOutput::new();
+/// output.write_boolean(true);
+/// output.write_int32(300);
+/// output.write_utf("a\0😀").unwrap();
+/// assert_eq!([1, 0, 0, 1, 44, 0, 9, 97, 0xc0, 0x80, 0xed], output.as_slice()[..11]);
+///
+/// let mut input = DataInput::new(output.as_slice());
+/// assert_eq!(Ok(true), input.read_boolean());
+/// assert_eq!(Ok(300), input.read_int32());
+/// assert_eq!("a\0😀", input.read_utf().unwrap());
+/// ```
+#[cfg(feature = "alloc")]
+pub struct DataOutput {
+    writer: Writer<Big>,
+}
+
+#[cfg(feature = "alloc")]
+impl DataOutput {
+    pub fn new() -> Self { DataOutput { writer: Writer::new() } }
+
+    /// Returns the underlying writer.
+    pub fn into_inner(self) -> Writer<Big> { self.writer }
+
+    /// Appends a `bool` as a byte of 1 or 0.
+    pub fn write_boolean(&mut self, value: bool) { self.writer.write_uint8(value.into()) }
+
+    /// Appends a Java `char`, a UTF-16 code unit, as `writeChar` does.
+    pub fn write_char(&mut self, value: u16) { self.writer.write_uint16(value) }
+
+    /// Appends every UTF-16 code unit of a string, without a length, as
+    /// `writeChars` does.
+    pub fn write_chars(&mut self, value: &str) {
+        value.encode_utf16().for_each(|unit| self.writer.write_uint16(unit))
+    }
+
+    /// Appends an `f32`, as `writeFloat` does.
+    pub fn write_float(&mut self, value: f32) { self.writer.write_uint32(value.to_bits()) }
+
+    /// Appends an `f64`, as `writeDouble` does.
+    pub fn write_double(&mut self, value: f64) { self.writer.write_uint64(value.to_bits()) }
+
+    /// Appends a string in modified UTF-8, prefixed by its length in bytes as
+    /// a `u16`, as `writeUTF` does.
+    ///
+    /// Fails with `ErrorKind::LengthOverflow`, writing nothing, when the
+    /// encoded string is longer than 65535 bytes, where `writeUTF` throws a
+    /// `UTFDataFormatException`.
+    pub fn write_utf(&mut self, value: &str) -> Result<()> {
+        let start_index = self.writer.position();
+        let len: usize = value.encode_utf16().map(modified_utf8_len).sum();
+        let len = u16::try_from(len)
+            .map_err(|_| Error::new(ErrorKind::LengthOverflow { start_index, len }))?;
+        self.writer.write_uint16(len);
+        for unit in value.encode_utf16() {
+            match modified_utf8_len(unit) {
+                1 => self.writer.write_uint8(unit as u8),
+                2 => {
+                    self.writer.write_bytes(&[0xc0 | (unit >> 6) as u8, 0x80 | (unit & 0x3f) as u8])
+                }
+                _ => self.writer.write_bytes(&[
+                    0xe0 | (unit >> 12) as u8,
+                    0x80 | (unit >> 6 & 0x3f) as u8,
+                    0x80 | (unit & 0x3f) as u8,
+                ]),
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Returns the number of bytes modified UTF-8 encodes a UTF-16 code unit to.
+#[cfg(feature = "alloc")]
+fn modified_utf8_len(unit: u16) -> usize {
+    match unit {
+        0x0001..=0x007f => 1,
+        0x0000 | 0x0080..=0x07ff => 2,
+        _ => 3,
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Default for DataOutput {
+    fn default() -> Self { DataOutput::new() }
+}
+
+#[cfg(feature = "alloc")]
+impl Deref for DataOutput {
+    type Target = Writer<Big>;
+
+    fn deref(&self) -> &Writer<Big> { &self.writer }
+}
+
+#[cfg(feature = "alloc")]
+impl DerefMut for DataOutput {
+    fn deref_mut(&mut self) -> &mut Writer<Big> { &mut self.writer }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn data_input_test() {
+        let buffer =
+            [2, 0, 0, 0xe9, 0xbf, 0xc0, 0, 0, 0x40, 0x09, 0x21, 0xfb, 0x54, 0x44, 0x2d, 0x18, 7];
+        let mut input = DataInput::new(&buffer);
+        assert_eq!(Ok(true), input.read_boolean());
+        assert_eq!(Ok(false), input.read_boolean());
+        assert_eq!(Ok(0xe9), input.read_char());
+        assert_eq!(Ok(-1.5), input.read_float());
+        assert_eq!(Ok(core::f64::consts::PI), input.read_double());
+        assert!(input.read_double().is_err());
+        assert_eq!(16, input.position());
+        assert_eq!(Ok(7), input.into_inner().read_uint8());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn read_utf_test() {
+        let cases: [(&[u8], &str); 5] = [
+            (&[0, 0], ""),
+            (&[0, 3, 104, 195, 169], "hé"),
+            (&[0, 3, 0xe2, 0x82, 0xac], "€"),
+            (&[0, 3, 0, 0xc0, 0x80], "\0\0"),
+            (&[0, 6, 0xed, 0xa0, 0xbd, 0xed, 0xb8, 0x80], "😀"),
+        ];
+        for (bytes, value) in cases {
+            let mut input = DataInput::new(bytes);
+            assert_eq!(Ok(value), input.read_utf().as_deref());
+            assert_eq!(0, input.remaining());
+        }
+        let mut input = DataInput::new(&[0, 2, 97, 98]);
+        assert!(matches!(input.read_utf(), Ok(Cow::Borrowed("ab"))));
+        // Four-byte sequences, stray continuation bytes, truncated sequences
+        // and unpaired surrogates.
+        let cases: [(&[u8], usize); 6] = [
+            (&[0, 5, 97, 0xf0, 0x9f, 0x98, 0x80], 3),
+            (&[0, 2, 97, 0x80], 3),
+            (&[0, 2, 97, 0xc3], 4),
+            (&[0, 3, 97, 0xe2, 0x41], 4),
+            (&[0, 4, 0xed, 0xa0, 0xbd, 97], 2),
+            (&[0, 3, 0xed, 0xb8, 0x80], 2),
+        ];
+        for (bytes, start_index) in cases {
+            let mut input = DataInput::new(bytes);
+            assert_eq!(
+                Err(Error::new(ErrorKind::InvalidEncoding { start_index })),
+                input.read_utf()
+            );
+            assert_eq!(0, input.position());
+        }
+        assert!(DataInput::new(&[0, 3, 97, 98]).read_utf().is_err());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn data_output_test() {
+        let mut output = DataOutput::new();
+        output.write_boolean(false);
+        output.write_char(0xd83d);
+        output.write_chars("a😀");
+        output.write_float(-1.5);
+        output.write_double(core::f64::consts::PI);
+        assert_eq!(
+            [0, 0xd8, 0x3d, 0, 97, 0xd8, 0x3d, 0xde, 0x00, 0xbf, 0xc0, 0, 0, 0x40, 0x09],
+            output.as_slice()[..15]
+        );
+        assert_eq!(21, output.position());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn write_utf_test() {
+        for value in ["", "hé", "€", "\0\0", "😀", "a\u{7f}\u{80}\u{7ff}\u{800}\u{ffff}"] {
+            let mut output = DataOutput::new();
+            assert_eq!(Ok(()), output.write_utf(value));
+            assert_eq!(Ok(value), DataInput::new(output.as_slice()).read_utf().as_deref());
+        }
+        let mut output = DataOutput::new();
+        assert_eq!(Ok(()), output.write_utf("\0é"));
+        assert_eq!([0, 4, 0xc0, 0x80, 0xc3, 0xa9], output.as_slice());
+        let mut output = DataOutput::new();
+        output.write_uint8(1);
+        assert_eq!(
+            Err(Error::new(ErrorKind::LengthOverflow { start_index: 1, len: 65536 })),
+            output.write_utf(&"é".repeat(32768))
+        );
+        assert_eq!(1, output.position());
+        assert_eq!(Ok(()), output.write_utf(&"a".repeat(65535)));
+        assert_eq!(65538, output.position());
+    }
+}
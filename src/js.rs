@@ -0,0 +1,249 @@
+//! View over a byte buffer with the semantics of JavaScript's `DataView`.
+use crate::{endian::Endian, from_bytes_with, to_bytes_with, Error, ErrorKind, Result};
+#[cfg(feature = "half")]
+use half::f16;
+
+/// Returns the endianness the `littleEndian` argument of `DataView` selects.
+const fn endian(little_endian: bool) -> Endian {
+    match little_endian {
+        true => Endian::LE,
+        false => Endian::BE,
+    }
+}
+
+macro_rules! DataViewImpl {
+    (@get $($(#[$attr:meta])* $get:ident $js:literal $type:ty $(as $bits:ty)?;)+) => {$(
+        #[doc = concat!(
+            "Reads a `", stringify!($type), "` at offset into the view, as `get", $js, "` does."
+        )]
+        $(#[$attr])*
+        #[inline]
+        pub fn $get(&self, offset: usize, little_endian: bool) -> Result<$type> {
+            DataViewImpl!(@read self, offset, little_endian, $type $(as $bits)?)
+        }
+    )+};
+    (@set $($(#[$attr:meta])* $set:ident $js:literal $type:ty $(as $bits:ty)?;)+) => {$(
+        #[doc = concat!(
+            "Writes a `", stringify!($type), "` at offset into the view, as `set", $js, "` does."
+        )]
+        $(#[$attr])*
+        #[inline]
+        pub fn $set(&mut self, offset: usize, value: $type, little_endian: bool) -> Result<()> {
+            to_bytes_with(endian(little_endian), &(value$(.to_bits() as $bits)?), self.view_mut(), offset)
+                .map(|_| ())
+        }
+    )+};
+    (@read $self:ident, $offset:ident, $little_endian:ident, $type:ty) => {
+        from_bytes_with::<$type>(endian($little_endian), $self.view(), $offset).map(|(value, _)| value)
+    };
+    (@read $self:ident, $offset:ident, $little_endian:ident, $type:ty as $bits:ty) => {
+        from_bytes_with::<$bits>(endian($little_endian), $self.view(), $offset)
+            .map(|(bits, _)| <$type>::from_bits(bits))
+    };
+}
+
+/// View over a range of a byte buffer with the methods of JavaScript's
+/// `DataView`, such as `get_uint32(offset, little_endian)` for
+/// `getUint32(byteOffset, littleEndian)`, so code ported from the browser or
+/// written against WebAssembly memory keeps choosing the endianness at each
+/// call as it did.
+///
+/// Offsets are relative to the start of the view. Where `DataView` throws a
+/// `RangeError`, the methods fail with `ErrorKind::OutOfBounds` instead,
+/// relative to the view as well, and writing nothing. JavaScript's
+/// `littleEndian` argument defaults to `false`, so `false` is what to pass
+/// where the ported code leaves it out.
+/// # Example
+///
+/// ```
+/// use bitconv::DataView;
+///
+/// let mut buffer = [0; 8];
+/// let mut view = DataView::with_range(&mut buffer, 2, 6).unwrap();
+/// view.set_uint16(0, 0xcafe, false).unwrap();
+/// view.set_int32(2, -2, true).unwrap();
+/// assert_eq!(Ok(0xfeca), view.get_uint16(0, true));
+/// assert_eq!(Ok(-2), view.get_int32(2, true));
+/// assert!(view.get_uint32(4, true).is_err());
+/// assert_eq!([0, 0, 0xca, 0xfe, 254, 255, 255, 255], buffer);
+/// ```
+#[derive(Clone, Debug)]
+pub struct DataView<B> {
+    buffer: B,
+    byte_offset: usize,
+    byte_length: usize,
+}
+
+impl<B: AsRef<[u8]>> DataView<B> {
+    /// Creates a view over the whole buffer.
+    pub fn new(buffer: B) -> Self {
+        let byte_length = buffer.as_ref().len();
+        DataView { buffer, byte_offset: 0, byte_length }
+    }
+
+    /// Creates a view over byte_length bytes of the buffer starting at
+    /// byte_offset, as `new DataView(buffer, byteOffset, byteLength)` does.
+    ///
+    /// Fails with `ErrorKind::OutOfBounds` if the range does not fit in the
+    /// buffer.
+    pub fn with_range(buffer: B, byte_offset: usize, byte_length: usize) -> Result<Self> {
+        let len = buffer.as_ref().len();
+        match byte_offset.checked_add(byte_length) {
+            Some(end) if end <= len => Ok(DataView { buffer, byte_offset, byte_length }),
+            _ => Err(Error::new(ErrorKind::OutOfBounds {
+                start_index: byte_offset,
+                requested: byte_length,
+                len,
+            })),
+        }
+    }
+
+    /// Returns the offset of the view in the buffer, as `byteOffset` does.
+    pub fn byte_offset(&self) -> usize { self.byte_offset }
+
+    /// Returns the length of the view, as `byteLength` does.
+    pub fn byte_length(&self) -> usize { self.byte_length }
+
+    /// Returns a reference to the underlying buffer.
+    pub fn buffer(&self) -> &B { &self.buffer }
+
+    /// Returns the underlying buffer.
+    pub fn into_inner(self) -> B { self.buffer }
+
+    fn view(&self) -> &[u8] {
+        let range = self.byte_offset..self.byte_offset + self.byte_length;
+        self.buffer.as_ref().get(range).unwrap_or_default()
+    }
+
+    /// Reads a `u8` at offset into the view, as `getUint8` does.
+    #[inline]
+    pub fn get_uint8(&self, offset: usize) -> Result<u8> {
+        from_bytes_with(Endian::BE, self.view(), offset).map(|(value, _)| value)
+    }
+
+    /// Reads an `i8` at offset into the view, as `getInt8` does.
+    #[inline]
+    pub fn get_int8(&self, offset: usize) -> Result<i8> {
+        from_bytes_with(Endian::BE, self.view(), offset).map(|(value, _)| value)
+    }
+
+    DataViewImpl!(@get
+        get_uint16 "Uint16" u16;
+        get_int16 "Int16" i16;
+        get_uint32 "Uint32" u32;
+        get_int32 "Int32" i32;
+        get_big_uint64 "BigUint64" u64;
+        get_big_int64 "BigInt64" i64;
+        #[cfg(feature = "half")]
+        get_float16 "Float16" f16 as u16;
+        get_float32 "Float32" f32 as u32;
+        get_float64 "Float64" f64 as u64;
+    );
+}
+
+impl<B: AsRef<[u8]> + AsMut<[u8]>> DataView<B> {
+    fn view_mut(&mut self) -> &mut [u8] {
+        let range = self.byte_offset..self.byte_offset + self.byte_length;
+        self.buffer.as_mut().get_mut(range).unwrap_or_default()
+    }
+
+    /// Writes a `u8` at offset into the view, as `setUint8` does.
+    #[inline]
+    pub fn set_uint8(&mut self, offset: usize, value: u8) -> Result<()> {
+        to_bytes_with(Endian::BE, &value, self.view_mut(), offset).map(|_| ())
+    }
+
+    /// Writes an `i8` at offset into the view, as `setInt8` does.
+    #[inline]
+    pub fn set_int8(&mut self, offset: usize, value: i8) -> Result<()> {
+        to_bytes_with(Endian::BE, &value, self.view_mut(), offset).map(|_| ())
+    }
+
+    DataViewImpl!(@set
+        set_uint16 "Uint16" u16;
+        set_int16 "Int16" i16;
+        set_uint32 "Uint32" u32;
+        set_int32 "Int32" i32;
+        set_big_uint64 "BigUint64" u64;
+        set_big_int64 "BigInt64" i64;
+        #[cfg(feature = "half")]
+        set_float16 "Float16" f16 as u16;
+        set_float32 "Float32" f32 as u32;
+        set_float64 "Float64" f64 as u64;
+    );
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn data_view_get_test() {
+        let buffer = [0xff, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08];
+        let view = DataView::new(&buffer[..]);
+        assert_eq!(9, view.byte_length());
+        assert_eq!(Ok(255), view.get_uint8(0));
+        assert_eq!(Ok(-1), view.get_int8(0));
+        assert_eq!(Ok(0x0201), view.get_uint16(1, true));
+        assert_eq!(Ok(0x0102), view.get_int16(1, false));
+        assert_eq!(Ok(0x04030201), view.get_uint32(1, true));
+        assert_eq!(Ok(-0xfefdfd), view.get_int32(0, false));
+        assert_eq!(Ok(0x0807060504030201), view.get_big_uint64(1, true));
+        assert_eq!(Ok(0x0102030405060708), view.get_big_int64(1, false));
+        assert_eq!(Ok(1.5), DataView::new([0x3f, 0xc0, 0, 0]).get_float32(0, false));
+        assert_eq!(Ok(-2.0), DataView::new([0, 0, 0, 0, 0, 0, 0, 0xc0]).get_float64(0, true));
+        assert_eq!(
+            Err(Error::new(ErrorKind::OutOfBounds { start_index: 2, requested: 8, len: 9 })),
+            view.get_big_uint64(2, false)
+        );
+    }
+
+    #[test]
+    fn data_view_range_test() {
+        let buffer = [1, 2, 3, 4, 5, 6];
+        let view = DataView::with_range(buffer, 2, 3).unwrap();
+        assert_eq!((2, 3), (view.byte_offset(), view.byte_length()));
+        assert_eq!(Ok(3), view.get_uint8(0));
+        assert_eq!(Ok(0x0405), view.get_uint16(1, false));
+        assert_eq!(
+            Err(Error::new(ErrorKind::OutOfBounds { start_index: 2, requested: 2, len: 3 })),
+            view.get_uint16(2, false)
+        );
+        assert!(view.get_uint8(3).is_err());
+        assert!(DataView::with_range(buffer, 6, 0).is_ok());
+        assert_eq!(
+            Err(Error::new(ErrorKind::OutOfBounds { start_index: 4, requested: 3, len: 6 })),
+            DataView::with_range(buffer, 4, 3).map(|_| ())
+        );
+        assert!(DataView::with_range(buffer, usize::MAX, 2).is_err());
+    }
+
+    #[test]
+    fn data_view_set_test() {
+        let mut view = DataView::new([0u8; 8]);
+        view.set_int8(0, -2).unwrap();
+        view.set_uint16(1, 0x0201, true).unwrap();
+        view.set_int32(3, 0x03040506, false).unwrap();
+        view.set_uint8(7, 7).unwrap();
+        assert_eq!(&[254, 1, 2, 3, 4, 5, 6, 7], view.buffer());
+        assert!(view.set_big_int64(1, -1, true).is_err());
+        assert_eq!(&[254, 1, 2, 3, 4, 5, 6, 7], view.buffer());
+        view.set_float64(0, 1.0, false).unwrap();
+        assert_eq!([0x3f, 0xf0, 0, 0, 0, 0, 0, 0], view.into_inner());
+
+        let mut buffer = [0u8; 6];
+        let mut view = DataView::with_range(&mut buffer, 1, 4).unwrap();
+        view.set_float32(0, 1.0, true).unwrap();
+        assert!(view.set_uint8(4, 1).is_err());
+        assert_eq!([0, 0, 0, 0x80, 0x3f, 0], buffer);
+    }
+
+    #[cfg(feature = "half")]
+    #[test]
+    fn data_view_float16_test() {
+        let mut view = DataView::new([0u8; 2]);
+        view.set_float16(0, f16::ONE, true).unwrap();
+        assert_eq!([0, 0x3c], *view.buffer());
+        assert_eq!(Ok(f16::ONE), view.get_float16(0, true));
+    }
+}
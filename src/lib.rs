@@ -1,8 +1,164 @@
 #![no_std]
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
 use core::{convert::TryInto, mem};
 use endian::*;
 
+#[cfg(feature = "derive")]
+pub use bitconv_derive::{calcsize, pack, unpack, unpack_from, FixedSize, FromBytes, ToBytes};
+
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::hex::hex_scan;
+    #[cfg(feature = "alloc")]
+    pub use alloc::vec::Vec;
+    #[cfg(feature = "tracing")]
+    pub use tracing;
+}
+
+/// Emits a trace event for the field of a derived type about to be read at
+/// offset, or nothing without the `tracing` feature.
+#[cfg(feature = "tracing")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __trace_field {
+    ($type:expr, $field:expr, $offset:expr) => {
+        $crate::__private::tracing::trace!(
+            target: "bitconv",
+            r#type = $type,
+            field = $field,
+            offset = $offset
+        )
+    };
+}
+
+#[cfg(not(feature = "tracing"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __trace_field {
+    ($type:expr, $field:expr, $offset:expr) => {};
+}
+
+mod base58;
+mod base85;
+pub mod binary;
+pub mod bitfield;
+mod bits;
+mod bitstream;
+#[cfg(feature = "alloc")]
+mod builder;
+#[cfg(feature = "byteorder")]
+pub mod byteorder;
+#[cfg(feature = "bytes")]
+pub mod bytes;
+#[macro_use]
+pub mod chain;
+mod decimal;
+#[cfg(feature = "alloc")]
+pub mod decoder;
+#[cfg(feature = "alloc")]
+mod deque;
+#[cfg(feature = "dotnet-names")]
+pub mod dotnet;
+#[cfg(feature = "embedded-io")]
+pub mod embedded;
+#[cfg(feature = "embedded-io-async")]
+pub mod embedded_async;
+pub mod error;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+mod field;
+#[cfg(feature = "futures-io")]
+pub mod futures;
+mod hex;
+mod hexdump;
+#[cfg(feature = "std")]
+pub mod io;
+pub mod iter;
+pub mod java;
+pub mod js;
+#[cfg(feature = "mmap")]
+pub mod mmap;
+mod nibble;
+pub mod node;
+mod padded;
+pub mod protobuf;
+mod python;
+#[macro_use]
+pub mod reader;
+pub mod records;
+mod reverse;
+mod rfc4648;
+#[cfg(feature = "alloc")]
+pub mod schema;
+#[cfg(feature = "serde")]
+pub mod serde;
+pub mod shared;
+pub mod sink;
+mod split;
+pub mod table;
+#[cfg(feature = "tokio")]
+pub mod tokio;
+#[cfg(feature = "ux")]
+pub mod ux;
+mod varint;
+mod wrapping;
+mod write;
+#[cfg(feature = "alloc")]
+pub mod writer;
+
+pub use base58::Base58;
+pub use base85::Base85;
+pub use binary::BinaryReader;
+#[cfg(feature = "alloc")]
+pub use binary::BinaryWriter;
+pub use bits::*;
+pub use bitstream::*;
+#[cfg(feature = "alloc")]
+pub use builder::BytesBuilder;
+pub use chain::ChainReader;
+pub use decimal::Decimal;
+#[cfg(feature = "alloc")]
+pub use deque::*;
+pub use error::{Error, ErrorKind};
+pub use field::*;
+pub use hex::*;
+pub use hexdump::*;
+#[cfg(feature = "std")]
+pub use io::{binary_read, binary_write, EndianReader, EndianWriter, VectoredWriter};
+pub use iter::{IterReader, TryIterReader};
+pub use java::DataInput;
+#[cfg(feature = "alloc")]
+pub use java::DataOutput;
+pub use js::DataView;
+pub use nibble::*;
+pub use padded::*;
+pub use python::*;
+pub use reader::{FromReader, Mark, Reader, SeekFrom};
+pub use records::Records;
+pub use reverse::*;
+pub use rfc4648::{Base16, Base32, Base64};
+pub use shared::SharedReader;
+pub use sink::{ByteSink, SinkWriter};
+pub use split::*;
+pub use table::OffsetTable;
+#[cfg(feature = "ux")]
+pub use ux::NarrowInt;
+pub use varint::*;
+pub use wrapping::*;
+pub use write::*;
+#[cfg(feature = "alloc")]
+pub use writer::{Placeholder, ToWriter, Writer};
+
+/// Result type returned by the fallible conversions.
+pub type Result<T> = core::result::Result<T, Error>;
+
 pub mod endian {
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    #[cfg_attr(feature = "defmt", derive(defmt::Format))]
     pub enum Endian {
         LE,
         BE,
@@ -29,7 +185,7 @@ impl BitConvEndian for Big {
 impl BitConvEndian for Native {}
 
 macro_rules! BitConvImpl {
-    ($type:ty, $generic:ty, $data:tt, $start:tt) => {{
+    (@try $type:ty, $generic:ty, $data:tt, $start:tt) => {{
         let f = match <$generic>::ENDIANNESS {
             Endian::LE => <$type>::from_le_bytes,
             Endian::BE => <$type>::from_be_bytes,
@@ -39,8 +195,329 @@ macro_rules! BitConvImpl {
             .get($start..)
             .and_then(|bytes| bytes.get(..mem::size_of::<$type>()))
             .map(|bytes| f(bytes.try_into().unwrap()))
-            .expect(concat!("Failed to read ", stringify!($type), ". Invalid buffer provided."))
     }};
+    ($type:ty, $generic:ty, $data:tt, $start:tt) => {
+        match BitConvImpl!(@try $type, $generic, $data, $start) {
+            Some(value) => value,
+            None => error::conversion_failed(
+                "read",
+                stringify!($type),
+                Error::new(ErrorKind::OutOfBounds {
+                    start_index: $start,
+                    requested: mem::size_of::<$type>(),
+                    len: $data.len(),
+                }),
+            ),
+        }
+    };
+}
+
+/// Types that can be converted from a sequence of bytes at a specified
+/// position in a byte array.
+///
+/// `FromBytes` is implemented for the integer primitives and for tuples of up
+/// to twelve `FromBytes` types, whose elements are converted one after another
+/// in order. With the `derive` feature enabled, it can also be derived for
+/// structs.
+///
+/// Any start_index is accepted, so indices taken from untrusted headers can be
+/// passed as they are: one past the end of data, up to and including
+/// `usize::MAX`, fails with `ErrorKind::OutOfBounds` carrying it unchanged
+/// rather than wrapping around or overflowing.
+pub trait FromBytes: Sized {
+    /// Converts the bytes starting at start_index to a value, returning it
+    /// along with the number of bytes read.
+    fn from_bytes<T: BitConvEndian>(data: &[u8], start_index: usize) -> Result<(Self, usize)>;
+
+    /// Converts the bytes starting at start_index to a value as laid out in
+    /// the given format version.
+    ///
+    /// Types whose layout does not change between versions read the same
+    /// bytes as `from_bytes`, which is what the default implementation does.
+    #[inline]
+    fn from_bytes_versioned<T: BitConvEndian>(
+        data: &[u8],
+        start_index: usize,
+        version: u32,
+    ) -> Result<(Self, usize)> {
+        let _ = version;
+        Self::from_bytes::<T>(data, start_index)
+    }
+}
+
+/// Conversion from a sequence of bytes that carries on past failed fields.
+///
+/// Instead of stopping at the first field that cannot be converted, the field
+/// is set to its default value and the conversion continues with the next
+/// one, so that every problem in a damaged buffer can be reported at once.
+/// A failed field consumes no bytes, so the fields after it are read from
+/// where it started.
+#[cfg(feature = "alloc")]
+pub trait FromBytesLenient: Sized {
+    /// Converts the bytes starting at start_index to a value, returning it
+    /// along with the number of bytes read and the errors of the fields that
+    /// were replaced by their default value.
+    fn from_bytes_lenient<T: BitConvEndian>(
+        data: &[u8],
+        start_index: usize,
+    ) -> (Self, usize, alloc::vec::Vec<Error>);
+}
+
+/// Converts a value with the endianness chosen at runtime.
+pub(crate) fn from_bytes_with<V: FromBytes>(
+    endian: Endian,
+    data: &[u8],
+    start_index: usize,
+) -> Result<(V, usize)> {
+    match endian {
+        Endian::LE => V::from_bytes::<Little>(data, start_index),
+        Endian::BE => V::from_bytes::<Big>(data, start_index),
+        Endian::NE => V::from_bytes::<Native>(data, start_index),
+    }
+}
+
+/// Converts a value to bytes with the endianness chosen at runtime.
+pub(crate) fn to_bytes_with<V: ToBytes + ?Sized>(
+    endian: Endian,
+    value: &V,
+    data: &mut [u8],
+    start_index: usize,
+) -> Result<usize> {
+    match endian {
+        Endian::LE => value.to_bytes::<Little>(data, start_index),
+        Endian::BE => value.to_bytes::<Big>(data, start_index),
+        Endian::NE => value.to_bytes::<Native>(data, start_index),
+    }
+}
+
+macro_rules! FromBytesImpl {
+    ($($type:ty),+) => {$(
+        impl FromBytes for $type {
+            #[inline]
+            fn from_bytes<T: BitConvEndian>(data: &[u8], start_index: usize) -> Result<(Self, usize)> {
+                let requested = mem::size_of::<$type>();
+                let value = BitConvImpl!(@try $type, T, data, start_index).ok_or_else(|| {
+                    Error::new(ErrorKind::OutOfBounds { start_index, requested, len: data.len() })
+                })?;
+                #[cfg(feature = "tracing")]
+                tracing::trace!(
+                    target: "bitconv",
+                    start_index,
+                    r#type = stringify!($type),
+                    value
+                );
+                Ok((value, requested))
+            }
+        }
+    )+};
+}
+
+FromBytesImpl!(i8, i16, i32, i64, u8, u16, u32, u64);
+
+impl<const N: usize> FromBytes for [u8; N] {
+    #[inline]
+    fn from_bytes<T: BitConvEndian>(data: &[u8], start_index: usize) -> Result<(Self, usize)> {
+        data.get(start_index..)
+            .and_then(|bytes| bytes.get(..N))
+            .map(|bytes| (bytes.try_into().unwrap(), N))
+            .ok_or_else(|| {
+                Error::new(ErrorKind::OutOfBounds { start_index, requested: N, len: data.len() })
+            })
+    }
+}
+
+macro_rules! FromBytesTupleImpl {
+    ($($index:tt $name:ident $value:ident),+) => {
+        impl<$($name: FromBytes),+> FromBytes for ($($name,)+) {
+            #[inline]
+            fn from_bytes<T: BitConvEndian>(data: &[u8], start_index: usize) -> Result<(Self, usize)> {
+                let mut offset = start_index;
+                $(
+                    let ($value, read) = $name::from_bytes::<T>(data, offset)
+                        .map_err(|error| error.with_field(stringify!($index)))?;
+                    offset += read;
+                )+
+                Ok((($($value,)+), offset - start_index))
+            }
+
+            #[inline]
+            fn from_bytes_versioned<T: BitConvEndian>(
+                data: &[u8],
+                start_index: usize,
+                version: u32,
+            ) -> Result<(Self, usize)> {
+                let mut offset = start_index;
+                $(
+                    let ($value, read) = $name::from_bytes_versioned::<T>(data, offset, version)
+                        .map_err(|error| error.with_field(stringify!($index)))?;
+                    offset += read;
+                )+
+                Ok((($($value,)+), offset - start_index))
+            }
+        }
+    };
+}
+
+FromBytesTupleImpl!(0 A a);
+FromBytesTupleImpl!(0 A a, 1 B b);
+FromBytesTupleImpl!(0 A a, 1 B b, 2 C c);
+FromBytesTupleImpl!(0 A a, 1 B b, 2 C c, 3 D d);
+FromBytesTupleImpl!(0 A a, 1 B b, 2 C c, 3 D d, 4 E e);
+FromBytesTupleImpl!(0 A a, 1 B b, 2 C c, 3 D d, 4 E e, 5 F f);
+FromBytesTupleImpl!(0 A a, 1 B b, 2 C c, 3 D d, 4 E e, 5 F f, 6 G g);
+FromBytesTupleImpl!(0 A a, 1 B b, 2 C c, 3 D d, 4 E e, 5 F f, 6 G g, 7 H h);
+FromBytesTupleImpl!(0 A a, 1 B b, 2 C c, 3 D d, 4 E e, 5 F f, 6 G g, 7 H h, 8 I i);
+FromBytesTupleImpl!(0 A a, 1 B b, 2 C c, 3 D d, 4 E e, 5 F f, 6 G g, 7 H h, 8 I i, 9 J j);
+FromBytesTupleImpl!(0 A a, 1 B b, 2 C c, 3 D d, 4 E e, 5 F f, 6 G g, 7 H h, 8 I i, 9 J j, 10 K k);
+FromBytesTupleImpl!(
+    0 A a, 1 B b, 2 C c, 3 D d, 4 E e, 5 F f, 6 G g, 7 H h, 8 I i, 9 J j, 10 K k, 11 L l
+);
+
+/// Types that can be converted to a sequence of bytes written at a specified
+/// position in a byte array.
+///
+/// `ToBytes` is implemented for the integer and floating-point primitives and
+/// `bool`, which is written as a byte of 1 or 0, for tuples of up to
+/// twelve `ToBytes` types and for slices, `Vec`s and `Option`s of them, whose
+/// elements are written one after another in order. With the `derive` feature
+/// enabled, it can also be derived for structs and tagged enums.
+///
+/// As with [`FromBytes`], a start_index past the end of data, however large,
+/// fails with `ErrorKind::OutOfBounds`.
+pub trait ToBytes {
+    /// Writes the bytes of the value starting at start_index, returning the
+    /// number of bytes written.
+    ///
+    /// A primitive that does not fit leaves data untouched, while a tuple
+    /// keeps the elements written before the one that did not fit.
+    fn to_bytes<T: BitConvEndian>(&self, data: &mut [u8], start_index: usize) -> Result<usize>;
+
+    /// Returns the number of bytes `to_bytes` writes for the value.
+    fn byte_len(&self) -> usize;
+}
+
+macro_rules! ToBytesImpl {
+    ($($type:ty),+) => {$(
+        impl ToBytes for $type {
+            #[inline]
+            fn to_bytes<T: BitConvEndian>(&self, data: &mut [u8], start_index: usize) -> Result<usize> {
+                let bytes = match T::ENDIANNESS {
+                    Endian::LE => self.to_le_bytes(),
+                    Endian::BE => self.to_be_bytes(),
+                    Endian::NE => self.to_ne_bytes(),
+                };
+                let len = data.len();
+                data.get_mut(start_index..)
+                    .and_then(|data| data.get_mut(..bytes.len()))
+                    .map(|data| {
+                        data.copy_from_slice(&bytes);
+                        bytes.len()
+                    })
+                    .ok_or_else(|| {
+                        Error::new(ErrorKind::OutOfBounds { start_index, requested: bytes.len(), len })
+                    })
+            }
+
+            #[inline]
+            fn byte_len(&self) -> usize { mem::size_of::<$type>() }
+        }
+    )+};
+}
+
+ToBytesImpl!(i8, i16, i32, i64, u8, u16, u32, u64, f32, f64);
+
+impl ToBytes for bool {
+    #[inline]
+    fn to_bytes<T: BitConvEndian>(&self, data: &mut [u8], start_index: usize) -> Result<usize> {
+        u8::from(*self).to_bytes::<T>(data, start_index)
+    }
+
+    #[inline]
+    fn byte_len(&self) -> usize { 1 }
+}
+
+impl<const N: usize> ToBytes for [u8; N] {
+    #[inline]
+    fn to_bytes<T: BitConvEndian>(&self, data: &mut [u8], start_index: usize) -> Result<usize> {
+        let len = data.len();
+        data.get_mut(start_index..)
+            .and_then(|data| data.get_mut(..N))
+            .map(|data| {
+                data.copy_from_slice(self);
+                N
+            })
+            .ok_or_else(|| Error::new(ErrorKind::OutOfBounds { start_index, requested: N, len }))
+    }
+
+    #[inline]
+    fn byte_len(&self) -> usize { N }
+}
+
+macro_rules! ToBytesTupleImpl {
+    ($($index:tt $name:ident),+) => {
+        impl<$($name: ToBytes),+> ToBytes for ($($name,)+) {
+            #[inline]
+            fn to_bytes<T: BitConvEndian>(&self, data: &mut [u8], start_index: usize) -> Result<usize> {
+                let mut offset = start_index;
+                $(
+                    offset += self.$index.to_bytes::<T>(data, offset)
+                        .map_err(|error| error.with_field(stringify!($index)))?;
+                )+
+                Ok(offset - start_index)
+            }
+
+            #[inline]
+            fn byte_len(&self) -> usize { 0 $(+ self.$index.byte_len())+ }
+        }
+    };
+}
+
+ToBytesTupleImpl!(0 A);
+ToBytesTupleImpl!(0 A, 1 B);
+ToBytesTupleImpl!(0 A, 1 B, 2 C);
+ToBytesTupleImpl!(0 A, 1 B, 2 C, 3 D);
+ToBytesTupleImpl!(0 A, 1 B, 2 C, 3 D, 4 E);
+ToBytesTupleImpl!(0 A, 1 B, 2 C, 3 D, 4 E, 5 F);
+ToBytesTupleImpl!(0 A, 1 B, 2 C, 3 D, 4 E, 5 F, 6 G);
+ToBytesTupleImpl!(0 A, 1 B, 2 C, 3 D, 4 E, 5 F, 6 G, 7 H);
+ToBytesTupleImpl!(0 A, 1 B, 2 C, 3 D, 4 E, 5 F, 6 G, 7 H, 8 I);
+ToBytesTupleImpl!(0 A, 1 B, 2 C, 3 D, 4 E, 5 F, 6 G, 7 H, 8 I, 9 J);
+ToBytesTupleImpl!(0 A, 1 B, 2 C, 3 D, 4 E, 5 F, 6 G, 7 H, 8 I, 9 J, 10 K);
+ToBytesTupleImpl!(0 A, 1 B, 2 C, 3 D, 4 E, 5 F, 6 G, 7 H, 8 I, 9 J, 10 K, 11 L);
+
+impl<V: ToBytes> ToBytes for [V] {
+    fn to_bytes<T: BitConvEndian>(&self, data: &mut [u8], start_index: usize) -> Result<usize> {
+        let mut offset = start_index;
+        for (index, value) in self.iter().enumerate() {
+            offset += value.to_bytes::<T>(data, offset).map_err(|error| error.with_index(index))?;
+        }
+        Ok(offset - start_index)
+    }
+
+    fn byte_len(&self) -> usize { self.iter().map(ToBytes::byte_len).sum() }
+}
+
+#[cfg(feature = "alloc")]
+impl<V: ToBytes> ToBytes for alloc::vec::Vec<V> {
+    #[inline]
+    fn to_bytes<T: BitConvEndian>(&self, data: &mut [u8], start_index: usize) -> Result<usize> {
+        self.as_slice().to_bytes::<T>(data, start_index)
+    }
+
+    #[inline]
+    fn byte_len(&self) -> usize { self.as_slice().byte_len() }
+}
+
+/// An absent value writes no bytes, matching how optional fields of derived
+/// types are read.
+impl<V: ToBytes> ToBytes for Option<V> {
+    #[inline]
+    fn to_bytes<T: BitConvEndian>(&self, data: &mut [u8], start_index: usize) -> Result<usize> {
+        self.as_ref().map_or(Ok(0), |value| value.to_bytes::<T>(data, start_index))
+    }
+
+    #[inline]
+    fn byte_len(&self) -> usize { self.as_ref().map_or(0, ToBytes::byte_len) }
 }
 
 /// Returns a 16-bit signed integer converted from two bytes at a specified
@@ -60,6 +537,7 @@ macro_rules! BitConvImpl {
 /// assert_eq!(255, to_int16::<Big>(&buffer, 2));
 /// ```
 #[inline]
+#[track_caller]
 pub fn to_int16<T: BitConvEndian>(data: &[u8], start_index: usize) -> i16 {
     BitConvImpl!(i16, T, data, start_index)
 }
@@ -81,6 +559,7 @@ pub fn to_int16<T: BitConvEndian>(data: &[u8], start_index: usize) -> i16 {
 /// assert_eq!(-2146424848, to_int32::<Big>(&buffer, 3));
 /// ```
 #[inline]
+#[track_caller]
 pub fn to_int32<T: BitConvEndian>(data: &[u8], start_index: usize) -> i32 {
     BitConvImpl!(i32, T, data, start_index)
 }
@@ -102,6 +581,7 @@ pub fn to_int32<T: BitConvEndian>(data: &[u8], start_index: usize) -> i32 {
 /// assert_eq!(140806877927665, to_int64::<Big>(&buffer, 1));
 /// ```
 #[inline]
+#[track_caller]
 pub fn to_int64<T: BitConvEndian>(data: &[u8], start_index: usize) -> i64 {
     BitConvImpl!(i64, T, data, start_index)
 }
@@ -123,6 +603,7 @@ pub fn to_int64<T: BitConvEndian>(data: &[u8], start_index: usize) -> i64 {
 /// assert_eq!(255, to_uint16::<Big>(&buffer, 2));
 /// ```
 #[inline]
+#[track_caller]
 pub fn to_uint16<T: BitConvEndian>(data: &[u8], start_index: usize) -> u16 {
     BitConvImpl!(u16, T, data, start_index)
 }
@@ -144,6 +625,7 @@ pub fn to_uint16<T: BitConvEndian>(data: &[u8], start_index: usize) -> u16 {
 /// assert_eq!(16712448, to_uint32::<Big>(&buffer, 6));
 /// ```
 #[inline]
+#[track_caller]
 pub fn to_uint32<T: BitConvEndian>(data: &[u8], start_index: usize) -> u32 {
     BitConvImpl!(u32, T, data, start_index)
 }
@@ -165,10 +647,219 @@ pub fn to_uint32<T: BitConvEndian>(data: &[u8], start_index: usize) -> u32 {
 /// assert_eq!(18374686479671623680, to_uint64::<Big>(&buffer, 2));
 /// ```
 #[inline]
+#[track_caller]
 pub fn to_uint64<T: BitConvEndian>(data: &[u8], start_index: usize) -> u64 {
     BitConvImpl!(u64, T, data, start_index)
 }
 
+/// Returns a Boolean value converted from the byte at a specified position in
+/// a byte array.
+///
+/// The `to_boolean` function returns `true` if the byte at start_index is
+/// nonzero, and `false` otherwise.
+/// # Example
+///
+/// ```
+/// use bitconv::to_boolean;
+///
+/// let buffer = [0, 1, 255];
+/// assert!(!to_boolean(&buffer, 0));
+/// assert!(to_boolean(&buffer, 1));
+/// assert!(to_boolean(&buffer, 2));
+/// ```
+#[inline]
+#[track_caller]
+pub fn to_boolean(data: &[u8], start_index: usize) -> bool {
+    match try_to_boolean(data, start_index) {
+        Ok(value) => value,
+        Err(error) => error::conversion_failed("read", "bool", error),
+    }
+}
+
+/// Returns a Unicode character converted from two bytes at a specified
+/// position in a byte array.
+///
+/// The `to_char` function converts the UTF-16 code unit from index
+/// start_index to start_index + 1 to a `char` value. Unlike C#'s `char`, a
+/// Rust `char` cannot hold half of a surrogate pair, so code units between
+/// `0xD800` and `0xDFFF` panic.
+/// # Example
+///
+/// ```
+/// use bitconv::{
+///     endian::{Big, Little}, to_char
+/// };
+///
+/// let buffer = [65, 0, 233, 0, 0, 216];
+/// assert_eq!('A', to_char::<Little>(&buffer, 0));
+/// assert_eq!('é', to_char::<Little>(&buffer, 2));
+/// assert_eq!('\u{4100}', to_char::<Big>(&buffer, 0));
+/// ```
+#[inline]
+#[track_caller]
+pub fn to_char<T: BitConvEndian>(data: &[u8], start_index: usize) -> char {
+    match try_to_char::<T>(data, start_index) {
+        Ok(value) => value,
+        Err(error) => error::conversion_failed("read", "char", error),
+    }
+}
+
+macro_rules! TryToImpl {
+    ($($(#[$attr:meta])* $name:ident $to:ident $type:ty),+) => {$(
+        #[doc = concat!(
+            "Returns a `", stringify!($type), "` converted from the bytes at a specified ",
+            "position in a byte array, or an `ErrorKind::OutOfBounds` error if data is too ",
+            "short.\n\n",
+            "This is the fallible counterpart of [`", stringify!($to), "`], for parsers ",
+            "that must not panic whatever input they are given."
+        )]
+        $(#[$attr])*
+        #[inline]
+        #[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
+        pub fn $name<T: BitConvEndian>(data: &[u8], start_index: usize) -> Result<$type> {
+            <$type>::from_bytes::<T>(data, start_index).map(|(value, _)| value)
+        }
+    )+};
+}
+
+TryToImpl!(
+    try_to_int16 to_int16 i16,
+    try_to_int32 to_int32 i32,
+    try_to_int64 to_int64 i64,
+    try_to_uint16 to_uint16 u16,
+    /// # Example
+    ///
+    /// ```
+    /// use bitconv::{endian::Little, try_to_uint32, ErrorKind};
+    ///
+    /// let buffer = [15, 0, 0, 0, 0, 16, 0, 255, 3, 0, 0, 202, 19];
+    /// assert_eq!(Ok(261888), try_to_uint32::<Little>(&buffer, 6));
+    /// assert_eq!(
+    ///     ErrorKind::OutOfBounds { start_index: 10, requested: 4, len: 13 },
+    ///     try_to_uint32::<Little>(&buffer, 10).unwrap_err().kind()
+    /// );
+    /// ```
+    try_to_uint32 to_uint32 u32,
+    try_to_uint64 to_uint64 u64
+);
+
+/// Returns a `bool` converted from the byte at a specified position in a byte
+/// array, or an `ErrorKind::OutOfBounds` error if data is too short.
+///
+/// This is the fallible counterpart of [`to_boolean`].
+#[inline]
+#[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
+pub fn try_to_boolean(data: &[u8], start_index: usize) -> Result<bool> {
+    u8::from_bytes::<Native>(data, start_index).map(|(byte, _)| byte != 0)
+}
+
+/// Returns a `char` converted from the UTF-16 code unit at a specified
+/// position in a byte array.
+///
+/// This is the fallible counterpart of [`to_char`], failing with
+/// `ErrorKind::OutOfBounds` if data is too short and with
+/// `ErrorKind::InvalidEncoding` if the code unit is a surrogate.
+#[inline]
+#[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
+pub fn try_to_char<T: BitConvEndian>(data: &[u8], start_index: usize) -> Result<char> {
+    let unit = try_to_uint16::<T>(data, start_index)?;
+    char::from_u32(unit.into())
+        .ok_or_else(|| Error::new(ErrorKind::InvalidEncoding { start_index }))
+}
+
+/// Types that are always converted from the same number of bytes.
+///
+/// `FixedSize` is implemented for the integer primitives and for tuples of up
+/// to twelve `FixedSize` types. With the `derive` feature enabled, it can also
+/// be derived for structs whose fields are all `FixedSize`, optionally
+/// checking the total at compile time with `#[bitconv(size = N)]`.
+pub trait FixedSize {
+    /// The number of bytes the value is converted from.
+    const SIZE: usize;
+}
+
+macro_rules! FixedSizeImpl {
+    ($($type:ty),+) => {$(
+        impl FixedSize for $type {
+            const SIZE: usize = mem::size_of::<$type>();
+        }
+    )+};
+}
+
+FixedSizeImpl!(i8, i16, i32, i64, u8, u16, u32, u64);
+
+impl<const N: usize> FixedSize for [u8; N] {
+    const SIZE: usize = N;
+}
+
+macro_rules! FixedSizeTupleImpl {
+    ($($name:ident),+) => {
+        impl<$($name: FixedSize),+> FixedSize for ($($name,)+) {
+            const SIZE: usize = 0 $(+ $name::SIZE)+;
+        }
+    };
+}
+
+FixedSizeTupleImpl!(A);
+FixedSizeTupleImpl!(A, B);
+FixedSizeTupleImpl!(A, B, C);
+FixedSizeTupleImpl!(A, B, C, D);
+FixedSizeTupleImpl!(A, B, C, D, E);
+FixedSizeTupleImpl!(A, B, C, D, E, F);
+FixedSizeTupleImpl!(A, B, C, D, E, F, G);
+FixedSizeTupleImpl!(A, B, C, D, E, F, G, H);
+FixedSizeTupleImpl!(A, B, C, D, E, F, G, H, I);
+FixedSizeTupleImpl!(A, B, C, D, E, F, G, H, I, J);
+FixedSizeTupleImpl!(A, B, C, D, E, F, G, H, I, J, K);
+FixedSizeTupleImpl!(A, B, C, D, E, F, G, H, I, J, K, L);
+
+/// Returns a tuple of values converted from consecutive bytes at a specified
+/// position in a byte array, along with the number of bytes read.
+///
+/// The `read_tuple` function converts the bytes from index start_index
+/// onwards to each element of the tuple in turn, so that each element starts
+/// where the previous one ended.
+/// # Example
+///
+/// ```
+/// use bitconv::{
+///     endian::{Big, Little}, read_tuple
+/// };
+///
+/// let buffer = [15, 0, 0, 255, 3, 16, 39, 255, 255, 127];
+/// assert_eq!(((15, 268697344), 6), read_tuple::<(u16, u32), Little>(&buffer, 0));
+/// assert_eq!(((0, 65283, 4135), 5), read_tuple::<(u8, u16, u16), Big>(&buffer, 2));
+/// ```
+#[inline]
+#[track_caller]
+pub fn read_tuple<V: FromBytes, T: BitConvEndian>(data: &[u8], start_index: usize) -> (V, usize) {
+    match V::from_bytes::<T>(data, start_index) {
+        Ok(result) => result,
+        Err(error) => error::conversion_failed("read", "tuple", error),
+    }
+}
+
+/// Returns a copy of the N bytes at a specified position in a byte array, or
+/// an `ErrorKind::OutOfBounds` error if data is too short.
+///
+/// This extracts fixed-size fields such as magic numbers, hashes and keys as
+/// arrays without converting a slice at every call site.
+/// # Example
+///
+/// ```
+/// use bitconv::read_array;
+///
+/// let buffer = [137, 80, 78, 71, 13, 10, 26, 10, 0];
+/// assert_eq!(Ok([137, 80, 78, 71]), read_array::<4>(&buffer, 0));
+/// assert_eq!(Ok(*b"PNG"), read_array(&buffer, 1));
+/// assert!(read_array::<4>(&buffer, 6).is_err());
+/// ```
+#[inline]
+#[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
+pub fn read_array<const N: usize>(data: &[u8], start_index: usize) -> Result<[u8; N]> {
+    <[u8; N]>::from_bytes::<Native>(data, start_index).map(|(array, _)| array)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -205,7 +896,9 @@ mod test {
     }
 
     #[test]
-    #[should_panic]
+    #[should_panic(
+        expected = "Failed to read i32: 4 bytes requested at index 16 of a buffer of 19 bytes."
+    )]
     fn to_int32_le_panic_test() {
         let buffer = [15, 0, 0, 0, 0, 16, 0, 255, 3, 0, 0, 202, 154, 59, 255, 255, 255, 255, 127];
         to_int32::<Little>(&buffer, 16);
@@ -445,4 +1138,200 @@ mod test {
         ];
         to_uint64::<Big>(&buffer, 45);
     }
+
+    #[test]
+    fn try_to_test() {
+        let buffer = [15, 0, 0, 128, 16, 39, 240, 216, 241, 255, 127];
+        assert_eq!(Ok(to_int16::<Little>(&buffer, 9)), try_to_int16::<Little>(&buffer, 9));
+        assert_eq!(Ok(to_int32::<Big>(&buffer, 3)), try_to_int32::<Big>(&buffer, 3));
+        assert_eq!(Ok(to_int64::<Little>(&buffer, 3)), try_to_int64::<Little>(&buffer, 3));
+        assert_eq!(Ok(to_uint16::<Big>(&buffer, 0)), try_to_uint16::<Big>(&buffer, 0));
+        assert_eq!(Ok(to_uint32::<Native>(&buffer, 7)), try_to_uint32::<Native>(&buffer, 7));
+        assert_eq!(Ok(to_uint64::<Big>(&buffer, 0)), try_to_uint64::<Big>(&buffer, 0));
+        assert_eq!(
+            Err(Error::new(ErrorKind::OutOfBounds { start_index: 10, requested: 2, len: 11 })),
+            try_to_uint16::<Little>(&buffer, 10)
+        );
+        assert!(try_to_int64::<Big>(&buffer, 4).is_err());
+        assert!(try_to_int32::<Little>(&[], 0).is_err());
+    }
+
+    #[test]
+    fn to_boolean_test() {
+        let buffer = [0, 1, 2, 128, 255];
+        assert!(!to_boolean(&buffer, 0));
+        assert_eq!([true; 4], [1, 2, 3, 4].map(|index| to_boolean(&buffer, index)));
+        assert_eq!(Ok(false), try_to_boolean(&buffer, 0));
+        assert_eq!(
+            Err(Error::new(ErrorKind::OutOfBounds { start_index: 5, requested: 1, len: 5 })),
+            try_to_boolean(&buffer, 5)
+        );
+        assert!(try_to_boolean(&buffer, usize::MAX).is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "Failed to read bool: 1 bytes requested at index 5")]
+    fn to_boolean_panic_test() { to_boolean(&[0; 5], 5); }
+
+    #[test]
+    fn to_char_test() {
+        let buffer = [65, 0, 172, 32, 255, 215, 0, 224, 0, 216, 255, 223];
+        assert_eq!('A', to_char::<Little>(&buffer, 0));
+        assert_eq!('€', to_char::<Little>(&buffer, 2));
+        assert_eq!('\u{d7ff}', to_char::<Little>(&buffer, 4));
+        assert_eq!('\u{e000}', to_char::<Little>(&buffer, 6));
+        assert_eq!('\u{ac20}', to_char::<Big>(&buffer, 2));
+        assert_eq!(Ok('\u{ffd7}'), try_to_char::<Big>(&buffer, 4));
+        assert_eq!(
+            Err(Error::new(ErrorKind::InvalidEncoding { start_index: 8 })),
+            try_to_char::<Little>(&buffer, 8)
+        );
+        assert!(try_to_char::<Little>(&buffer, 10).is_err());
+        assert!(try_to_char::<Big>(&buffer, 11).is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "Failed to read char: invalid encoding at index 0")]
+    fn to_char_surrogate_panic_test() { to_char::<Little>(&[0, 220], 0); }
+
+    #[test]
+    fn read_tuple_test_le() {
+        let buffer = [15, 0, 0, 255, 3, 16, 39, 255, 255, 127];
+        assert_eq!(((15,), 2), read_tuple::<(u16,), Little>(&buffer, 0));
+        assert_eq!(((15, 0, -256), 4), read_tuple::<(u8, u8, i16), Little>(&buffer, 0));
+        assert_eq!(((1023, 4294911760), 6), read_tuple::<(u16, u32), Little>(&buffer, 3));
+        assert_eq!(
+            ((3, 16, 39, -1, 32767), 6),
+            read_tuple::<(i8, u8, i8, i8, i16), Little>(&buffer, 4)
+        );
+        assert_eq!(((9223371105115569920,), 8), read_tuple::<(u64,), Little>(&buffer, 2));
+    }
+
+    #[test]
+    #[should_panic]
+    fn read_tuple_le_panic_test() {
+        let buffer = [15, 0, 0, 255, 3, 16, 39, 255, 255, 127];
+        read_tuple::<(u16, u32, u64), Little>(&buffer, 0);
+    }
+
+    #[test]
+    fn read_tuple_test_be() {
+        let buffer = [15, 0, 0, 255, 3, 16, 39, 255, 255, 127];
+        assert_eq!(((3840,), 2), read_tuple::<(u16,), Big>(&buffer, 0));
+        assert_eq!(((15, 0, 255), 4), read_tuple::<(u8, u8, i16), Big>(&buffer, 0));
+        assert_eq!(((65283, 271056895), 6), read_tuple::<(u16, u32), Big>(&buffer, 3));
+        assert_eq!(((251658495, 51390463), 8), read_tuple::<(u32, u32), Big>(&buffer, 0));
+    }
+
+    #[test]
+    #[should_panic]
+    fn read_tuple_be_panic_test() {
+        let buffer = [15, 0, 0, 255, 3, 16, 39, 255, 255, 127];
+        read_tuple::<(u32, u32, u8, u8), Big>(&buffer, 1);
+    }
+
+    #[test]
+    fn fixed_size_test() {
+        assert_eq!(1, u8::SIZE);
+        assert_eq!(2, i16::SIZE);
+        assert_eq!(8, u64::SIZE);
+        assert_eq!(4, <(u32,)>::SIZE);
+        assert_eq!(14, <(u16, u32, u64)>::SIZE);
+        assert_eq!(15, <(u8, (u16, u32), u64)>::SIZE);
+        assert_eq!(20, <([u8; 16], u32)>::SIZE);
+        let buffer = [0; 15];
+        assert_eq!(
+            <(u8, (u16, u32), u64)>::SIZE,
+            read_tuple::<(u8, (u16, u32), u64), Little>(&buffer, 0).1
+        );
+    }
+
+    #[test]
+    fn from_bytes_error_test() {
+        let buffer = [15, 0, 0, 255, 3, 16, 39, 255, 255, 127];
+        let error = u32::from_bytes::<Little>(&buffer, 8).unwrap_err();
+        assert_eq!(ErrorKind::OutOfBounds { start_index: 8, requested: 4, len: 10 }, error.kind());
+        assert!(error.context().is_empty());
+        let error = <(u16, (u8, u64))>::from_bytes::<Big>(&buffer, 0).unwrap_err();
+        assert_eq!(ErrorKind::OutOfBounds { start_index: 3, requested: 8, len: 10 }, error.kind());
+        assert_eq!(
+            [error::Segment::Field("1"), error::Segment::Field("1")],
+            [error.context().iter().next().unwrap(), error.context().iter().nth(1).unwrap()]
+        );
+        let error = i8::from_bytes::<Big>(&buffer, 11).unwrap_err();
+        assert_eq!(ErrorKind::OutOfBounds { start_index: 11, requested: 1, len: 10 }, error.kind());
+    }
+
+    #[test]
+    fn to_bytes_test() {
+        let mut buffer = [0; 10];
+        assert_eq!(Ok(4), 2147483663u32.to_bytes::<Little>(&mut buffer, 0));
+        assert_eq!(Ok(2), (-10000i16).to_bytes::<Big>(&mut buffer, 4));
+        assert_eq!(Ok(4), (-15i8, 258u16, 127u8).to_bytes::<Little>(&mut buffer, 6));
+        assert_eq!([15, 0, 0, 128, 216, 240, 241, 2, 1, 127], buffer);
+        assert_eq!(13, (0u8, (1u32, 2i64)).byte_len());
+        assert_eq!(
+            Err(Error::new(ErrorKind::OutOfBounds { start_index: 7, requested: 4, len: 10 })),
+            u32::MAX.to_bytes::<Native>(&mut buffer, 7)
+        );
+        assert!(0u8.to_bytes::<Native>(&mut buffer, usize::MAX).is_err());
+        let error = (1u64, (2u8, 3u16)).to_bytes::<Big>(&mut buffer, 0).unwrap_err();
+        assert_eq!(ErrorKind::OutOfBounds { start_index: 9, requested: 2, len: 10 }, error.kind());
+        assert_eq!([0, 0, 0, 0, 0, 0, 0, 1, 2, 127], buffer);
+        let mut segments = error.context().iter();
+        assert_eq!(Some(error::Segment::Field("1")), segments.next());
+        assert_eq!(Some(error::Segment::Field("1")), segments.next());
+    }
+
+    #[test]
+    fn start_index_overflow_test() {
+        let mut buffer = [15, 0, 0, 128, 16, 39, 240, 216, 241, 255, 127];
+        for start_index in [usize::MAX, usize::MAX - 1, usize::MAX - 7, 12] {
+            let out_of_bounds =
+                |requested| ErrorKind::OutOfBounds { start_index, requested, len: 11 };
+            assert_eq!(
+                out_of_bounds(8),
+                try_to_uint64::<Little>(&buffer, start_index).unwrap_err().kind()
+            );
+            assert_eq!(
+                out_of_bounds(2),
+                try_to_int16::<Big>(&buffer, start_index).unwrap_err().kind()
+            );
+            assert_eq!(
+                out_of_bounds(1),
+                i8::from_bytes::<Native>(&buffer, start_index).unwrap_err().kind()
+            );
+            let error = <(u32, u8)>::from_bytes::<Little>(&buffer, start_index).unwrap_err();
+            assert_eq!(out_of_bounds(4), error.kind());
+            assert_eq!(out_of_bounds(3), read_array::<3>(&buffer, start_index).unwrap_err().kind());
+            assert_eq!(out_of_bounds(0), read_array::<0>(&buffer, start_index).unwrap_err().kind());
+            let error = 7u64.to_bytes::<Big>(&mut buffer, start_index).unwrap_err();
+            assert_eq!(out_of_bounds(8), error.kind());
+            let error = try_write_uint16_at::<Little>(&mut buffer, start_index, 7).unwrap_err();
+            assert_eq!(out_of_bounds(2), error.kind());
+        }
+        assert_eq!([15, 0, 0, 128, 16, 39, 240, 216, 241, 255, 127], buffer);
+    }
+
+    #[test]
+    #[should_panic(expected = "Failed to read u32: 4 bytes requested at index")]
+    fn start_index_overflow_panic_test() { to_uint32::<Little>(&[0; 8], usize::MAX - 2); }
+
+    #[test]
+    fn read_array_test() {
+        let buffer = [15, 0, 0, 128, 16, 39, 240, 216, 241, 255, 127];
+        assert_eq!(Ok([15, 0, 0, 128]), read_array::<4>(&buffer, 0));
+        assert_eq!(Ok([255, 127]), read_array::<2>(&buffer, 9));
+        assert_eq!(Ok([]), read_array::<0>(&buffer, 11));
+        assert_eq!(
+            Err(Error::new(ErrorKind::OutOfBounds { start_index: 8, requested: 4, len: 11 })),
+            read_array::<4>(&buffer, 8)
+        );
+        assert!(read_array::<0>(&buffer, 12).is_err());
+        assert_eq!(Ok(((16, [39, 240]), 3)), <(u8, [u8; 2])>::from_bytes::<Big>(&buffer, 4));
+        let mut patched = buffer;
+        assert_eq!(Ok(3), [1, 2, 3].to_bytes::<Big>(&mut patched, 8));
+        assert_eq!([16, 39, 240, 216, 1, 2, 3], patched[4..]);
+        assert!([0; 4].to_bytes::<Big>(&mut patched, 8).is_err());
+    }
 }
@@ -0,0 +1,152 @@
+//! Random access to memory-mapped files.
+use crate::{BitConvEndian, FixedSize, FromBytes, OffsetTable, Reader, Records, Result};
+use ::memmap2::Mmap;
+use core::{convert::TryFrom, ops::Deref};
+use std::{fs::File, io, path::Path};
+
+/// Read-only memory map of a file, exposing the slice-based views of the
+/// crate over its contents.
+///
+/// Only the pages that are accessed are read from disk, which suits large
+/// archives that are looked up at random rather than parsed from start to
+/// end. The map dereferences to `[u8]`, so the free functions of the crate
+/// can be used on it as well.
+/// # Example
+///
+/// ```
+/// use bitconv::{endian::Little, mmap::MappedFile};
+///
+/// # let path = std::env::temp_dir().join("bitconv-mmap-doctest.bin");
+/// # std::fs::write(&path, [2, 5, 0, 7, 0, 104, 105, 33]).unwrap();
+/// // Safety: the archive is not modified while it is mapped.
+/// let archive = unsafe { MappedFile::open(&path) }.unwrap();
+/// let table = archive.offset_table_with_count_prefix::<u16, u8, Little>(0).unwrap();
+/// assert_eq!(Ok(&b"hi"[..]), table.get(0));
+/// assert_eq!(Ok(2), archive.reader::<Little>().read_uint8());
+/// # drop(archive);
+/// # std::fs::remove_file(&path).unwrap();
+/// ```
+pub struct MappedFile {
+    map: Mmap,
+}
+
+impl MappedFile {
+    /// Opens the file at path and maps it into memory.
+    ///
+    /// # Safety
+    ///
+    /// The file must not be modified or truncated, by this or any other
+    /// process, for as long as the map is alive, as the views over it assume
+    /// its contents never change.
+    pub unsafe fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        MappedFile::map(&File::open(path)?)
+    }
+
+    /// Maps the contents of an open file into memory.
+    ///
+    /// The file can be closed afterwards without affecting the map.
+    ///
+    /// # Safety
+    ///
+    /// The same as for [`open`](MappedFile::open).
+    pub unsafe fn map(file: &File) -> io::Result<Self> { Ok(MappedFile { map: Mmap::map(file)? }) }
+
+    /// Returns the mapped contents of the file.
+    pub fn as_slice(&self) -> &[u8] { &self.map }
+
+    /// Returns a reader positioned at the start of the file.
+    pub fn reader<E: BitConvEndian>(&self) -> Reader<'_, E> { Reader::new(&self.map) }
+
+    /// Returns a view over the file as consecutive records of type `T`.
+    pub fn records<T: FromBytes + FixedSize, E: BitConvEndian>(&self) -> Records<'_, T, E> {
+        Records::new(&self.map)
+    }
+
+    /// Returns a table of len offsets stored at start_index in the file.
+    pub fn offset_table<O, E>(
+        &self,
+        start_index: usize,
+        len: usize,
+    ) -> Result<OffsetTable<'_, O, E>>
+    where
+        O: FromBytes + FixedSize,
+        usize: TryFrom<O>,
+        E: BitConvEndian,
+    {
+        OffsetTable::new(&self.map, start_index, len)
+    }
+
+    /// Returns a table whose offsets follow a count of type `C` stored at
+    /// start_index in the file.
+    pub fn offset_table_with_count_prefix<O, C, E>(
+        &self,
+        start_index: usize,
+    ) -> Result<OffsetTable<'_, O, E>>
+    where
+        O: FromBytes + FixedSize,
+        usize: TryFrom<O>,
+        C: FromBytes,
+        usize: TryFrom<C>,
+        E: BitConvEndian,
+    {
+        OffsetTable::with_count_prefix::<C>(&self.map, start_index)
+    }
+}
+
+impl Deref for MappedFile {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] { &self.map }
+}
+
+impl AsRef<[u8]> for MappedFile {
+    fn as_ref(&self) -> &[u8] { &self.map }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{endian::*, Error, ErrorKind};
+    use std::{format, fs, path::PathBuf, process};
+
+    /// Writes bytes to a file unique to the test, returning its path.
+    fn temp_file(name: &str, bytes: &[u8]) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("bitconv-{}-{}.bin", name, process::id()));
+        fs::write(&path, bytes).unwrap();
+        path
+    }
+
+    #[test]
+    fn mapped_file_test() {
+        let path = temp_file("mapped", &[15, 0, 0, 128, 16, 39, 240, 216, 241, 255, 127]);
+        let file = unsafe { MappedFile::open(&path) }.unwrap();
+        assert_eq!(11, file.len());
+        assert_eq!(Ok(2147483663), file.reader::<Little>().read_uint32());
+        let records = file.records::<u32, Big>();
+        assert_eq!(2, records.len());
+        assert_eq!(Ok(271053016), records.get(1));
+        assert_eq!([241, 255, 127], records.remainder());
+        let table = file.offset_table::<u8, Little>(0, 2).unwrap();
+        assert_eq!(Err(Error::new(ErrorKind::InvalidOffset { start_index: 0 })), table.get(0));
+        assert_eq!(Ok(&file[..]), table.get(1));
+        assert_eq!(
+            Err(Error::new(ErrorKind::OutOfBounds { start_index: 10, requested: 2, len: 11 })),
+            file.offset_table::<u16, Little>(10, 1).map(|_| ())
+        );
+        drop(file);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn mapped_file_count_prefix_test() {
+        let path = temp_file("count-prefix", &[2, 5, 0, 7, 0, 104, 105, 33]);
+        let file = unsafe { MappedFile::map(&File::open(&path).unwrap()) }.unwrap();
+        let table = file.offset_table_with_count_prefix::<u16, u8, Little>(0).unwrap();
+        assert_eq!(2, table.len());
+        assert_eq!(Ok(&b"hi"[..]), table.get(0));
+        assert_eq!(Ok(&b"!"[..]), table.get(1));
+        assert_eq!(b"hi!", &file.as_slice()[5..]);
+        drop(file);
+        fs::remove_file(&path).unwrap();
+    }
+}
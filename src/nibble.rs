@@ -0,0 +1,178 @@
+//! Conversions of the 4-bit halves of bytes, for packed BCD digits and the
+//! nibble-oriented fields of some radio protocols.
+use crate::{endian::Native, error, Error, ErrorKind, FromBytes, Result};
+use core::convert::TryInto;
+
+macro_rules! NibbleImpl {
+    ($($(#[$attr:meta])* $half:literal $shift:literal
+        $to:ident $try_to:ident $write:ident $try_write:ident),+) => {$(
+        #[doc = concat!(
+            "Returns the ", $half, " nibble of the byte at a specified position in a byte ",
+            "array, as a value from 0 to 15.\n",
+            "# Panics\n\n",
+            "Panics if start_index is out of bounds. See [`", stringify!($try_to), "`] for a ",
+            "version that returns an error instead."
+        )]
+        $(#[$attr])*
+        #[inline]
+        #[track_caller]
+        pub fn $to(data: &[u8], start_index: usize) -> u8 {
+            match $try_to(data, start_index) {
+                Ok(value) => value,
+                Err(error) => error::conversion_failed("read", "nibble", error),
+            }
+        }
+
+        #[doc = concat!(
+            "Returns the ", $half, " nibble of the byte at a specified position in a byte ",
+            "array, or fails with `ErrorKind::OutOfBounds` if start_index is out of bounds.\n\n",
+            "This is the fallible counterpart of [`", stringify!($to), "`]."
+        )]
+        #[inline]
+        #[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
+        pub fn $try_to(data: &[u8], start_index: usize) -> Result<u8> {
+            u8::from_bytes::<Native>(data, start_index).map(|(byte, _)| byte >> $shift & 0xf)
+        }
+
+        #[doc = concat!(
+            "Writes value, from 0 to 15, to the ", $half, " nibble of the byte at a specified ",
+            "position in a byte array, leaving its other nibble as it is.\n",
+            "# Panics\n\n",
+            "Panics if start_index is out of bounds or value is larger than 15. See [`",
+            stringify!($try_write), "`] for a version that returns an error instead."
+        )]
+        #[inline]
+        #[track_caller]
+        pub fn $write(data: &mut [u8], start_index: usize, value: u8) {
+            if let Err(error) = $try_write(data, start_index, value) {
+                error::conversion_failed("write", "nibble", error);
+            }
+        }
+
+        #[doc = concat!(
+            "Writes value, from 0 to 15, to the ", $half, " nibble of the byte at a specified ",
+            "position in a byte array, leaving its other nibble as it is.\n\n",
+            "Fails with `ErrorKind::InvalidEncoding` if value is larger than 15, and with ",
+            "`ErrorKind::OutOfBounds` if start_index is out of bounds, leaving data untouched ",
+            "either way."
+        )]
+        #[inline]
+        #[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
+        pub fn $try_write(data: &mut [u8], start_index: usize, value: u8) -> Result<()> {
+            if value > 0xf {
+                return Err(Error::new(ErrorKind::InvalidEncoding { start_index }));
+            }
+            let len = data.len();
+            match data.get_mut(start_index) {
+                Some(byte) => {
+                    *byte = *byte & !(0xf << $shift) | value << $shift;
+                    Ok(())
+                }
+                None => Err(Error::new(ErrorKind::OutOfBounds { start_index, requested: 1, len })),
+            }
+        }
+    )+};
+}
+
+NibbleImpl!(
+    /// # Example
+    ///
+    /// ```
+    /// use bitconv::{to_high_nibble, to_low_nibble};
+    ///
+    /// // The packed BCD digits of 1987.
+    /// let buffer = [0x19, 0x87];
+    /// assert_eq!(1, to_high_nibble(&buffer, 0));
+    /// assert_eq!(9, to_low_nibble(&buffer, 0));
+    /// assert_eq!(8, to_high_nibble(&buffer, 1));
+    /// ```
+    "high" 4 to_high_nibble try_to_high_nibble write_high_nibble_at try_write_high_nibble_at,
+    /// # Example
+    ///
+    /// ```
+    /// use bitconv::{to_low_nibble, write_high_nibble_at, write_low_nibble_at};
+    ///
+    /// let mut buffer = [0x19, 0x87];
+    /// write_low_nibble_at(&mut buffer, 0, 0xa);
+    /// write_high_nibble_at(&mut buffer, 1, 0);
+    /// assert_eq!([0x1a, 0x07], buffer);
+    /// assert_eq!(7, to_low_nibble(&buffer, 1));
+    /// ```
+    "low" 0 to_low_nibble try_to_low_nibble write_low_nibble_at try_write_low_nibble_at
+);
+
+/// Swaps the high and low nibbles of every byte of data, eight bytes at a
+/// time as 64-bit words.
+/// # Example
+///
+/// ```
+/// use bitconv::swap_nibbles;
+///
+/// let mut buffer = [0x12, 0xab, 0xf0];
+/// swap_nibbles(&mut buffer);
+/// assert_eq!([0x21, 0xba, 0x0f], buffer);
+/// ```
+#[inline]
+#[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
+pub fn swap_nibbles(data: &mut [u8]) {
+    const LOW: u64 = 0x0f0f_0f0f_0f0f_0f0f;
+    let mut chunks = data.chunks_exact_mut(8);
+    for chunk in &mut chunks {
+        let word = u64::from_ne_bytes(chunk.as_ref().try_into().unwrap_or_default());
+        let word = (word >> 4 & LOW | (word & LOW) << 4).to_ne_bytes();
+        chunk.iter_mut().zip(word).for_each(|(byte, swapped)| *byte = swapped);
+    }
+    for byte in chunks.into_remainder() {
+        *byte = byte.rotate_left(4);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn nibble_test() {
+        let mut buffer = [0x5a, 0xc3];
+        assert_eq!(Ok(5), try_to_high_nibble(&buffer, 0));
+        assert_eq!(Ok(0xa), try_to_low_nibble(&buffer, 0));
+        assert_eq!(Ok(0xc), try_to_high_nibble(&buffer, 1));
+        assert_eq!(Ok(3), try_to_low_nibble(&buffer, 1));
+        assert_eq!(Ok(()), try_write_high_nibble_at(&mut buffer, 0, 0xf));
+        assert_eq!(Ok(()), try_write_low_nibble_at(&mut buffer, 1, 0));
+        assert_eq!([0xfa, 0xc0], buffer);
+        assert_eq!(
+            Err(Error::new(ErrorKind::OutOfBounds { start_index: 2, requested: 1, len: 2 })),
+            try_to_low_nibble(&buffer, 2)
+        );
+        assert_eq!(
+            Err(Error::new(ErrorKind::OutOfBounds { start_index: 2, requested: 1, len: 2 })),
+            try_write_high_nibble_at(&mut buffer, 2, 1)
+        );
+        assert_eq!(
+            Err(Error::new(ErrorKind::InvalidEncoding { start_index: 1 })),
+            try_write_low_nibble_at(&mut buffer, 1, 0x10)
+        );
+        assert_eq!([0xfa, 0xc0], buffer);
+    }
+
+    #[test]
+    #[should_panic]
+    fn write_nibble_range_test() { write_high_nibble_at(&mut [0], 0, 16); }
+
+    #[test]
+    fn swap_nibbles_test() {
+        let mut buffer = [0u8; 256];
+        buffer.iter_mut().zip(0..=255).for_each(|(byte, value)| *byte = value);
+        for len in [0, 1, 8, 13, 256] {
+            let mut swapped = buffer;
+            swap_nibbles(&mut swapped[..len]);
+            for (index, (&byte, &swapped)) in buffer.iter().zip(&swapped).enumerate() {
+                match index < len {
+                    true => assert_eq!(byte.rotate_left(4), swapped),
+                    false => assert_eq!(byte, swapped),
+                }
+            }
+        }
+    }
+}
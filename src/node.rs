@@ -0,0 +1,122 @@
+//! Extension trait with the method names of Node.js's `Buffer`.
+use crate::{
+    endian::{Big, Little, Native}, FromBytes, Result, ToBytes
+};
+
+macro_rules! BufferExtImpl {
+    ($($read:ident $write:ident $node:literal $type:ty $(as $bits:ty)?, $endian:ty;)+) => {$(
+        #[doc = concat!("Reads a `", stringify!($type), "` at offset, as `read", $node, "` does.")]
+        #[inline]
+        fn $read(&self, offset: usize) -> Result<$type> {
+            BufferExtImpl!(@read self, offset, $type $(as $bits)?, $endian)
+        }
+
+        #[doc = concat!(
+            "Writes a `", stringify!($type), "` at offset, returning the offset past it, as `write",
+            $node, "` does."
+        )]
+        #[inline]
+        fn $write(&mut self, value: $type, offset: usize) -> Result<usize> {
+            value.to_bytes::<$endian>(self.as_mut(), offset).map(|len| offset + len)
+        }
+    )+};
+    (@read $self:ident, $offset:ident, $type:ty, $endian:ty) => {
+        <$type>::from_bytes::<$endian>($self.as_ref(), $offset).map(|(value, _)| value)
+    };
+    (@read $self:ident, $offset:ident, $type:ty as $bits:ty, $endian:ty) => {
+        <$bits>::from_bytes::<$endian>($self.as_ref(), $offset).map(|(bits, _)| <$type>::from_bits(bits))
+    };
+}
+
+/// Extension trait for byte slices with the method names of Node.js's
+/// `Buffer`, such as `read_uint32_le(offset)` for `readUInt32LE(offset)`, so
+/// ported packet code keeps the names it was written with.
+///
+/// The arguments are in the order Node takes them, value first for writes,
+/// and writes return the offset past the value as Node does. Where Node throws
+/// a `RangeError`, the methods fail with `ErrorKind::OutOfBounds` instead,
+/// writing nothing.
+/// # Example
+///
+/// ```
+/// use bitconv::node::BufferExt;
+///
+/// let mut buf = [0; 6];
+/// let offset = buf.write_uint16_be(0xcafe, 0).unwrap();
+/// let offset = buf.write_int32_le(-2, offset).unwrap();
+/// assert_eq!(6, offset);
+/// assert_eq!([0xca, 0xfe, 254, 255, 255, 255], buf);
+/// assert_eq!(Ok(0xcafe), buf.read_uint16_be(0));
+/// assert_eq!(Ok(-2), buf.read_int32_le(2));
+/// assert!(buf.read_uint32_be(4).is_err());
+/// ```
+pub trait BufferExt: AsRef<[u8]> + AsMut<[u8]> {
+    BufferExtImpl!(
+        read_uint8 write_uint8 "UInt8" u8, Native;
+        read_int8 write_int8 "Int8" i8, Native;
+        read_uint16_le write_uint16_le "UInt16LE" u16, Little;
+        read_uint16_be write_uint16_be "UInt16BE" u16, Big;
+        read_int16_le write_int16_le "Int16LE" i16, Little;
+        read_int16_be write_int16_be "Int16BE" i16, Big;
+        read_uint32_le write_uint32_le "UInt32LE" u32, Little;
+        read_uint32_be write_uint32_be "UInt32BE" u32, Big;
+        read_int32_le write_int32_le "Int32LE" i32, Little;
+        read_int32_be write_int32_be "Int32BE" i32, Big;
+        read_big_uint64_le write_big_uint64_le "BigUInt64LE" u64, Little;
+        read_big_uint64_be write_big_uint64_be "BigUInt64BE" u64, Big;
+        read_big_int64_le write_big_int64_le "BigInt64LE" i64, Little;
+        read_big_int64_be write_big_int64_be "BigInt64BE" i64, Big;
+        read_float_le write_float_le "FloatLE" f32 as u32, Little;
+        read_float_be write_float_be "FloatBE" f32 as u32, Big;
+        read_double_le write_double_le "DoubleLE" f64 as u64, Little;
+        read_double_be write_double_be "DoubleBE" f64 as u64, Big;
+    );
+}
+
+impl BufferExt for [u8] {}
+
+impl<const N: usize> BufferExt for [u8; N] {}
+
+#[cfg(feature = "alloc")]
+impl BufferExt for alloc::vec::Vec<u8> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{Error, ErrorKind};
+
+    #[test]
+    fn buffer_ext_read_test() {
+        let buf: &[u8] = &[0xff, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08];
+        assert_eq!(Ok(255), buf.read_uint8(0));
+        assert_eq!(Ok(-1), buf.read_int8(0));
+        assert_eq!(Ok(0x0201), buf.read_uint16_le(1));
+        assert_eq!(Ok(0x0102), buf.read_int16_be(1));
+        assert_eq!(Ok(0x04030201), buf.read_uint32_le(1));
+        assert_eq!(Ok(-0xfefdfd), buf.read_int32_be(0));
+        assert_eq!(Ok(0x0807060504030201), buf.read_big_uint64_le(1));
+        assert_eq!(Ok(0x0102030405060708), buf.read_big_int64_be(1));
+        assert_eq!(Ok(1.5), [0x3f, 0xc0, 0, 0].read_float_be(0));
+        assert_eq!(Ok(-2.0), [0, 0, 0, 0, 0, 0, 0, 0xc0].read_double_le(0));
+        assert_eq!(
+            Err(Error::new(ErrorKind::OutOfBounds { start_index: 2, requested: 8, len: 9 })),
+            buf.read_big_uint64_be(2)
+        );
+    }
+
+    #[test]
+    fn buffer_ext_write_test() {
+        let mut buf = [0u8; 8];
+        assert_eq!(Ok(1), buf.write_int8(-2, 0));
+        assert_eq!(Ok(3), buf.write_uint16_le(0x0201, 1));
+        assert_eq!(Ok(7), buf.write_int32_be(0x03040506, 3));
+        assert_eq!(Ok(8), buf.write_uint8(7, 7));
+        assert_eq!([254, 1, 2, 3, 4, 5, 6, 7], buf);
+        assert!(buf.write_big_int64_le(-1, 1).is_err());
+        assert_eq!([254, 1, 2, 3, 4, 5, 6, 7], buf);
+        assert_eq!(Ok(8), buf.write_double_be(1.0, 0));
+        assert_eq!([0x3f, 0xf0, 0, 0, 0, 0, 0, 0], buf);
+        assert_eq!(Ok(4), buf[..4].write_float_le(1.0, 0));
+        assert_eq!([0, 0, 0x80, 0x3f], buf[..4]);
+    }
+}
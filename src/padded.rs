@@ -0,0 +1,87 @@
+//! Conversions that treat a short buffer as if it were padded with zeros.
+use crate::{endian::Endian, BitConvEndian};
+use core::mem;
+
+macro_rules! PaddedImpl {
+    ($($(#[$attr:meta])* $name:ident $type:ty),+) => {$(
+        #[doc = concat!(
+            "Returns a `", stringify!($type), "` converted from the bytes at a specified ",
+            "position in a byte array, reading zeros for any bytes past the end of data.\n\n",
+            "Nothing is ever out of bounds, so the value is whatever part of it is ",
+            "present: the bytes of a truncated file can be recovered without copying it ",
+            "into a padded buffer first, and a start_index at or past the end of data reads ",
+            "zero."
+        )]
+        $(#[$attr])*
+        #[inline]
+        #[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
+        pub fn $name<T: BitConvEndian>(data: &[u8], start_index: usize) -> $type {
+            let mut bytes = [0; mem::size_of::<$type>()];
+            if let Some(present) = data.get(start_index..) {
+                let len = present.len().min(bytes.len());
+                bytes[..len].copy_from_slice(&present[..len]);
+            }
+            match T::ENDIANNESS {
+                Endian::LE => <$type>::from_le_bytes(bytes),
+                Endian::BE => <$type>::from_be_bytes(bytes),
+                Endian::NE => <$type>::from_ne_bytes(bytes),
+            }
+        }
+    )+};
+}
+
+PaddedImpl!(
+    to_int16_padded i16,
+    to_int32_padded i32,
+    to_int64_padded i64,
+    to_uint16_padded u16,
+    /// # Example
+    ///
+    /// ```
+    /// use bitconv::{
+    ///     endian::{Big, Little}, to_uint32_padded
+    /// };
+    ///
+    /// let truncated = [0xab, 0xcd];
+    /// assert_eq!(0xcdab, to_uint32_padded::<Little>(&truncated, 0));
+    /// assert_eq!(0xabcd0000, to_uint32_padded::<Big>(&truncated, 0));
+    /// assert_eq!(0, to_uint32_padded::<Big>(&truncated, 2));
+    /// ```
+    to_uint32_padded u32,
+    to_uint64_padded u64
+);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{endian::*, to_int16, to_uint32, to_uint64};
+
+    #[test]
+    fn to_padded_test_le() {
+        let buffer = [15, 0, 0, 128, 16, 39, 240, 216, 241, 255, 127];
+        for start_index in 0..buffer.len() - 7 {
+            assert_eq!(
+                to_uint64::<Little>(&buffer, start_index),
+                to_uint64_padded::<Little>(&buffer, start_index)
+            );
+        }
+        assert_eq!(to_int16::<Little>(&buffer, 9), to_int16_padded::<Little>(&buffer, 9));
+        assert_eq!(127, to_int16_padded::<Little>(&buffer, 10));
+        assert_eq!(32767, to_uint32_padded::<Little>(&buffer, 9));
+        assert_eq!(549754886384, to_int64_padded::<Little>(&buffer, 6));
+        assert_eq!(0, to_uint16_padded::<Little>(&buffer, 11));
+        assert_eq!(0, to_int32_padded::<Little>(&buffer, usize::MAX));
+        assert_eq!(0, to_uint64_padded::<Little>(&[], 0));
+    }
+
+    #[test]
+    fn to_padded_test_be() {
+        let buffer = [15, 0, 0, 128, 16, 39, 240, 216, 241, 255, 127];
+        assert_eq!(to_uint32::<Big>(&buffer, 7), to_uint32_padded::<Big>(&buffer, 7));
+        assert_eq!(32512, to_int16_padded::<Big>(&buffer, 10));
+        assert_eq!(4286513152, to_uint32_padded::<Big>(&buffer, 9));
+        assert_eq!(17437795920178577408, to_uint64_padded::<Big>(&buffer, 8));
+        assert_eq!(-268435456, to_int32_padded::<Big>(&buffer[6..7], 0));
+        assert_eq!(0, to_int64_padded::<Big>(&buffer, 12));
+    }
+}
@@ -0,0 +1,352 @@
+//! Field headers of the protobuf wire format, which together with the varints
+//! of [`read_uleb128`] and [`read_sleb128`] cover hand-written parsing of
+//! protobuf messages.
+//!
+//! [`read_uleb128`]: crate::read_uleb128
+//! [`read_sleb128`]: crate::read_sleb128
+#[cfg(feature = "alloc")]
+use crate::Writer;
+use crate::{
+    varint::decode_uleb128, write_uleb128, BitConvEndian, Error, ErrorKind, Reader, Result
+};
+use core::convert::TryFrom;
+
+/// Largest field number a protobuf tag can hold.
+pub const MAX_FIELD_NUMBER: u32 = (1 << 29) - 1;
+
+/// Wire type of a protobuf field, which tells how its value is stored.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum WireType {
+    /// An unsigned LEB128 integer, for `int32`, `uint64`, `sint32`, `bool`,
+    /// enumerations and the like.
+    Varint = 0,
+    /// Eight little-endian bytes, for `fixed64`, `sfixed64` and `double`.
+    I64 = 1,
+    /// An unsigned LEB128 length followed by as many bytes, for strings,
+    /// bytes, embedded messages and packed repeated fields.
+    Len = 2,
+    /// The start of a deprecated group, whose fields follow up to the
+    /// matching [`WireType::EndGroup`].
+    StartGroup = 3,
+    /// The end of a deprecated group.
+    EndGroup = 4,
+    /// Four little-endian bytes, for `fixed32`, `sfixed32` and `float`.
+    I32 = 5,
+}
+
+impl WireType {
+    /// Returns the wire type the 3 low bits of a tag select, or `None` for
+    /// the reserved 6 and 7.
+    pub const fn from_bits(bits: u8) -> Option<Self> {
+        match bits {
+            0 => Some(WireType::Varint),
+            1 => Some(WireType::I64),
+            2 => Some(WireType::Len),
+            3 => Some(WireType::StartGroup),
+            4 => Some(WireType::EndGroup),
+            5 => Some(WireType::I32),
+            _ => None,
+        }
+    }
+}
+
+/// Header of a protobuf field, made of its field number and wire type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Tag {
+    field_number: u32,
+    wire_type: WireType,
+}
+
+impl Tag {
+    /// Creates the tag of a field, or returns `None` if field_number is 0 or
+    /// larger than [`MAX_FIELD_NUMBER`].
+    pub const fn new(field_number: u32, wire_type: WireType) -> Option<Self> {
+        match field_number {
+            1..=MAX_FIELD_NUMBER => Some(Tag { field_number, wire_type }),
+            _ => None,
+        }
+    }
+
+    /// Returns the field number, from 1 to [`MAX_FIELD_NUMBER`].
+    pub const fn field_number(&self) -> u32 { self.field_number }
+
+    /// Returns the wire type.
+    pub const fn wire_type(&self) -> WireType { self.wire_type }
+
+    /// Returns the value the tag stores as a varint.
+    const fn bits(&self) -> u32 { self.field_number << 3 | self.wire_type as u32 }
+}
+
+/// Returns the protobuf tag at start_index, along with the number of bytes it
+/// takes.
+///
+/// Fails with `ErrorKind::OutOfBounds` if data ends before the last byte of
+/// the varint, with `ErrorKind::InvalidEncoding` if it does not fit in 32
+/// bits, and with `ErrorKind::InvalidTag` for a field number of 0 or a
+/// reserved wire type.
+/// # Example
+///
+/// ```
+/// use bitconv::protobuf::{read_tag, WireType};
+///
+/// // The header of `string name = 2;` followed by that of `int32 id = 150;`.
+/// let buffer = [0x12, 0xb0, 0x09];
+/// let (tag, len) = read_tag(&buffer, 0).unwrap();
+/// assert_eq!((2, WireType::Len, 1), (tag.field_number(), tag.wire_type(), len));
+/// let (tag, len) = read_tag(&buffer, 1).unwrap();
+/// assert_eq!((150, WireType::Varint, 2), (tag.field_number(), tag.wire_type(), len));
+/// ```
+#[inline]
+#[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
+pub fn read_tag(data: &[u8], start_index: usize) -> Result<(Tag, usize)> {
+    let (bits, len) = decode_uleb128(data, start_index, 32)?;
+    WireType::from_bits(bits as u8 & 7)
+        .and_then(|wire_type| Tag::new((bits >> 3) as u32, wire_type))
+        .map(|tag| (tag, len))
+        .ok_or_else(|| Error::new(ErrorKind::InvalidTag { start_index }))
+}
+
+/// Writes a protobuf tag at start_index, returning the number of bytes
+/// written.
+///
+/// Fails with `ErrorKind::OutOfBounds`, leaving data untouched, if the tag
+/// does not fit.
+/// # Example
+///
+/// ```
+/// use bitconv::protobuf::{write_tag, Tag, WireType};
+///
+/// let mut buffer = [0; 2];
+/// let tag = Tag::new(150, WireType::Varint).unwrap();
+/// assert_eq!(Ok(2), write_tag(&mut buffer, 0, tag));
+/// assert_eq!([0xb0, 0x09], buffer);
+/// ```
+#[inline]
+#[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
+pub fn write_tag(data: &mut [u8], start_index: usize, tag: Tag) -> Result<usize> {
+    write_uleb128(data, start_index, tag.bits().into())
+}
+
+/// Returns the number of bytes the value of the field with tag takes at
+/// start_index, just past the tag, so that unknown fields can be skipped.
+///
+/// A group is skipped along with the groups nested in it, up to and including
+/// its end tag. Fails with `ErrorKind::OutOfBounds` if data ends before the
+/// value does, with `ErrorKind::InvalidTag` if tag ends a group rather than
+/// starting a field or if a group ends with the tag of another field, and as
+/// [`read_tag`] does for the tags inside a group.
+/// # Example
+///
+/// ```
+/// use bitconv::protobuf::{read_tag, skip_field};
+///
+/// // A string field holding "hi", followed by a fixed32 field.
+/// let buffer = [0x0a, 2, b'h', b'i', 0x15, 1, 0, 0, 0];
+/// let (tag, len) = read_tag(&buffer, 0).unwrap();
+/// assert_eq!(Ok(3), skip_field(&buffer, len, tag));
+/// let (tag, len) = read_tag(&buffer, 4).unwrap();
+/// assert_eq!(Ok(4), skip_field(&buffer, 4 + len, tag));
+/// ```
+#[inline]
+#[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
+pub fn skip_field(data: &[u8], start_index: usize, tag: Tag) -> Result<usize> {
+    match tag.wire_type {
+        WireType::StartGroup => skip_group(data, start_index, tag.field_number),
+        wire_type => skip_value(data, start_index, wire_type),
+    }
+}
+
+/// Returns the number of bytes the value of wire_type takes at start_index,
+/// failing with `ErrorKind::InvalidTag` for the wire types of groups, whose
+/// fields are not values of their own.
+#[inline]
+fn skip_value(data: &[u8], start_index: usize, wire_type: WireType) -> Result<usize> {
+    let len = match wire_type {
+        WireType::Varint => return decode_uleb128(data, start_index, 64).map(|(_, len)| len),
+        WireType::I64 => 8,
+        WireType::I32 => 4,
+        WireType::StartGroup | WireType::EndGroup => {
+            return Err(Error::new(ErrorKind::InvalidTag { start_index }));
+        }
+        WireType::Len => {
+            let (len, prefix) = decode_uleb128(data, start_index, 64)?;
+            usize::try_from(len).ok().and_then(|len| len.checked_add(prefix)).unwrap_or(usize::MAX)
+        }
+    };
+    match data.get(start_index..).and_then(|bytes| bytes.get(..len)) {
+        Some(_) => Ok(len),
+        None => {
+            Err(Error::new(ErrorKind::OutOfBounds { start_index, requested: len, len: data.len() }))
+        }
+    }
+}
+
+/// Returns the number of bytes from start_index up to and including the end
+/// tag of the group of field_number, keeping count of the nested groups
+/// rather than recursing into them.
+#[inline]
+fn skip_group(data: &[u8], start_index: usize, field_number: u32) -> Result<usize> {
+    let mut index = start_index;
+    let mut depth = 0usize;
+    loop {
+        let (tag, len) = read_tag(data, index)?;
+        let value_index = index.wrapping_add(len);
+        match tag.wire_type {
+            WireType::StartGroup => depth = depth.wrapping_add(1),
+            WireType::EndGroup if depth > 0 => depth = depth.wrapping_sub(1),
+            WireType::EndGroup if tag.field_number == field_number => {
+                return Ok(value_index.wrapping_sub(start_index));
+            }
+            WireType::EndGroup => {
+                return Err(Error::new(ErrorKind::InvalidTag { start_index: index }))
+            }
+            wire_type => {
+                index = value_index.wrapping_add(skip_value(data, value_index, wire_type)?);
+                continue;
+            }
+        }
+        index = value_index;
+    }
+}
+
+impl<'a, E: BitConvEndian> Reader<'a, E> {
+    /// Reads a protobuf tag and advances past it, as [`read_tag`] does.
+    pub fn read_protobuf_tag(&mut self) -> Result<Tag> {
+        let (tag, len) = read_tag(self.get_ref(), self.position())?;
+        self.read_bytes(len)?;
+        Ok(tag)
+    }
+
+    /// Advances past the value of the field with tag, which was just read, as
+    /// [`skip_field`] measures it, leaving the position unchanged on error.
+    pub fn skip_protobuf_field(&mut self, tag: Tag) -> Result<()> {
+        let len = skip_field(self.get_ref(), self.position(), tag)?;
+        self.read_bytes(len).map(|_| ())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<E: BitConvEndian> Writer<E> {
+    /// Appends a protobuf tag.
+    pub fn write_protobuf_tag(&mut self, tag: Tag) { self.write_uleb128(tag.bits().into()); }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::endian::*;
+
+    #[test]
+    fn tag_test() {
+        assert_eq!(None, Tag::new(0, WireType::Varint));
+        assert_eq!(None, Tag::new(MAX_FIELD_NUMBER + 1, WireType::Varint));
+        let cases: [(u32, WireType, &[u8]); 4] = [
+            (1, WireType::Varint, &[0x08]),
+            (15, WireType::I32, &[0x7d]),
+            (16, WireType::EndGroup, &[0x84, 0x01]),
+            (MAX_FIELD_NUMBER, WireType::I64, &[0xf9, 0xff, 0xff, 0xff, 0x0f]),
+        ];
+        for (field_number, wire_type, bytes) in cases {
+            let tag = Tag::new(field_number, wire_type).unwrap();
+            assert_eq!(Ok((tag, bytes.len())), read_tag(bytes, 0));
+            let mut buffer = [0xaa; 7];
+            assert_eq!(Ok(bytes.len()), write_tag(&mut buffer, 1, tag));
+            assert_eq!(bytes, &buffer[1..=bytes.len()]);
+            assert_eq!(0xaa, buffer[bytes.len() + 1]);
+        }
+    }
+
+    #[test]
+    fn read_tag_error_test() {
+        assert_eq!(
+            Err(Error::new(ErrorKind::InvalidTag { start_index: 1 })),
+            read_tag(&[0, 0x07], 1)
+        );
+        assert_eq!(Err(Error::new(ErrorKind::InvalidTag { start_index: 0 })), read_tag(&[0x0e], 0));
+        assert_eq!(
+            Err(Error::new(ErrorKind::InvalidEncoding { start_index: 0 })),
+            read_tag(&[0xff, 0xff, 0xff, 0xff, 0x1f], 0)
+        );
+        assert_eq!(
+            Err(Error::new(ErrorKind::OutOfBounds { start_index: 2, requested: 1, len: 2 })),
+            read_tag(&[0x80, 0x80], 0)
+        );
+    }
+
+    #[test]
+    fn skip_field_test() {
+        let tag = |field_number, wire_type| Tag::new(field_number, wire_type).unwrap();
+        assert_eq!(Ok(3), skip_field(&[0, 0x80, 0x80, 0x01], 1, tag(1, WireType::Varint)));
+        assert_eq!(Ok(8), skip_field(&[0; 8], 0, tag(1, WireType::I64)));
+        assert_eq!(Ok(4), skip_field(&[0; 4], 0, tag(1, WireType::I32)));
+        assert_eq!(Ok(3), skip_field(&[2, 1, 2], 0, tag(1, WireType::Len)));
+        // A group of field 1 holding a varint and a group of field 2, itself
+        // holding a fixed32.
+        let group = [0x08, 1, 0x13, 0x1d, 0, 0, 0, 0, 0x14, 0x0c, 0xff];
+        assert_eq!(Ok(10), skip_field(&group, 0, tag(1, WireType::StartGroup)));
+        assert_eq!(
+            Err(Error::new(ErrorKind::InvalidTag { start_index: 9 })),
+            skip_field(&group, 0, tag(3, WireType::StartGroup))
+        );
+        assert_eq!(
+            Err(Error::new(ErrorKind::InvalidTag { start_index: 0 })),
+            skip_field(&group, 0, tag(1, WireType::EndGroup))
+        );
+    }
+
+    #[test]
+    fn skip_field_error_test() {
+        let tag = |wire_type| Tag::new(1, wire_type).unwrap();
+        assert_eq!(
+            Err(Error::new(ErrorKind::OutOfBounds { start_index: 1, requested: 8, len: 8 })),
+            skip_field(&[0; 8], 1, tag(WireType::I64))
+        );
+        assert_eq!(
+            Err(Error::new(ErrorKind::OutOfBounds { start_index: 0, requested: 4, len: 3 })),
+            skip_field(&[3, 1, 2], 0, tag(WireType::Len))
+        );
+        assert_eq!(
+            Err(Error::new(ErrorKind::OutOfBounds {
+                start_index: 0,
+                requested: usize::MAX,
+                len: 10
+            })),
+            skip_field(
+                &[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x01],
+                0,
+                tag(WireType::Len)
+            )
+        );
+        assert_eq!(
+            Err(Error::new(ErrorKind::OutOfBounds { start_index: 2, requested: 1, len: 2 })),
+            skip_field(&[0x08, 1], 0, tag(WireType::StartGroup))
+        );
+    }
+
+    #[test]
+    fn reader_protobuf_test() {
+        let buffer = [0x0a, 2, b'h', b'i', 0x10, 0x96, 0x01, 0x1a, 5, 0];
+        let mut reader = Reader::<Little>::new(&buffer);
+        let tag = reader.read_protobuf_tag().unwrap();
+        reader.skip_protobuf_field(tag).unwrap();
+        assert_eq!(Ok(Tag::new(2, WireType::Varint).unwrap()), reader.read_protobuf_tag());
+        assert_eq!(Ok(150), reader.read_uleb128());
+        let tag = reader.read_protobuf_tag().unwrap();
+        assert!(reader.skip_protobuf_field(tag).is_err());
+        assert_eq!(8, reader.position());
+        assert!(reader.read_protobuf_tag().is_err());
+        assert_eq!(8, reader.position());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn writer_protobuf_test() {
+        let mut writer = Writer::<Little>::new();
+        writer.write_protobuf_tag(Tag::new(2, WireType::Varint).unwrap());
+        writer.write_uleb128(150);
+        writer.write_protobuf_tag(Tag::new(16, WireType::Len).unwrap());
+        assert_eq!([0x10, 0x96, 0x01, 0x82, 0x01], writer.as_slice());
+    }
+}
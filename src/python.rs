@@ -0,0 +1,293 @@
+//! Conversions between integers and byte strings of any length, with the
+//! semantics of Python's `int.from_bytes` and `int.to_bytes`.
+use crate::endian::Endian;
+#[cfg(feature = "alloc")]
+use alloc::{vec, vec::Vec};
+
+/// Returns whether bytes in the endianness are stored least significant
+/// first.
+const fn is_little(endian: Endian) -> bool {
+    match endian {
+        Endian::LE => true,
+        Endian::BE => false,
+        Endian::NE => cfg!(target_endian = "little"),
+    }
+}
+
+/// Returns the integer a byte string of any length represents, as Python's
+/// `int.from_bytes(bytes, byteorder, signed=signed)` does, or `None` if it
+/// does not fit in an `i128`.
+///
+/// An empty string is 0. Strings longer than 16 bytes are accepted as long as
+/// the bytes beyond the sixteenth are only there to extend the sign, and
+/// unsigned values above `i128::MAX` need the big-integer variant,
+/// `big_int_from_bytes`, of the `num-bigint` feature.
+/// # Example
+///
+/// ```
+/// use bitconv::{endian::Endian, int_from_bytes};
+///
+/// assert_eq!(Some(1024), int_from_bytes(&[0, 4], Endian::LE, false));
+/// assert_eq!(Some(-1024), int_from_bytes(&[252, 0], Endian::BE, true));
+/// assert_eq!(Some(64512), int_from_bytes(&[252, 0], Endian::BE, false));
+/// assert_eq!(Some(0), int_from_bytes(&[], Endian::BE, true));
+/// assert_eq!(None, int_from_bytes(&[255; 16], Endian::LE, false));
+/// ```
+pub fn int_from_bytes(bytes: &[u8], endian: Endian, signed: bool) -> Option<i128> {
+    let byte = |index: usize| match is_little(endian) {
+        true => bytes[index],
+        false => bytes[bytes.len() - 1 - index],
+    };
+    let negative = signed && !bytes.is_empty() && byte(bytes.len() - 1) & 0x80 != 0;
+    let extension = if negative { 0xff } else { 0 };
+    if (16..bytes.len()).any(|index| byte(index) != extension) {
+        return None;
+    }
+    let mut value = [extension; 16];
+    for (index, slot) in value.iter_mut().enumerate().take(bytes.len()) {
+        *slot = byte(index);
+    }
+    let value = i128::from_le_bytes(value);
+    match (value < 0) == negative {
+        true => Some(value),
+        false => None,
+    }
+}
+
+/// Returns the length of the shortest byte string holding value, which is
+/// the smallest length `int.to_bytes` accepts for it, or `None` for a
+/// negative value that is not signed.
+///
+/// Like in Python, 0 fits in an empty string.
+/// # Example
+///
+/// ```
+/// use bitconv::int_byte_len;
+///
+/// assert_eq!(Some(0), int_byte_len(0, false));
+/// assert_eq!(Some(1), int_byte_len(255, false));
+/// assert_eq!(Some(2), int_byte_len(255, true));
+/// assert_eq!(Some(1), int_byte_len(-128, true));
+/// assert_eq!(None, int_byte_len(-1, false));
+/// ```
+pub const fn int_byte_len(value: i128, signed: bool) -> Option<usize> {
+    let bits = match (value < 0, signed) {
+        (_, _) if value == 0 => return Some(0),
+        (true, false) => return None,
+        (false, false) => 128 - value.leading_zeros(),
+        (false, true) => 129 - value.leading_zeros(),
+        (true, true) => 129 - (!value).leading_zeros(),
+    };
+    Some(bits.div_ceil(8) as usize)
+}
+
+/// Writes value over all of destination, returning whether it fit, with the
+/// semantics of Python's `int.to_bytes(len(destination), byteorder,
+/// signed=signed)`.
+///
+/// Nothing is written when `int.to_bytes` would raise an `OverflowError`, for
+/// a value needing more bytes than destination has or a negative value that
+/// is not signed. Bytes beyond the sixteenth extend the sign. This is the
+/// counterpart of [`int_to_bytes`] that needs no allocator.
+/// # Example
+///
+/// ```
+/// use bitconv::{endian::Endian, try_write_int_bytes};
+///
+/// let mut destination = [0; 3];
+/// assert!(try_write_int_bytes(-1024, &mut destination, Endian::BE, true));
+/// assert_eq!([255, 252, 0], destination);
+/// assert!(!try_write_int_bytes(-1024, &mut destination, Endian::BE, false));
+/// assert!(!try_write_int_bytes(1 << 24, &mut destination, Endian::LE, false));
+/// ```
+#[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
+pub fn try_write_int_bytes(
+    value: i128,
+    destination: &mut [u8],
+    endian: Endian,
+    signed: bool,
+) -> bool {
+    match int_byte_len(value, signed) {
+        Some(len) if len <= destination.len() => {}
+        _ => return false,
+    }
+    let bytes = value.to_le_bytes();
+    let extension = if value < 0 { 0xff } else { 0 };
+    let little = is_little(endian);
+    let len = destination.len();
+    for (index, slot) in destination.iter_mut().enumerate() {
+        let index = if little { index } else { len - 1 - index };
+        *slot = bytes.get(index).copied().unwrap_or(extension);
+    }
+    true
+}
+
+/// Returns value as a byte string of len bytes, as Python's
+/// `int.to_bytes(len, byteorder, signed=signed)` does, or `None` where it
+/// would raise an `OverflowError`.
+///
+/// Passing [`int_byte_len`] as len gives the shortest encoding.
+/// # Example
+///
+/// ```
+/// use bitconv::{endian::Endian, int_byte_len, int_to_bytes};
+///
+/// assert_eq!(Some(vec![0, 4]), int_to_bytes(1024, 2, Endian::LE, false));
+/// assert_eq!(Some(vec![255, 255, 255]), int_to_bytes(-1, 3, Endian::BE, true));
+/// assert_eq!(None, int_to_bytes(1024, 1, Endian::BE, false));
+///
+/// let len = int_byte_len(-129, true).unwrap();
+/// assert_eq!(Some(vec![255, 127]), int_to_bytes(-129, len, Endian::BE, true));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn int_to_bytes(value: i128, len: usize, endian: Endian, signed: bool) -> Option<Vec<u8>> {
+    let mut bytes = vec![0; len];
+    match try_write_int_bytes(value, &mut bytes, endian, signed) {
+        true => Some(bytes),
+        false => None,
+    }
+}
+
+/// Returns the integer a byte string of any length represents, as Python's
+/// `int.from_bytes` does, without the `i128` limit of [`int_from_bytes`].
+#[cfg(feature = "num-bigint")]
+pub fn big_int_from_bytes(bytes: &[u8], endian: Endian, signed: bool) -> ::num_bigint::BigInt {
+    use ::num_bigint::{BigInt, Sign};
+
+    match (is_little(endian), signed) {
+        (true, true) => BigInt::from_signed_bytes_le(bytes),
+        (false, true) => BigInt::from_signed_bytes_be(bytes),
+        (true, false) => BigInt::from_bytes_le(Sign::Plus, bytes),
+        (false, false) => BigInt::from_bytes_be(Sign::Plus, bytes),
+    }
+}
+
+/// Returns an integer of any size as a byte string of len bytes, as Python's
+/// `int.to_bytes` does, or `None` where it would raise an `OverflowError`.
+#[cfg(feature = "num-bigint")]
+pub fn big_int_to_bytes(
+    value: &::num_bigint::BigInt,
+    len: usize,
+    endian: Endian,
+    signed: bool,
+) -> Option<Vec<u8>> {
+    use ::num_bigint::Sign;
+
+    let (mut bytes, extension) = match (value.sign(), signed) {
+        (Sign::NoSign, _) => (Vec::new(), 0),
+        (Sign::Minus, false) => return None,
+        (Sign::Minus, true) => (value.to_signed_bytes_le(), 0xff),
+        (Sign::Plus, true) => (value.to_signed_bytes_le(), 0),
+        (Sign::Plus, false) => (value.to_bytes_le().1, 0),
+    };
+    if bytes.len() > len {
+        return None;
+    }
+    bytes.resize(len, extension);
+    if !is_little(endian) {
+        bytes.reverse();
+    }
+    Some(bytes)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn int_from_bytes_test() {
+        let cases: [(&[u8], Endian, bool, Option<i128>); 10] = [
+            (&[], Endian::LE, false, Some(0)),
+            (&[0x80], Endian::LE, true, Some(-128)),
+            (&[0x80], Endian::BE, false, Some(128)),
+            (&[1, 0, 0], Endian::BE, true, Some(65536)),
+            (&[1, 0, 0], Endian::LE, true, Some(1)),
+            (&[0xff; 20], Endian::LE, true, Some(-1)),
+            (&[0xff; 16], Endian::BE, false, None),
+            (
+                &[
+                    0x7f, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+                    0xff, 0xff, 0xff,
+                ],
+                Endian::BE,
+                false,
+                Some(i128::MAX),
+            ),
+            (&[0, 0x80, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0], Endian::BE, true, None),
+            (
+                &[0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0x80, 0xff],
+                Endian::LE,
+                true,
+                Some(i128::MIN),
+            ),
+        ];
+        for (bytes, endian, signed, value) in cases {
+            assert_eq!(value, int_from_bytes(bytes, endian, signed), "{:?}", bytes);
+        }
+        assert_eq!(
+            int_from_bytes(&[1, 2], Endian::NE, false),
+            Some(i128::from(u16::from_ne_bytes([1, 2])))
+        );
+    }
+
+    #[test]
+    fn int_byte_len_test() {
+        let cases = [
+            (0, false, Some(0)),
+            (0, true, Some(0)),
+            (1, false, Some(1)),
+            (127, true, Some(1)),
+            (128, true, Some(2)),
+            (256, false, Some(2)),
+            (-1, true, Some(1)),
+            (-129, true, Some(2)),
+            (-1, false, None),
+            (i128::MAX, false, Some(16)),
+            (i128::MAX, true, Some(16)),
+            (i128::MIN, true, Some(16)),
+        ];
+        for (value, signed, len) in cases {
+            assert_eq!(len, int_byte_len(value, signed), "{}", value);
+        }
+    }
+
+    #[test]
+    fn try_write_int_bytes_test() {
+        let mut destination = [7; 18];
+        assert!(try_write_int_bytes(i128::MIN, &mut destination, Endian::LE, true));
+        assert_eq!([0x80, 0xff, 0xff], destination[15..]);
+        assert!(try_write_int_bytes(0x0102, &mut destination[..2], Endian::BE, false));
+        assert_eq!([1, 2, 0], destination[..3]);
+        assert!(!try_write_int_bytes(0x8000, &mut destination[..2], Endian::LE, true));
+        assert!(!try_write_int_bytes(1, &mut [], Endian::LE, true));
+        assert!(try_write_int_bytes(0, &mut [], Endian::LE, true));
+        assert_eq!([1, 2, 0], destination[..3]);
+        for value in [0, 1, -1, 255, -256, 65535, i128::MAX, i128::MIN] {
+            let mut bytes = [0; 17];
+            assert!(try_write_int_bytes(value, &mut bytes, Endian::BE, true));
+            assert_eq!(Some(value), int_from_bytes(&bytes, Endian::BE, true));
+        }
+    }
+
+    #[cfg(feature = "num-bigint")]
+    #[test]
+    fn big_int_test() {
+        use ::num_bigint::BigInt;
+
+        let value = big_int_from_bytes(&[0xff; 17], Endian::LE, false);
+        assert_eq!((BigInt::from(1) << 136u32) - 1, value);
+        assert_eq!(BigInt::from(-1), big_int_from_bytes(&[0xff; 17], Endian::LE, true));
+        assert_eq!(BigInt::from(0), big_int_from_bytes(&[], Endian::BE, true));
+        assert_eq!(BigInt::from(-1024), big_int_from_bytes(&[252, 0], Endian::BE, true));
+        assert_eq!(Some(vec![0xff; 17]), big_int_to_bytes(&value, 17, Endian::BE, false));
+        assert_eq!(None, big_int_to_bytes(&value, 17, Endian::BE, true));
+        assert_eq!(
+            Some(vec![0, 252, 255]),
+            big_int_to_bytes(&BigInt::from(-1024), 3, Endian::LE, true)
+        );
+        assert_eq!(None, big_int_to_bytes(&BigInt::from(-1), 1, Endian::LE, false));
+        assert_eq!(Some(vec![]), big_int_to_bytes(&BigInt::from(0), 0, Endian::LE, false));
+        assert_eq!(Some(vec![0, 128]), big_int_to_bytes(&BigInt::from(128), 2, Endian::BE, true));
+        assert_eq!(None, big_int_to_bytes(&BigInt::from(128), 1, Endian::BE, true));
+    }
+}
@@ -0,0 +1,701 @@
+//! Cursor converting values one after another from a byte slice.
+use crate::{endian::Endian, from_bytes_with, BitConvEndian, Error, ErrorKind, FromBytes, Result};
+use core::marker::PhantomData;
+
+/// Types that can be read from a [`Reader`].
+///
+/// Every [`FromBytes`] type, including those with a derived implementation,
+/// is a `FromReader` type as well. Implementing `FromReader` directly suits
+/// types whose layout is easier to describe as a sequence of reads, and lets
+/// them nest inside each other the same way.
+/// # Example
+///
+/// ```
+/// use bitconv::{endian::Little, BitConvEndian, FromReader, Reader, Result};
+///
+/// struct Point {
+///     x: i16,
+///     y: i16,
+/// }
+///
+/// impl FromReader for Point {
+///     fn read_from<E: BitConvEndian>(reader: &mut Reader<'_, E>) -> Result<Self> {
+///         Ok(Point { x: i16::read_from(reader)?, y: i16::read_from(reader)? })
+///     }
+/// }
+///
+/// struct Line {
+///     start: Point,
+///     end: Point,
+/// }
+///
+/// impl FromReader for Line {
+///     fn read_from<E: BitConvEndian>(reader: &mut Reader<'_, E>) -> Result<Self> {
+///         Ok(Line { start: Point::read_from(reader)?, end: Point::read_from(reader)? })
+///     }
+/// }
+///
+/// let buffer = [1, 0, 2, 0, 255, 255, 4, 0];
+/// let line = Line::read_from(&mut Reader::<Little>::new(&buffer)).unwrap();
+/// assert_eq!((1, 2, -1, 4), (line.start.x, line.start.y, line.end.x, line.end.y));
+/// ```
+///
+/// Composition only goes one way: a derived `FromBytes` type reads its fields
+/// through `FromBytes`, so a type that only implements `FromReader` cannot be
+/// one of them. The type holding it implements `FromReader` by hand instead,
+/// reading any derived parts with `read_from` like the rest.
+///
+/// ```
+/// # #[cfg(feature = "derive")] {
+/// use bitconv::{endian::Big, BitConvEndian, FromBytes, FromReader, Reader, Result};
+///
+/// #[derive(FromBytes)]
+/// struct Header {
+///     kind: u8,
+///     flags: u8,
+/// }
+///
+/// struct Name(Vec<u8>);
+///
+/// impl FromReader for Name {
+///     fn read_from<E: BitConvEndian>(reader: &mut Reader<'_, E>) -> Result<Self> {
+///         let len = u8::read_from(reader)?;
+///         Ok(Name(reader.read_bytes(len.into())?.to_vec()))
+///     }
+/// }
+///
+/// // Deriving `FromBytes` for this would not compile, as `Name` is no
+/// // `FromBytes` type.
+/// struct Record {
+///     header: Header,
+///     name: Name,
+/// }
+///
+/// impl FromReader for Record {
+///     fn read_from<E: BitConvEndian>(reader: &mut Reader<'_, E>) -> Result<Self> {
+///         Ok(Record { header: Header::read_from(reader)?, name: Name::read_from(reader)? })
+///     }
+/// }
+///
+/// let buffer = [7, 0, 2, b'h', b'i'];
+/// let record = Record::read_from(&mut Reader::<Big>::new(&buffer)).unwrap();
+/// assert_eq!((7, 0, &b"hi"[..]), (record.header.kind, record.header.flags, &record.name.0[..]));
+/// # }
+/// ```
+pub trait FromReader: Sized {
+    /// Reads a value, advancing the reader past it.
+    fn read_from<E: BitConvEndian>(reader: &mut Reader<'_, E>) -> Result<Self>;
+}
+
+impl<V: FromBytes> FromReader for V {
+    #[inline]
+    fn read_from<E: BitConvEndian>(reader: &mut Reader<'_, E>) -> Result<Self> { reader.read() }
+}
+
+/// Saved position of a [`Reader`], returned by [`Reader::mark`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub struct Mark {
+    position: usize,
+}
+
+/// Position to move a [`Reader`] to, in the manner of `std::io::SeekFrom`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum SeekFrom {
+    /// The given number of bytes from the start of the buffer.
+    Start(usize),
+    /// The given offset from the end of the buffer.
+    End(isize),
+    /// The given offset from the current position.
+    Current(isize),
+}
+
+/// Cursor over a byte slice that converts values in the endianness `E`,
+/// advancing past each value it reads.
+///
+/// The `peek_*` methods convert the value at the current position without
+/// advancing, which allows looking at a tag before deciding how to parse what
+/// follows it.
+///
+/// The reader keeps statistics on how much of the buffer it has consumed,
+/// which together with [`assert_exhausted`](Reader::assert_exhausted) help
+/// detect trailing data a parser did not expect.
+/// # Example
+///
+/// ```
+/// use bitconv::{endian::Little, Reader};
+///
+/// let buffer = [1, 16, 39, 255, 255, 255, 127];
+/// let mut reader = Reader::<Little>::new(&buffer);
+/// assert_eq!(Ok(1), reader.peek_uint8());
+/// assert_eq!(Ok(1), reader.read_uint8());
+/// assert_eq!(Ok(10000), reader.read_uint16());
+/// assert_eq!(Ok(i32::MAX), reader.read_int32());
+/// assert_eq!(0, reader.remaining());
+/// ```
+pub struct Reader<'a, E> {
+    data: &'a [u8],
+    position: usize,
+    consumed: usize,
+    reads: usize,
+    high_water_mark: usize,
+    endian: PhantomData<E>,
+}
+
+macro_rules! ReaderImpl {
+    ($($read:ident $read_with:ident $peek:ident $type:ty),+) => {$(
+        #[doc = concat!("Reads a `", stringify!($type), "` and advances past it.")]
+        #[inline]
+        pub fn $read(&mut self) -> Result<$type> { self.read() }
+
+        #[doc = concat!("Reads a `", stringify!($type), "` in the given endianness and advances past it.")]
+        #[inline]
+        pub fn $read_with(&mut self, endian: Endian) -> Result<$type> { self.read_with(endian) }
+
+        #[doc = concat!("Converts the `", stringify!($type), "` at the current position without advancing.")]
+        #[inline]
+        pub fn $peek(&self) -> Result<$type> { self.peek() }
+    )+};
+}
+
+impl<'a, E: BitConvEndian> Reader<'a, E> {
+    /// Creates a reader positioned at the start of data.
+    pub fn new(data: &'a [u8]) -> Self {
+        Reader { data, position: 0, consumed: 0, reads: 0, high_water_mark: 0, endian: PhantomData }
+    }
+
+    /// Returns the underlying byte slice.
+    pub fn get_ref(&self) -> &'a [u8] { self.data }
+
+    /// Returns the index of the next byte to be read.
+    pub fn position(&self) -> usize { self.position }
+
+    /// Moves to position, which may lie past the end of the buffer. Reads from
+    /// there fail until the reader is moved back.
+    pub fn set_position(&mut self, position: usize) { self.position = position; }
+
+    /// Moves back to the start of the buffer.
+    pub fn rewind(&mut self) { self.position = 0; }
+
+    /// Moves to the given position, returning the new position.
+    ///
+    /// Seeking past the end of the buffer is allowed, like
+    /// [`set_position`](Reader::set_position). Seeking before the start of
+    /// the buffer fails with `ErrorKind::InvalidSeek` and leaves the position
+    /// unchanged.
+    /// # Example
+    ///
+    /// ```
+    /// use bitconv::{endian::Big, Reader, SeekFrom};
+    ///
+    /// // Header holding the offset of a record, followed by the record.
+    /// let buffer = [0, 4, 255, 255, 0, 42];
+    /// let mut reader = Reader::<Big>::new(&buffer);
+    /// let offset = reader.read_uint16().unwrap();
+    /// reader.seek(SeekFrom::Start(offset.into())).unwrap();
+    /// assert_eq!(Ok(42), reader.read_uint16());
+    /// assert_eq!(Ok(2), reader.seek(SeekFrom::Current(-4)));
+    /// assert_eq!(Ok(5), reader.seek(SeekFrom::End(-1)));
+    /// assert!(reader.seek(SeekFrom::Current(-6)).is_err());
+    /// ```
+    pub fn seek(&mut self, pos: SeekFrom) -> Result<usize> {
+        let (base, offset) = match pos {
+            SeekFrom::Start(position) => (position, 0),
+            SeekFrom::End(offset) => (self.data.len(), offset),
+            SeekFrom::Current(offset) => (self.position, offset),
+        };
+        let position = if offset < 0 {
+            base.checked_sub(offset.unsigned_abs())
+        } else {
+            base.checked_add(offset.unsigned_abs())
+        };
+        self.position = position
+            .ok_or_else(|| Error::new(ErrorKind::InvalidSeek { position: base, offset }))?;
+        Ok(self.position)
+    }
+
+    /// Returns the number of bytes left after the current position.
+    pub fn remaining(&self) -> usize { self.data.len().saturating_sub(self.position) }
+
+    /// Returns the total number of bytes advanced past by reads, counting
+    /// bytes read again after seeking back each time.
+    pub fn consumed(&self) -> usize { self.consumed }
+
+    /// Returns the number of successful reads, including calls to
+    /// [`take`](Reader::take). Peeks are not counted.
+    pub fn reads(&self) -> usize { self.reads }
+
+    /// Returns the furthest position any read has advanced to.
+    pub fn high_water_mark(&self) -> usize { self.high_water_mark }
+
+    /// Checks that the whole buffer has been read, failing with
+    /// `ErrorKind::TrailingBytes` if any bytes remain after the current
+    /// position.
+    /// # Example
+    ///
+    /// ```
+    /// use bitconv::{endian::Little, ErrorKind, Reader};
+    ///
+    /// let mut reader = Reader::<Little>::new(&[16, 39, 0]);
+    /// assert_eq!(Ok(10000), reader.read_uint16());
+    /// assert_eq!(
+    ///     ErrorKind::TrailingBytes { start_index: 2, len: 1 },
+    ///     reader.assert_exhausted().unwrap_err().kind()
+    /// );
+    /// assert_eq!(Ok(0), reader.read_uint8());
+    /// assert_eq!(Ok(()), reader.assert_exhausted());
+    /// ```
+    pub fn assert_exhausted(&self) -> Result<()> {
+        match self.remaining() {
+            0 => Ok(()),
+            len => Err(Error::new(ErrorKind::TrailingBytes { start_index: self.position, len })),
+        }
+    }
+
+    fn advance(&mut self, len: usize) {
+        self.position += len;
+        self.consumed += len;
+        self.reads += 1;
+        self.high_water_mark = self.high_water_mark.max(self.position);
+    }
+
+    /// Converts a value at the current position and advances past it.
+    ///
+    /// On error the position is left unchanged.
+    pub fn read<V: FromBytes>(&mut self) -> Result<V> {
+        let (value, read) = V::from_bytes::<E>(self.data, self.position)?;
+        self.advance(read);
+        Ok(value)
+    }
+
+    /// Converts a value at the current position in the given endianness
+    /// instead of `E` and advances past it.
+    ///
+    /// This covers the odd field of a mixed-endian format without giving up
+    /// the reader.
+    /// # Example
+    ///
+    /// ```
+    /// use bitconv::{
+    ///     endian::{Endian, Little}, Reader
+    /// };
+    ///
+    /// let buffer = [16, 39, 0, 0, 1, 0];
+    /// let mut reader = Reader::<Little>::new(&buffer);
+    /// assert_eq!(Ok(10000), reader.read_uint16());
+    /// assert_eq!(Ok(256), reader.read_uint32_with(Endian::BE));
+    /// ```
+    pub fn read_with<V: FromBytes>(&mut self, endian: Endian) -> Result<V> {
+        let (value, read) = from_bytes_with::<V>(endian, self.data, self.position)?;
+        self.advance(read);
+        Ok(value)
+    }
+
+    /// Converts a value at the current position in the given endianness
+    /// instead of `E` without advancing.
+    pub fn peek_with<V: FromBytes>(&self, endian: Endian) -> Result<V> {
+        from_bytes_with::<V>(endian, self.data, self.position).map(|(value, _)| value)
+    }
+
+    /// Converts a value at the current position without advancing.
+    pub fn peek<V: FromBytes>(&self) -> Result<V> {
+        V::from_bytes::<E>(self.data, self.position).map(|(value, _)| value)
+    }
+
+    /// Returns the next len bytes and advances past them.
+    ///
+    /// The bytes are borrowed from the underlying buffer rather than copied, so
+    /// they outlive the reader.
+    /// # Example
+    ///
+    /// ```
+    /// use bitconv::{endian::Big, Reader};
+    ///
+    /// let packet = [0, 3, 7, 8, 9, 1];
+    /// let payload = {
+    ///     let mut reader = Reader::<Big>::new(&packet);
+    ///     let len = reader.read_uint16().unwrap();
+    ///     reader.read_bytes(len.into()).unwrap()
+    /// };
+    /// assert_eq!([7, 8, 9], payload);
+    /// ```
+    pub fn read_bytes(&mut self, len: usize) -> Result<&'a [u8]> {
+        let bytes = self.peek_bytes(len)?;
+        self.advance(len);
+        Ok(bytes)
+    }
+
+    /// Returns a copy of the next N bytes and advances past them.
+    pub fn read_array<const N: usize>(&mut self) -> Result<[u8; N]> { self.read() }
+
+    /// Returns the next len bytes without advancing.
+    pub fn peek_bytes(&self, len: usize) -> Result<&'a [u8]> {
+        self.data.get(self.position..).and_then(|bytes| bytes.get(..len)).ok_or_else(|| {
+            Error::new(ErrorKind::OutOfBounds {
+                start_index: self.position,
+                requested: len,
+                len: self.data.len(),
+            })
+        })
+    }
+
+    /// Saves the current position so that it can be returned to with
+    /// [`reset`](Reader::reset).
+    ///
+    /// Marks are plain values, so any number of them can be held at once for
+    /// nested attempts.
+    pub fn mark(&self) -> Mark { Mark { position: self.position } }
+
+    /// Moves back to the position saved by mark.
+    ///
+    /// The consumption statistics keep counting the reads made since the mark.
+    pub fn reset(&mut self, mark: Mark) { self.position = mark.position; }
+
+    /// Runs f on the reader, moving back to the current position if it fails.
+    ///
+    /// This lets a parser try one layout and fall back to another without
+    /// keeping track of what the failed attempt read.
+    /// # Example
+    ///
+    /// ```
+    /// use bitconv::{endian::Little, Error, ErrorKind, Reader};
+    ///
+    /// let buffer = [2, 10, 0];
+    /// let mut reader = Reader::<Little>::new(&buffer);
+    /// let attempt = reader.transaction(|reader| match reader.read_uint8()? {
+    ///     1 => reader.read_uint32(),
+    ///     _ => Err(Error::new(ErrorKind::InvalidTag { start_index: 0 })),
+    /// });
+    /// assert!(attempt.is_err());
+    /// assert_eq!(0, reader.position());
+    /// assert_eq!(Ok((2, 10)), reader.read::<(u8, u16)>());
+    /// ```
+    pub fn transaction<V>(&mut self, f: impl FnOnce(&mut Self) -> Result<V>) -> Result<V> {
+        let mark = self.mark();
+        let result = f(self);
+        if result.is_err() {
+            self.reset(mark);
+        }
+        result
+    }
+
+    /// Returns a reader over the next len bytes and advances past them.
+    ///
+    /// The returned reader starts at position 0 and fails to read past its len
+    /// bytes, keeping a length-delimited structure from running into the data
+    /// after it. Errors if fewer than len bytes remain.
+    /// # Example
+    ///
+    /// ```
+    /// use bitconv::{endian::Little, Reader};
+    ///
+    /// // Two chunks, each a length followed by that many bytes.
+    /// let buffer = [2, 10, 0, 3, 7, 8, 9];
+    /// let mut reader = Reader::<Little>::new(&buffer);
+    /// let len = reader.read_uint8().unwrap();
+    /// let mut chunk = reader.take(len.into()).unwrap();
+    /// assert_eq!(Ok(10), chunk.read_uint16());
+    /// assert!(chunk.read_uint8().is_err());
+    /// assert_eq!(Ok(3), reader.read_uint8());
+    /// ```
+    pub fn take(&mut self, len: usize) -> Result<Reader<'a, E>> {
+        self.read_bytes(len).map(Reader::new)
+    }
+
+    ReaderImpl!(
+        read_int8 read_int8_with peek_int8 i8,
+        read_int16 read_int16_with peek_int16 i16,
+        read_int32 read_int32_with peek_int32 i32,
+        read_int64 read_int64_with peek_int64 i64,
+        read_uint8 read_uint8_with peek_uint8 u8,
+        read_uint16 read_uint16_with peek_uint16 u16,
+        read_uint32 read_uint32_with peek_uint32 u32,
+        read_uint64 read_uint64_with peek_uint64 u64
+    );
+}
+
+#[cfg(feature = "std")]
+impl<E: BitConvEndian> std::io::Seek for Reader<'_, E> {
+    fn seek(&mut self, pos: std::io::SeekFrom) -> std::io::Result<u64> {
+        use core::convert::TryFrom;
+        let pos = match pos {
+            std::io::SeekFrom::Start(position) => usize::try_from(position).map(SeekFrom::Start),
+            std::io::SeekFrom::End(offset) => isize::try_from(offset).map(SeekFrom::End),
+            std::io::SeekFrom::Current(offset) => isize::try_from(offset).map(SeekFrom::Current),
+        };
+        pos.ok()
+            .and_then(|pos| Reader::seek(self, pos).ok())
+            .map(|position| position as u64)
+            .ok_or_else(|| {
+                std::io::Error::new(
+                    std::io::ErrorKind::InvalidInput,
+                    "invalid seek to a negative or overflowing position",
+                )
+            })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::endian::*;
+
+    #[test]
+    fn reader_read_test_le() {
+        let buffer = [15, 0, 0, 128, 16, 39, 240, 216, 241, 255, 127, 86, 85, 85, 3];
+        let mut reader = Reader::<Little>::new(&buffer);
+        assert_eq!(Ok(2147483663), reader.read_uint32());
+        assert_eq!(Ok(10000), reader.read_int16());
+        assert_eq!(Ok(-10000), reader.read_int16());
+        assert_eq!(Ok((-15, 255u8)), reader.read::<(i8, u8)>());
+        assert_eq!(10, reader.position());
+        assert_eq!(5, reader.remaining());
+        assert_eq!(Ok(127), reader.peek_uint8());
+        assert_eq!(
+            Err(Error::new(ErrorKind::OutOfBounds { start_index: 10, requested: 8, len: 15 })),
+            reader.read_uint64()
+        );
+        assert_eq!(10, reader.position());
+    }
+
+    #[test]
+    fn reader_read_test_be() {
+        let buffer = [15, 0, 0, 128, 16, 39, 240, 216, 241, 255, 127, 86, 85, 85, 3];
+        let mut reader = Reader::<Big>::new(&buffer);
+        assert_eq!(Ok(251658368), reader.read_int32());
+        assert_eq!(Ok(4135), reader.read_uint16());
+        assert_eq!(Ok(-1091856829982026411), reader.read_int64());
+        assert_eq!(1, reader.remaining());
+        assert_eq!(Ok(3), reader.read_int8());
+        assert!(reader.read_int8().is_err());
+    }
+
+    #[test]
+    fn reader_peek_test() {
+        let buffer = [2, 10, 0, 246, 255];
+        let mut reader = Reader::<Little>::new(&buffer);
+        assert_eq!(Ok(2), reader.peek_int8());
+        assert_eq!(Ok(2562), reader.peek_uint16());
+        assert_eq!(Ok(4127197698), reader.peek_uint32());
+        assert!(reader.peek_int64().is_err());
+        assert_eq!(0, reader.position());
+        assert_eq!(Ok(2), reader.read_uint8());
+        assert_eq!(Ok((10, -10)), reader.peek::<(i16, i16)>());
+        assert_eq!(1, reader.position());
+    }
+
+    #[test]
+    fn reader_peek_bytes_test() {
+        let buffer = [2, 10, 0, 246, 255];
+        let mut reader = Reader::<Native>::new(&buffer);
+        assert_eq!(Ok(&[2, 10][..]), reader.peek_bytes(2));
+        assert_eq!(Ok(&[][..]), reader.peek_bytes(0));
+        assert_eq!(Ok(2), reader.read_uint8());
+        assert_eq!(Ok(&buffer[1..]), reader.peek_bytes(4));
+        assert_eq!(
+            Err(Error::new(ErrorKind::OutOfBounds { start_index: 1, requested: 5, len: 5 })),
+            reader.peek_bytes(5)
+        );
+        assert_eq!(1, reader.position());
+    }
+
+    #[test]
+    fn reader_seek_test() {
+        let buffer = [2, 10, 0, 246, 255];
+        let mut reader = Reader::<Little>::new(&buffer);
+        assert_eq!(Ok(3), reader.seek(SeekFrom::Start(3)));
+        assert_eq!(Ok(-10), reader.read_int16());
+        assert_eq!(Ok(1), reader.seek(SeekFrom::End(-4)));
+        assert_eq!(Ok(10), reader.read_uint16());
+        assert_eq!(Ok(2), reader.seek(SeekFrom::Current(-1)));
+        assert_eq!(Ok(8), reader.seek(SeekFrom::Current(6)));
+        assert_eq!(0, reader.remaining());
+        assert!(reader.read_uint8().is_err());
+        assert_eq!(
+            Err(Error::new(ErrorKind::InvalidSeek { position: 5, offset: -6 })),
+            reader.seek(SeekFrom::End(-6))
+        );
+        reader.set_position(usize::MAX);
+        assert_eq!(
+            Err(Error::new(ErrorKind::InvalidSeek { position: usize::MAX, offset: 1 })),
+            reader.seek(SeekFrom::Current(1))
+        );
+        assert_eq!(usize::MAX, reader.position());
+        assert_eq!(0, reader.remaining());
+        assert_eq!(
+            Err(Error::new(ErrorKind::OutOfBounds {
+                start_index: usize::MAX,
+                requested: 2,
+                len: 5
+            })),
+            reader.read_uint16()
+        );
+        assert!(reader.read_bytes(usize::MAX).is_err());
+        reader.set_position(4);
+        assert_eq!(Ok(255), reader.read_uint8());
+        reader.rewind();
+        assert_eq!(0, reader.position());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn reader_io_seek_test() {
+        use std::io::{Seek, SeekFrom};
+        let buffer = [2, 10, 0, 246, 255];
+        let mut reader = Reader::<Little>::new(&buffer);
+        assert_eq!(3, Seek::seek(&mut reader, SeekFrom::Start(3)).unwrap());
+        assert_eq!(4, Seek::seek(&mut reader, SeekFrom::Current(1)).unwrap());
+        assert_eq!(4, reader.stream_position().unwrap());
+        assert!(Seek::seek(&mut reader, SeekFrom::End(-6)).is_err());
+        assert_eq!(4, reader.position());
+        Seek::rewind(&mut reader).unwrap();
+        assert_eq!(0, reader.position());
+    }
+
+    #[test]
+    fn reader_take_test() {
+        let buffer = [4, 15, 0, 0, 128, 2, 16, 39];
+        let mut reader = Reader::<Big>::new(&buffer);
+        let empty = reader.take(0).unwrap();
+        assert_eq!(0, empty.remaining());
+        assert!(empty.peek_int8().is_err());
+        let len = reader.read_uint8().unwrap();
+        let mut chunk = reader.take(len.into()).unwrap();
+        assert_eq!(5, reader.position());
+        assert!(chunk.read_uint64().is_err());
+        assert_eq!(Ok(3840), chunk.read_uint16());
+        let mut inner = chunk.take(1).unwrap();
+        assert_eq!(Ok(0), inner.read_uint8());
+        assert_eq!(
+            Err(Error::new(ErrorKind::OutOfBounds { start_index: 3, requested: 2, len: 4 })),
+            chunk.take(2).map(|_| ())
+        );
+        assert_eq!(Ok(128), chunk.read_uint8());
+        assert_eq!(0, chunk.remaining());
+        assert!(reader.take(4).is_err());
+        assert_eq!(5, reader.position());
+        let mut rest = reader.take(3).unwrap();
+        assert_eq!(Ok((2, 4135)), rest.read::<(u8, u16)>());
+        assert_eq!(0, reader.remaining());
+    }
+
+    #[test]
+    fn reader_stats_test() {
+        let buffer = [15, 0, 0, 128, 16, 39, 240, 216];
+        let mut reader = Reader::<Little>::new(&buffer);
+        assert_eq!((0, 0, 0), (reader.consumed(), reader.reads(), reader.high_water_mark()));
+        assert_eq!(Ok(15), reader.read_uint16());
+        assert_eq!(Ok(32768), reader.peek_uint16());
+        assert!(reader.read_uint64().is_err());
+        reader.take(4).unwrap();
+        assert_eq!((6, 2, 6), (reader.consumed(), reader.reads(), reader.high_water_mark()));
+        assert_eq!(
+            Err(Error::new(ErrorKind::TrailingBytes { start_index: 6, len: 2 })),
+            reader.assert_exhausted()
+        );
+        reader.seek(SeekFrom::Start(2)).unwrap();
+        assert_eq!(Ok(655392768), reader.read_int32());
+        assert_eq!((10, 3, 6), (reader.consumed(), reader.reads(), reader.high_water_mark()));
+        reader.set_position(9);
+        assert_eq!(Ok(()), reader.assert_exhausted());
+        assert_eq!(6, reader.high_water_mark());
+    }
+
+    #[test]
+    fn reader_mark_test() {
+        let buffer = [15, 0, 0, 128, 16, 39];
+        let mut reader = Reader::<Big>::new(&buffer);
+        let start = reader.mark();
+        assert_eq!(Ok(3840), reader.read_uint16());
+        let middle = reader.mark();
+        assert_eq!(Ok(128), reader.read_uint16());
+        reader.reset(middle);
+        assert_eq!(Ok(8392743), reader.read_int32());
+        reader.reset(start);
+        assert_eq!(0, reader.position());
+        assert_eq!(3, reader.reads());
+        assert_eq!(Ok(15), reader.read_uint8());
+    }
+
+    #[test]
+    fn reader_transaction_test() {
+        let buffer = [15, 0, 0, 128, 16, 39];
+        let mut reader = Reader::<Little>::new(&buffer);
+        assert_eq!(Ok(15), reader.transaction(|reader| reader.read_uint16()));
+        assert_eq!(2, reader.position());
+        let result = reader.transaction(|reader| {
+            reader.read_uint16()?;
+            reader.transaction(|reader| reader.read_uint8())?;
+            reader.read_uint16()
+        });
+        assert_eq!(
+            Err(Error::new(ErrorKind::OutOfBounds { start_index: 5, requested: 2, len: 6 })),
+            result
+        );
+        assert_eq!(2, reader.position());
+        assert_eq!(Ok((32768, 10000)), reader.transaction(|reader| reader.read::<(u16, u16)>()));
+        assert_eq!(0, reader.remaining());
+    }
+
+    #[test]
+    fn reader_read_bytes_test() {
+        let buffer = [2, 10, 0, 246, 255];
+        let mut reader = Reader::<Little>::new(&buffer);
+        assert_eq!(Ok(&[][..]), reader.read_bytes(0));
+        assert_eq!(Ok(&[2, 10][..]), reader.read_bytes(2));
+        assert_eq!(
+            Err(Error::new(ErrorKind::OutOfBounds { start_index: 2, requested: 4, len: 5 })),
+            reader.read_bytes(4)
+        );
+        let rest = reader.read_bytes(3).unwrap();
+        assert_eq!(buffer[2..].as_ptr(), rest.as_ptr());
+        assert_eq!((5, 3), (reader.consumed(), reader.reads()));
+        assert_eq!(Ok(()), reader.assert_exhausted());
+    }
+
+    #[test]
+    fn reader_read_with_test() {
+        let buffer = [15, 0, 0, 128, 16, 39, 240, 216, 241, 255, 127];
+        let mut reader = Reader::<Little>::new(&buffer);
+        assert_eq!(Ok(3840), reader.peek_with::<u16>(Endian::BE));
+        assert_eq!(Ok(3840), reader.read_uint16_with(Endian::BE));
+        assert_eq!(Ok(8392743), reader.read_int32_with(Endian::BE));
+        assert_eq!(Ok(-10000), reader.read_int16_with(Endian::LE));
+        assert_eq!(Ok(u16::from_ne_bytes([241, 255])), reader.read_uint16_with(Endian::NE));
+        assert!(reader.read_uint64_with(Endian::BE).is_err());
+        assert_eq!(Ok(127), reader.read_with::<u8>(Endian::BE));
+        assert_eq!(0, reader.remaining());
+    }
+
+    #[test]
+    fn reader_read_array_test() {
+        let buffer = [137, 80, 78, 71, 13, 10];
+        let mut reader = Reader::<Big>::new(&buffer);
+        assert_eq!(Ok([137, 80, 78, 71]), reader.read_array::<4>());
+        assert!(reader.read_array::<4>().is_err());
+        assert_eq!(Ok([13, 10]), reader.read_array());
+        assert_eq!(Ok(()), reader.assert_exhausted());
+    }
+
+    #[test]
+    fn from_reader_test() {
+        struct Pascal(u8, u16);
+
+        impl FromReader for Pascal {
+            fn read_from<E: BitConvEndian>(reader: &mut Reader<'_, E>) -> Result<Self> {
+                let len = reader.read_uint8()?;
+                let sum = reader.read_bytes(len.into())?.iter().map(|byte| u16::from(*byte)).sum();
+                Ok(Pascal(len, sum))
+            }
+        }
+
+        let buffer = [15, 0, 3, 1, 2, 3, 0, 128, 16];
+        let mut reader = Reader::<Big>::new(&buffer);
+        assert_eq!(Ok(3840), u16::read_from(&mut reader));
+        let pascal = Pascal::read_from(&mut reader).unwrap();
+        assert_eq!((3, 6), (pascal.0, pascal.1));
+        assert_eq!(Ok((0, 32784)), <(u8, u16)>::read_from(&mut reader));
+        assert!(Pascal::read_from(&mut Reader::<Big>::new(&[2, 1])).is_err());
+    }
+}
@@ -0,0 +1,110 @@
+//! Random access to buffers of fixed-size records.
+use crate::{BitConvEndian, Error, ErrorKind, FixedSize, FromBytes, Result};
+use core::marker::PhantomData;
+
+/// View over a buffer holding consecutive records of type `T`, each decoded
+/// in the endianness `E` when it is accessed.
+///
+/// Any bytes after the last whole record are left out of the view and can be
+/// inspected with [`remainder`](Records::remainder).
+/// # Example
+///
+/// ```
+/// use bitconv::{endian::Little, Records};
+///
+/// // Records of an id and a score.
+/// let file = [1, 16, 39, 2, 232, 3, 3, 0];
+/// let records = Records::<(u8, u16), Little>::new(&file);
+/// assert_eq!(2, records.len());
+/// assert_eq!(Ok((2, 1000)), records.get(1));
+/// assert_eq!(11000, records.iter().map(|record| record.unwrap().1).sum::<u16>());
+/// assert_eq!([3, 0], records.remainder());
+/// ```
+pub struct Records<'a, T, E> {
+    data: &'a [u8],
+    marker: PhantomData<(T, E)>,
+}
+
+impl<'a, T: FromBytes + FixedSize, E: BitConvEndian> Records<'a, T, E> {
+    pub fn new(data: &'a [u8]) -> Self { Records { data, marker: PhantomData } }
+
+    /// Returns the number of whole records in the buffer, or 0 for records
+    /// that take up no bytes.
+    pub fn len(&self) -> usize {
+        match T::SIZE {
+            0 => 0,
+            size => self.data.len() / size,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool { self.len() == 0 }
+
+    /// Decodes the record at index.
+    pub fn get(&self, index: usize) -> Result<T> {
+        let start_index = index.saturating_mul(T::SIZE);
+        if index >= self.len() {
+            return Err(Error::new(ErrorKind::OutOfBounds {
+                start_index,
+                requested: T::SIZE,
+                len: self.data.len(),
+            }));
+        }
+        T::from_bytes::<E>(self.data, start_index)
+            .map(|(record, _)| record)
+            .map_err(|error| error.with_index(index))
+    }
+
+    /// Returns the bytes after the last whole record.
+    pub fn remainder(&self) -> &'a [u8] { &self.data[self.len() * T::SIZE..] }
+
+    /// Returns an iterator decoding each record in order.
+    pub fn iter(&self) -> impl Iterator<Item = Result<T>> + '_ {
+        (0..self.len()).map(move |index| self.get(index))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::endian::*;
+
+    #[test]
+    fn records_test_le() {
+        let buffer = [15, 0, 0, 128, 16, 39, 240, 216, 241, 255, 127];
+        let records = Records::<u32, Little>::new(&buffer);
+        assert_eq!(2, records.len());
+        assert_eq!(Ok(2147483663), records.get(0));
+        assert_eq!(Ok(3639617296), records.get(1));
+        assert_eq!(
+            Err(Error::new(ErrorKind::OutOfBounds { start_index: 8, requested: 4, len: 11 })),
+            records.get(2)
+        );
+        assert!(records.get(usize::MAX).is_err());
+        assert_eq!([241, 255, 127], records.remainder());
+        let mut iter = records.iter();
+        assert_eq!(Some(Ok(2147483663)), iter.next());
+        assert_eq!(Some(Ok(3639617296)), iter.next());
+        assert_eq!(None, iter.next());
+    }
+
+    #[test]
+    fn records_test_be() {
+        let buffer = [15, 0, 0, 128, 16, 39, 240, 216, 241, 255, 127];
+        let records = Records::<(i8, i16), Big>::new(&buffer);
+        assert_eq!(3, records.len());
+        assert_eq!(Ok((15, 0)), records.get(0));
+        assert_eq!(Ok((-16, -9999)), records.get(2));
+        assert_eq!([255, 127], records.remainder());
+        assert_eq!(
+            Err(Error::new(ErrorKind::OutOfBounds {
+                start_index: usize::MAX,
+                requested: 3,
+                len: 11
+            })),
+            records.get(usize::MAX)
+        );
+        let empty = Records::<u64, Big>::new(&buffer[..7]);
+        assert!(empty.is_empty());
+        assert_eq!(0, empty.iter().count());
+    }
+}
@@ -0,0 +1,89 @@
+//! Reversal of the order of bits in buffers, for hardware and checksums that
+//! take bytes least significant bit first.
+use core::convert::TryInto;
+
+/// Reverses the order of the bits within each byte of data, so that its most
+/// significant bit becomes its least significant one, leaving the bytes where
+/// they are.
+///
+/// This turns bytes for a controller shifting them out least significant bit
+/// first into what an MSB-first peripheral, such as most SPI ones, needs to
+/// send, and the input of a reflected CRC into that of a plain one. Bytes are
+/// reversed eight at a time as 64-bit words.
+/// # Example
+///
+/// ```
+/// use bitconv::reverse_bits_per_byte;
+///
+/// let mut buffer = [0b0000_0001, 0b1100_1010, 0xf0];
+/// reverse_bits_per_byte(&mut buffer);
+/// assert_eq!([0b1000_0000, 0b0101_0011, 0x0f], buffer);
+/// ```
+#[inline]
+#[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
+pub fn reverse_bits_per_byte(data: &mut [u8]) {
+    let mut chunks = data.chunks_exact_mut(8);
+    for chunk in &mut chunks {
+        let word = u64::from_ne_bytes(chunk.as_ref().try_into().unwrap_or_default());
+        let word = word.reverse_bits().swap_bytes().to_ne_bytes();
+        chunk.iter_mut().zip(word).for_each(|(byte, reversed)| *byte = reversed);
+    }
+    for byte in chunks.into_remainder() {
+        *byte = byte.reverse_bits();
+    }
+}
+
+/// Reverses the order of all the bits of data, taken as a single string of
+/// bits, so that the least significant bit of the last byte becomes the most
+/// significant bit of the first.
+///
+/// This is the bit-level counterpart of reversing the bytes of a buffer, for
+/// a frame sent least significant bit first from its end.
+/// # Example
+///
+/// ```
+/// use bitconv::reverse_bit_order;
+///
+/// let mut buffer = [0b1000_0000, 0b0000_0011];
+/// reverse_bit_order(&mut buffer);
+/// assert_eq!([0b1100_0000, 0b0000_0001], buffer);
+/// ```
+#[inline]
+#[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
+pub fn reverse_bit_order(data: &mut [u8]) {
+    data.reverse();
+    reverse_bits_per_byte(data);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn reverse_bits_per_byte_test() {
+        let mut buffer = [0u8; 256];
+        buffer.iter_mut().zip(0..=255).for_each(|(byte, value)| *byte = value);
+        for len in [0, 1, 7, 8, 9, 17, 256] {
+            let mut reversed = buffer;
+            reverse_bits_per_byte(&mut reversed[..len]);
+            for (index, (&byte, &reversed)) in buffer.iter().zip(&reversed).enumerate() {
+                match index < len {
+                    true => assert_eq!(byte.reverse_bits(), reversed),
+                    false => assert_eq!(byte, reversed),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn reverse_bit_order_test() {
+        let mut buffer: [u8; 11] =
+            0x01_2345_6789_abcd_ef11_2233u128.to_be_bytes()[5..].try_into().unwrap();
+        reverse_bit_order(&mut buffer);
+        let expected = (0x01_2345_6789_abcd_ef11_2233u128.reverse_bits() >> 40).to_be_bytes();
+        assert_eq!(expected[5..], buffer);
+        reverse_bit_order(&mut buffer);
+        assert_eq!(0x01_2345_6789_abcd_ef11_2233u128.to_be_bytes()[5..], buffer);
+        reverse_bit_order(&mut []);
+    }
+}
@@ -0,0 +1,534 @@
+//! Base64, Base32 and Base16 text for byte arrays, in the alphabets of RFC
+//! 4648, with or without padding.
+//!
+//! The three share a single codec, which splits data into groups of bytes
+//! that fill a whole number of characters: three bytes in four characters for
+//! Base64, five bytes in eight characters for Base32 and one byte in two
+//! characters for Base16.
+use crate::{Error, ErrorKind, Result};
+#[cfg(feature = "alloc")]
+use alloc::{string::String, vec, vec::Vec};
+use core::{fmt, str};
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+const BASE64_URL_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+const BASE32_ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+const BASE32_HEX_ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHIJKLMNOPQRSTUV";
+const BASE16_UPPER_ALPHABET: &[u8; 16] = b"0123456789ABCDEF";
+const BASE16_LOWER_ALPHABET: &[u8; 16] = b"0123456789abcdef";
+const BASE64_VALUES: [u8; 256] = values(BASE64_ALPHABET);
+const BASE64_URL_VALUES: [u8; 256] = values(BASE64_URL_ALPHABET);
+const BASE32_VALUES: [u8; 256] = values(BASE32_ALPHABET);
+const BASE32_HEX_VALUES: [u8; 256] = values(BASE32_HEX_ALPHABET);
+const BASE16_UPPER_VALUES: [u8; 256] = values(BASE16_UPPER_ALPHABET);
+const BASE16_LOWER_VALUES: [u8; 256] = values(BASE16_LOWER_ALPHABET);
+
+/// Returns the value of each character of alphabet, and 0xff for the others.
+const fn values(alphabet: &[u8]) -> [u8; 256] {
+    let mut values = [0xff; 256];
+    let mut value = 0;
+    while value < alphabet.len() {
+        values[alphabet[value] as usize] = value as u8;
+        value += 1;
+    }
+    values
+}
+
+/// An alphabet of 2 to the power of bits characters, and whether text is
+/// padded with `=` to a whole group.
+#[derive(Clone, Copy, PartialEq, Eq)]
+struct Codec {
+    alphabet: &'static [u8],
+    values: &'static [u8; 256],
+    bits: usize,
+    padding: bool,
+}
+
+impl Codec {
+    const fn new(alphabet: &'static [u8], values: &'static [u8; 256], bits: usize) -> Self {
+        Codec { alphabet, values, bits, padding: true }
+    }
+
+    /// Returns the number of characters of a group.
+    const fn group_chars(&self) -> usize { 8 / gcd(8, self.bits) }
+
+    /// Returns the number of bytes of a group.
+    const fn group_bytes(&self) -> usize { self.bits / gcd(8, self.bits) }
+
+    const fn encoded_len(&self, len: usize) -> usize {
+        let full = (len / self.group_bytes()).saturating_mul(self.group_chars());
+        match (len % self.group_bytes(), self.padding) {
+            (0, _) => full,
+            (_, true) => full.saturating_add(self.group_chars()),
+            (rest, false) => full.saturating_add((rest * 8).div_ceil(self.bits)),
+        }
+    }
+
+    fn encode_into(&self, data: &[u8], out: &mut [u8]) -> Result<usize> {
+        let len = self.encoded_len(data.len());
+        let text = match out.get_mut(..len) {
+            Some(text) => text,
+            None => {
+                let kind =
+                    ErrorKind::OutOfBounds { start_index: 0, requested: len, len: out.len() };
+                return Err(Error::new(kind));
+            }
+        };
+        let group_bits = 8 * self.group_bytes();
+        let groups = data.chunks(self.group_bytes()).zip(text.chunks_mut(self.group_chars()));
+        for (bytes, digits) in groups {
+            let group = bytes.iter().fold(0u64, |group, &byte| group << 8 | u64::from(byte));
+            let group = group << (group_bits - 8 * bytes.len());
+            let used = (bytes.len() * 8).div_ceil(self.bits);
+            for (index, digit) in digits.iter_mut().enumerate() {
+                let value =
+                    group >> (group_bits - self.bits * (index + 1)) & ((1 << self.bits) - 1);
+                *digit = match index < used {
+                    true => self.alphabet[value as usize],
+                    false => b'=',
+                };
+            }
+        }
+        Ok(len)
+    }
+
+    /// Returns the characters of text that hold data, without its padding,
+    /// or fails with the offset of the first character outside the alphabet,
+    /// or else of the end of text or of the last character holding data if
+    /// its length cannot be that of an encoding.
+    fn body<'a>(&self, text: &'a str) -> Result<&'a [u8]> {
+        let text = text.as_bytes();
+        let mut body = text;
+        if self.padding {
+            for _ in (8usize.div_ceil(self.bits))..self.group_chars() {
+                if let [rest @ .., b'='] = body {
+                    body = rest;
+                }
+            }
+        }
+        if let Some(start_index) = body.iter().position(|&digit| self.value(digit).is_none()) {
+            return Err(Error::new(ErrorKind::InvalidEncoding { start_index }));
+        }
+        if self.padding && text.len() & (self.group_chars() - 1) != 0 {
+            return Err(Error::new(ErrorKind::InvalidEncoding { start_index: text.len() }));
+        }
+        // Whether a group of this many characters is the shortest that holds
+        // its bytes, since the others hold bits of no byte at all.
+        let rest = body.len() % self.group_chars();
+        match (rest * self.bits / 8 * 8).div_ceil(self.bits) == rest {
+            true => Ok(body),
+            false => Err(Error::new(ErrorKind::InvalidEncoding { start_index: body.len() - 1 })),
+        }
+    }
+
+    fn value(&self, digit: u8) -> Option<u64> {
+        match self.values[usize::from(digit)] {
+            0xff => None,
+            value => Some(u64::from(value)),
+        }
+    }
+
+    fn decoded_len(&self, text: &str) -> Result<usize> {
+        self.body(text).map(|body| {
+            let full = body.len() / self.group_chars() * self.group_bytes();
+            full + body.len() % self.group_chars() * self.bits / 8
+        })
+    }
+
+    fn decode_into(&self, text: &str, out: &mut [u8]) -> Result<usize> {
+        let (body, len) = (self.body(text)?, self.decoded_len(text)?);
+        let data = match out.get_mut(..len) {
+            Some(data) => data,
+            None => {
+                let kind =
+                    ErrorKind::OutOfBounds { start_index: 0, requested: len, len: out.len() };
+                return Err(Error::new(kind));
+            }
+        };
+        let group_bits = 8 * self.group_bytes();
+        let groups = body.chunks(self.group_chars()).zip(data.chunks_mut(self.group_bytes()));
+        for (index, (digits, bytes)) in groups.enumerate() {
+            let start_index = index * self.group_chars();
+            let group = digits.iter().fold(0, |group, &digit| {
+                group << self.bits | self.value(digit).unwrap_or_default()
+            });
+            let group = group << (self.bits * (self.group_chars() - digits.len()));
+            if group & ((1 << (group_bits - 8 * bytes.len())) - 1) != 0 {
+                let start_index = start_index + digits.len() - 1;
+                return Err(Error::new(ErrorKind::InvalidEncoding { start_index }));
+            }
+            for (offset, byte) in bytes.iter_mut().enumerate() {
+                *byte = (group >> (group_bits - 8 * (offset + 1))) as u8;
+            }
+        }
+        Ok(len)
+    }
+}
+
+const fn gcd(a: usize, b: usize) -> usize {
+    match b {
+        0 => a,
+        _ => gcd(b, a % b),
+    }
+}
+
+macro_rules! Rfc4648Impl {
+    ($($type:ident $group:literal),+) => {$(
+        impl $type {
+            #[doc = concat!(
+                "Returns the number of characters of the ", stringify!($type), " text of len bytes."
+            )]
+            pub const fn encoded_len(&self, len: usize) -> usize { self.0.encoded_len(len) }
+
+            #[doc = concat!(
+                "Writes the ", stringify!($type), " text of data to the start of out and returns ",
+                "the number of characters written, or fails with `ErrorKind::OutOfBounds`, ",
+                "writing nothing, if out is shorter than [`encoded_len`](", stringify!($type),
+                "::encoded_len)."
+            )]
+            pub fn encode_into(&self, data: &[u8], out: &mut [u8]) -> Result<usize> {
+                self.0.encode_into(data, out)
+            }
+
+            #[doc = concat!("Returns the ", stringify!($type), " text of data as a new string.")]
+            #[cfg(feature = "alloc")]
+            pub fn encode(&self, data: &[u8]) -> String {
+                let mut text = vec![0; self.encoded_len(data.len())];
+                let _ = self.encode_into(data, &mut text);
+                text.into_iter().map(char::from).collect()
+            }
+
+            #[doc = concat!(
+                "Returns the number of bytes encoded by text, or fails as [`decode_into`](",
+                stringify!($type), "::decode_into) does if its length cannot be that of an ",
+                "encoding."
+            )]
+            pub fn decoded_len(&self, text: &str) -> Result<usize> { self.0.decoded_len(text) }
+
+            #[doc = concat!(
+                "Parses ", stringify!($type), " text into the start of out and returns the number ",
+                "of bytes written.\n\n",
+                "Fails with `ErrorKind::InvalidEncoding` at the byte offset in text of the first ",
+                "character outside the alphabet or the padding, of the last character if its ",
+                "leftover bits are not zero or no group of ", $group, " characters can end with ",
+                "it, or of the end of text if it is too short for its padding, and with ",
+                "`ErrorKind::OutOfBounds`, writing nothing, if out is shorter than ",
+                "[`decoded_len`](", stringify!($type), "::decoded_len). Part of out may have been ",
+                "written when text is invalid."
+            )]
+            pub fn decode_into(&self, text: &str, out: &mut [u8]) -> Result<usize> {
+                self.0.decode_into(text, out)
+            }
+
+            #[doc = concat!(
+                "Parses ", stringify!($type), " text into a new vector, failing as ",
+                "[`decode_into`](", stringify!($type), "::decode_into) does."
+            )]
+            #[cfg(feature = "alloc")]
+            pub fn decode(&self, text: &str) -> Result<Vec<u8>> {
+                let mut data = vec![0; self.decoded_len(text)?];
+                self.decode_into(text, &mut data)?;
+                Ok(data)
+            }
+        }
+
+        impl fmt::Debug for $type {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.debug_struct(stringify!($type))
+                    .field("alphabet", &str::from_utf8(self.0.alphabet).unwrap_or_default())
+                    .field("padding", &self.0.padding)
+                    .finish()
+            }
+        }
+    )+};
+}
+
+/// A variant of Base64: its alphabet and whether text is padded with `=` to
+/// a multiple of four characters.
+///
+/// Decoding is strict, so that every byte array has a single encoding: text
+/// must use the alphabet and padding of the variant, and the bits left over
+/// by its last character must be zero.
+/// # Example
+///
+/// ```
+/// use bitconv::{Base64, ErrorKind};
+///
+/// let mut text = [0; 8];
+/// let len = Base64::STANDARD.encode_into(&[0xfb, 0xff, 0x01, 0x02], &mut text).unwrap();
+/// assert_eq!(b"+/8BAg==", &text[..len]);
+/// let len = Base64::URL_SAFE_NO_PAD.encode_into(&[0xfb, 0xff, 0x01, 0x02], &mut text).unwrap();
+/// assert_eq!(b"-_8BAg", &text[..len]);
+///
+/// let mut data = [0; 4];
+/// assert_eq!(Ok(4), Base64::URL_SAFE_NO_PAD.decode_into("-_8BAg", &mut data));
+/// assert_eq!([0xfb, 0xff, 0x01, 0x02], data);
+/// let error = Base64::STANDARD.decode_into("Zm9v,mFy", &mut data).unwrap_err();
+/// assert_eq!(ErrorKind::InvalidEncoding { start_index: 4 }, error.kind());
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Base64(Codec);
+
+impl Base64 {
+    /// The standard alphabet, with `+` and `/`, and padding, as in MIME and
+    /// most JSON and YAML fields.
+    pub const STANDARD: Base64 = Base64(Codec::new(BASE64_ALPHABET, &BASE64_VALUES, 6));
+    /// The standard alphabet without padding.
+    pub const STANDARD_NO_PAD: Base64 = Base64::STANDARD.padding(false);
+    /// The URL and file name safe alphabet, with `-` and `_`, and padding.
+    pub const URL_SAFE: Base64 = Base64(Codec::new(BASE64_URL_ALPHABET, &BASE64_URL_VALUES, 6));
+    /// The URL and file name safe alphabet without padding, as in JSON Web
+    /// Tokens.
+    pub const URL_SAFE_NO_PAD: Base64 = Base64::URL_SAFE.padding(false);
+
+    /// Sets whether text is padded with `=` to a multiple of four characters.
+    pub const fn padding(mut self, padding: bool) -> Self {
+        self.0.padding = padding;
+        self
+    }
+}
+
+/// A variant of Base32: its alphabet and whether text is padded with `=` to
+/// a multiple of eight characters.
+///
+/// Decoding is as strict as that of [`Base64`], and so also takes uppercase
+/// letters only.
+/// # Example
+///
+/// ```
+/// use bitconv::Base32;
+///
+/// // A TOTP secret, as in the URI of an authenticator app QR code.
+/// let mut secret = [0; 10];
+/// assert_eq!(Ok(10), Base32::STANDARD_NO_PAD.decode_into("JBSWY3DPEHPK3PXP", &mut secret));
+/// assert_eq!(*b"Hello!\xde\xad\xbe\xef", secret);
+///
+/// let mut text = [0; 8];
+/// assert_eq!(Ok(8), Base32::HEX.encode_into(b"foo", &mut text));
+/// assert_eq!(b"CPNMU===", &text);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Base32(Codec);
+
+impl Base32 {
+    /// The standard alphabet, of uppercase letters and the digits 2 to 7,
+    /// and padding.
+    pub const STANDARD: Base32 = Base32(Codec::new(BASE32_ALPHABET, &BASE32_VALUES, 5));
+    /// The standard alphabet without padding, as in TOTP secrets.
+    pub const STANDARD_NO_PAD: Base32 = Base32::STANDARD.padding(false);
+    /// The extended hex alphabet, of the digits and the letters A to V, and
+    /// padding, which sorts as the data it encodes does.
+    pub const HEX: Base32 = Base32(Codec::new(BASE32_HEX_ALPHABET, &BASE32_HEX_VALUES, 5));
+    /// The extended hex alphabet without padding, as in the hashed owner
+    /// names of DNSSEC NSEC3 records.
+    pub const HEX_NO_PAD: Base32 = Base32::HEX.padding(false);
+
+    /// Sets whether text is padded with `=` to a multiple of eight characters.
+    pub const fn padding(mut self, padding: bool) -> Self {
+        self.0.padding = padding;
+        self
+    }
+}
+
+/// A variant of Base16, two hexadecimal digits per byte in a single case.
+///
+/// Unlike [`try_from_hex_string_into`](crate::try_from_hex_string_into),
+/// decoding takes nothing but digits of the case of the variant, for formats
+/// that fix the text of a value.
+/// # Example
+///
+/// ```
+/// use bitconv::Base16;
+///
+/// let mut text = [0; 6];
+/// assert_eq!(Ok(6), Base16::UPPER.encode_into(&[15, 0, 255], &mut text));
+/// assert_eq!(b"0F00FF", &text);
+/// let mut data = [0; 3];
+/// assert_eq!(Ok(3), Base16::LOWER.decode_into("0f00ff", &mut data));
+/// assert!(Base16::UPPER.decode_into("0f00ff", &mut data).is_err());
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Base16(Codec);
+
+impl Base16 {
+    /// Uppercase digits, as in RFC 4648.
+    pub const UPPER: Base16 = Base16(Codec {
+        padding: false,
+        ..Codec::new(BASE16_UPPER_ALPHABET, &BASE16_UPPER_VALUES, 4)
+    });
+    /// Lowercase digits, as in the text of most hashes.
+    pub const LOWER: Base16 = Base16(Codec {
+        padding: false,
+        ..Codec::new(BASE16_LOWER_ALPHABET, &BASE16_LOWER_VALUES, 4)
+    });
+}
+
+Rfc4648Impl!(Base64 "four", Base32 "eight", Base16 "two");
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn invalid(start_index: usize) -> Result<usize> {
+        Err(Error::new(ErrorKind::InvalidEncoding { start_index }))
+    }
+
+    fn check_vectors(
+        encode_into: fn(&[u8], &mut [u8]) -> Result<usize>,
+        no_pad: fn(&[u8], &mut [u8]) -> Result<usize>,
+        vectors: &[(&[u8], &str)],
+    ) {
+        let mut text = [0; 16];
+        for &(data, expected) in vectors {
+            let len = expected.len();
+            assert_eq!(Ok(len), encode_into(data, &mut text));
+            assert_eq!(expected.as_bytes(), &text[..len]);
+            let len = expected.trim_end_matches('=').len();
+            assert_eq!(Ok(len), no_pad(data, &mut text));
+            assert_eq!(&expected.as_bytes()[..len], &text[..len]);
+        }
+    }
+
+    #[test]
+    fn base64_encode_into_test() {
+        check_vectors(
+            |data, out| Base64::STANDARD.encode_into(data, out),
+            |data, out| Base64::STANDARD_NO_PAD.encode_into(data, out),
+            &[
+                (b"", ""),
+                (b"f", "Zg=="),
+                (b"fo", "Zm8="),
+                (b"foo", "Zm9v"),
+                (b"foob", "Zm9vYg=="),
+                (b"fooba", "Zm9vYmE="),
+                (b"foobar", "Zm9vYmFy"),
+            ],
+        );
+        let mut text = [0; 4];
+        assert_eq!(Ok(4), Base64::URL_SAFE.encode_into(&[0xfb, 0xef, 0xff], &mut text));
+        assert_eq!(b"--__", &text);
+        assert_eq!(Ok(4), Base64::STANDARD.encode_into(&[0xfb, 0xef, 0xff], &mut text));
+        assert_eq!(b"++//", &text);
+        assert_eq!(
+            Err(Error::new(ErrorKind::OutOfBounds { start_index: 0, requested: 4, len: 3 })),
+            Base64::STANDARD.encode_into(b"f", &mut text[..3])
+        );
+        assert_eq!(usize::MAX, Base64::STANDARD.encoded_len(usize::MAX));
+    }
+
+    #[test]
+    fn base64_decode_into_test() {
+        let mut data = [0; 6];
+        let mut decode = |base64: Base64, text| base64.decode_into(text, &mut data);
+        assert_eq!(Ok(0), decode(Base64::STANDARD, ""));
+        assert_eq!(Ok(6), decode(Base64::STANDARD, "Zm9vYmFy"));
+        assert_eq!(Ok(4), decode(Base64::STANDARD, "Zm9vYg=="));
+        assert_eq!(Ok(5), decode(Base64::STANDARD_NO_PAD, "Zm9vYmE"));
+        assert_eq!(Ok(3), decode(Base64::URL_SAFE_NO_PAD, "--__"));
+        assert_eq!(invalid(0), decode(Base64::URL_SAFE, "++//"));
+        assert_eq!(invalid(2), decode(Base64::URL_SAFE, "--+_"));
+        assert_eq!(invalid(7), decode(Base64::STANDARD, "Zm9vYmE"));
+        assert_eq!(invalid(7), decode(Base64::STANDARD_NO_PAD, "Zm9vYmE="));
+        assert_eq!(invalid(4), decode(Base64::STANDARD_NO_PAD, "Zm9vY"));
+        assert_eq!(invalid(4), decode(Base64::STANDARD, "Zm9v===="));
+        assert_eq!(invalid(2), decode(Base64::STANDARD, "Zm=v"));
+        assert_eq!(invalid(1), decode(Base64::STANDARD, "Zh=="));
+        assert_eq!(invalid(2), decode(Base64::STANDARD, "Zm9="));
+        assert_eq!(invalid(4), decode(Base64::STANDARD, "Zm9v\u{e9}=="));
+        assert_eq!(
+            Err(Error::new(ErrorKind::OutOfBounds { start_index: 0, requested: 4, len: 3 })),
+            Base64::STANDARD.decode_into("Zm9vYg==", &mut data[..3])
+        );
+        assert_eq!(Ok(4), Base64::STANDARD.decoded_len("Zm9vYg=="));
+    }
+
+    #[test]
+    fn base32_encode_into_test() {
+        let vectors: [(&[u8], &str, &str); 7] = [
+            (b"", "", ""),
+            (b"f", "MY======", "CO======"),
+            (b"fo", "MZXQ====", "CPNG===="),
+            (b"foo", "MZXW6===", "CPNMU==="),
+            (b"foob", "MZXW6YQ=", "CPNMUOG="),
+            (b"fooba", "MZXW6YTB", "CPNMUOJ1"),
+            (b"foobar", "MZXW6YTBOI======", "CPNMUOJ1E8======"),
+        ];
+        let standard: [_; 7] = vectors.map(|(data, standard, _)| (data, standard));
+        let hex: [_; 7] = vectors.map(|(data, _, hex)| (data, hex));
+        check_vectors(
+            |data, out| Base32::STANDARD.encode_into(data, out),
+            |data, out| Base32::STANDARD_NO_PAD.encode_into(data, out),
+            &standard,
+        );
+        check_vectors(
+            |data, out| Base32::HEX.encode_into(data, out),
+            |data, out| Base32::HEX_NO_PAD.encode_into(data, out),
+            &hex,
+        );
+        assert_eq!(16, Base32::STANDARD.encoded_len(6));
+        assert_eq!(10, Base32::STANDARD_NO_PAD.encoded_len(6));
+    }
+
+    #[test]
+    fn base32_decode_into_test() {
+        let mut data = [0; 6];
+        let mut decode = |base32: Base32, text| base32.decode_into(text, &mut data);
+        assert_eq!(Ok(6), decode(Base32::STANDARD, "MZXW6YTBOI======"));
+        assert_eq!(Ok(4), decode(Base32::STANDARD_NO_PAD, "MZXW6YQ"));
+        assert_eq!(Ok(3), decode(Base32::HEX, "CPNMU==="));
+        assert_eq!(invalid(0), decode(Base32::STANDARD, "mzxw6==="));
+        assert_eq!(invalid(0), decode(Base32::HEX, "WPNMU==="));
+        assert_eq!(invalid(7), decode(Base32::STANDARD, "MZXW6YQ"));
+        assert_eq!(invalid(2), decode(Base32::STANDARD, "MZX====="));
+        assert_eq!(invalid(0), decode(Base32::STANDARD_NO_PAD, "M"));
+        assert_eq!(invalid(5), decode(Base32::STANDARD_NO_PAD, "MZXW6Y"));
+        assert_eq!(invalid(1), decode(Base32::STANDARD, "MZ======"));
+        assert_eq!(invalid(1), decode(Base32::STANDARD, "M======="));
+        assert_eq!(invalid(2), decode(Base32::STANDARD, "MY=Y===="));
+    }
+
+    #[test]
+    fn base16_test() {
+        let mut text = [0; 12];
+        assert_eq!(Ok(12), Base16::UPPER.encode_into(b"foobar", &mut text));
+        assert_eq!(b"666F6F626172", &text);
+        assert_eq!(Ok(4), Base16::LOWER.encode_into(&[0xab, 0xcd], &mut text));
+        assert_eq!(b"abcd", &text[..4]);
+        let mut data = [0; 2];
+        assert_eq!(Ok(2), Base16::UPPER.decode_into("ABCD", &mut data));
+        assert_eq!([0xab, 0xcd], data);
+        assert_eq!(invalid(1), Base16::UPPER.decode_into("AbCD", &mut data));
+        assert_eq!(invalid(2), Base16::LOWER.decode_into("abc", &mut data));
+        assert_eq!(invalid(2), Base16::LOWER.decode_into("ab cd", &mut data));
+        assert_eq!(invalid(4), Base16::LOWER.decode_into("abcd==", &mut data));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn round_trip_test() {
+        let data: Vec<u8> = (0..=255).collect();
+        for len in 0..16 {
+            let data = &data[..len * 17];
+            for base64 in [
+                Base64::STANDARD,
+                Base64::STANDARD_NO_PAD,
+                Base64::URL_SAFE,
+                Base64::URL_SAFE_NO_PAD,
+            ] {
+                assert_eq!(Ok(data), base64.decode(&base64.encode(data)).as_deref());
+            }
+            for base32 in
+                [Base32::STANDARD, Base32::STANDARD_NO_PAD, Base32::HEX, Base32::HEX_NO_PAD]
+            {
+                assert_eq!(Ok(data), base32.decode(&base32.encode(data)).as_deref());
+            }
+            for base16 in [Base16::UPPER, Base16::LOWER] {
+                assert_eq!(Ok(data), base16.decode(&base16.encode(data)).as_deref());
+            }
+        }
+        assert_eq!("AAECAw==", Base64::STANDARD.encode(&[0, 1, 2, 3]));
+        assert_eq!("MZXW6YTBOI", Base32::STANDARD_NO_PAD.encode(b"foobar"));
+    }
+}
@@ -0,0 +1,243 @@
+//! Parsing of layouts that are only known at runtime.
+//!
+//! A [`Schema`] is a list of named fields, each with a type, an endianness and
+//! optionally a fixed offset, built while the program runs (for example from a
+//! user-supplied format definition) instead of at compile time.
+use crate::{endian::*, from_bytes_with, Error, ErrorKind, Result};
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+
+/// Type of a field described at runtime.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+pub enum FieldType {
+    Int8,
+    Int16,
+    Int32,
+    Int64,
+    UInt8,
+    UInt16,
+    UInt32,
+    UInt64,
+    /// A run of the given number of raw bytes.
+    Bytes(usize),
+}
+
+/// Value read for a field described at runtime.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Value {
+    Int8(i8),
+    Int16(i16),
+    Int32(i32),
+    Int64(i64),
+    UInt8(u8),
+    UInt16(u16),
+    UInt32(u32),
+    UInt64(u64),
+    Bytes(Vec<u8>),
+}
+
+/// A named field of a [`Schema`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Field {
+    name: String,
+    ty: FieldType,
+    endian: Endian,
+    offset: Option<usize>,
+}
+
+impl Field {
+    /// Creates a field that starts where the previous field of the schema
+    /// ended.
+    pub fn new(name: impl Into<String>, ty: FieldType, endian: Endian) -> Self {
+        Field { name: name.into(), ty, endian, offset: None }
+    }
+
+    /// Places the field at a fixed offset from the start of the schema
+    /// instead. Fields that follow it continue from where it ends.
+    pub fn at(mut self, offset: usize) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+
+    pub fn name(&self) -> &str { &self.name }
+
+    pub fn ty(&self) -> FieldType { self.ty }
+
+    pub fn endian(&self) -> Endian { self.endian }
+
+    pub fn offset(&self) -> Option<usize> { self.offset }
+}
+
+/// A layout description built at runtime.
+/// # Example
+///
+/// ```
+/// use bitconv::{
+///     endian::Endian, schema::{Field, FieldType, Schema, Value}
+/// };
+///
+/// let schema = Schema::new()
+///     .field(Field::new("magic", FieldType::Bytes(2), Endian::LE))
+///     .field(Field::new("length", FieldType::UInt16, Endian::BE))
+///     .field(Field::new("flags", FieldType::Int8, Endian::LE).at(6));
+///
+/// let buffer = [66, 67, 1, 0, 255, 255, 254];
+/// let (values, read) = schema.parse(&buffer, 0).unwrap();
+/// assert_eq!(Value::Bytes(vec![66, 67]), values["magic"]);
+/// assert_eq!(Value::UInt16(256), values["length"]);
+/// assert_eq!(Value::Int8(-2), values["flags"]);
+/// assert_eq!(7, read);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Schema {
+    fields: Vec<Field>,
+}
+
+impl Schema {
+    pub fn new() -> Self { Schema::default() }
+
+    /// Appends a field to the schema.
+    pub fn field(mut self, field: Field) -> Self {
+        self.push(field);
+        self
+    }
+
+    /// Appends a field to the schema in place.
+    pub fn push(&mut self, field: Field) { self.fields.push(field); }
+
+    pub fn fields(&self) -> &[Field] { &self.fields }
+
+    /// Parses the bytes starting at start_index according to the schema,
+    /// returning the value of each field by name along with the number of
+    /// bytes spanned by the fields.
+    ///
+    /// When several fields share a name, the value of the last one is kept.
+    /// Errors are given the position of the failed field within the schema as
+    /// context.
+    pub fn parse(
+        &self,
+        data: &[u8],
+        start_index: usize,
+    ) -> Result<(BTreeMap<String, Value>, usize)> {
+        let mut values = BTreeMap::new();
+        let mut offset = start_index;
+        let mut end = start_index;
+        for (index, field) in self.fields.iter().enumerate() {
+            if let Some(field_offset) = field.offset {
+                offset = start_index.saturating_add(field_offset);
+            }
+            let (value, read) = read_value(field.ty, field.endian, data, offset)
+                .map_err(|error| error.with_index(index))?;
+            offset += read;
+            end = end.max(offset);
+            values.insert(field.name.clone(), value);
+        }
+        Ok((values, end - start_index))
+    }
+}
+
+fn read_value(
+    ty: FieldType,
+    endian: Endian,
+    data: &[u8],
+    start_index: usize,
+) -> Result<(Value, usize)> {
+    Ok(match ty {
+        FieldType::Int8 => {
+            from_bytes_with(endian, data, start_index).map(|(v, n)| (Value::Int8(v), n))?
+        }
+        FieldType::Int16 => {
+            from_bytes_with(endian, data, start_index).map(|(v, n)| (Value::Int16(v), n))?
+        }
+        FieldType::Int32 => {
+            from_bytes_with(endian, data, start_index).map(|(v, n)| (Value::Int32(v), n))?
+        }
+        FieldType::Int64 => {
+            from_bytes_with(endian, data, start_index).map(|(v, n)| (Value::Int64(v), n))?
+        }
+        FieldType::UInt8 => {
+            from_bytes_with(endian, data, start_index).map(|(v, n)| (Value::UInt8(v), n))?
+        }
+        FieldType::UInt16 => {
+            from_bytes_with(endian, data, start_index).map(|(v, n)| (Value::UInt16(v), n))?
+        }
+        FieldType::UInt32 => {
+            from_bytes_with(endian, data, start_index).map(|(v, n)| (Value::UInt32(v), n))?
+        }
+        FieldType::UInt64 => {
+            from_bytes_with(endian, data, start_index).map(|(v, n)| (Value::UInt64(v), n))?
+        }
+        FieldType::Bytes(len) => {
+            let bytes =
+                data.get(start_index..).and_then(|bytes| bytes.get(..len)).ok_or_else(|| {
+                    Error::new(ErrorKind::OutOfBounds {
+                        start_index,
+                        requested: len,
+                        len: data.len(),
+                    })
+                })?;
+            (Value::Bytes(bytes.to_vec()), len)
+        }
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn schema_parse_test() {
+        let schema = Schema::new()
+            .field(Field::new("a", FieldType::UInt32, Endian::LE))
+            .field(Field::new("b", FieldType::Int16, Endian::BE))
+            .field(Field::new("c", FieldType::Int64, Endian::LE))
+            .field(Field::new("d", FieldType::UInt8, Endian::NE))
+            .field(Field::new("e", FieldType::Bytes(3), Endian::BE));
+        let buffer = [15, 0, 0, 128, 16, 39, 240, 216, 241, 255, 127, 86, 85, 85, 3, 1, 2, 3];
+        let (values, read) = schema.parse(&buffer, 0).unwrap();
+        assert_eq!(18, read);
+        assert_eq!(5, values.len());
+        assert_eq!(Value::UInt32(2147483663), values["a"]);
+        assert_eq!(Value::Int16(4135), values["b"]);
+        assert_eq!(Value::Int64(6148915973999155440), values["c"]);
+        assert_eq!(Value::UInt8(3), values["d"]);
+        assert_eq!(Value::Bytes(vec![1, 2, 3]), values["e"]);
+    }
+
+    #[test]
+    fn schema_offset_test() {
+        let schema = Schema::new()
+            .field(Field::new("count", FieldType::UInt16, Endian::LE).at(4))
+            .field(Field::new("next", FieldType::Int8, Endian::LE))
+            .field(Field::new("magic", FieldType::UInt32, Endian::BE).at(0))
+            .field(Field::new("next", FieldType::UInt8, Endian::LE));
+        let buffer = [0, 0, 0, 255, 3, 16, 39, 255, 255, 127];
+        let (values, read) = schema.parse(&buffer, 1).unwrap();
+        assert_eq!(7, read);
+        assert_eq!(Value::UInt16(10000), values["count"]);
+        assert_eq!(Value::UInt32(65283), values["magic"]);
+        assert_eq!(Value::UInt8(16), values["next"]);
+        assert_eq!(Schema::new().parse(&buffer, 20), Ok((BTreeMap::new(), 0)));
+    }
+
+    #[test]
+    fn schema_short_buffer_test() {
+        let buffer = [15, 0, 0, 128, 16];
+        let schema = Schema::new()
+            .field(Field::new("a", FieldType::UInt8, Endian::LE))
+            .field(Field::new("b", FieldType::UInt64, Endian::LE));
+        let error = schema.parse(&buffer, 0).unwrap_err();
+        assert_eq!(ErrorKind::OutOfBounds { start_index: 1, requested: 8, len: 5 }, error.kind());
+        assert_eq!(Some(crate::error::Segment::Index(1)), error.context().iter().next());
+        let schema = Schema::new().field(Field::new("a", FieldType::Bytes(2), Endian::LE).at(4));
+        let error = schema.parse(&buffer, 0).unwrap_err();
+        assert_eq!(ErrorKind::OutOfBounds { start_index: 4, requested: 2, len: 5 }, error.kind());
+        let schema = Schema::new().field(Field::new("a", FieldType::UInt8, Endian::LE).at(1));
+        let error = schema.parse(&buffer, usize::MAX).unwrap_err();
+        assert_eq!(
+            ErrorKind::OutOfBounds { start_index: usize::MAX, requested: 1, len: 5 },
+            error.kind()
+        );
+    }
+}
@@ -0,0 +1,775 @@
+//! Serde data format writing values in a fixed binary layout in the byte order
+//! of an endianness marker.
+//!
+//! The format is not self-describing: a value is read back with the type it
+//! was written with, field by field in declaration order, like the types
+//! derived with `#[derive(FromBytes, ToBytes)]`.
+//!
+//! - integers, `f32` and `f64` are written in the width of their type, and
+//!   `bool` as a byte that is 0 or 1.
+//! - `char` is written as its scalar value in a `u32`.
+//! - strings, byte strings, sequences and maps are prefixed by their length, or
+//!   number of elements or entries, as a `u64`.
+//! - `Option` is prefixed by a byte that is 0 for `None` and 1 for `Some`.
+//! - enum variants are prefixed by their index as a `u32`.
+//! - structs, tuples and arrays are written as their fields one after another,
+//!   and unit types and unit structs take no space at all.
+use crate::{endian::Endian, BitConvEndian, ErrorKind, Reader, Writer};
+use ::serde::{de, ser, Deserialize, Serialize};
+use alloc::{
+    string::{String, ToString}, vec::Vec
+};
+use core::{convert::TryFrom, fmt, str};
+
+/// Error returned when a value cannot be serialized or deserialized.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Error {
+    /// Reading the bytes failed, or they are not a valid encoding of the
+    /// value.
+    Conversion(crate::Error),
+    /// A sequence or map was serialized without knowing its length up front,
+    /// which its prefix needs.
+    UnknownLength,
+    /// A type asked for the format to tell it what the next value is, as
+    /// `deserialize_any` does, which a format that is not self-describing
+    /// cannot.
+    NotSelfDescribing,
+    /// An error reported by the `Serialize` or `Deserialize` implementation.
+    Custom(String),
+}
+
+/// Result type returned by the serializer and deserializer.
+pub type Result<V> = core::result::Result<V, Error>;
+
+impl From<crate::Error> for Error {
+    fn from(error: crate::Error) -> Self { Error::Conversion(error) }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Conversion(error) => write!(f, "{}", error),
+            Error::UnknownLength => f.write_str("sequence or map of unknown length"),
+            Error::NotSelfDescribing => f.write_str("the format is not self-describing"),
+            Error::Custom(message) => f.write_str(message),
+        }
+    }
+}
+
+impl ser::StdError for Error {}
+
+impl ser::Error for Error {
+    fn custom<M: fmt::Display>(message: M) -> Self { Error::Custom(message.to_string()) }
+}
+
+impl de::Error for Error {
+    fn custom<M: fmt::Display>(message: M) -> Self { Error::Custom(message.to_string()) }
+}
+
+/// Serializes a value to bytes in the endianness `T`.
+/// # Example
+///
+/// ```
+/// use bitconv::{endian::Big, serde::to_vec};
+///
+/// #[derive(serde::Serialize)]
+/// struct Header<'a> {
+///     version: u16,
+///     name: &'a str,
+///     flags: Option<u8>,
+/// }
+///
+/// let header = Header { version: 2, name: "ab", flags: None };
+/// assert_eq!(vec![0, 2, 0, 0, 0, 0, 0, 0, 0, 2, 97, 98, 0], to_vec::<Big, _>(&header).unwrap());
+/// ```
+pub fn to_vec<T: BitConvEndian, V: Serialize + ?Sized>(value: &V) -> Result<Vec<u8>> {
+    let mut writer = Writer::<T>::new();
+    value.serialize(&mut Serializer::new(&mut writer))?;
+    Ok(writer.into_inner())
+}
+
+/// Deserializes a value from the whole of data in the endianness `T`,
+/// borrowing strings and byte strings from it where the type allows.
+///
+/// Fails with `ErrorKind::TrailingBytes` if the value does not take up all
+/// of data. Use a [`Deserializer`] over a [`Reader`] to read values one after
+/// another instead.
+/// # Example
+///
+/// ```
+/// use bitconv::{endian::Little, serde::from_bytes};
+///
+/// #[derive(Debug, PartialEq, serde::Deserialize)]
+/// enum Shape {
+///     Point,
+///     Circle { radius: f32 },
+/// }
+///
+/// let buffer = [1, 0, 0, 0, 0, 0, 0x80, 0x3f];
+/// assert_eq!(Ok(Shape::Circle { radius: 1.0 }), from_bytes::<Little, _>(&buffer));
+/// assert!(from_bytes::<Little, Shape>(&buffer[..6]).is_err());
+/// assert_eq!(
+///     Ok(vec![Shape::Point]),
+///     from_bytes::<Little, _>(&[1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0])
+/// );
+/// ```
+pub fn from_bytes<'de, T: BitConvEndian, V: Deserialize<'de>>(data: &'de [u8]) -> Result<V> {
+    let mut reader = Reader::<T>::new(data);
+    let value = V::deserialize(&mut Deserializer::new(&mut reader))?;
+    reader.assert_exhausted()?;
+    Ok(value)
+}
+
+/// Serializer appending values to a [`Writer`].
+pub struct Serializer<'w, E> {
+    writer: &'w mut Writer<E>,
+}
+
+impl<'w, E: BitConvEndian> Serializer<'w, E> {
+    /// Creates a serializer appending to writer.
+    pub fn new(writer: &'w mut Writer<E>) -> Self { Serializer { writer } }
+
+    fn write_len(&mut self, len: Option<usize>) -> Result<()> {
+        let len = len.ok_or(Error::UnknownLength)?;
+        self.writer.write(&(len as u64));
+        Ok(())
+    }
+}
+
+macro_rules! SerializeImpl {
+    ($($name:ident $type:ty $(as $bits:ty)?),+) => {$(
+        fn $name(self, value: $type) -> Result<()> {
+            self.writer.write(&(value $(as $bits)?));
+            Ok(())
+        }
+    )+};
+}
+
+impl<'a, 'w, E: BitConvEndian> ser::Serializer for &'a mut Serializer<'w, E> {
+    type Ok = ();
+    type Error = Error;
+    type SerializeSeq = Self;
+    type SerializeTuple = Self;
+    type SerializeTupleStruct = Self;
+    type SerializeTupleVariant = Self;
+    type SerializeMap = Self;
+    type SerializeStruct = Self;
+    type SerializeStructVariant = Self;
+
+    SerializeImpl!(
+        serialize_bool bool,
+        serialize_i8 i8,
+        serialize_i16 i16,
+        serialize_i32 i32,
+        serialize_i64 i64,
+        serialize_u8 u8,
+        serialize_u16 u16,
+        serialize_u32 u32,
+        serialize_u64 u64,
+        serialize_f32 f32,
+        serialize_f64 f64,
+        serialize_char char as u32
+    );
+
+    fn serialize_i128(self, value: i128) -> Result<()> {
+        self.writer.write_bytes(&match E::ENDIANNESS {
+            Endian::LE => value.to_le_bytes(),
+            Endian::BE => value.to_be_bytes(),
+            Endian::NE => value.to_ne_bytes(),
+        });
+        Ok(())
+    }
+
+    fn serialize_u128(self, value: u128) -> Result<()> { self.serialize_i128(value as i128) }
+
+    fn serialize_str(self, value: &str) -> Result<()> { self.serialize_bytes(value.as_bytes()) }
+
+    fn serialize_bytes(self, value: &[u8]) -> Result<()> {
+        self.write_len(Some(value.len()))?;
+        self.writer.write_bytes(value);
+        Ok(())
+    }
+
+    fn serialize_none(self) -> Result<()> { self.serialize_u8(0) }
+
+    fn serialize_some<V: Serialize + ?Sized>(self, value: &V) -> Result<()> {
+        self.serialize_u8(1)?;
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<()> { Ok(()) }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<()> { Ok(()) }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+    ) -> Result<()> {
+        self.serialize_u32(variant_index)
+    }
+
+    fn serialize_newtype_struct<V: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &V,
+    ) -> Result<()> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<V: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        value: &V,
+    ) -> Result<()> {
+        self.serialize_u32(variant_index)?;
+        value.serialize(self)
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self> {
+        self.write_len(len)?;
+        Ok(self)
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self> { Ok(self) }
+
+    fn serialize_tuple_struct(self, _name: &'static str, _len: usize) -> Result<Self> { Ok(self) }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self> {
+        self.serialize_u32(variant_index)?;
+        Ok(self)
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<Self> {
+        self.write_len(len)?;
+        Ok(self)
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self> { Ok(self) }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self> {
+        self.serialize_u32(variant_index)?;
+        Ok(self)
+    }
+
+    fn is_human_readable(&self) -> bool { false }
+}
+
+macro_rules! SerializeCompoundImpl {
+    ($($trait:ident $method:ident $(, $key:ty)?;)+) => {$(
+        impl<'a, 'w, E: BitConvEndian> ser::$trait for &'a mut Serializer<'w, E> {
+            type Ok = ();
+            type Error = Error;
+
+            fn $method<V: Serialize + ?Sized>(&mut self, $(_key: $key,)? value: &V) -> Result<()> {
+                value.serialize(&mut **self)
+            }
+
+            fn end(self) -> Result<()> { Ok(()) }
+        }
+    )+};
+}
+
+SerializeCompoundImpl!(
+    SerializeSeq serialize_element;
+    SerializeTuple serialize_element;
+    SerializeTupleStruct serialize_field;
+    SerializeTupleVariant serialize_field;
+    SerializeStruct serialize_field, &'static str;
+    SerializeStructVariant serialize_field, &'static str;
+);
+
+impl<'a, 'w, E: BitConvEndian> ser::SerializeMap for &'a mut Serializer<'w, E> {
+    type Ok = ();
+    type Error = Error;
+
+    fn serialize_key<K: Serialize + ?Sized>(&mut self, key: &K) -> Result<()> {
+        key.serialize(&mut **self)
+    }
+
+    fn serialize_value<V: Serialize + ?Sized>(&mut self, value: &V) -> Result<()> {
+        value.serialize(&mut **self)
+    }
+
+    fn end(self) -> Result<()> { Ok(()) }
+}
+
+/// Deserializer reading values from a [`Reader`], borrowing strings and byte
+/// strings from its buffer.
+///
+/// Errors carry the context of the value that failed, such as
+/// `entries[2].name`, with the position of the reader left wherever the
+/// failure happened.
+pub struct Deserializer<'r, 'de, E> {
+    reader: &'r mut Reader<'de, E>,
+}
+
+impl<'r, 'de, E: BitConvEndian> Deserializer<'r, 'de, E> {
+    /// Creates a deserializer reading from reader.
+    pub fn new(reader: &'r mut Reader<'de, E>) -> Self { Deserializer { reader } }
+
+    fn read_len(&mut self) -> Result<usize> {
+        let start_index = self.reader.position();
+        let len = self.reader.read_uint64()?;
+        usize::try_from(len)
+            .map_err(|_| crate::Error::new(ErrorKind::InvalidCount { start_index }).into())
+    }
+
+    fn read_bytes(&mut self) -> Result<&'de [u8]> {
+        let len = self.read_len()?;
+        Ok(self.reader.read_bytes(len)?)
+    }
+}
+
+macro_rules! DeserializeImpl {
+    ($($name:ident $visit:ident $type:ty),+) => {$(
+        fn $name<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+            visitor.$visit(self.reader.read::<$type>()?)
+        }
+    )+};
+}
+
+impl<'de, 'a, 'r, E: BitConvEndian> de::Deserializer<'de> for &'a mut Deserializer<'r, 'de, E> {
+    type Error = Error;
+
+    fn deserialize_any<V: de::Visitor<'de>>(self, _visitor: V) -> Result<V::Value> {
+        Err(Error::NotSelfDescribing)
+    }
+
+    DeserializeImpl!(
+        deserialize_i8 visit_i8 i8,
+        deserialize_i16 visit_i16 i16,
+        deserialize_i32 visit_i32 i32,
+        deserialize_i64 visit_i64 i64,
+        deserialize_u8 visit_u8 u8,
+        deserialize_u16 visit_u16 u16,
+        deserialize_u32 visit_u32 u32,
+        deserialize_u64 visit_u64 u64
+    );
+
+    fn deserialize_bool<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let start_index = self.reader.position();
+        match self.reader.read_uint8()? {
+            0 => visitor.visit_bool(false),
+            1 => visitor.visit_bool(true),
+            _ => Err(crate::Error::new(ErrorKind::InvalidEncoding { start_index }).into()),
+        }
+    }
+
+    fn deserialize_i128<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let bytes = self.reader.read::<[u8; 16]>()?;
+        visitor.visit_i128(match E::ENDIANNESS {
+            Endian::LE => i128::from_le_bytes(bytes),
+            Endian::BE => i128::from_be_bytes(bytes),
+            Endian::NE => i128::from_ne_bytes(bytes),
+        })
+    }
+
+    fn deserialize_u128<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let bytes = self.reader.read::<[u8; 16]>()?;
+        visitor.visit_u128(match E::ENDIANNESS {
+            Endian::LE => u128::from_le_bytes(bytes),
+            Endian::BE => u128::from_be_bytes(bytes),
+            Endian::NE => u128::from_ne_bytes(bytes),
+        })
+    }
+
+    fn deserialize_f32<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_f32(f32::from_bits(self.reader.read_uint32()?))
+    }
+
+    fn deserialize_f64<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_f64(f64::from_bits(self.reader.read_uint64()?))
+    }
+
+    fn deserialize_char<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let start_index = self.reader.position();
+        match char::from_u32(self.reader.read_uint32()?) {
+            Some(value) => visitor.visit_char(value),
+            None => Err(crate::Error::new(ErrorKind::InvalidEncoding { start_index }).into()),
+        }
+    }
+
+    fn deserialize_str<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let bytes = self.read_bytes()?;
+        let start_index = self.reader.position() - bytes.len();
+        match str::from_utf8(bytes) {
+            Ok(value) => visitor.visit_borrowed_str(value),
+            Err(error) => Err(crate::Error::new(ErrorKind::InvalidEncoding {
+                start_index: start_index + error.valid_up_to(),
+            })
+            .into()),
+        }
+    }
+
+    fn deserialize_string<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_bytes<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_borrowed_bytes(self.read_bytes()?)
+    }
+
+    fn deserialize_byte_buf<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_option<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let start_index = self.reader.position();
+        match self.reader.read_uint8()? {
+            0 => visitor.visit_none(),
+            1 => visitor.visit_some(self),
+            _ => Err(crate::Error::new(ErrorKind::InvalidTag { start_index }).into()),
+        }
+    }
+
+    fn deserialize_unit<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V: de::Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V: de::Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let len = self.read_len()?;
+        visitor.visit_seq(Access::new(self, len, None))
+    }
+
+    fn deserialize_tuple<V: de::Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value> {
+        visitor.visit_seq(Access::new(self, len, None))
+    }
+
+    fn deserialize_tuple_struct<V: de::Visitor<'de>>(
+        self,
+        _name: &'static str,
+        len: usize,
+        visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_seq(Access::new(self, len, None))
+    }
+
+    fn deserialize_map<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value> {
+        let len = self.read_len()?;
+        visitor.visit_map(Access::new(self, len, None))
+    }
+
+    fn deserialize_struct<V: de::Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_seq(Access::new(self, fields.len(), Some(fields)))
+    }
+
+    fn deserialize_enum<V: de::Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_enum(self)
+    }
+
+    fn deserialize_identifier<V: de::Visitor<'de>>(self, _visitor: V) -> Result<V::Value> {
+        Err(Error::NotSelfDescribing)
+    }
+
+    fn deserialize_ignored_any<V: de::Visitor<'de>>(self, _visitor: V) -> Result<V::Value> {
+        Err(Error::NotSelfDescribing)
+    }
+
+    fn is_human_readable(&self) -> bool { false }
+}
+
+/// Elements of a sequence, tuple, struct or map, which records the field
+/// name or index of the element that failed in its error.
+struct Access<'a, 'r, 'de, E> {
+    deserializer: &'a mut Deserializer<'r, 'de, E>,
+    len: usize,
+    index: usize,
+    fields: Option<&'static [&'static str]>,
+}
+
+impl<'a, 'r, 'de, E: BitConvEndian> Access<'a, 'r, 'de, E> {
+    fn new(
+        deserializer: &'a mut Deserializer<'r, 'de, E>,
+        len: usize,
+        fields: Option<&'static [&'static str]>,
+    ) -> Self {
+        Access { deserializer, len, index: 0, fields }
+    }
+
+    fn with_context(&self, error: Error) -> Error {
+        match error {
+            Error::Conversion(error) => Error::Conversion(match self.fields {
+                Some(fields) => error.with_field(fields[self.index]),
+                None => error.with_index(self.index),
+            }),
+            error => error,
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        // Capped so that a count read from the input never reserves more
+        // memory than there are bytes left to fill it.
+        Some((self.len - self.index).min(self.deserializer.reader.remaining()))
+    }
+}
+
+impl<'a, 'r, 'de, E: BitConvEndian> de::SeqAccess<'de> for Access<'a, 'r, 'de, E> {
+    type Error = Error;
+
+    fn next_element_seed<S: de::DeserializeSeed<'de>>(
+        &mut self,
+        seed: S,
+    ) -> Result<Option<S::Value>> {
+        if self.index == self.len {
+            return Ok(None);
+        }
+        let value =
+            seed.deserialize(&mut *self.deserializer).map_err(|error| self.with_context(error))?;
+        self.index += 1;
+        Ok(Some(value))
+    }
+
+    fn size_hint(&self) -> Option<usize> { Access::size_hint(self) }
+}
+
+impl<'a, 'r, 'de, E: BitConvEndian> de::MapAccess<'de> for Access<'a, 'r, 'de, E> {
+    type Error = Error;
+
+    fn next_key_seed<S: de::DeserializeSeed<'de>>(&mut self, seed: S) -> Result<Option<S::Value>> {
+        if self.index == self.len {
+            return Ok(None);
+        }
+        seed.deserialize(&mut *self.deserializer)
+            .map(Some)
+            .map_err(|error| self.with_context(error))
+    }
+
+    fn next_value_seed<S: de::DeserializeSeed<'de>>(&mut self, seed: S) -> Result<S::Value> {
+        let value =
+            seed.deserialize(&mut *self.deserializer).map_err(|error| self.with_context(error))?;
+        self.index += 1;
+        Ok(value)
+    }
+
+    fn size_hint(&self) -> Option<usize> { Access::size_hint(self) }
+}
+
+impl<'de, 'a, 'r, E: BitConvEndian> de::EnumAccess<'de> for &'a mut Deserializer<'r, 'de, E> {
+    type Error = Error;
+    type Variant = Self;
+
+    fn variant_seed<S: de::DeserializeSeed<'de>>(self, seed: S) -> Result<(S::Value, Self)> {
+        let start_index = self.reader.position();
+        let index = self.reader.read_uint32()?;
+        let variant = de::IntoDeserializer::<Error>::into_deserializer(index);
+        match seed.deserialize(variant) {
+            Ok(value) => Ok((value, self)),
+            Err(_) => Err(crate::Error::new(ErrorKind::InvalidTag { start_index }).into()),
+        }
+    }
+}
+
+impl<'de, 'a, 'r, E: BitConvEndian> de::VariantAccess<'de> for &'a mut Deserializer<'r, 'de, E> {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<()> { Ok(()) }
+
+    fn newtype_variant_seed<S: de::DeserializeSeed<'de>>(self, seed: S) -> Result<S::Value> {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V: de::Visitor<'de>>(self, len: usize, visitor: V) -> Result<V::Value> {
+        visitor.visit_seq(Access::new(self, len, None))
+    }
+
+    fn struct_variant<V: de::Visitor<'de>>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value> {
+        visitor.visit_seq(Access::new(self, fields.len(), Some(fields)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::endian::*;
+    use ::serde::{Deserialize, Serialize};
+    use alloc::{borrow::ToOwned, collections::BTreeMap, vec};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Entry<'a> {
+        id: u32,
+        name: &'a str,
+        data: Vec<i16>,
+    }
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    enum Message<'a> {
+        Ping,
+        Text(String),
+        Move(i8, i8),
+        Entries {
+            #[serde(borrow)]
+            entries: Vec<Entry<'a>>,
+        },
+    }
+
+    #[test]
+    fn serde_primitives_test() {
+        let value = (true, -2i8, 0x0102u16, -3i32, 4u64, 1.5f32, -2.0f64, 'é', (), -5i128);
+        let bytes = to_vec::<Little, _>(&value).unwrap();
+        let expected = [
+            &[1, 254, 2, 1, 253, 255, 255, 255, 4, 0, 0, 0, 0, 0, 0, 0][..],
+            &1.5f32.to_le_bytes(),
+            &(-2.0f64).to_le_bytes(),
+            &[0xe9, 0, 0, 0],
+            &(-5i128).to_le_bytes(),
+        ]
+        .concat();
+        assert_eq!(expected, bytes);
+        assert_eq!(Ok(value), from_bytes::<Little, _>(&bytes));
+
+        let bytes = to_vec::<Big, _>(&(1u16, u128::MAX - 1, Some(7u8), None::<u8>)).unwrap();
+        assert_eq!([&[0, 1][..], &(u128::MAX - 1).to_be_bytes(), &[1, 7, 0]].concat(), bytes);
+        assert_eq!(Ok((1u16, u128::MAX - 1, Some(7u8), None::<u8>)), from_bytes::<Big, _>(&bytes));
+    }
+
+    #[test]
+    fn serde_compound_test() {
+        let entry = Entry { id: 1, name: "ab", data: vec![-1, 2] };
+        let bytes = to_vec::<Big, _>(&entry).unwrap();
+        let expected =
+            [0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 2, 97, 98, 0, 0, 0, 0, 0, 0, 0, 2, 255, 255, 0, 2];
+        assert_eq!(expected, *bytes);
+        let value: Entry = from_bytes::<Big, _>(&bytes).unwrap();
+        assert_eq!(entry, value);
+        assert_eq!(bytes[12..14].as_ptr(), value.name.as_ptr());
+
+        let messages = vec![
+            Message::Ping,
+            Message::Text("hi".to_owned()),
+            Message::Move(-1, 1),
+            Message::Entries { entries: vec![entry] },
+        ];
+        let bytes = to_vec::<Little, _>(&messages).unwrap();
+        assert_eq!([4, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 2], bytes[..17]);
+        assert_eq!(Ok(messages), from_bytes::<Little, _>(&bytes));
+
+        let map: BTreeMap<u8, bool> = vec![(1, true), (2, false)].into_iter().collect();
+        let bytes = to_vec::<Little, _>(&map).unwrap();
+        assert_eq!([2, 0, 0, 0, 0, 0, 0, 0, 1, 1, 2, 0], *bytes);
+        assert_eq!(Ok(map), from_bytes::<Little, _>(&bytes));
+    }
+
+    #[test]
+    fn serde_deserializer_test() {
+        let buffer = [1, 0, 2, 0, 9];
+        let mut reader = Reader::<Little>::new(&buffer);
+        let mut deserializer = Deserializer::new(&mut reader);
+        assert_eq!(Ok(1), u16::deserialize(&mut deserializer));
+        assert_eq!(Ok(2), u16::deserialize(&mut deserializer));
+        assert_eq!(4, reader.position());
+        assert_eq!(
+            Err(Error::Conversion(crate::Error::new(ErrorKind::TrailingBytes {
+                start_index: 4,
+                len: 1
+            }))),
+            from_bytes::<Little, (u16, u16)>(&buffer)
+        );
+    }
+
+    fn conversion<V>(error: crate::Error) -> Result<V> { Err(Error::Conversion(error)) }
+
+    #[test]
+    fn serde_error_test() {
+        let bytes = [0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 2, 97, 0xff, 0, 0, 0, 0, 0, 0, 0, 0];
+        assert_eq!(
+            conversion(
+                crate::Error::new(ErrorKind::InvalidEncoding { start_index: 13 })
+                    .with_field("name")
+            ),
+            from_bytes::<Big, Entry>(&bytes)
+        );
+        assert_eq!(
+            conversion(
+                crate::Error::new(ErrorKind::OutOfBounds { start_index: 4, requested: 2, len: 5 })
+                    .with_index(1)
+            ),
+            from_bytes::<Little, (u32, u16)>(&[0; 5])
+        );
+        assert_eq!(
+            conversion(crate::Error::new(ErrorKind::InvalidEncoding { start_index: 0 })),
+            from_bytes::<Little, bool>(&[2])
+        );
+        assert_eq!(
+            conversion(crate::Error::new(ErrorKind::InvalidTag { start_index: 0 })),
+            from_bytes::<Little, Option<u8>>(&[2])
+        );
+        assert_eq!(
+            conversion(crate::Error::new(ErrorKind::InvalidEncoding { start_index: 0 })),
+            from_bytes::<Little, char>(&0xd800u32.to_le_bytes())
+        );
+        assert_eq!(
+            conversion(crate::Error::new(ErrorKind::InvalidTag { start_index: 0 })),
+            from_bytes::<Little, Message>(&[4, 0, 0, 0])
+        );
+        assert_eq!(
+            conversion(crate::Error::new(ErrorKind::OutOfBounds {
+                start_index: 8,
+                requested: usize::MAX,
+                len: 8
+            })),
+            from_bytes::<Little, &[u8]>(&[255; 8])
+        );
+        assert_eq!(
+            Err(Error::NotSelfDescribing),
+            from_bytes::<Little, ::serde::de::IgnoredAny>(&[])
+        );
+
+        struct Unsized;
+        impl Serialize for Unsized {
+            fn serialize<S: ::serde::Serializer>(
+                &self,
+                serializer: S,
+            ) -> core::result::Result<S::Ok, S::Error> {
+                serializer.collect_seq([1u8].iter().filter(|_| true))
+            }
+        }
+        assert_eq!(Err(Error::UnknownLength), to_vec::<Little, _>(&Unsized));
+    }
+}
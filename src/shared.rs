@@ -0,0 +1,153 @@
+//! Reader that owns a handle to a shared buffer.
+use crate::{endian::Endian, BitConvEndian, FromBytes, Reader, Result};
+use core::marker::PhantomData;
+
+/// Cursor over a shared buffer such as an `Arc<[u8]>` or `Rc<[u8]>`,
+/// converting values in the endianness `E`.
+///
+/// Unlike [`Reader`], which borrows its bytes, a `SharedReader` holds the
+/// buffer itself, so it can be stored or sent to another thread freely.
+/// Cloning it clones only the handle: the clone starts at the same position
+/// and moves independently from then on, which allows several sections of the
+/// same buffer to be parsed in parallel.
+/// # Example
+///
+/// ```
+/// use bitconv::{endian::Little, SharedReader};
+/// use std::{sync::Arc, thread};
+///
+/// let buffer: Arc<[u8]> = Arc::from(&[16, 39, 0, 0, 1, 0][..]);
+/// let mut reader = SharedReader::<_, Little>::new(buffer);
+/// let mut section = reader.clone();
+/// section.set_position(4);
+/// let handle = thread::spawn(move || section.read_uint16());
+/// assert_eq!(Ok(10000), reader.read_uint16());
+/// assert_eq!(Ok(1), handle.join().unwrap());
+/// ```
+pub struct SharedReader<B, E> {
+    buffer: B,
+    position: usize,
+    endian: PhantomData<E>,
+}
+
+impl<B: AsRef<[u8]>, E: BitConvEndian> SharedReader<B, E> {
+    /// Creates a reader positioned at the start of buffer.
+    pub fn new(buffer: B) -> Self { SharedReader { buffer, position: 0, endian: PhantomData } }
+
+    /// Returns the underlying buffer.
+    pub fn get_ref(&self) -> &B { &self.buffer }
+
+    /// Returns the underlying buffer, dropping the position.
+    pub fn into_inner(self) -> B { self.buffer }
+
+    /// Returns the index of the next byte to be read.
+    pub fn position(&self) -> usize { self.position }
+
+    /// Moves to position, which may lie past the end of the buffer.
+    pub fn set_position(&mut self, position: usize) { self.position = position; }
+
+    /// Returns the number of bytes left after the current position.
+    pub fn remaining(&self) -> usize { self.buffer.as_ref().len().saturating_sub(self.position) }
+
+    /// Returns a [`Reader`] borrowing the buffer at the current position, for
+    /// the parts of its interface a `SharedReader` lacks.
+    ///
+    /// Moving the returned reader leaves this one where it was; use
+    /// [`set_position`](SharedReader::set_position) to carry its position
+    /// back.
+    pub fn as_reader(&self) -> Reader<'_, E> {
+        let mut reader = Reader::new(self.buffer.as_ref());
+        reader.set_position(self.position);
+        reader
+    }
+
+    /// Converts a value at the current position and advances past it.
+    ///
+    /// On error the position is left unchanged.
+    pub fn read<V: FromBytes>(&mut self) -> Result<V> {
+        let (value, read) = V::from_bytes::<E>(self.buffer.as_ref(), self.position)?;
+        self.position += read;
+        Ok(value)
+    }
+
+    /// Converts a value at the current position in the given endianness
+    /// instead of `E` and advances past it.
+    pub fn read_with<V: FromBytes>(&mut self, endian: Endian) -> Result<V> {
+        let mut reader = self.as_reader();
+        let value = reader.read_with(endian)?;
+        self.position = reader.position();
+        Ok(value)
+    }
+
+    /// Converts a value at the current position without advancing.
+    pub fn peek<V: FromBytes>(&self) -> Result<V> { self.as_reader().peek() }
+
+    /// Returns the next len bytes and advances past them.
+    pub fn read_bytes(&mut self, len: usize) -> Result<&[u8]> {
+        let mut reader = Reader::<E>::new(self.buffer.as_ref());
+        reader.set_position(self.position);
+        let bytes = reader.read_bytes(len)?;
+        self.position += len;
+        Ok(bytes)
+    }
+
+    ReaderImpl!(
+        read_int8 read_int8_with peek_int8 i8,
+        read_int16 read_int16_with peek_int16 i16,
+        read_int32 read_int32_with peek_int32 i32,
+        read_int64 read_int64_with peek_int64 i64,
+        read_uint8 read_uint8_with peek_uint8 u8,
+        read_uint16 read_uint16_with peek_uint16 u16,
+        read_uint32 read_uint32_with peek_uint32 u32,
+        read_uint64 read_uint64_with peek_uint64 u64
+    );
+}
+
+impl<B: Clone, E> Clone for SharedReader<B, E> {
+    fn clone(&self) -> Self {
+        SharedReader { buffer: self.buffer.clone(), position: self.position, endian: PhantomData }
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod test {
+    use super::*;
+    use crate::{endian::*, Error, ErrorKind};
+    use alloc::{rc::Rc, sync::Arc};
+
+    #[test]
+    fn shared_reader_test_le() {
+        let buffer: Arc<[u8]> = Arc::from(&[15, 0, 0, 128, 16, 39, 240, 216, 241, 255, 127][..]);
+        let mut reader = SharedReader::<_, Little>::new(buffer.clone());
+        assert_eq!(Ok(2147483663), reader.read_uint32());
+        let mut clone = reader.clone();
+        assert_eq!(3, Arc::strong_count(&buffer));
+        assert_eq!(Ok(10000), reader.read_int16());
+        assert_eq!(Ok(4135), clone.read_uint16_with(Endian::BE));
+        assert_eq!(Ok(-10000), clone.peek_int16());
+        assert_eq!((6, 6), (reader.position(), clone.position()));
+        assert_eq!(Ok(&[240, 216][..]), clone.read_bytes(2));
+        assert_eq!(Ok((-15, 32767)), clone.read::<(i8, i16)>());
+        assert_eq!(0, clone.remaining());
+        assert_eq!(5, reader.remaining());
+        assert_eq!(
+            Err(Error::new(ErrorKind::OutOfBounds { start_index: 6, requested: 8, len: 11 })),
+            reader.read_uint64()
+        );
+        assert_eq!(6, reader.position());
+    }
+
+    #[test]
+    fn shared_reader_test_be() {
+        let buffer: Rc<[u8]> = Rc::from(&[15, 0, 0, 128, 16, 39][..]);
+        let mut reader = SharedReader::<_, Big>::new(buffer);
+        assert_eq!(Ok(3840), reader.read_uint16());
+        let mut borrowed = reader.as_reader();
+        assert_eq!(Ok(128), borrowed.read_uint16());
+        assert_eq!(2, reader.position());
+        reader.set_position(borrowed.position());
+        assert_eq!(Ok(4135), reader.read_uint16());
+        assert!(reader.read_int8().is_err());
+        assert_eq!(6, reader.into_inner().len());
+    }
+}
@@ -0,0 +1,315 @@
+//! Writers over caller-provided byte buffers, which need no allocator when
+//! the buffer has a fixed capacity.
+use crate::{endian::Endian, BitConvEndian, Error, ErrorKind, Result, ToBytes};
+use core::marker::PhantomData;
+
+/// Byte buffer that a [`SinkWriter`] appends to.
+///
+/// Implemented for `Vec<u8>` with the `alloc` feature, and for the
+/// `heapless::Vec<u8, N>`, `arrayvec::ArrayVec<u8, N>` and
+/// `smallvec::SmallVec<[u8; N]>` types of the feature of the same name. Being
+/// inline, the last one only allocates once a message outgrows `N` bytes.
+pub trait ByteSink {
+    /// Returns the bytes held by the sink.
+    fn as_bytes(&self) -> &[u8];
+
+    /// Returns the bytes held by the sink, for writing over.
+    fn as_bytes_mut(&mut self) -> &mut [u8];
+
+    /// Appends len zeroed bytes, or returns false and leaves the sink
+    /// unchanged if there is no room for them.
+    fn try_grow(&mut self, len: usize) -> bool;
+
+    /// Shortens the sink to len bytes, keeping the first ones.
+    fn truncate(&mut self, len: usize);
+}
+
+#[cfg(feature = "alloc")]
+impl ByteSink for alloc::vec::Vec<u8> {
+    fn as_bytes(&self) -> &[u8] { self }
+
+    fn as_bytes_mut(&mut self) -> &mut [u8] { self }
+
+    fn try_grow(&mut self, len: usize) -> bool {
+        match self.try_reserve(len) {
+            Ok(()) => {
+                self.resize(self.len() + len, 0);
+                true
+            }
+            Err(_) => false,
+        }
+    }
+
+    fn truncate(&mut self, len: usize) { alloc::vec::Vec::truncate(self, len) }
+}
+
+#[cfg(feature = "arrayvec")]
+impl<const N: usize> ByteSink for ::arrayvec::ArrayVec<u8, N> {
+    fn as_bytes(&self) -> &[u8] { self }
+
+    fn as_bytes_mut(&mut self) -> &mut [u8] { self }
+
+    fn try_grow(&mut self, len: usize) -> bool {
+        match len <= self.remaining_capacity() {
+            true => {
+                self.extend(core::iter::repeat_n(0, len));
+                true
+            }
+            false => false,
+        }
+    }
+
+    fn truncate(&mut self, len: usize) { ::arrayvec::ArrayVec::truncate(self, len) }
+}
+
+#[cfg(feature = "heapless")]
+impl<const N: usize> ByteSink for ::heapless::Vec<u8, N> {
+    fn as_bytes(&self) -> &[u8] { self }
+
+    fn as_bytes_mut(&mut self) -> &mut [u8] { self }
+
+    fn try_grow(&mut self, len: usize) -> bool {
+        match self.len().checked_add(len) {
+            Some(new_len) => self.resize(new_len, 0).is_ok(),
+            None => false,
+        }
+    }
+
+    fn truncate(&mut self, len: usize) { ::heapless::Vec::truncate(self, len) }
+}
+
+#[cfg(feature = "smallvec")]
+impl<A: ::smallvec::Array<Item = u8>> ByteSink for ::smallvec::SmallVec<A> {
+    fn as_bytes(&self) -> &[u8] { self }
+
+    fn as_bytes_mut(&mut self) -> &mut [u8] { self }
+
+    fn try_grow(&mut self, len: usize) -> bool {
+        match self.try_reserve(len) {
+            Ok(()) => {
+                self.resize(self.len() + len, 0);
+                true
+            }
+            Err(_) => false,
+        }
+    }
+
+    fn truncate(&mut self, len: usize) { ::smallvec::SmallVec::truncate(self, len) }
+}
+
+/// Writer appending values in the endianness `E` to a [`ByteSink`].
+///
+/// Unlike [`Writer`](crate::Writer) every write is fallible, failing with
+/// `ErrorKind::OutOfBounds` and leaving the sink unchanged when the value does
+/// not fit, so sinks of a fixed capacity can hold variable-length frames.
+/// # Example
+///
+/// ```
+/// # #[cfg(feature = "heapless")] {
+/// use bitconv::{endian::Big, SinkWriter};
+///
+/// let mut writer = SinkWriter::<_, Big>::new(heapless::Vec::<u8, 6>::new());
+/// writer.write_uint16(0xcafe).unwrap();
+/// writer.write(&(1u8, 2u16)).unwrap();
+/// assert!(writer.write_uint16(3).is_err());
+/// writer.write_uint8(4).unwrap();
+/// assert_eq!([0xca, 0xfe, 1, 0, 2, 4], writer.as_slice());
+/// # }
+/// ```
+pub struct SinkWriter<S, E> {
+    sink: S,
+    endian: PhantomData<E>,
+}
+
+macro_rules! SinkWriterImpl {
+    ($($write:ident $type:ty),+) => {$(
+        #[doc = concat!("Appends a `", stringify!($type), "`.")]
+        #[inline]
+        pub fn $write(&mut self, value: $type) -> Result<()> {
+            let bytes = match E::ENDIANNESS {
+                Endian::LE => value.to_le_bytes(),
+                Endian::BE => value.to_be_bytes(),
+                Endian::NE => value.to_ne_bytes(),
+            };
+            self.write_bytes(&bytes)
+        }
+    )+};
+}
+
+impl<S: ByteSink, E: BitConvEndian> SinkWriter<S, E> {
+    /// Creates a writer appending to the bytes already held by sink.
+    pub fn new(sink: S) -> Self { SinkWriter { sink, endian: PhantomData } }
+
+    /// Returns a reference to the underlying sink.
+    pub fn get_ref(&self) -> &S { &self.sink }
+
+    /// Returns the underlying sink.
+    pub fn into_inner(self) -> S { self.sink }
+
+    /// Returns the number of bytes held by the sink.
+    pub fn position(&self) -> usize { self.sink.as_bytes().len() }
+
+    /// Returns the bytes held by the sink.
+    pub fn as_slice(&self) -> &[u8] { self.sink.as_bytes() }
+
+    /// Appends a value.
+    ///
+    /// Nothing is appended if the value does not fit or fails to convert.
+    pub fn write<V: ToBytes + ?Sized>(&mut self, value: &V) -> Result<()> {
+        let start_index = self.position();
+        self.grow(value.byte_len())?;
+        value
+            .to_bytes::<E>(self.sink.as_bytes_mut(), start_index)
+            .map(|_| ())
+            .inspect_err(|_| self.sink.truncate(start_index))
+    }
+
+    /// Appends raw bytes.
+    pub fn write_bytes(&mut self, bytes: &[u8]) -> Result<()> {
+        let start_index = self.position();
+        self.grow(bytes.len())?;
+        self.sink.as_bytes_mut()[start_index..].copy_from_slice(bytes);
+        Ok(())
+    }
+
+    fn grow(&mut self, requested: usize) -> Result<()> {
+        match self.sink.try_grow(requested) {
+            true => Ok(()),
+            false => {
+                let len = self.position();
+                Err(Error::new(ErrorKind::OutOfBounds { start_index: len, requested, len }))
+            }
+        }
+    }
+
+    SinkWriterImpl!(
+        write_int8 i8,
+        write_int16 i16,
+        write_int32 i32,
+        write_int64 i64,
+        write_uint8 u8,
+        write_uint16 u16,
+        write_uint32 u32,
+        write_uint64 u64
+    );
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use super::*;
+    use crate::endian::*;
+
+    /// Sink over an array of eight bytes.
+    #[derive(Default)]
+    pub(crate) struct Frame {
+        pub(crate) bytes: [u8; 8],
+        len: usize,
+    }
+
+    impl ByteSink for Frame {
+        fn as_bytes(&self) -> &[u8] { &self.bytes[..self.len] }
+
+        fn as_bytes_mut(&mut self) -> &mut [u8] { &mut self.bytes[..self.len] }
+
+        fn try_grow(&mut self, len: usize) -> bool {
+            match self.len.checked_add(len) {
+                Some(new_len) if new_len <= self.bytes.len() => {
+                    self.bytes[self.len..new_len].fill(0);
+                    self.len = new_len;
+                    true
+                }
+                _ => false,
+            }
+        }
+
+        fn truncate(&mut self, len: usize) { self.len = self.len.min(len) }
+    }
+
+    #[test]
+    fn sink_writer_test() {
+        let mut writer = SinkWriter::<_, Little>::new(Frame::default());
+        assert_eq!(Ok(()), writer.write_uint32(2147483663));
+        assert_eq!(Ok(()), writer.write(&(10000i16, -15i8)));
+        assert_eq!(
+            Err(Error::new(ErrorKind::OutOfBounds { start_index: 7, requested: 2, len: 7 })),
+            writer.write_uint16(1)
+        );
+        assert!(writer.write_bytes(&[0; 9]).is_err());
+        assert_eq!(Ok(()), writer.write_uint8(255));
+        assert!(writer.write_int8(0).is_err());
+        assert_eq!([15, 0, 0, 128, 16, 39, 241, 255], writer.as_slice());
+        let mut writer = SinkWriter::<_, Big>::new(Frame::default());
+        assert_eq!(Ok(()), writer.write_int16(-2));
+        assert_eq!(Ok(()), writer.write_uint16(1));
+        assert!(writer.write_int64(-1).is_err());
+        assert!(writer.write_uint64(1).is_err());
+        assert_eq!(Ok(()), writer.write_int32(-3));
+        assert_eq!([255, 254, 0, 1, 255, 255, 255, 253], writer.into_inner().bytes);
+    }
+
+    #[test]
+    fn sink_writer_failed_conversion_test() {
+        struct Overrun;
+
+        impl ToBytes for Overrun {
+            fn to_bytes<T: BitConvEndian>(
+                &self,
+                data: &mut [u8],
+                start_index: usize,
+            ) -> Result<usize> {
+                0u32.to_bytes::<T>(data, start_index)
+            }
+
+            fn byte_len(&self) -> usize { 2 }
+        }
+
+        let mut writer = SinkWriter::<_, Little>::new(Frame::default());
+        assert_eq!(Ok(()), writer.write_uint8(1));
+        assert_eq!(
+            Err(Error::new(ErrorKind::OutOfBounds { start_index: 1, requested: 4, len: 3 })),
+            writer.write(&Overrun)
+        );
+        assert_eq!(1, writer.position());
+    }
+
+    #[cfg(feature = "heapless")]
+    #[test]
+    fn heapless_sink_test() {
+        let mut writer = SinkWriter::<_, Little>::new(::heapless::Vec::<u8, 4>::new());
+        assert_eq!(Ok(()), writer.write_bytes(&[1, 2]));
+        assert!(writer.write_uint32(3).is_err());
+        assert_eq!(Ok(()), writer.write_uint16(3));
+        assert!(writer.write_bytes(&[0; 5]).is_err());
+        assert_eq!([1, 2, 3, 0], writer.get_ref()[..]);
+    }
+
+    #[cfg(feature = "arrayvec")]
+    #[test]
+    fn arrayvec_sink_test() {
+        let mut writer = SinkWriter::<_, Big>::new(::arrayvec::ArrayVec::<u8, 4>::new());
+        assert_eq!(Ok(()), writer.write_uint16(1));
+        assert!(writer.write_uint32(2).is_err());
+        assert_eq!(Ok(()), writer.write(&-2i16));
+        assert!(writer.write_uint8(3).is_err());
+        assert_eq!([0, 1, 255, 254], writer.into_inner().into_inner().unwrap());
+    }
+
+    #[cfg(feature = "smallvec")]
+    #[test]
+    fn smallvec_sink_test() {
+        let mut writer = SinkWriter::<_, Little>::new(::smallvec::SmallVec::<[u8; 4]>::new());
+        assert_eq!(Ok(()), writer.write_uint32(2147483663));
+        assert!(!writer.get_ref().spilled());
+        assert_eq!(Ok(()), writer.write_int16(10000));
+        assert!(writer.get_ref().spilled());
+        assert_eq!([15, 0, 0, 128, 16, 39], writer.as_slice());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn vec_sink_test() {
+        let mut writer = SinkWriter::<_, Big>::new(alloc::vec![7]);
+        assert_eq!(Ok(()), writer.write_uint32(256));
+        assert_eq!([7, 0, 0, 1, 0], writer.into_inner()[..]);
+    }
+}
@@ -0,0 +1,87 @@
+//! Conversions that consume a value from the front of a byte slice.
+use crate::{BitConvEndian, FromBytes};
+
+/// Converts a value from the start of a byte slice, returning it along with
+/// the bytes that follow it, or `None` if data is too short.
+///
+/// Handing back the remainder instead of keeping a position lets parsers be
+/// written as plain functions that each take a slice and return what is left.
+/// # Example
+///
+/// ```
+/// use bitconv::{endian::Little, split, split_uint16, split_uint8};
+///
+/// fn parse_point(data: &[u8]) -> Option<((i16, i16), &[u8])> {
+///     let (x, rest) = split::<i16, Little>(data)?;
+///     let (y, rest) = split::<i16, Little>(rest)?;
+///     Some(((x, y), rest))
+/// }
+///
+/// let buffer = [2, 10, 0, 246, 255, 7];
+/// let (count, rest) = split_uint8::<Little>(&buffer).unwrap();
+/// assert_eq!(2, count);
+/// assert_eq!(Some(((10, -10), &[7][..])), parse_point(rest));
+/// assert_eq!(None, split_uint16::<Little>(&buffer[5..]));
+/// ```
+#[inline]
+pub fn split<V: FromBytes, T: BitConvEndian>(data: &[u8]) -> Option<(V, &[u8])> {
+    let (value, read) = V::from_bytes::<T>(data, 0).ok()?;
+    Some((value, &data[read..]))
+}
+
+macro_rules! SplitImpl {
+    ($($name:ident $type:ty),+) => {$(
+        #[doc = concat!(
+            "Converts a `", stringify!($type), "` from the start of a byte slice, returning it ",
+            "along with the bytes that follow it, or `None` if data is too short.\n\n",
+            "See [`split`] for details."
+        )]
+        #[inline]
+        #[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
+        pub fn $name<T: BitConvEndian>(data: &[u8]) -> Option<($type, &[u8])> {
+            split::<$type, T>(data)
+        }
+    )+};
+}
+
+SplitImpl!(
+    split_int8 i8,
+    split_int16 i16,
+    split_int32 i32,
+    split_int64 i64,
+    split_uint8 u8,
+    split_uint16 u16,
+    split_uint32 u32,
+    split_uint64 u64
+);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::endian::*;
+
+    #[test]
+    fn split_test_le() {
+        let buffer = [15, 0, 0, 128, 16, 39, 240, 216, 241, 255, 127];
+        let (value, rest) = split_uint32::<Little>(&buffer).unwrap();
+        assert_eq!(2147483663, value);
+        assert_eq!(&buffer[4..], rest);
+        let (value, rest) = split_int16::<Little>(rest).unwrap();
+        assert_eq!(10000, value);
+        let (value, rest) = split::<(i16, i8), Little>(rest).unwrap();
+        assert_eq!((-10000, -15), value);
+        assert_eq!(Some((32767, &[][..])), split_int16::<Little>(rest));
+        assert_eq!(None, split_int64::<Little>(rest));
+        assert_eq!(Some((127, &[][..])), split_uint8::<Little>(&rest[1..]));
+        assert_eq!(None, split_int8::<Little>(&[]));
+    }
+
+    #[test]
+    fn split_test_be() {
+        let buffer = [15, 0, 0, 128, 16, 39, 240, 216, 241, 255, 127];
+        assert_eq!(Some((251658368, &buffer[4..])), split_int32::<Big>(&buffer));
+        assert_eq!(Some((3840, &buffer[2..])), split_uint16::<Big>(&buffer));
+        assert_eq!(Some((1080864460595785944, &buffer[8..])), split_uint64::<Big>(&buffer));
+        assert_eq!(None, split_uint64::<Big>(&buffer[4..]));
+    }
+}
@@ -0,0 +1,174 @@
+//! Directories of offsets pointing into the same buffer.
+use crate::{BitConvEndian, Error, ErrorKind, FixedSize, FromBytes, Result};
+use core::{convert::TryFrom, marker::PhantomData};
+
+/// Table of offsets of type `O`, stored in the endianness `E`, that divide a
+/// buffer into regions, as found in the directories of archive and font
+/// formats.
+///
+/// Offsets count from the start of the buffer. Each region runs from its
+/// offset to the next one, and the last region runs to the end of the buffer.
+/// Regions are bounds-checked as they are looked up.
+/// # Example
+///
+/// ```
+/// use bitconv::{endian::Little, OffsetTable};
+///
+/// // A count, two u16 offsets, then the regions they point to.
+/// let archive = [2, 5, 0, 7, 0, 104, 105, 33];
+/// let table = OffsetTable::<u16, Little>::with_count_prefix::<u8>(&archive, 0).unwrap();
+/// assert_eq!(2, table.len());
+/// assert_eq!(Ok(&b"hi"[..]), table.get(0));
+/// assert_eq!(Ok(&b"!"[..]), table.get(1));
+/// ```
+pub struct OffsetTable<'a, O, E> {
+    data: &'a [u8],
+    start_index: usize,
+    len: usize,
+    marker: PhantomData<(O, E)>,
+}
+
+impl<'a, O, E> OffsetTable<'a, O, E>
+where
+    O: FromBytes + FixedSize,
+    usize: TryFrom<O>,
+    E: BitConvEndian,
+{
+    /// Creates a table of len offsets stored at start_index in data.
+    ///
+    /// Fails if data is too short to hold the offsets themselves.
+    pub fn new(data: &'a [u8], start_index: usize, len: usize) -> Result<Self> {
+        let requested = len.saturating_mul(O::SIZE);
+        match start_index.checked_add(requested) {
+            Some(end) if end <= data.len() => {
+                Ok(OffsetTable { data, start_index, len, marker: PhantomData })
+            }
+            _ => {
+                Err(Error::new(ErrorKind::OutOfBounds { start_index, requested, len: data.len() }))
+            }
+        }
+    }
+
+    /// Creates a table whose offsets follow a count of type `C` stored at
+    /// start_index in data.
+    pub fn with_count_prefix<C>(data: &'a [u8], start_index: usize) -> Result<Self>
+    where
+        C: FromBytes,
+        usize: TryFrom<C>,
+    {
+        let (count, read) = C::from_bytes::<E>(data, start_index)?;
+        let count = usize::try_from(count)
+            .map_err(|_| Error::new(ErrorKind::InvalidCount { start_index }))?;
+        OffsetTable::new(data, start_index + read, count)
+    }
+
+    /// Returns the number of offsets in the table.
+    pub fn len(&self) -> usize { self.len }
+
+    pub fn is_empty(&self) -> bool { self.len == 0 }
+
+    /// Returns the index of the first byte after the table.
+    pub fn end(&self) -> usize { self.start_index + self.len * O::SIZE }
+
+    /// Returns the offset at index in the table.
+    ///
+    /// Fails with `ErrorKind::InvalidOffset` if the offset lies past the end
+    /// of the buffer.
+    pub fn offset(&self, index: usize) -> Result<usize> {
+        let start_index = self.entry(index)?;
+        let (offset, _) = O::from_bytes::<E>(self.data, start_index)?;
+        usize::try_from(offset)
+            .ok()
+            .filter(|offset| *offset <= self.data.len())
+            .ok_or_else(|| Error::new(ErrorKind::InvalidOffset { start_index }))
+    }
+
+    /// Returns the region at index in the table.
+    ///
+    /// Fails with `ErrorKind::InvalidOffset` if the region would extend past
+    /// the end of the buffer or has an end before its start.
+    pub fn get(&self, index: usize) -> Result<&'a [u8]> {
+        let start = self.offset(index)?;
+        let end = match index + 1 {
+            next if next < self.len => self.offset(next)?,
+            _ => self.data.len(),
+        };
+        self.data.get(start..end).ok_or_else(|| {
+            Error::new(ErrorKind::InvalidOffset { start_index: self.start_index + index * O::SIZE })
+        })
+    }
+
+    /// Returns an iterator over the regions of the table, in order.
+    pub fn iter(&self) -> impl Iterator<Item = Result<&'a [u8]>> + '_ {
+        (0..self.len).map(move |index| self.get(index).map_err(|error| error.with_index(index)))
+    }
+
+    fn entry(&self, index: usize) -> Result<usize> {
+        match index < self.len {
+            true => Ok(self.start_index + index * O::SIZE),
+            false => Err(Error::new(ErrorKind::OutOfBounds {
+                start_index: self.start_index.saturating_add(index.saturating_mul(O::SIZE)),
+                requested: O::SIZE,
+                len: self.end(),
+            })),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::endian::*;
+
+    #[test]
+    fn offset_table_test() {
+        let buffer = [0, 0, 0, 8, 0, 0, 0, 10, 1, 2, 3, 4, 5];
+        let table = OffsetTable::<u32, Big>::new(&buffer, 0, 2).unwrap();
+        assert_eq!((2, 8), (table.len(), table.end()));
+        assert_eq!(Ok(10), table.offset(1));
+        assert_eq!(Ok(&[1, 2][..]), table.get(0));
+        assert_eq!(Ok(&[3, 4, 5][..]), table.get(1));
+        assert_eq!(
+            Err(Error::new(ErrorKind::OutOfBounds { start_index: 8, requested: 4, len: 8 })),
+            table.get(2)
+        );
+        let mut regions = table.iter();
+        assert_eq!(Some(Ok(&[1, 2][..])), regions.next());
+        assert_eq!(Some(Ok(&[3, 4, 5][..])), regions.next());
+        assert_eq!(None, regions.next());
+        assert!(OffsetTable::<u32, Big>::new(&buffer, 0, 4).is_err());
+        assert!(OffsetTable::<u32, Big>::new(&buffer, usize::MAX, 1).is_err());
+        assert!(OffsetTable::<u64, Big>::new(&buffer, 0, usize::MAX).is_err());
+        let empty = OffsetTable::<u16, Big>::new(&buffer, 13, 0).unwrap();
+        assert!(empty.is_empty());
+        assert_eq!(0, empty.iter().count());
+    }
+
+    #[test]
+    fn offset_table_invalid_test() {
+        let buffer = [3, 7, 4, 14, 1, 2, 3, 4];
+        let table = OffsetTable::<u8, Little>::with_count_prefix::<u8>(&buffer, 0).unwrap();
+        assert_eq!(Err(Error::new(ErrorKind::InvalidOffset { start_index: 1 })), table.get(0));
+        assert_eq!(Err(Error::new(ErrorKind::InvalidOffset { start_index: 3 })), table.get(1));
+        assert_eq!(Err(Error::new(ErrorKind::InvalidOffset { start_index: 3 })), table.offset(2));
+        let error = table.iter().find_map(|region| region.err()).unwrap();
+        assert_eq!(ErrorKind::InvalidOffset { start_index: 1 }, error.kind());
+        assert_eq!(Some(crate::error::Segment::Index(0)), error.context().iter().next());
+        assert_eq!(
+            ErrorKind::InvalidCount { start_index: 0 },
+            OffsetTable::<u8, Little>::with_count_prefix::<i8>(&[255], 0)
+                .map(|_| ())
+                .unwrap_err()
+                .kind()
+        );
+        assert!(OffsetTable::<u8, Little>::with_count_prefix::<u16>(&[1], 0).is_err());
+        assert_eq!(
+            ErrorKind::OutOfBounds { start_index: usize::MAX, requested: 2, len: 8 },
+            OffsetTable::<u8, Little>::new(&buffer, usize::MAX, 2).map(|_| ()).unwrap_err().kind()
+        );
+        assert_eq!(
+            ErrorKind::OutOfBounds { start_index: 1, requested: usize::MAX, len: 8 },
+            OffsetTable::<u32, Little>::new(&buffer, 1, usize::MAX).map(|_| ()).unwrap_err().kind()
+        );
+    }
+}
@@ -0,0 +1,158 @@
+//! Fields of a bitstream read and written as the narrow integer types of the
+//! `ux` crate, so they keep their width in the type system.
+use crate::{sign_extend, BitOrder, BitReader, BitWriter, ByteSink, Result};
+use ::ux::*;
+use core::convert::TryFrom;
+
+/// Integer types of fewer than 64 bits from the `ux` crate, such as `u3` and
+/// `i12`, that [`BitReader`] and [`BitWriter`] take as fields of their width.
+pub trait NarrowInt: Sized {
+    /// The number of bits of the type.
+    const BITS: u32;
+
+    /// Converts the BITS low bits of bits to a value, sign-extending them for
+    /// signed types.
+    fn from_bits(bits: u64) -> Self;
+
+    /// Returns the value as BITS low bits, in two's complement for signed
+    /// types.
+    fn to_bits(self) -> u64;
+}
+
+macro_rules! NarrowUnsignedImpl {
+    ($($type:ident $bits:literal),+) => {$(
+        impl NarrowInt for $type {
+            const BITS: u32 = $bits;
+
+            #[inline]
+            fn from_bits(bits: u64) -> Self {
+                <$type>::try_from(bits & ((1 << $bits) - 1)).unwrap_or(<$type>::MAX)
+            }
+
+            #[inline]
+            fn to_bits(self) -> u64 { u64::from(self) }
+        }
+    )+};
+}
+
+macro_rules! NarrowSignedImpl {
+    ($($type:ident $bits:literal),+) => {$(
+        impl NarrowInt for $type {
+            const BITS: u32 = $bits;
+
+            #[inline]
+            fn from_bits(bits: u64) -> Self {
+                <$type>::try_from(sign_extend(bits, $bits)).unwrap_or(<$type>::MAX)
+            }
+
+            #[inline]
+            fn to_bits(self) -> u64 { i64::from(self) as u64 & ((1 << $bits) - 1) }
+        }
+    )+};
+}
+
+NarrowUnsignedImpl!(
+    u1 1, u2 2, u3 3, u4 4, u5 5, u6 6, u7 7, u9 9, u10 10, u11 11, u12 12, u13 13, u14 14,
+    u15 15, u17 17, u18 18, u19 19, u20 20, u21 21, u22 22, u23 23, u24 24, u25 25, u26 26,
+    u27 27, u28 28, u29 29, u30 30, u31 31, u33 33, u34 34, u35 35, u36 36, u37 37, u38 38,
+    u39 39, u40 40, u41 41, u42 42, u43 43, u44 44, u45 45, u46 46, u47 47, u48 48, u49 49,
+    u50 50, u51 51, u52 52, u53 53, u54 54, u55 55, u56 56, u57 57, u58 58, u59 59, u60 60,
+    u61 61, u62 62, u63 63
+);
+
+NarrowSignedImpl!(
+    i2 2, i3 3, i4 4, i5 5, i6 6, i7 7, i9 9, i10 10, i11 11, i12 12, i13 13, i14 14, i15 15,
+    i17 17, i18 18, i19 19, i20 20, i21 21, i22 22, i23 23, i24 24, i25 25, i26 26, i27 27,
+    i28 28, i29 29, i30 30, i31 31, i33 33, i34 34, i35 35, i36 36, i37 37, i38 38, i39 39,
+    i40 40, i41 41, i42 42, i43 43, i44 44, i45 45, i46 46, i47 47, i48 48, i49 49, i50 50,
+    i51 51, i52 52, i53 53, i54 54, i55 55, i56 56, i57 57, i58 58, i59 59, i60 60, i61 61,
+    i62 62, i63 63
+);
+
+impl<'a, O: BitOrder> BitReader<'a, O> {
+    /// Returns the next field of the width of `T` without advancing, failing
+    /// as [`peek_bits`](BitReader::peek_bits) does.
+    pub fn peek_ux<T: NarrowInt>(&self) -> Result<T> { self.peek_bits(T::BITS).map(T::from_bits) }
+
+    /// Reads a field of the width of `T` and advances past it, failing as
+    /// [`read_bits`](BitReader::read_bits) does.
+    /// # Example
+    ///
+    /// ```
+    /// use bitconv::{BitReader, Msb0};
+    /// use ux::{i5, u12, u3};
+    ///
+    /// let mut reader = BitReader::<Msb0>::new(&[0xab, 0xcd, 0xef]);
+    /// assert_eq!(Ok(u12::new(0xabc)), reader.read_ux());
+    /// assert_eq!(Ok(u3::new(0b110)), reader.read_ux());
+    /// assert_eq!(Ok(i5::new(-2)), reader.read_ux());
+    /// assert_eq!(20, reader.position());
+    /// ```
+    pub fn read_ux<T: NarrowInt>(&mut self) -> Result<T> {
+        self.read_bits(T::BITS).map(T::from_bits)
+    }
+}
+
+impl<S: ByteSink, O: BitOrder> BitWriter<S, O> {
+    /// Appends value as a field of the width of its type, failing as
+    /// [`write_bits`](BitWriter::write_bits) does.
+    /// # Example
+    ///
+    /// ```
+    /// # #[cfg(feature = "alloc")] {
+    /// use bitconv::{BitWriter, Msb0};
+    /// use ux::{i5, u12, u3};
+    ///
+    /// let mut writer = BitWriter::<_, Msb0>::new(Vec::new());
+    /// writer.write_ux(u12::new(0xabc)).unwrap();
+    /// writer.write_ux(u3::new(0b110)).unwrap();
+    /// writer.write_ux(i5::new(-2)).unwrap();
+    /// assert_eq!([0xab, 0xcd, 0xe0], writer.into_inner()[..]);
+    /// # }
+    /// ```
+    pub fn write_ux<T: NarrowInt>(&mut self, value: T) -> Result<()> {
+        self.write_bits(value.to_bits(), T::BITS)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{sink::test::Frame, Lsb0, Msb0};
+
+    #[test]
+    fn narrow_int_test() {
+        assert_eq!(u1::new(1), u1::from_bits(1));
+        assert_eq!(u63::MAX, u63::from_bits(u64::MAX));
+        assert_eq!(u64::MAX >> 1, u63::MAX.to_bits());
+        assert_eq!(i2::new(-2), i2::from_bits(0b10));
+        assert_eq!(i12::new(-2048), i12::from_bits(0x800));
+        assert_eq!(i12::new(2047), i12::from_bits(0x7ff));
+        assert_eq!(0xfff, i12::new(-1).to_bits());
+        assert_eq!(i63::MIN, i63::from_bits(i63::MIN.to_bits()));
+        assert_eq!(1 << 62, i63::MIN.to_bits());
+    }
+
+    #[test]
+    fn bit_reader_ux_test() {
+        let buffer = [0b1100_0101, 0xff];
+        let mut reader = BitReader::<Lsb0>::new(&buffer);
+        assert_eq!(Ok(u4::new(0b0101)), reader.peek_ux());
+        assert_eq!(Ok(i3::new(-3)), reader.read_ux());
+        assert_eq!(Ok(u9::new(0x1f8)), reader.read_ux());
+        assert!(reader.read_ux::<u5>().is_err());
+        assert_eq!(12, reader.position());
+        assert_eq!(Ok(i4::new(-1)), reader.read_ux());
+    }
+
+    #[test]
+    fn bit_writer_ux_test() {
+        let mut writer = BitWriter::<_, Msb0>::new(Frame::default());
+        assert_eq!(Ok(()), writer.write_ux(i7::new(-64)));
+        assert_eq!(Ok(()), writer.write_ux(u1::new(1)));
+        assert_eq!(Ok(()), writer.write_ux(i56::new(-1)));
+        assert_eq!(64, writer.position());
+        assert!(writer.write_ux(u1::new(0)).is_err());
+        assert_eq!([0x81, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff], writer.into_inner().bytes);
+    }
+}
@@ -0,0 +1,1474 @@
+//! Variable-length integer encodings, which store small values in fewer bytes
+//! than their fixed-width counterparts.
+#[cfg(feature = "alloc")]
+use crate::Writer;
+use crate::{
+    iter::ReadError, BitConvEndian, Error, ErrorKind, IterReader, Reader, Result, TryIterReader
+};
+use core::iter::FusedIterator;
+#[cfg(feature = "std")]
+use std::io;
+
+/// Function decoding a varint at an index, returning it along with the
+/// number of bytes it takes.
+type Decode<V> = fn(&[u8], usize) -> Result<(V, usize)>;
+
+/// Returns the error for a varint whose byte at index is missing.
+fn truncated(data: &[u8], index: usize) -> Error {
+    Error::new(ErrorKind::OutOfBounds { start_index: index, requested: 1, len: data.len() })
+}
+
+/// Returns the error for an encoding of len bytes that does not fit in data at
+/// start_index.
+fn no_room(data: &[u8], start_index: usize, len: usize) -> Error {
+    Error::new(ErrorKind::OutOfBounds { start_index, requested: len, len: data.len() })
+}
+
+/// Decodes an unsigned LEB128 integer of at most bits bits at start_index,
+/// returning it along with the number of bytes read.
+///
+/// Fails with `ErrorKind::InvalidEncoding` if the value has more bits.
+#[inline]
+pub(crate) fn decode_uleb128(data: &[u8], start_index: usize, bits: u32) -> Result<(u64, usize)> {
+    let bytes = data.get(start_index..).ok_or_else(|| truncated(data, start_index))?;
+    let mut value = 0;
+    let mut shift = 0u32;
+    for (index, &byte) in bytes.iter().enumerate() {
+        // The byte holding the top bits may only hold as many as are left,
+        // and so cannot be followed by another.
+        let left = bits.wrapping_sub(shift);
+        if left <= 7 && byte.checked_shr(left).unwrap_or(0) != 0 {
+            return Err(Error::new(ErrorKind::InvalidEncoding { start_index }));
+        }
+        value |= u64::from(byte & 0x7f).wrapping_shl(shift);
+        if byte & 0x80 == 0 {
+            return Ok((value, index + 1));
+        }
+        shift = shift.wrapping_add(7);
+    }
+    Err(truncated(data, data.len()))
+}
+
+/// Decodes an unsigned LEB128 integer from the bytes next returns, pulling
+/// no more of them than the integer takes, for readers of streams that cannot
+/// look ahead. A value that does not fit in a `u64` fails with the
+/// `ErrorKind::InvalidEncoding` error at start_index passed through invalid.
+fn pull_uleb128<X>(
+    start_index: usize,
+    mut next: impl FnMut() -> core::result::Result<u8, X>,
+    invalid: impl FnOnce(Error) -> X,
+) -> core::result::Result<u64, X> {
+    let mut value = 0;
+    let mut shift = 0u32;
+    loop {
+        let byte = next()?;
+        let left = 64u32.wrapping_sub(shift);
+        if left <= 7 && byte.checked_shr(left).unwrap_or(0) != 0 {
+            return Err(invalid(Error::new(ErrorKind::InvalidEncoding { start_index })));
+        }
+        value |= u64::from(byte & 0x7f).wrapping_shl(shift);
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+        shift = shift.wrapping_add(7);
+    }
+}
+
+/// Returns the number of bytes the unsigned LEB128 encoding of value takes,
+/// from 1 to 10.
+/// # Example
+///
+/// ```
+/// use bitconv::uleb128_len;
+///
+/// assert_eq!(1, uleb128_len(127));
+/// assert_eq!(2, uleb128_len(128));
+/// assert_eq!(10, uleb128_len(u64::MAX));
+/// ```
+#[inline]
+pub const fn uleb128_len(value: u64) -> usize {
+    match value {
+        0 => 1,
+        _ => (64 - value.leading_zeros() as usize).div_ceil(7),
+    }
+}
+
+/// Returns the unsigned LEB128 integer at start_index, along with the number
+/// of bytes it takes.
+///
+/// LEB128 stores 7 bits in each byte, least significant group first, with
+/// the high bit set on every byte but the last, as used by DWARF and
+/// WebAssembly. Encodings padded with extra `0x80` bytes are accepted as long
+/// as they are at most 10 bytes long. Fails with `ErrorKind::OutOfBounds` if
+/// data ends before the last byte, and with `ErrorKind::InvalidEncoding` if
+/// the value does not fit in a `u64`.
+/// # Example
+///
+/// ```
+/// use bitconv::{read_uleb128, ErrorKind};
+///
+/// let buffer = [0xe5, 0x8e, 0x26, 0x7f, 0x80];
+/// assert_eq!(Ok((624485, 3)), read_uleb128(&buffer, 0));
+/// assert_eq!(Ok((127, 1)), read_uleb128(&buffer, 3));
+/// assert_eq!(
+///     ErrorKind::OutOfBounds { start_index: 5, requested: 1, len: 5 },
+///     read_uleb128(&buffer, 4).unwrap_err().kind()
+/// );
+/// ```
+#[inline]
+#[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
+pub fn read_uleb128(data: &[u8], start_index: usize) -> Result<(u64, usize)> {
+    decode_uleb128(data, start_index, 64)
+}
+
+/// Writes value as an unsigned LEB128 integer at start_index, returning the
+/// number of bytes written.
+///
+/// Fails with `ErrorKind::OutOfBounds`, leaving data untouched, if the
+/// encoding does not fit.
+/// # Example
+///
+/// ```
+/// use bitconv::write_uleb128;
+///
+/// let mut buffer = [0; 4];
+/// assert_eq!(Ok(3), write_uleb128(&mut buffer, 1, 624485));
+/// assert_eq!([0, 0xe5, 0x8e, 0x26], buffer);
+/// assert!(write_uleb128(&mut buffer, 2, 624485).is_err());
+/// ```
+#[inline]
+#[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
+pub fn write_uleb128(data: &mut [u8], start_index: usize, mut value: u64) -> Result<usize> {
+    let len = uleb128_len(value);
+    let bytes = match data.get_mut(start_index..).and_then(|bytes| bytes.get_mut(..len)) {
+        Some(bytes) => bytes,
+        None => return Err(no_room(data, start_index, len)),
+    };
+    for byte in bytes.iter_mut() {
+        *byte = value as u8 | 0x80;
+        value >>= 7;
+    }
+    if let Some(last) = bytes.last_mut() {
+        *last &= 0x7f;
+    }
+    Ok(len)
+}
+
+/// Returns the number of bytes the signed LEB128 encoding of value takes,
+/// from 1 to 10.
+/// # Example
+///
+/// ```
+/// use bitconv::sleb128_len;
+///
+/// assert_eq!(1, sleb128_len(63));
+/// assert_eq!(2, sleb128_len(64));
+/// assert_eq!(1, sleb128_len(-64));
+/// assert_eq!(10, sleb128_len(i64::MIN));
+/// ```
+#[inline]
+pub const fn sleb128_len(value: i64) -> usize {
+    let magnitude = if value < 0 { !value } else { value };
+    (65 - magnitude.leading_zeros() as usize).div_ceil(7)
+}
+
+/// Returns the signed LEB128 integer at start_index, along with the number of
+/// bytes it takes.
+///
+/// Signed LEB128 is laid out like [`read_uleb128`] reads it, with the value
+/// in two's complement and the second highest bit of the last byte extended
+/// as its sign. Fails with `ErrorKind::OutOfBounds` if data ends before the
+/// last byte, and with `ErrorKind::InvalidEncoding` if the value does not fit
+/// in an `i64`.
+/// # Example
+///
+/// ```
+/// use bitconv::read_sleb128;
+///
+/// let buffer = [0x7e, 0xc0, 0xbb, 0x78, 0x80, 0x7f];
+/// assert_eq!(Ok((-2, 1)), read_sleb128(&buffer, 0));
+/// assert_eq!(Ok((-123456, 3)), read_sleb128(&buffer, 1));
+/// assert_eq!(Ok((-128, 2)), read_sleb128(&buffer, 4));
+/// ```
+#[inline]
+#[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
+pub fn read_sleb128(data: &[u8], start_index: usize) -> Result<(i64, usize)> {
+    let bytes = data.get(start_index..).ok_or_else(|| truncated(data, start_index))?;
+    let mut value = 0;
+    let mut shift = 0u32;
+    for (index, &byte) in bytes.iter().enumerate() {
+        // The tenth byte holds the top bit, so the rest of it can only extend
+        // that bit's sign.
+        if shift == 63 && byte != 0 && byte != 0x7f {
+            return Err(Error::new(ErrorKind::InvalidEncoding { start_index }));
+        }
+        value |= i64::from(byte & 0x7f).wrapping_shl(shift);
+        shift = shift.wrapping_add(7);
+        if byte & 0x80 == 0 {
+            if shift < 64 && byte & 0x40 != 0 {
+                value |= (-1i64).wrapping_shl(shift);
+            }
+            return Ok((value, index + 1));
+        }
+    }
+    Err(truncated(data, data.len()))
+}
+
+/// Writes value as a signed LEB128 integer at start_index, returning the
+/// number of bytes written.
+///
+/// Fails with `ErrorKind::OutOfBounds`, leaving data untouched, if the
+/// encoding does not fit.
+/// # Example
+///
+/// ```
+/// use bitconv::write_sleb128;
+///
+/// let mut buffer = [0; 3];
+/// assert_eq!(Ok(3), write_sleb128(&mut buffer, 0, -123456));
+/// assert_eq!([0xc0, 0xbb, 0x78], buffer);
+/// ```
+#[inline]
+#[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
+pub fn write_sleb128(data: &mut [u8], start_index: usize, mut value: i64) -> Result<usize> {
+    let len = sleb128_len(value);
+    let bytes = match data.get_mut(start_index..).and_then(|bytes| bytes.get_mut(..len)) {
+        Some(bytes) => bytes,
+        None => return Err(no_room(data, start_index, len)),
+    };
+    for byte in bytes.iter_mut() {
+        *byte = value as u8 | 0x80;
+        value >>= 7;
+    }
+    if let Some(last) = bytes.last_mut() {
+        *last &= 0x7f;
+    }
+    Ok(len)
+}
+
+/// Maps an `i32` to a `u32` so that values of small magnitude, negative or
+/// not, map to small values: 0, -1, 1, -2 map to 0, 1, 2, 3 and so on, as
+/// protobuf does for its `sint32` fields before writing them as varints.
+/// # Example
+///
+/// ```
+/// use bitconv::{zigzag_decode_int32, zigzag_encode_int32};
+///
+/// assert_eq!(3, zigzag_encode_int32(-2));
+/// assert_eq!(u32::MAX, zigzag_encode_int32(i32::MIN));
+/// assert_eq!(-2, zigzag_decode_int32(3));
+/// ```
+#[inline]
+pub const fn zigzag_encode_int32(value: i32) -> u32 { ((value << 1) ^ (value >> 31)) as u32 }
+
+/// Maps a `u32` back to the `i32` [`zigzag_encode_int32`] maps to it.
+#[inline]
+pub const fn zigzag_decode_int32(value: u32) -> i32 { (value >> 1) as i32 ^ -((value & 1) as i32) }
+
+/// Maps an `i64` to a `u64` so that values of small magnitude map to small
+/// values, as [`zigzag_encode_int32`] does for an `i32` and protobuf does for
+/// its `sint64` fields.
+#[inline]
+pub const fn zigzag_encode_int64(value: i64) -> u64 { ((value << 1) ^ (value >> 63)) as u64 }
+
+/// Maps a `u64` back to the `i64` [`zigzag_encode_int64`] maps to it.
+#[inline]
+pub const fn zigzag_decode_int64(value: u64) -> i64 { (value >> 1) as i64 ^ -((value & 1) as i64) }
+
+/// Largest value a QUIC variable-length integer can hold, 2^62 - 1.
+pub const QUIC_VARINT_MAX: u64 = (1 << 62) - 1;
+
+/// Returns the number of bytes the shortest QUIC variable-length encoding of
+/// value takes, 1, 2, 4 or 8, or `None` if it is larger than
+/// [`QUIC_VARINT_MAX`].
+/// # Example
+///
+/// ```
+/// use bitconv::{quic_varint_len, QUIC_VARINT_MAX};
+///
+/// assert_eq!(Some(1), quic_varint_len(63));
+/// assert_eq!(Some(2), quic_varint_len(64));
+/// assert_eq!(Some(8), quic_varint_len(QUIC_VARINT_MAX));
+/// assert_eq!(None, quic_varint_len(QUIC_VARINT_MAX + 1));
+/// ```
+#[inline]
+pub const fn quic_varint_len(value: u64) -> Option<usize> {
+    match value {
+        0..=0x3f => Some(1),
+        0x40..=0x3fff => Some(2),
+        0x4000..=0x3fff_ffff => Some(4),
+        0x4000_0000..=QUIC_VARINT_MAX => Some(8),
+        _ => None,
+    }
+}
+
+/// Returns the QUIC variable-length integer at start_index, along with the
+/// number of bytes it takes.
+///
+/// As laid out by RFC 9000, the two high bits of the first byte give the
+/// length of the integer, 1, 2, 4 or 8 bytes, and the remaining bits hold it
+/// in big-endian. Longer encodings than a value needs are accepted, as the
+/// RFC allows. Fails with `ErrorKind::OutOfBounds` if data ends before the
+/// integer does.
+/// # Example
+///
+/// ```
+/// use bitconv::read_quic_varint;
+///
+/// let buffer = [0x25, 0x7b, 0xbd, 0x9d, 0x7f, 0x3e, 0x7d];
+/// assert_eq!(Ok((37, 1)), read_quic_varint(&buffer, 0));
+/// assert_eq!(Ok((15293, 2)), read_quic_varint(&buffer, 1));
+/// assert_eq!(Ok((494878333, 4)), read_quic_varint(&buffer, 3));
+/// ```
+#[inline]
+#[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
+pub fn read_quic_varint(data: &[u8], start_index: usize) -> Result<(u64, usize)> {
+    let first = *data.get(start_index).ok_or_else(|| truncated(data, start_index))?;
+    let len = 1 << (first >> 6);
+    let bytes = match data.get(start_index..).and_then(|bytes| bytes.get(..len)) {
+        Some(bytes) => bytes,
+        None => return Err(no_room(data, start_index, len)),
+    };
+    let value = bytes.iter().fold(0, |value, &byte| value << 8 | u64::from(byte));
+    Ok((value & (u64::MAX >> (64 - 8 * len as u32 + 2)), len))
+}
+
+/// Writes value as a QUIC variable-length integer at start_index, in as few
+/// bytes as it fits in, returning the number of bytes written.
+///
+/// Fails, leaving data untouched, with `ErrorKind::OutOfBounds` if the
+/// encoding does not fit and with `ErrorKind::InvalidEncoding` if value is
+/// larger than [`QUIC_VARINT_MAX`] and so has no encoding.
+/// # Example
+///
+/// ```
+/// use bitconv::write_quic_varint;
+///
+/// let mut buffer = [0; 4];
+/// assert_eq!(Ok(4), write_quic_varint(&mut buffer, 0, 494878333));
+/// assert_eq!([0x9d, 0x7f, 0x3e, 0x7d], buffer);
+/// assert_eq!(Ok(2), write_quic_varint(&mut buffer, 1, 15293));
+/// assert_eq!([0x9d, 0x7b, 0xbd, 0x7d], buffer);
+/// ```
+#[inline]
+#[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
+pub fn write_quic_varint(data: &mut [u8], start_index: usize, value: u64) -> Result<usize> {
+    let len = match quic_varint_len(value) {
+        Some(len) => len,
+        None => return Err(Error::new(ErrorKind::InvalidEncoding { start_index })),
+    };
+    let bytes = match data.get_mut(start_index..).and_then(|bytes| bytes.get_mut(..len)) {
+        Some(bytes) => bytes,
+        None => return Err(no_room(data, start_index, len)),
+    };
+    let encoded = value | u64::from(len.trailing_zeros()) << (8 * len as u32 - 2);
+    bytes.copy_from_slice(&encoded.to_be_bytes()[8 - len..]);
+    Ok(len)
+}
+
+/// Returns the number of bytes the offset encoding of Git packfiles takes for
+/// value, from 1 to 10.
+/// # Example
+///
+/// ```
+/// use bitconv::git_offset_len;
+///
+/// assert_eq!(1, git_offset_len(127));
+/// assert_eq!(2, git_offset_len(128));
+/// assert_eq!(2, git_offset_len(16511));
+/// assert_eq!(3, git_offset_len(16512));
+/// ```
+#[inline]
+pub const fn git_offset_len(mut value: u64) -> usize {
+    let mut len = 1;
+    while value >= 0x80 {
+        value = (value >> 7) - 1;
+        len += 1;
+    }
+    len
+}
+
+/// Returns the offset of an `OFS_DELTA` object of a Git packfile at
+/// start_index, along with the number of bytes it takes.
+///
+/// Like LEB128, the offset is stored 7 bits at a time with the high bit set
+/// on every byte but the last, but the most significant group comes first,
+/// and 1 is added to the value for every byte that follows another, so that
+/// each offset has exactly one encoding. Fails with `ErrorKind::OutOfBounds`
+/// if data ends before the last byte, and with `ErrorKind::InvalidEncoding`
+/// if the offset does not fit in a `u64`.
+/// # Example
+///
+/// ```
+/// use bitconv::read_git_offset;
+///
+/// let buffer = [0x7f, 0x80, 0x00, 0x91, 0x2e];
+/// assert_eq!(Ok((127, 1)), read_git_offset(&buffer, 0));
+/// assert_eq!(Ok((128, 2)), read_git_offset(&buffer, 1));
+/// assert_eq!(Ok((2350, 2)), read_git_offset(&buffer, 3));
+/// ```
+#[inline]
+#[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
+pub fn read_git_offset(data: &[u8], start_index: usize) -> Result<(u64, usize)> {
+    let bytes = data.get(start_index..).ok_or_else(|| truncated(data, start_index))?;
+    let mut value = 0u64;
+    for (index, &byte) in bytes.iter().enumerate() {
+        if index > 0 {
+            value = match value.checked_add(1).and_then(|value| value.checked_mul(0x80)) {
+                Some(value) => value,
+                None => return Err(Error::new(ErrorKind::InvalidEncoding { start_index })),
+            };
+        }
+        value |= u64::from(byte & 0x7f);
+        if byte & 0x80 == 0 {
+            return Ok((value, index + 1));
+        }
+    }
+    Err(truncated(data, data.len()))
+}
+
+/// Writes value in the offset encoding of Git packfiles at start_index,
+/// returning the number of bytes written.
+///
+/// Fails with `ErrorKind::OutOfBounds`, leaving data untouched, if the
+/// encoding does not fit.
+/// # Example
+///
+/// ```
+/// use bitconv::write_git_offset;
+///
+/// let mut buffer = [0; 2];
+/// assert_eq!(Ok(2), write_git_offset(&mut buffer, 0, 2350));
+/// assert_eq!([0x91, 0x2e], buffer);
+/// ```
+#[inline]
+#[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
+pub fn write_git_offset(data: &mut [u8], start_index: usize, mut value: u64) -> Result<usize> {
+    let len = git_offset_len(value);
+    let bytes = match data.get_mut(start_index..).and_then(|bytes| bytes.get_mut(..len)) {
+        Some(bytes) => bytes,
+        None => return Err(no_room(data, start_index, len)),
+    };
+    let mut flag = 0;
+    for byte in bytes.iter_mut().rev() {
+        *byte = flag | (value & 0x7f) as u8;
+        value = (value >> 7).wrapping_sub(1);
+        flag = 0x80;
+    }
+    Ok(len)
+}
+
+/// Returns the number of bytes the SQLite varint encoding of value takes,
+/// from 1 to 9.
+/// # Example
+///
+/// ```
+/// use bitconv::sqlite_varint_len;
+///
+/// assert_eq!(1, sqlite_varint_len(127));
+/// assert_eq!(2, sqlite_varint_len(128));
+/// assert_eq!(8, sqlite_varint_len((1 << 56) - 1));
+/// assert_eq!(9, sqlite_varint_len(1 << 56));
+/// ```
+#[inline]
+pub const fn sqlite_varint_len(value: u64) -> usize {
+    match value {
+        0 => 1,
+        0x0100_0000_0000_0000.. => 9,
+        _ => (64 - value.leading_zeros() as usize).div_ceil(7),
+    }
+}
+
+/// Returns the SQLite varint at start_index, along with the number of bytes
+/// it takes.
+///
+/// As used by the record format and b-tree pages of SQLite databases, the
+/// varint is stored 7 bits at a time with the most significant group first
+/// and the high bit set on every byte but the last, except that a ninth byte
+/// holds the 8 lowest bits in full. Every run of bytes is a valid encoding,
+/// so this only fails, with `ErrorKind::OutOfBounds`, if data ends before the
+/// last byte. SQLite reads the result as an `i64` when it is a row ID or an
+/// integer value, which casting it with `as i64` gives.
+/// # Example
+///
+/// ```
+/// use bitconv::read_sqlite_varint;
+///
+/// let buffer = [0x81, 0x00, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff];
+/// assert_eq!(Ok((128, 2)), read_sqlite_varint(&buffer, 0));
+/// assert_eq!(Ok((u64::MAX, 9)), read_sqlite_varint(&buffer, 2));
+/// assert_eq!(Ok((-1, 9)), read_sqlite_varint(&buffer, 2).map(|(value, len)| (value as i64, len)));
+/// ```
+#[inline]
+#[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
+pub fn read_sqlite_varint(data: &[u8], start_index: usize) -> Result<(u64, usize)> {
+    let bytes = data.get(start_index..).ok_or_else(|| truncated(data, start_index))?;
+    let mut value = 0u64;
+    for (index, &byte) in bytes.iter().enumerate() {
+        if index == 8 {
+            return Ok((value << 8 | u64::from(byte), 9));
+        }
+        value = value << 7 | u64::from(byte & 0x7f);
+        if byte & 0x80 == 0 {
+            return Ok((value, index + 1));
+        }
+    }
+    Err(truncated(data, data.len()))
+}
+
+/// Writes value as a SQLite varint at start_index, in as few bytes as it fits
+/// in, returning the number of bytes written.
+///
+/// Signed values, such as row IDs, are written by casting them with
+/// `as u64`, which stores negative ones in 9 bytes. Fails with
+/// `ErrorKind::OutOfBounds`, leaving data untouched, if the encoding does not
+/// fit.
+/// # Example
+///
+/// ```
+/// use bitconv::write_sqlite_varint;
+///
+/// let mut buffer = [0; 9];
+/// assert_eq!(Ok(2), write_sqlite_varint(&mut buffer, 0, 128));
+/// assert_eq!([0x81, 0x00], buffer[..2]);
+/// assert_eq!(Ok(9), write_sqlite_varint(&mut buffer, 0, -2i64 as u64));
+/// assert_eq!([0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xfe], buffer);
+/// ```
+#[inline]
+#[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
+pub fn write_sqlite_varint(data: &mut [u8], start_index: usize, mut value: u64) -> Result<usize> {
+    let len = sqlite_varint_len(value);
+    let bytes = match data.get_mut(start_index..).and_then(|bytes| bytes.get_mut(..len)) {
+        Some(bytes) => bytes,
+        None => return Err(no_room(data, start_index, len)),
+    };
+    let mut flag = 0;
+    for (index, byte) in bytes.iter_mut().enumerate().rev() {
+        match index {
+            8 => {
+                *byte = value as u8;
+                value >>= 8;
+            }
+            _ => {
+                *byte = flag | (value & 0x7f) as u8;
+                value >>= 7;
+            }
+        }
+        flag = 0x80;
+    }
+    Ok(len)
+}
+
+/// Returns the number of bytes the variable-length quantity encoding of value
+/// takes, from 1 to 10.
+/// # Example
+///
+/// ```
+/// use bitconv::vlq_len;
+///
+/// assert_eq!(1, vlq_len(127));
+/// assert_eq!(2, vlq_len(128));
+/// assert_eq!(4, vlq_len(0x0fff_ffff));
+/// ```
+#[inline]
+pub const fn vlq_len(value: u64) -> usize { uleb128_len(value) }
+
+/// Returns the variable-length quantity at start_index, along with the number
+/// of bytes it takes.
+///
+/// Variable-length quantities, as used by MIDI files, store 7 bits in each
+/// byte like LEB128, but with the most significant group first. Encodings
+/// padded with leading `0x80` bytes are accepted as long as they are at most
+/// 10 bytes long. MIDI itself limits quantities to 4 bytes, or `0x0fff_ffff`,
+/// which callers can check against the returned length. Fails with
+/// `ErrorKind::OutOfBounds` if data ends before the last byte, and with
+/// `ErrorKind::InvalidEncoding` if the encoding is longer or the value does
+/// not fit in a `u64`.
+/// # Example
+///
+/// ```
+/// use bitconv::read_vlq;
+///
+/// let buffer = [0x81, 0x00, 0xff, 0xff, 0xff, 0x7f];
+/// assert_eq!(Ok((128, 2)), read_vlq(&buffer, 0));
+/// assert_eq!(Ok((0x0fff_ffff, 4)), read_vlq(&buffer, 2));
+/// ```
+#[inline]
+#[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
+pub fn read_vlq(data: &[u8], start_index: usize) -> Result<(u64, usize)> {
+    let bytes = data.get(start_index..).ok_or_else(|| truncated(data, start_index))?;
+    let mut value = 0u64;
+    for (index, &byte) in bytes.iter().enumerate() {
+        if index == 10 || value >> 57 != 0 {
+            return Err(Error::new(ErrorKind::InvalidEncoding { start_index }));
+        }
+        value = value << 7 | u64::from(byte & 0x7f);
+        if byte & 0x80 == 0 {
+            return Ok((value, index + 1));
+        }
+    }
+    Err(truncated(data, data.len()))
+}
+
+/// Writes value as a variable-length quantity at start_index, in as few
+/// bytes as it fits in, returning the number of bytes written.
+///
+/// Fails with `ErrorKind::OutOfBounds`, leaving data untouched, if the
+/// encoding does not fit.
+/// # Example
+///
+/// ```
+/// use bitconv::write_vlq;
+///
+/// let mut buffer = [0; 3];
+/// assert_eq!(Ok(3), write_vlq(&mut buffer, 0, 0x1f_ffff));
+/// assert_eq!([0xff, 0xff, 0x7f], buffer);
+/// ```
+#[inline]
+#[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
+pub fn write_vlq(data: &mut [u8], start_index: usize, mut value: u64) -> Result<usize> {
+    let len = vlq_len(value);
+    let bytes = match data.get_mut(start_index..).and_then(|bytes| bytes.get_mut(..len)) {
+        Some(bytes) => bytes,
+        None => return Err(no_room(data, start_index, len)),
+    };
+    let mut flag = 0;
+    for byte in bytes.iter_mut().rev() {
+        *byte = flag | (value & 0x7f) as u8;
+        value >>= 7;
+        flag = 0x80;
+    }
+    Ok(len)
+}
+
+/// Returns the number of bytes the order-preserving encoding of value takes,
+/// from 1 to 9.
+/// # Example
+///
+/// ```
+/// use bitconv::ordered_varint_len;
+///
+/// assert_eq!(1, ordered_varint_len(240));
+/// assert_eq!(2, ordered_varint_len(2287));
+/// assert_eq!(3, ordered_varint_len(67823));
+/// assert_eq!(9, ordered_varint_len(u64::MAX));
+/// ```
+#[inline]
+pub const fn ordered_varint_len(value: u64) -> usize {
+    match value {
+        0..=240 => 1,
+        241..=2287 => 2,
+        2288..=67823 => 3,
+        _ => 1 + (71 - value.leading_zeros() as usize) / 8,
+    }
+}
+
+/// Returns the order-preserving varint at start_index, along with the number
+/// of bytes it takes.
+///
+/// The encoding is the one of SQLite 4, which sorts like the values it holds
+/// when compared as raw bytes, so it can be used in keys of ordered stores:
+///
+/// - 0 to 240 take a single byte holding the value.
+/// - 241 to 2287 take two bytes, the first from 241 to 248.
+/// - 2288 to 67823 take three bytes, 249 followed by the value less 2288 as a
+///   big-endian `u16`.
+/// - larger values take 250 to 255 followed by the value in the 3 to 8
+///   big-endian bytes it needs.
+///
+/// Each value has a single encoding, so that equal keys are equal bytes.
+/// Fails with `ErrorKind::OutOfBounds` if data ends before the last byte, and
+/// with `ErrorKind::InvalidEncoding` for a value stored in more bytes than it
+/// needs.
+/// # Example
+///
+/// ```
+/// use bitconv::read_ordered_varint;
+///
+/// let buffer = [240, 241, 16, 249, 0, 0, 250, 1, 8, 240];
+/// assert_eq!(Ok((240, 1)), read_ordered_varint(&buffer, 0));
+/// assert_eq!(Ok((256, 2)), read_ordered_varint(&buffer, 1));
+/// assert_eq!(Ok((2288, 3)), read_ordered_varint(&buffer, 3));
+/// assert_eq!(Ok((67824, 4)), read_ordered_varint(&buffer, 6));
+/// ```
+#[inline]
+#[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
+pub fn read_ordered_varint(data: &[u8], start_index: usize) -> Result<(u64, usize)> {
+    let first = *data.get(start_index).ok_or_else(|| truncated(data, start_index))?;
+    let len = match first {
+        0..=240 => return Ok((u64::from(first), 1)),
+        241..=248 => 2,
+        249 => 3,
+        _ => usize::from(first) - 246,
+    };
+    let bytes = match data.get(start_index..).and_then(|bytes| bytes.get(1..len)) {
+        Some(bytes) => bytes,
+        None => return Err(no_room(data, start_index, len)),
+    };
+    let tail = bytes.iter().fold(0, |value, &byte| value << 8 | u64::from(byte));
+    let value = match first {
+        241..=248 => 240 + 256 * u64::from(first - 241) + tail,
+        249 => 2288 + tail,
+        _ => tail,
+    };
+    match ordered_varint_len(value) == len {
+        true => Ok((value, len)),
+        false => Err(Error::new(ErrorKind::InvalidEncoding { start_index })),
+    }
+}
+
+/// Writes value in the order-preserving encoding of [`read_ordered_varint`]
+/// at start_index, returning the number of bytes written.
+///
+/// Fails with `ErrorKind::OutOfBounds`, leaving data untouched, if the
+/// encoding does not fit.
+/// # Example
+///
+/// ```
+/// use bitconv::write_ordered_varint;
+///
+/// let mut low = [0; 9];
+/// let mut high = [0; 9];
+/// let low_len = write_ordered_varint(&mut low, 0, 2000).unwrap();
+/// let high_len = write_ordered_varint(&mut high, 0, 70000).unwrap();
+/// assert_eq!([247, 224], low[..low_len]);
+/// assert_eq!([250, 1, 17, 112], high[..high_len]);
+/// assert!(low[..low_len] < high[..high_len]);
+/// ```
+#[inline]
+#[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
+pub fn write_ordered_varint(data: &mut [u8], start_index: usize, value: u64) -> Result<usize> {
+    let len = ordered_varint_len(value);
+    let bytes = match data.get_mut(start_index..).and_then(|bytes| bytes.get_mut(..len)) {
+        Some(bytes) => bytes,
+        None => return Err(no_room(data, start_index, len)),
+    };
+    let (first, tail) = match len {
+        1 => (value as u8, 0),
+        2 => ((241 + (value - 240) / 256) as u8, (value - 240) % 256),
+        3 => (249, value - 2288),
+        _ => (246 + len as u8, value),
+    };
+    let tail = tail.to_be_bytes();
+    if let Some((head, rest)) = bytes.split_first_mut() {
+        *head = first;
+        let skip = tail.len().saturating_sub(rest.len());
+        rest.copy_from_slice(tail.get(skip..).unwrap_or_default());
+    }
+    Ok(len)
+}
+
+/// Iterator decoding consecutive varints from a slice until it is exhausted,
+/// for postings lists, length streams and the like.
+///
+/// The varints are decoded with any of the `read_*` functions of this crate,
+/// such as [`read_uleb128`] or [`read_sleb128`]. An error is yielded, with
+/// the index of the varint as context, when the slice ends in the middle of a
+/// varint or holds an invalid one, after which the iterator stops and
+/// [`remainder`](Varints::remainder) returns the bytes from the start of the
+/// offending varint.
+/// # Example
+///
+/// ```
+/// use bitconv::{read_uleb128, Varints};
+///
+/// let postings = [3, 0xac, 0x02, 7];
+/// let mut varints = Varints::new(&postings, read_uleb128);
+/// assert_eq!(Some(Ok(3)), varints.next());
+/// assert_eq!(vec![Ok(300), Ok(7)], varints.collect::<Vec<_>>());
+/// assert_eq!(1, Varints::new(&[0x80], read_uleb128).filter(Result::is_err).count());
+/// ```
+#[derive(Clone, Debug)]
+pub struct Varints<'a, V> {
+    data: &'a [u8],
+    position: usize,
+    index: usize,
+    failed: bool,
+    decode: Decode<V>,
+}
+
+impl<'a, V> Varints<'a, V> {
+    /// Creates an iterator decoding the varints of data with decode.
+    pub fn new(data: &'a [u8], decode: Decode<V>) -> Self {
+        Varints { data, position: 0, index: 0, failed: false, decode }
+    }
+
+    /// Returns the index in the slice of the next varint.
+    pub fn position(&self) -> usize { self.position }
+
+    /// Returns the bytes that have not been decoded yet.
+    pub fn remainder(&self) -> &'a [u8] { self.data.get(self.position..).unwrap_or_default() }
+}
+
+impl<'a, V> Iterator for Varints<'a, V> {
+    type Item = Result<V>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed || self.position >= self.data.len() {
+            return None;
+        }
+        match (self.decode)(self.data, self.position) {
+            Ok((value, len)) => {
+                self.position += len;
+                self.index += 1;
+                Some(Ok(value))
+            }
+            Err(error) => {
+                self.failed = true;
+                Some(Err(error.with_index(self.index)))
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match self.failed {
+            true => (0, Some(0)),
+            false => {
+                let len = self.remainder().len();
+                (len.min(1), Some(len))
+            }
+        }
+    }
+}
+
+impl<'a, V> FusedIterator for Varints<'a, V> {}
+
+/// Iterator reading varints from a [`Reader`], returned by
+/// [`Reader::read_varints`].
+pub struct ReaderVarints<'r, 'a, V, E> {
+    reader: &'r mut Reader<'a, E>,
+    index: usize,
+    failed: bool,
+    decode: Decode<V>,
+}
+
+impl<'r, 'a, V, E: BitConvEndian> Iterator for ReaderVarints<'r, 'a, V, E> {
+    type Item = Result<V>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed || self.reader.remaining() == 0 {
+            return None;
+        }
+        let value =
+            self.reader.read_varint(self.decode).map_err(|error| error.with_index(self.index));
+        self.index += 1;
+        self.failed = value.is_err();
+        Some(value)
+    }
+}
+
+impl<'r, 'a, V, E: BitConvEndian> FusedIterator for ReaderVarints<'r, 'a, V, E> {}
+
+impl<'a, E: BitConvEndian> Reader<'a, E> {
+    /// Decodes a varint at the current position with decode and advances
+    /// past it, leaving the position unchanged on error.
+    fn read_varint<V>(&mut self, decode: Decode<V>) -> Result<V> {
+        let (value, len) = decode(self.get_ref(), self.position())?;
+        self.read_bytes(len)?;
+        Ok(value)
+    }
+
+    /// Returns an iterator reading varints with decode, as [`Varints`] does,
+    /// until the reader is exhausted or a read fails, leaving the reader at
+    /// the start of the varint that failed.
+    /// # Example
+    ///
+    /// ```
+    /// use bitconv::{endian::Little, read_vlq, Reader};
+    ///
+    /// let mut reader = Reader::<Little>::new(&[0x81, 0x00, 0x40, 0xff]);
+    /// let lengths: Vec<_> = reader.read_varints(read_vlq).collect();
+    /// assert_eq!(3, lengths.len());
+    /// assert_eq!([Ok(128), Ok(64)], lengths[..2]);
+    /// assert_eq!(3, reader.position());
+    /// ```
+    pub fn read_varints<V>(&mut self, decode: Decode<V>) -> ReaderVarints<'_, 'a, V, E> {
+        ReaderVarints { reader: self, index: 0, failed: false, decode }
+    }
+
+    /// Reads an unsigned LEB128 integer and advances past it, as
+    /// [`read_uleb128`] does.
+    pub fn read_uleb128(&mut self) -> Result<u64> { self.read_varint(read_uleb128) }
+
+    /// Reads a signed LEB128 integer and advances past it, as
+    /// [`read_sleb128`] does.
+    pub fn read_sleb128(&mut self) -> Result<i64> { self.read_varint(read_sleb128) }
+
+    /// Reads a QUIC variable-length integer and advances past it, as
+    /// [`read_quic_varint`] does.
+    pub fn read_quic_varint(&mut self) -> Result<u64> { self.read_varint(read_quic_varint) }
+
+    /// Reads an offset in the encoding of Git packfiles and advances past it,
+    /// as [`read_git_offset`] does.
+    pub fn read_git_offset(&mut self) -> Result<u64> { self.read_varint(read_git_offset) }
+
+    /// Reads a SQLite varint and advances past it, as [`read_sqlite_varint`]
+    /// does.
+    pub fn read_sqlite_varint(&mut self) -> Result<u64> { self.read_varint(read_sqlite_varint) }
+
+    /// Reads a variable-length quantity and advances past it, as
+    /// [`read_vlq`] does.
+    pub fn read_vlq(&mut self) -> Result<u64> { self.read_varint(read_vlq) }
+
+    /// Reads an order-preserving varint and advances past it, as
+    /// [`read_ordered_varint`] does.
+    pub fn read_ordered_varint(&mut self) -> Result<u64> { self.read_varint(read_ordered_varint) }
+}
+
+impl<I: Iterator<Item = u8>, E: BitConvEndian> IterReader<I, E> {
+    /// Reads an unsigned LEB128 integer, as [`read_uleb128`] does, pulling
+    /// bytes until one has its continuation bit clear.
+    ///
+    /// The bytes pulled are consumed even if the read fails.
+    /// # Example
+    ///
+    /// ```
+    /// use bitconv::{endian::Little, IterReader};
+    ///
+    /// let mut reader = IterReader::<_, Little>::new([0xe5, 0x8e, 0x26, 7].iter().copied());
+    /// assert_eq!(Ok(624485), reader.read_uleb128());
+    /// assert_eq!(Ok(7), reader.read_uint8());
+    /// ```
+    pub fn read_uleb128(&mut self) -> Result<u64> {
+        pull_uleb128(self.position(), || self.read_uint8(), |error| error)
+    }
+}
+
+impl<I: Iterator<Item = core::result::Result<u8, X>>, X, E: BitConvEndian> TryIterReader<I, E> {
+    /// Reads an unsigned LEB128 integer, as [`read_uleb128`] does, pulling
+    /// bytes until one has its continuation bit clear.
+    ///
+    /// The bytes pulled are consumed even if the read fails.
+    pub fn read_uleb128(&mut self) -> core::result::Result<u64, ReadError<X>> {
+        pull_uleb128(self.position(), || self.read_uint8(), ReadError::Conversion)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<R: io::Read, E: BitConvEndian> crate::EndianReader<R, E> {
+    /// Reads an unsigned LEB128 integer, as [`read_uleb128`] does, reading
+    /// from the underlying reader a byte at a time until one has its
+    /// continuation bit clear.
+    ///
+    /// A value that does not fit in a `u64` fails with
+    /// `io::ErrorKind::InvalidData`, holding an `ErrorKind::InvalidEncoding`
+    /// error at index 0, the start of the integer. The bytes read are consumed
+    /// either way.
+    /// # Example
+    ///
+    /// ```
+    /// use bitconv::{endian::Big, EndianReader};
+    ///
+    /// let stream: &[u8] = &[0xe5, 0x8e, 0x26, 0x80];
+    /// let mut reader = EndianReader::<_, Big>::new(stream);
+    /// assert_eq!(624485, reader.read_uleb128().unwrap());
+    /// assert!(reader.read_uleb128().is_err());
+    /// ```
+    pub fn read_uleb128(&mut self) -> io::Result<u64> {
+        pull_uleb128(
+            0,
+            || self.read_uint8(),
+            |error| io::Error::new(io::ErrorKind::InvalidData, error),
+        )
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<E: BitConvEndian> Writer<E> {
+    /// Appends the bytes encode writes, which are no more than the 10 any of
+    /// the encodings takes.
+    fn write_varint(&mut self, encode: impl FnOnce(&mut [u8]) -> Result<usize>) {
+        let mut bytes = [0; 10];
+        let len = encode(&mut bytes).expect("varints take at most 10 bytes");
+        self.write_bytes(&bytes[..len]);
+    }
+
+    /// Appends value as an unsigned LEB128 integer.
+    pub fn write_uleb128(&mut self, value: u64) {
+        self.write_varint(|bytes| write_uleb128(bytes, 0, value));
+    }
+
+    /// Appends value as a signed LEB128 integer.
+    pub fn write_sleb128(&mut self, value: i64) {
+        self.write_varint(|bytes| write_sleb128(bytes, 0, value));
+    }
+
+    /// Appends value in the offset encoding of Git packfiles.
+    pub fn write_git_offset(&mut self, value: u64) {
+        self.write_varint(|bytes| write_git_offset(bytes, 0, value));
+    }
+
+    /// Appends value as a SQLite varint.
+    pub fn write_sqlite_varint(&mut self, value: u64) {
+        self.write_varint(|bytes| write_sqlite_varint(bytes, 0, value));
+    }
+
+    /// Appends value as a variable-length quantity.
+    pub fn write_vlq(&mut self, value: u64) {
+        self.write_varint(|bytes| write_vlq(bytes, 0, value));
+    }
+
+    /// Appends value in the order-preserving encoding of
+    /// [`read_ordered_varint`].
+    pub fn write_ordered_varint(&mut self, value: u64) {
+        self.write_varint(|bytes| write_ordered_varint(bytes, 0, value));
+    }
+
+    /// Appends value as a QUIC variable-length integer, in as few bytes as it
+    /// fits in.
+    ///
+    /// Fails with `ErrorKind::InvalidEncoding`, appending nothing, if value
+    /// is larger than [`QUIC_VARINT_MAX`].
+    pub fn write_quic_varint(&mut self, value: u64) -> Result<()> {
+        let mut bytes = [0; 8];
+        let len = write_quic_varint(&mut bytes, 0, value)
+            .map_err(|_| Error::new(ErrorKind::InvalidEncoding { start_index: self.position() }))?;
+        self.write_bytes(&bytes[..len]);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::endian::*;
+
+    #[test]
+    fn uleb128_test() {
+        let cases: [(u64, &[u8]); 7] = [
+            (0, &[0]),
+            (1, &[1]),
+            (127, &[0x7f]),
+            (128, &[0x80, 1]),
+            (300, &[0xac, 2]),
+            (624485, &[0xe5, 0x8e, 0x26]),
+            (u64::MAX, &[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 1]),
+        ];
+        for (value, bytes) in cases {
+            assert_eq!(bytes.len(), uleb128_len(value));
+            assert_eq!(Ok((value, bytes.len())), read_uleb128(bytes, 0));
+            let mut buffer = [0xaa; 12];
+            assert_eq!(Ok(bytes.len()), write_uleb128(&mut buffer, 1, value));
+            assert_eq!(bytes, &buffer[1..=bytes.len()]);
+            assert_eq!(0xaa, buffer[bytes.len() + 1]);
+        }
+    }
+
+    #[test]
+    fn read_uleb128_error_test() {
+        assert_eq!(Ok((0, 2)), read_uleb128(&[0x80, 0], 0));
+        assert_eq!(
+            Ok((1, 10)),
+            read_uleb128(&[0x81, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0], 0)
+        );
+        assert_eq!(
+            Err(Error::new(ErrorKind::InvalidEncoding { start_index: 1 })),
+            read_uleb128(&[0, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 2], 1)
+        );
+        assert_eq!(
+            Err(Error::new(ErrorKind::InvalidEncoding { start_index: 0 })),
+            read_uleb128(&[0x80; 11], 0)
+        );
+        assert_eq!(
+            Err(Error::new(ErrorKind::OutOfBounds { start_index: 2, requested: 1, len: 2 })),
+            read_uleb128(&[0x80, 0x80], 0)
+        );
+        assert_eq!(
+            Err(Error::new(ErrorKind::OutOfBounds {
+                start_index: usize::MAX,
+                requested: 1,
+                len: 2
+            })),
+            read_uleb128(&[0, 0], usize::MAX)
+        );
+        assert_eq!(
+            Err(Error::new(ErrorKind::OutOfBounds {
+                start_index: usize::MAX,
+                requested: 2,
+                len: 2
+            })),
+            write_uleb128(&mut [0, 0], usize::MAX, 128)
+        );
+    }
+
+    #[test]
+    fn sleb128_test() {
+        let cases: [(i64, &[u8]); 12] = [
+            (0, &[0]),
+            (2, &[2]),
+            (-2, &[0x7e]),
+            (63, &[0x3f]),
+            (-64, &[0x40]),
+            (127, &[0xff, 0]),
+            (-127, &[0x81, 0x7f]),
+            (128, &[0x80, 1]),
+            (-128, &[0x80, 0x7f]),
+            (-123456, &[0xc0, 0xbb, 0x78]),
+            (i64::MAX, &[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0]),
+            (i64::MIN, &[0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x7f]),
+        ];
+        for (value, bytes) in cases {
+            assert_eq!(bytes.len(), sleb128_len(value));
+            assert_eq!(Ok((value, bytes.len())), read_sleb128(bytes, 0));
+            let mut buffer = [0xaa; 12];
+            assert_eq!(Ok(bytes.len()), write_sleb128(&mut buffer, 1, value));
+            assert_eq!(bytes, &buffer[1..=bytes.len()]);
+            assert_eq!(0xaa, buffer[bytes.len() + 1]);
+        }
+    }
+
+    #[test]
+    fn read_sleb128_error_test() {
+        assert_eq!(Ok((-1, 2)), read_sleb128(&[0xff, 0x7f], 0));
+        assert_eq!(
+            Err(Error::new(ErrorKind::InvalidEncoding { start_index: 0 })),
+            read_sleb128(&[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 1], 0)
+        );
+        assert_eq!(
+            Err(Error::new(ErrorKind::InvalidEncoding { start_index: 0 })),
+            read_sleb128(&[0x80; 11], 0)
+        );
+        assert_eq!(
+            Err(Error::new(ErrorKind::OutOfBounds { start_index: 3, requested: 1, len: 3 })),
+            read_sleb128(&[0, 0xc0, 0xbb], 1)
+        );
+        assert_eq!(
+            Err(Error::new(ErrorKind::OutOfBounds { start_index: 1, requested: 2, len: 2 })),
+            write_sleb128(&mut [0, 0], 1, 64)
+        );
+    }
+
+    #[test]
+    fn zigzag_test() {
+        let cases =
+            [(0, 0), (-1, 1), (1, 2), (-2, 3), (i32::MAX, u32::MAX - 1), (i32::MIN, u32::MAX)];
+        for (value, encoded) in cases {
+            assert_eq!(encoded, zigzag_encode_int32(value));
+            assert_eq!(value, zigzag_decode_int32(encoded));
+            assert_eq!(u64::from(encoded), zigzag_encode_int64(i64::from(value)));
+            assert_eq!(i64::from(value), zigzag_decode_int64(u64::from(encoded)));
+        }
+        assert_eq!(u64::MAX, zigzag_encode_int64(i64::MIN));
+        assert_eq!(i64::MAX, zigzag_decode_int64(u64::MAX - 1));
+    }
+
+    #[test]
+    fn quic_varint_test() {
+        let cases: [(u64, &[u8]); 9] = [
+            (0, &[0]),
+            (37, &[0x25]),
+            (63, &[0x3f]),
+            (64, &[0x40, 0x40]),
+            (15293, &[0x7b, 0xbd]),
+            (16384, &[0x80, 0, 0x40, 0]),
+            (494878333, &[0x9d, 0x7f, 0x3e, 0x7d]),
+            (151288809941952652, &[0xc2, 0x19, 0x7c, 0x5e, 0xff, 0x14, 0xe8, 0x8c]),
+            (QUIC_VARINT_MAX, &[0xff; 8]),
+        ];
+        for (value, bytes) in cases {
+            assert_eq!(Some(bytes.len()), quic_varint_len(value));
+            assert_eq!(Ok((value, bytes.len())), read_quic_varint(bytes, 0));
+            let mut buffer = [0xaa; 10];
+            assert_eq!(Ok(bytes.len()), write_quic_varint(&mut buffer, 1, value));
+            assert_eq!(bytes, &buffer[1..=bytes.len()]);
+            assert_eq!(0xaa, buffer[bytes.len() + 1]);
+        }
+        assert_eq!(Ok((37, 2)), read_quic_varint(&[0x40, 0x25], 0));
+        assert_eq!(Ok((0, 8)), read_quic_varint(&[0xc0, 0, 0, 0, 0, 0, 0, 0], 0));
+    }
+
+    #[test]
+    fn quic_varint_error_test() {
+        assert_eq!(
+            Err(Error::new(ErrorKind::OutOfBounds { start_index: 1, requested: 4, len: 4 })),
+            read_quic_varint(&[0, 0x80, 0, 0], 1)
+        );
+        assert_eq!(
+            Err(Error::new(ErrorKind::OutOfBounds { start_index: 4, requested: 1, len: 4 })),
+            read_quic_varint(&[0; 4], 4)
+        );
+        assert_eq!(
+            Err(Error::new(ErrorKind::OutOfBounds { start_index: 1, requested: 2, len: 2 })),
+            write_quic_varint(&mut [0; 2], 1, 64)
+        );
+        assert_eq!(
+            Err(Error::new(ErrorKind::InvalidEncoding { start_index: 0 })),
+            write_quic_varint(&mut [0; 8], 0, QUIC_VARINT_MAX + 1)
+        );
+    }
+
+    #[test]
+    fn git_offset_test() {
+        let cases: [(u64, &[u8]); 8] = [
+            (0, &[0]),
+            (127, &[0x7f]),
+            (128, &[0x80, 0]),
+            (2350, &[0x91, 0x2e]),
+            (16511, &[0xff, 0x7f]),
+            (16512, &[0x80, 0x80, 0]),
+            (2113663, &[0xff, 0xff, 0x7f]),
+            (u64::MAX, &[0x80, 0xfe, 0xfe, 0xfe, 0xfe, 0xfe, 0xfe, 0xfe, 0xfe, 0x7f]),
+        ];
+        for (value, bytes) in cases {
+            assert_eq!(bytes.len(), git_offset_len(value));
+            assert_eq!(Ok((value, bytes.len())), read_git_offset(bytes, 0));
+            let mut buffer = [0xaa; 12];
+            assert_eq!(Ok(bytes.len()), write_git_offset(&mut buffer, 1, value));
+            assert_eq!(bytes, &buffer[1..=bytes.len()]);
+            assert_eq!(0xaa, buffer[bytes.len() + 1]);
+        }
+        // 2^64 + 5, which wraps around to 5 if the top group is not checked.
+        assert_eq!(
+            Err(Error::new(ErrorKind::InvalidEncoding { start_index: 0 })),
+            read_git_offset(&[0x80, 0xfe, 0xfe, 0xfe, 0xfe, 0xfe, 0xfe, 0xfe, 0xff, 0x05], 0)
+        );
+    }
+
+    #[test]
+    fn read_git_offset_error_test() {
+        assert_eq!(
+            Err(Error::new(ErrorKind::InvalidEncoding { start_index: 0 })),
+            read_git_offset(&[0x81, 0xfe, 0xfe, 0xfe, 0xfe, 0xfe, 0xfe, 0xfe, 0xfe, 0], 0)
+        );
+        assert_eq!(
+            Err(Error::new(ErrorKind::InvalidEncoding { start_index: 0 })),
+            read_git_offset(&[0x80; 11], 0)
+        );
+        assert_eq!(
+            Err(Error::new(ErrorKind::OutOfBounds { start_index: 2, requested: 1, len: 2 })),
+            read_git_offset(&[0x91, 0xae], 0)
+        );
+    }
+
+    #[test]
+    fn sqlite_varint_test() {
+        let cases: [(u64, &[u8]); 9] = [
+            (0, &[0]),
+            (127, &[0x7f]),
+            (128, &[0x81, 0]),
+            (240, &[0x81, 0x70]),
+            (16383, &[0xff, 0x7f]),
+            (16384, &[0x81, 0x80, 0]),
+            ((1 << 56) - 1, &[0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x7f]),
+            (1 << 56, &[0x80, 0xc0, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0]),
+            (0x0123_4567_89ab_cdef, &[0x80, 0xc8, 0xe8, 0xd6, 0xbc, 0xa6, 0xd7, 0xcd, 0xef]),
+        ];
+        for (value, bytes) in cases {
+            assert_eq!(bytes.len(), sqlite_varint_len(value));
+            assert_eq!(Ok((value, bytes.len())), read_sqlite_varint(bytes, 0));
+            let mut buffer = [0xaa; 11];
+            assert_eq!(Ok(bytes.len()), write_sqlite_varint(&mut buffer, 1, value));
+            assert_eq!(bytes, &buffer[1..=bytes.len()]);
+            assert_eq!(0xaa, buffer[bytes.len() + 1]);
+        }
+        assert_eq!(Ok((1, 3)), read_sqlite_varint(&[0x80, 0x80, 1], 0));
+        assert_eq!(
+            Ok((0xff, 9)),
+            read_sqlite_varint(&[0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0xff, 7], 0)
+        );
+        assert_eq!(
+            Err(Error::new(ErrorKind::OutOfBounds { start_index: 9, requested: 1, len: 9 })),
+            read_sqlite_varint(&[0x80; 9], 1)
+        );
+    }
+
+    #[test]
+    fn vlq_test() {
+        let cases: [(u64, &[u8]); 9] = [
+            (0, &[0]),
+            (0x40, &[0x40]),
+            (0x7f, &[0x7f]),
+            (0x80, &[0x81, 0]),
+            (0x2000, &[0xc0, 0]),
+            (0x3fff, &[0xff, 0x7f]),
+            (0x10_0000, &[0xc0, 0x80, 0]),
+            (0x0fff_ffff, &[0xff, 0xff, 0xff, 0x7f]),
+            (u64::MAX, &[0x81, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0x7f]),
+        ];
+        for (value, bytes) in cases {
+            assert_eq!(bytes.len(), vlq_len(value));
+            assert_eq!(Ok((value, bytes.len())), read_vlq(bytes, 0));
+            let mut buffer = [0xaa; 12];
+            assert_eq!(Ok(bytes.len()), write_vlq(&mut buffer, 1, value));
+            assert_eq!(bytes, &buffer[1..=bytes.len()]);
+            assert_eq!(0xaa, buffer[bytes.len() + 1]);
+        }
+        assert_eq!(Ok((1, 3)), read_vlq(&[0x80, 0x80, 1], 0));
+    }
+
+    #[test]
+    fn read_vlq_error_test() {
+        assert_eq!(
+            Err(Error::new(ErrorKind::InvalidEncoding { start_index: 0 })),
+            read_vlq(&[0x82, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0], 0)
+        );
+        assert_eq!(
+            Err(Error::new(ErrorKind::InvalidEncoding { start_index: 1 })),
+            read_vlq(&[0x80; 12], 1)
+        );
+        assert_eq!(
+            Err(Error::new(ErrorKind::OutOfBounds { start_index: 3, requested: 1, len: 3 })),
+            read_vlq(&[0, 0x81, 0x80], 1)
+        );
+    }
+
+    #[test]
+    fn ordered_varint_test() {
+        let cases: [(u64, &[u8]); 14] = [
+            (0, &[0]),
+            (240, &[240]),
+            (241, &[241, 1]),
+            (495, &[241, 255]),
+            (496, &[242, 0]),
+            (2287, &[248, 255]),
+            (2288, &[249, 0, 0]),
+            (67823, &[249, 255, 255]),
+            (67824, &[250, 1, 8, 240]),
+            (0xff_ffff, &[250, 255, 255, 255]),
+            (0x100_0000, &[251, 1, 0, 0, 0]),
+            (u32::MAX.into(), &[251, 255, 255, 255, 255]),
+            (1 << 56, &[255, 1, 0, 0, 0, 0, 0, 0, 0]),
+            (u64::MAX, &[255; 9]),
+        ];
+        let mut previous: &[u8] = &[];
+        for (value, bytes) in cases {
+            assert_eq!(bytes.len(), ordered_varint_len(value));
+            assert_eq!(Ok((value, bytes.len())), read_ordered_varint(bytes, 0));
+            let mut buffer = [0xaa; 11];
+            assert_eq!(Ok(bytes.len()), write_ordered_varint(&mut buffer, 1, value));
+            assert_eq!(bytes, &buffer[1..=bytes.len()]);
+            assert_eq!(0xaa, buffer[bytes.len() + 1]);
+            assert!(previous < bytes);
+            previous = bytes;
+        }
+    }
+
+    #[test]
+    fn read_ordered_varint_error_test() {
+        assert_eq!(
+            Err(Error::new(ErrorKind::InvalidEncoding { start_index: 0 })),
+            read_ordered_varint(&[250, 0, 255, 255], 0)
+        );
+        assert_eq!(
+            Err(Error::new(ErrorKind::InvalidEncoding { start_index: 1 })),
+            read_ordered_varint(&[0, 252, 0, 0, 255, 255, 255], 1)
+        );
+        assert_eq!(
+            Err(Error::new(ErrorKind::OutOfBounds { start_index: 0, requested: 9, len: 8 })),
+            read_ordered_varint(&[255; 8], 0)
+        );
+        assert_eq!(
+            Err(Error::new(ErrorKind::OutOfBounds { start_index: 1, requested: 1, len: 1 })),
+            read_ordered_varint(&[0], 1)
+        );
+    }
+
+    #[test]
+    fn varints_test() {
+        let buffer = [0x7f, 0x80, 0x01, 0x7e, 0xff, 0x80];
+        let mut varints = Varints::new(&buffer, read_sleb128);
+        assert_eq!((1, Some(6)), varints.size_hint());
+        assert_eq!(Some(Ok(-1)), varints.next());
+        assert_eq!(Some(Ok(128)), varints.next());
+        assert_eq!(Some(Ok(-2)), varints.next());
+        assert_eq!(4, varints.position());
+        assert_eq!(
+            Some(Err(Error::new(ErrorKind::OutOfBounds { start_index: 6, requested: 1, len: 6 })
+                .with_index(3))),
+            varints.next()
+        );
+        assert_eq!((0, Some(0)), varints.size_hint());
+        assert_eq!(None, varints.next());
+        assert_eq!([0xff, 0x80], varints.remainder());
+        assert_eq!(0, Varints::new(&[], read_uleb128).count());
+        assert!(Varints::new(&[1, 2], read_uleb128).eq([Ok(1), Ok(2)]));
+    }
+
+    #[test]
+    fn reader_varints_test() {
+        let buffer = [0xff, 1, 0x80, 0x80, 0x05, 0x80];
+        let mut reader = Reader::<Little>::new(&buffer);
+        assert_eq!(Ok(0xff), reader.read_uint8());
+        let mut varints = reader.read_varints(read_uleb128);
+        assert_eq!(Some(Ok(1)), varints.next());
+        assert_eq!(Some(Ok(0x14000)), varints.next());
+        assert!(varints.next().unwrap().is_err());
+        assert_eq!(None, varints.next());
+        assert_eq!(5, reader.position());
+        reader.rewind();
+        assert_eq!(3, reader.read_varints(read_sqlite_varint).count());
+        assert_eq!(5, reader.position());
+    }
+
+    #[test]
+    fn reader_uleb128_test() {
+        let buffer = [0xac, 2, 0x80];
+        let mut reader = Reader::<Big>::new(&buffer);
+        assert_eq!(Ok(300), reader.read_uleb128());
+        assert_eq!(2, reader.position());
+        assert!(reader.read_uleb128().is_err());
+        assert!(reader.read_sleb128().is_err());
+        assert!(reader.read_quic_varint().is_err());
+        assert!(reader.read_git_offset().is_err());
+        assert!(reader.read_sqlite_varint().is_err());
+        assert!(reader.read_vlq().is_err());
+        assert!(reader.read_ordered_varint().is_ok());
+        assert_eq!(3, reader.position());
+    }
+
+    #[test]
+    fn iter_reader_uleb128_test() {
+        let buffer = [0xac, 2, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 1, 0x80];
+        let mut reader = IterReader::<_, Little>::new(buffer.iter().copied());
+        assert_eq!(Ok(300), reader.read_uleb128());
+        assert_eq!(Ok(u64::MAX), reader.read_uleb128());
+        assert_eq!(
+            ErrorKind::OutOfBounds { start_index: 13, requested: 1, len: 13 },
+            reader.read_uleb128().unwrap_err().kind()
+        );
+        let overlong = [0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 2, 0];
+        let mut reader = IterReader::<_, Little>::new(overlong.iter().copied());
+        assert_eq!(
+            ErrorKind::InvalidEncoding { start_index: 0 },
+            reader.read_uleb128().unwrap_err().kind()
+        );
+        assert_eq!(Ok(0), reader.read_uint8());
+
+        let results = [Ok(0x80), Err(()), Ok(7)];
+        let mut reader = TryIterReader::<_, Big>::new(results.iter().copied());
+        assert_eq!(Err(ReadError::Source(())), reader.read_uleb128());
+        assert_eq!(Ok(7), reader.read_uleb128());
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn endian_reader_uleb128_test() {
+        let stream: &[u8] = &[0xac, 2, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 3];
+        let mut reader = crate::EndianReader::<_, Little>::new(stream);
+        assert_eq!(300, reader.read_uleb128().unwrap());
+        assert_eq!(io::ErrorKind::InvalidData, reader.read_uleb128().unwrap_err().kind());
+        assert_eq!(io::ErrorKind::UnexpectedEof, reader.read_uleb128().unwrap_err().kind());
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn writer_uleb128_test() {
+        let mut writer = Writer::<Little>::new();
+        writer.write_uleb128(0);
+        writer.write_uleb128(624485);
+        writer.write_sleb128(-128);
+        writer.write_git_offset(2350);
+        writer.write_sqlite_varint(240);
+        writer.write_vlq(0x2000);
+        writer.write_ordered_varint(2288);
+        assert_eq!(Ok(()), writer.write_quic_varint(15293));
+        assert!(writer.write_quic_varint(u64::MAX).is_err());
+        assert_eq!(
+            [
+                0, 0xe5, 0x8e, 0x26, 0x80, 0x7f, 0x91, 0x2e, 0x81, 0x70, 0xc0, 0, 249, 0, 0, 0x7b,
+                0xbd
+            ],
+            *writer.as_slice()
+        );
+    }
+}
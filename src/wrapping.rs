@@ -0,0 +1,136 @@
+//! Conversions that wrap around the end of a circular buffer.
+use crate::{endian::Endian, error, BitConvEndian, Error, ErrorKind, Result};
+use core::mem;
+
+macro_rules! WrappingImpl {
+    ($($(#[$attr:meta])* $name:ident $try_name:ident $type:ty),+) => {$(
+        #[doc = concat!(
+            "Returns a `", stringify!($type), "` converted from the bytes at a specified ",
+            "position in a circular buffer, continuing from the start of the buffer when ",
+            "the value runs past its end.\n\n",
+            "start_index is taken modulo the length of data, so offsets into an emulated ",
+            "address space or a DMA ring can be passed as they are.\n",
+            "# Panics\n\n",
+            "Panics if data is shorter than the value. See [`", stringify!($try_name), "`] ",
+            "for a version that returns an error instead."
+        )]
+        $(#[$attr])*
+        #[inline]
+        #[track_caller]
+        pub fn $name<T: BitConvEndian>(data: &[u8], start_index: usize) -> $type {
+            match $try_name::<T>(data, start_index) {
+                Ok(value) => value,
+                Err(error) => error::conversion_failed("read", stringify!($type), error),
+            }
+        }
+
+        #[doc = concat!(
+            "Returns a `", stringify!($type), "` converted from the bytes at a specified ",
+            "position in a circular buffer, or an `ErrorKind::OutOfBounds` error if data is ",
+            "shorter than the value.\n\n",
+            "This is the fallible counterpart of [`", stringify!($name), "`]."
+        )]
+        #[inline]
+        #[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
+        pub fn $try_name<T: BitConvEndian>(data: &[u8], start_index: usize) -> Result<$type> {
+            let mut bytes = [0; mem::size_of::<$type>()];
+            let len = data.len();
+            if len < bytes.len() {
+                return Err(Error::new(ErrorKind::OutOfBounds {
+                    start_index,
+                    requested: bytes.len(),
+                    len,
+                }));
+            }
+            let start = start_index % len;
+            for (i, byte) in bytes.iter_mut().enumerate() {
+                *byte = data.get((start + i) % len).copied().unwrap_or_default();
+            }
+            Ok(match T::ENDIANNESS {
+                Endian::LE => <$type>::from_le_bytes(bytes),
+                Endian::BE => <$type>::from_be_bytes(bytes),
+                Endian::NE => <$type>::from_ne_bytes(bytes),
+            })
+        }
+    )+};
+}
+
+WrappingImpl!(
+    to_int16_wrapping try_to_int16_wrapping i16,
+    to_int32_wrapping try_to_int32_wrapping i32,
+    to_int64_wrapping try_to_int64_wrapping i64,
+    to_uint16_wrapping try_to_uint16_wrapping u16,
+    /// # Example
+    ///
+    /// ```
+    /// use bitconv::{
+    ///     endian::{Big, Little}, to_uint32_wrapping
+    /// };
+    ///
+    /// let ring = [3, 4, 0, 0, 1, 2];
+    /// assert_eq!(0x04030201, to_uint32_wrapping::<Little>(&ring, 4));
+    /// assert_eq!(0x01020304, to_uint32_wrapping::<Big>(&ring, 10));
+    /// ```
+    to_uint32_wrapping try_to_uint32_wrapping u32,
+    to_uint64_wrapping try_to_uint64_wrapping u64
+);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{endian::*, to_int16, to_int32, to_uint32, to_uint64};
+
+    #[test]
+    fn to_wrapping_test_le() {
+        let buffer = [15, 0, 0, 128, 16, 39, 240, 216, 241, 255, 127];
+        for start_index in 0..buffer.len() - 1 {
+            assert_eq!(
+                to_int16::<Little>(&buffer, start_index),
+                to_int16_wrapping::<Little>(&buffer, start_index)
+            );
+        }
+        assert_eq!(3967, to_int16_wrapping::<Little>(&buffer, 10));
+        assert_eq!(1015807, to_int32_wrapping::<Little>(&buffer, 9));
+        assert_eq!(32767, to_uint16_wrapping::<Little>(&buffer, 20));
+        assert_eq!(2147483663, to_uint32_wrapping::<Little>(&buffer, 22));
+        assert_eq!(
+            to_uint32::<Little>(&buffer, 4),
+            to_uint32_wrapping::<Little>(&buffer, usize::MAX)
+        );
+        assert_eq!(to_uint64::<Little>(&buffer, 3), to_uint64_wrapping::<Little>(&buffer, 14));
+    }
+
+    #[test]
+    fn to_wrapping_test_be() {
+        let buffer = [15, 0, 0, 128, 16, 39, 240, 216, 241, 255, 127];
+        assert_eq!(to_int32::<Big>(&buffer, 7), to_int32_wrapping::<Big>(&buffer, 7));
+        assert_eq!(32527, to_int16_wrapping::<Big>(&buffer, 10));
+        assert_eq!(-3585, to_int64_wrapping::<Big>(&buffer, 8) >> 48);
+        assert_eq!(4135, to_uint16_wrapping::<Big>(&[16, 39], 2));
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Failed to read u32: 4 bytes requested at index 0 of a buffer of 3 bytes."
+    )]
+    fn to_wrapping_short_panic_test() { to_uint32_wrapping::<Little>(&[1, 2, 3], 0); }
+
+    #[test]
+    #[should_panic]
+    fn to_wrapping_empty_panic_test() { to_uint16_wrapping::<Big>(&[], 0); }
+
+    #[test]
+    fn try_to_wrapping_test() {
+        let buffer = [3, 4, 0, 0, 1, 2];
+        assert_eq!(Ok(0x04030201), try_to_uint32_wrapping::<Little>(&buffer, usize::MAX - 5));
+        assert_eq!(Ok(0x0304), try_to_int16_wrapping::<Big>(&buffer, 6));
+        assert_eq!(
+            ErrorKind::OutOfBounds { start_index: 1, requested: 8, len: 6 },
+            try_to_uint64_wrapping::<Native>(&buffer, 1).unwrap_err().kind()
+        );
+        assert_eq!(
+            ErrorKind::OutOfBounds { start_index: 0, requested: 2, len: 0 },
+            try_to_uint16_wrapping::<Little>(&[], 0).unwrap_err().kind()
+        );
+    }
+}
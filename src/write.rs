@@ -0,0 +1,164 @@
+//! Conversions that write values into existing byte arrays.
+use crate::{error, BitConvEndian, Result, ToBytes};
+
+macro_rules! WriteImpl {
+    ($($(#[$attr:meta])* $write:ident $try_write:ident $type:ty),+) => {$(
+        #[doc = concat!(
+            "Writes a `", stringify!($type), "` to the bytes at a specified position in a ",
+            "byte array, overwriting what was there.\n",
+            "# Panics\n\n",
+            "Panics if the value does not fit in data at start_index. See [`",
+            stringify!($try_write), "`] for a version that returns an error instead."
+        )]
+        $(#[$attr])*
+        #[inline]
+        #[track_caller]
+        pub fn $write<T: BitConvEndian>(data: &mut [u8], start_index: usize, value: $type) {
+            if let Err(error) = value.to_bytes::<T>(data, start_index) {
+                error::conversion_failed("write", stringify!($type), error);
+            }
+        }
+
+        #[doc = concat!(
+            "Writes a `", stringify!($type), "` to the bytes at a specified position in a ",
+            "byte array, overwriting what was there, or fails with `ErrorKind::OutOfBounds` ",
+            "leaving data untouched if the value does not fit."
+        )]
+        #[inline]
+        #[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
+        pub fn $try_write<T: BitConvEndian>(data: &mut [u8], start_index: usize, value: $type) -> Result<()> {
+            value.to_bytes::<T>(data, start_index).map(|_| ())
+        }
+    )+};
+}
+
+WriteImpl!(
+    write_int16_at try_write_int16_at i16,
+    write_int32_at try_write_int32_at i32,
+    write_int64_at try_write_int64_at i64,
+    write_uint16_at try_write_uint16_at u16,
+    /// # Example
+    ///
+    /// ```
+    /// use bitconv::{
+    ///     endian::{Big, Little}, try_write_uint32_at, write_uint32_at
+    /// };
+    ///
+    /// let mut save = [0, 1, 2, 3, 4, 5];
+    /// write_uint32_at::<Little>(&mut save, 1, 999999);
+    /// assert_eq!([0, 63, 66, 15, 0, 5], save);
+    /// write_uint32_at::<Big>(&mut save, 2, 999999);
+    /// assert_eq!([0, 63, 0, 15, 66, 63], save);
+    /// assert!(try_write_uint32_at::<Big>(&mut save, 3, 0).is_err());
+    /// ```
+    write_uint32_at try_write_uint32_at u32,
+    write_uint64_at try_write_uint64_at u64
+);
+
+/// Writes a value to the start of a byte array, returning whether it fit, in
+/// the manner of C#'s `BitConverter.TryWriteBytes`.
+///
+/// The value can be any [`ToBytes`] type, which covers every primitive of
+/// `TryWriteBytes` but `char`. Nothing is written when destination is too
+/// short. See [`try_write_int32_at`] and its siblings for writing elsewhere
+/// than at the start, and for an error saying how many bytes were missing.
+/// # Example
+///
+/// ```
+/// use bitconv::{endian::Little, try_write_bytes};
+///
+/// let mut destination = [0; 5];
+/// assert!(try_write_bytes::<Little, _>(&mut destination, 999999u32));
+/// assert_eq!([63, 66, 15, 0, 0], destination);
+/// assert!(try_write_bytes::<Little, _>(&mut destination[4..], true));
+/// assert!(!try_write_bytes::<Little, _>(&mut destination, 1.5f64));
+/// assert_eq!([63, 66, 15, 0, 1], destination);
+/// ```
+#[inline]
+#[cfg_attr(all(feature = "no-panic", not(debug_assertions)), no_panic::no_panic)]
+pub fn try_write_bytes<T: BitConvEndian, V: ToBytes>(destination: &mut [u8], value: V) -> bool {
+    value.byte_len() <= destination.len() && value.to_bytes::<T>(destination, 0).is_ok()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{endian::*, to_int16, to_int32, to_int64, to_uint16, to_uint32, to_uint64};
+    use crate::{Error, ErrorKind};
+
+    #[test]
+    fn write_at_test_le() {
+        let mut buffer = [0; 11];
+        write_uint32_at::<Little>(&mut buffer, 0, 2147483663);
+        write_int16_at::<Little>(&mut buffer, 4, 10000);
+        write_int16_at::<Little>(&mut buffer, 6, -10000);
+        write_uint16_at::<Little>(&mut buffer, 8, 65521);
+        write_int16_at::<Little>(&mut buffer, 9, 32767);
+        assert_eq!([15, 0, 0, 128, 16, 39, 240, 216, 241, 255, 127], buffer);
+        write_int64_at::<Little>(&mut buffer, 3, -1);
+        assert_eq!(-1, to_int64::<Little>(&buffer, 3));
+        write_uint64_at::<Little>(&mut buffer, 0, 1);
+        assert_eq!(1, to_uint64::<Little>(&buffer, 0));
+        write_int32_at::<Little>(&mut buffer, 7, -2);
+        assert_eq!(-2, to_int32::<Little>(&buffer, 7));
+    }
+
+    #[test]
+    fn write_at_test_be() {
+        let mut buffer = [0; 11];
+        write_uint64_at::<Big>(&mut buffer, 3, 1080864460595785944);
+        assert_eq!(1080864460595785944, to_uint64::<Big>(&buffer, 3));
+        write_uint16_at::<Big>(&mut buffer, 0, 4135);
+        assert_eq!(4135, to_uint16::<Big>(&buffer, 0));
+        write_int16_at::<Big>(&mut buffer, 9, -15);
+        assert_eq!(-15, to_int16::<Big>(&buffer, 9));
+        write_uint32_at::<Big>(&mut buffer, 1, 256);
+        assert_eq!([16, 0, 0, 1, 0, 0, 128, 16, 39, 255, 241], buffer);
+        assert_eq!(256, to_uint32::<Big>(&buffer, 1));
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Failed to write u32: 4 bytes requested at index 8 of a buffer of 11 bytes."
+    )]
+    fn write_at_le_panic_test() {
+        let mut buffer = [0; 11];
+        write_uint32_at::<Little>(&mut buffer, 8, 0);
+    }
+
+    #[test]
+    fn try_write_at_test() {
+        let mut buffer = [1; 5];
+        assert_eq!(Ok(()), try_write_int32_at::<Little>(&mut buffer, 1, -16773120));
+        assert_eq!([1, 0, 16, 0, 255], buffer);
+        assert_eq!(
+            Err(Error::new(ErrorKind::OutOfBounds { start_index: 2, requested: 4, len: 5 })),
+            try_write_uint32_at::<Big>(&mut buffer, 2, 0)
+        );
+        assert!(try_write_int64_at::<Little>(&mut buffer, 0, 0).is_err());
+        assert!(try_write_uint64_at::<Little>(&mut buffer, 0, 0).is_err());
+        assert!(try_write_uint16_at::<Native>(&mut buffer, usize::MAX, 0).is_err());
+        assert_eq!(Ok(()), try_write_int16_at::<Big>(&mut buffer, 3, 1));
+        assert_eq!([1, 0, 16, 0, 1], buffer);
+    }
+
+    #[test]
+    fn try_write_bytes_test() {
+        let mut buffer = [0; 11];
+        assert!(try_write_bytes::<Little, _>(&mut buffer, 2147483663u32));
+        assert!(try_write_bytes::<Little, _>(&mut buffer[4..], (10000i16, -10000i16)));
+        assert!(try_write_bytes::<Big, _>(&mut buffer[8..], -15i8));
+        assert!(try_write_bytes::<Big, _>(&mut buffer[9..], [255, 127]));
+        assert_eq!([15, 0, 0, 128, 16, 39, 240, 216, 241, 255, 127], buffer);
+        assert!(!try_write_bytes::<Big, _>(&mut buffer[9..], 0u32));
+        assert!(!try_write_bytes::<Little, _>(&mut buffer[4..], (0u32, 0u32)));
+        assert!(!try_write_bytes::<Little, _>(&mut [], false));
+        assert_eq!([15, 0, 0, 128, 16, 39, 240, 216, 241, 255, 127], buffer);
+        assert!(try_write_bytes::<Little, _>(&mut buffer, -1.5f32));
+        assert!(try_write_bytes::<Big, _>(&mut buffer[3..], core::f64::consts::PI));
+        assert!(try_write_bytes::<Native, _>(&mut buffer[10..], false));
+        assert_eq!([0, 0, 192, 64, 9, 33, 251, 84, 68, 45, 0], buffer);
+        assert!(try_write_bytes::<Little, _>(&mut buffer[3..], (true, 0u32, 5u16)));
+        assert_eq!([0, 0, 192, 1, 0, 0, 0, 0, 5, 0, 0], buffer);
+    }
+}
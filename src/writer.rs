@@ -0,0 +1,354 @@
+//! Growable buffer values are written to one after another.
+use crate::{error, BitConvEndian, Error, ErrorKind, FixedSize, Result, ToBytes};
+use alloc::vec::Vec;
+use core::{convert::TryFrom, marker::PhantomData};
+
+/// Space reserved in a [`Writer`] for a `V` whose value is only known later,
+/// such as the length of what follows it.
+///
+/// A placeholder is consumed by [`Writer::patch`] or [`Writer::patch_len`], so
+/// each one is filled in exactly once.
+#[must_use = "reserved space is left zeroed unless the placeholder is patched"]
+#[derive(Debug)]
+pub struct Placeholder<V> {
+    start_index: usize,
+    marker: PhantomData<V>,
+}
+
+impl<V> Placeholder<V> {
+    /// Returns the position of the reserved space in the writer.
+    pub fn start_index(&self) -> usize { self.start_index }
+}
+
+/// Buffer that values are appended to in the endianness `E`.
+/// # Example
+///
+/// ```
+/// use bitconv::{endian::Big, Writer};
+///
+/// let mut writer = Writer::<Big>::new();
+/// writer.write_uint8(7);
+/// let len = writer.reserve::<u16>();
+/// writer.write_uint32(256);
+/// writer.write_bytes(b"hi");
+/// writer.patch_len(len).unwrap();
+/// assert_eq!([7, 0, 6, 0, 0, 1, 0, 104, 105], writer.as_slice());
+/// ```
+pub struct Writer<E> {
+    buffer: Vec<u8>,
+    endian: PhantomData<E>,
+}
+
+macro_rules! WriterImpl {
+    ($($write:ident $type:ty),+) => {$(
+        #[doc = concat!("Appends a `", stringify!($type), "`.")]
+        #[inline]
+        pub fn $write(&mut self, value: $type) { self.write(&value) }
+    )+};
+}
+
+impl<E: BitConvEndian> Writer<E> {
+    pub fn new() -> Self { Writer { buffer: Vec::new(), endian: PhantomData } }
+
+    /// Creates a writer with room for capacity bytes before it reallocates.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Writer { buffer: Vec::with_capacity(capacity), endian: PhantomData }
+    }
+
+    /// Returns the number of bytes written so far.
+    pub fn position(&self) -> usize { self.buffer.len() }
+
+    /// Returns the bytes written so far.
+    pub fn as_slice(&self) -> &[u8] { &self.buffer }
+
+    /// Returns the written bytes.
+    pub fn into_inner(self) -> Vec<u8> { self.buffer }
+
+    /// Appends a value, such as a tuple or a derived type.
+    /// # Panics
+    ///
+    /// Panics if the value fails to convert, as it does when its `ToBytes`
+    /// implementation writes more than [`byte_len`](ToBytes::byte_len) bytes.
+    /// See [`try_write`](Writer::try_write) for a version that returns an
+    /// error instead.
+    #[track_caller]
+    pub fn write<V: ToBytes + ?Sized>(&mut self, value: &V) {
+        if let Err(error) = self.try_write(value) {
+            error::conversion_failed("write", "value", error);
+        }
+    }
+
+    /// Appends a value, such as a tuple or a derived type.
+    ///
+    /// If the value fails to convert, the error is returned and the writer is
+    /// truncated back to where the value started, so nothing of it is kept.
+    pub fn try_write<V: ToBytes + ?Sized>(&mut self, value: &V) -> Result<()> {
+        let start_index = self.buffer.len();
+        self.buffer.resize(start_index + value.byte_len(), 0);
+        let written = value.to_bytes::<E>(&mut self.buffer, start_index);
+        if written.is_err() {
+            self.buffer.truncate(start_index);
+        }
+        written.map(|_| ())
+    }
+
+    /// Appends raw bytes.
+    pub fn write_bytes(&mut self, bytes: &[u8]) { self.buffer.extend_from_slice(bytes); }
+
+    /// Appends zeroed space for a `V`, to be filled in later through the
+    /// returned placeholder.
+    pub fn reserve<V: FixedSize>(&mut self) -> Placeholder<V> {
+        let start_index = self.buffer.len();
+        self.buffer.resize(start_index + V::SIZE, 0);
+        Placeholder { start_index, marker: PhantomData }
+    }
+
+    /// Fills the space reserved by placeholder with value.
+    /// # Panics
+    ///
+    /// Panics if the value fails to convert, or if placeholder was reserved
+    /// past the end of this writer by another one. See
+    /// [`try_patch`](Writer::try_patch) for a version that returns an error
+    /// instead.
+    #[track_caller]
+    pub fn patch<V: ToBytes>(&mut self, placeholder: Placeholder<V>, value: V) {
+        if let Err(error) = self.try_patch(placeholder, value) {
+            error::conversion_failed("patch", "value", error);
+        }
+    }
+
+    /// Fills the space reserved by placeholder with value.
+    ///
+    /// Fails with the error of the conversion, such as the
+    /// `ErrorKind::OutOfBounds` of a placeholder reserved past the end of
+    /// this writer by another one.
+    pub fn try_patch<V: ToBytes>(&mut self, placeholder: Placeholder<V>, value: V) -> Result<()> {
+        value.to_bytes::<E>(&mut self.buffer, placeholder.start_index).map(|_| ())
+    }
+
+    /// Fills the space reserved by placeholder with the number of bytes
+    /// written after it.
+    ///
+    /// Fails with `ErrorKind::LengthOverflow`, leaving the space zeroed, if
+    /// the length does not fit in a `V`, and with `ErrorKind::OutOfBounds` if
+    /// placeholder was reserved past the end of this writer by another one.
+    pub fn patch_len<V: ToBytes + FixedSize + TryFrom<usize>>(
+        &mut self,
+        placeholder: Placeholder<V>,
+    ) -> Result<()> {
+        let start_index = placeholder.start_index;
+        let written = self.buffer.len();
+        let len = written.checked_sub(start_index).and_then(|len| len.checked_sub(V::SIZE));
+        let len = len.ok_or_else(|| {
+            Error::new(ErrorKind::OutOfBounds { start_index, requested: V::SIZE, len: written })
+        })?;
+        let value = V::try_from(len)
+            .map_err(|_| Error::new(ErrorKind::LengthOverflow { start_index, len }))?;
+        self.try_patch(placeholder, value)
+    }
+
+    WriterImpl!(
+        write_int8 i8,
+        write_int16 i16,
+        write_int32 i32,
+        write_int64 i64,
+        write_uint8 u8,
+        write_uint16 u16,
+        write_uint32 u32,
+        write_uint64 u64
+    );
+}
+
+impl<E: BitConvEndian> Default for Writer<E> {
+    fn default() -> Self { Writer::new() }
+}
+
+/// Types that can be written to a [`Writer`], the encoding counterpart of
+/// [`FromReader`](crate::FromReader).
+///
+/// Every `ToBytes` type is also a `ToWriter` that appends its bytes as
+/// [`Writer::try_write`] does, so implementations for composite types can
+/// write their parts in turn, passing on any error, without knowing the
+/// endianness or where in the buffer they end up. Implementing it
+/// by hand is useful for values laid out around the writer itself, such as
+/// those prefixed by a length computed with [`Writer::patch_len`].
+/// # Example
+///
+/// ```
+/// use bitconv::{endian::Little, BitConvEndian, Result, ToWriter, Writer};
+///
+/// struct Name<'a>(&'a str);
+///
+/// impl ToWriter for Name<'_> {
+///     fn write_to<E: BitConvEndian>(&self, writer: &mut Writer<E>) -> Result<()> {
+///         let len = writer.reserve::<u8>();
+///         writer.write_bytes(self.0.as_bytes());
+///         writer.patch_len(len)
+///     }
+/// }
+///
+/// struct Player<'a> {
+///     name: Name<'a>,
+///     score: u16,
+/// }
+///
+/// impl ToWriter for Player<'_> {
+///     fn write_to<E: BitConvEndian>(&self, writer: &mut Writer<E>) -> Result<()> {
+///         self.name.write_to(writer)?;
+///         self.score.write_to(writer)
+///     }
+/// }
+///
+/// let mut writer = Writer::<Little>::new();
+/// Player { name: Name("ab"), score: 300 }.write_to(&mut writer).unwrap();
+/// assert_eq!([2, 97, 98, 44, 1], writer.as_slice());
+/// ```
+pub trait ToWriter {
+    /// Appends the value to writer.
+    fn write_to<E: BitConvEndian>(&self, writer: &mut Writer<E>) -> Result<()>;
+}
+
+impl<V: ToBytes + ?Sized> ToWriter for V {
+    #[inline]
+    fn write_to<E: BitConvEndian>(&self, writer: &mut Writer<E>) -> Result<()> {
+        writer.try_write(self)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::endian::*;
+
+    /// Value writing more bytes than its `byte_len`.
+    struct Overrun;
+
+    impl ToBytes for Overrun {
+        fn to_bytes<T: BitConvEndian>(&self, data: &mut [u8], start_index: usize) -> Result<usize> {
+            0u32.to_bytes::<T>(data, start_index)
+        }
+
+        fn byte_len(&self) -> usize { 2 }
+    }
+
+    #[test]
+    fn writer_test_le() {
+        let mut writer = Writer::<Little>::with_capacity(11);
+        writer.write_uint32(2147483663);
+        writer.write_int16(10000);
+        writer.write(&(-10000i16, -15i8));
+        writer.write_uint8(255);
+        writer.write_int8(127);
+        assert_eq!(11, writer.position());
+        assert_eq!([15, 0, 0, 128, 16, 39, 240, 216, 241, 255, 127], writer.as_slice());
+        writer.write_int32(-2);
+        writer.write_uint16(1);
+        writer.write_int64(-1);
+        writer.write_uint64(1);
+        assert_eq!(33, writer.into_inner().len());
+    }
+
+    #[test]
+    fn writer_test_be() {
+        let mut writer = Writer::<Big>::default();
+        writer.write_uint64(1080864460595785944);
+        writer.write_bytes(&[241, 255]);
+        writer.write_int8(127);
+        assert_eq!([15, 0, 0, 128, 16, 39, 240, 216, 241, 255, 127], writer.as_slice());
+    }
+
+    #[test]
+    fn writer_patch_test() {
+        let mut writer = Writer::<Little>::new();
+        let magic = writer.reserve::<u32>();
+        let outer = writer.reserve::<u16>();
+        let inner = writer.reserve::<u8>();
+        assert_eq!((4, 6), (outer.start_index(), inner.start_index()));
+        writer.write_bytes(&[9; 3]);
+        writer.patch_len(inner).unwrap();
+        writer.write_uint16(10000);
+        writer.patch_len(outer).unwrap();
+        writer.patch(magic, 2147483663);
+        assert_eq!([15, 0, 0, 128, 6, 0, 3, 9, 9, 9, 16, 39], writer.as_slice());
+        let empty = writer.reserve::<u32>();
+        writer.patch_len(empty).unwrap();
+        assert_eq!([0; 4], writer.as_slice()[12..]);
+    }
+
+    #[test]
+    fn writer_patch_len_overflow_test() {
+        let mut writer = Writer::<Little>::new();
+        writer.write_uint8(1);
+        let len = writer.reserve::<u8>();
+        writer.write_bytes(&[0; 256]);
+        assert_eq!(
+            Err(Error::new(ErrorKind::LengthOverflow { start_index: 1, len: 256 })),
+            writer.patch_len(len)
+        );
+        assert_eq!(0, writer.as_slice()[1]);
+        let len = writer.reserve::<i8>();
+        writer.write_bytes(&[0; 127]);
+        assert_eq!(Ok(()), writer.patch_len(len));
+        assert_eq!(127, writer.as_slice()[258]);
+    }
+
+    #[test]
+    fn writer_try_write_test() {
+        let mut writer = Writer::<Big>::new();
+        assert_eq!(Ok(()), writer.try_write(&(7u8, 256u16)));
+        assert_eq!(
+            Err(Error::new(ErrorKind::OutOfBounds { start_index: 3, requested: 4, len: 5 })),
+            writer.try_write(&Overrun)
+        );
+        assert_eq!([7, 1, 0], writer.as_slice());
+    }
+
+    #[test]
+    #[should_panic(expected = "Failed to write value: 4 bytes requested at index 0")]
+    fn writer_write_panic_test() { Writer::<Little>::new().write(&Overrun); }
+
+    #[test]
+    fn writer_try_patch_test() {
+        let mut longer = Writer::<Little>::new();
+        longer.write_uint32(0);
+        let len = longer.reserve::<u16>();
+        let magic = longer.reserve::<u32>();
+        let mut writer = Writer::<Little>::new();
+        writer.write_bytes(&[0; 5]);
+        assert_eq!(
+            Err(Error::new(ErrorKind::OutOfBounds { start_index: 4, requested: 2, len: 5 })),
+            writer.patch_len(len)
+        );
+        assert_eq!(
+            Err(Error::new(ErrorKind::OutOfBounds { start_index: 6, requested: 4, len: 5 })),
+            writer.try_patch(magic, 0xcafe)
+        );
+        let magic = writer.reserve::<u32>();
+        assert_eq!(Ok(()), writer.try_patch(magic, 0xcafe));
+        assert_eq!([0, 0, 0, 0, 0, 0xfe, 0xca, 0, 0], writer.as_slice());
+    }
+
+    #[test]
+    #[should_panic(expected = "Failed to patch value: 4 bytes requested at index 6")]
+    fn writer_patch_panic_test() {
+        let mut longer = Writer::<Big>::new();
+        longer.write_bytes(&[0; 6]);
+        let magic = longer.reserve::<u32>();
+        Writer::<Big>::new().patch(magic, 1);
+    }
+
+    #[test]
+    fn to_writer_test() {
+        let mut writer = Writer::<Big>::new();
+        assert_eq!(Ok(()), 2147483663u32.write_to(&mut writer));
+        assert_eq!(Ok(()), (-10000i16, -15i8).write_to(&mut writer));
+        assert_eq!(Ok(()), [241u8, 255][..].write_to(&mut writer));
+        assert_eq!(Ok(()), alloc::vec![Some(127u8), None].write_to(&mut writer));
+        assert_eq!([128, 0, 0, 15, 216, 240, 241, 241, 255, 127], writer.as_slice());
+        assert_eq!(
+            Err(Error::new(ErrorKind::OutOfBounds { start_index: 10, requested: 4, len: 12 })),
+            Overrun.write_to(&mut writer)
+        );
+        assert_eq!(10, writer.position());
+    }
+}